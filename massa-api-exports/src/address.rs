@@ -1,14 +1,138 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
+use massa_hash::Hash;
 use massa_models::address::ExecutionAddressCycleInfo;
 use massa_models::endorsement::EndorsementId;
+use massa_models::error::ModelsError;
 use massa_models::operation::OperationId;
 use massa_models::slot::{IndexedSlot, Slot};
-use massa_models::{address::Address, amount::Amount, block_id::BlockId};
+use massa_models::{
+    address::{Address, AddressDeserializer, AddressSerializer},
+    amount::{Amount, AmountDeserializer, AmountSerializer},
+    block_id::BlockId,
+};
+use massa_serialization::{
+    Deserializer, SerializeError, Serializer, U64VarIntDeserializer, U64VarIntSerializer,
+};
+use massa_signature::{KeyPair, MassaSignatureError, PublicKey, Signature};
+use massa_time::{MassaTime, MassaTimeSerializer};
+use nom::{
+    error::{context, ContextError, ParseError},
+    number::complete::u8 as parse_u8,
+    sequence::tuple,
+    IResult, Parser,
+};
 use serde::{Deserialize, Serialize};
+use std::ops::Bound::Included;
 
+use crate::error::ApiError;
 use crate::slot::SlotAmount;
 
+/// Query for the upcoming draws of a staking address
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DrawScheduleQuery {
+    /// the staking address
+    pub address: Address,
+    /// number of slots to look ahead of the current slot
+    pub slots_ahead: u64,
+}
+
+/// Query for the roll sales of an address whose coins are not credited yet
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct PendingRollSalesQuery {
+    /// the address that sold rolls
+    pub address: Address,
+}
+
+/// A prospective roll sale to preview
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RollSellPreview {
+    /// the address selling rolls
+    pub address: Address,
+    /// number of rolls to sell
+    pub roll_count: u64,
+}
+
+/// Query of the information of many addresses at once, see `get_multi_address_info`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MultiAddressInfoQuery {
+    /// the addresses to look at
+    pub addresses: Vec<Address>,
+    /// if true, only the balances and roll counts are computed and returned as `CompactAddressInfo`
+    pub compact: bool,
+}
+
+/// Information about many addresses, one entry per queried address, in the order of the query.
+/// Addresses that do not exist are not dropped: their entry has zero balances and roll counts.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum MultiAddressInfo {
+    /// balances and roll counts, for compact queries
+    Compact(Vec<CompactAddressInfo>),
+    /// all the information
+    Full(Vec<AddressInfo>),
+}
+
+impl std::fmt::Display for MultiAddressInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MultiAddressInfo::Compact(infos) => {
+                for info in infos {
+                    write!(f, "{}", info)?;
+                }
+            }
+            MultiAddressInfo::Full(infos) => {
+                for info in infos {
+                    write!(f, "{}", info)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Kind of duty an address was drawn for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DrawKind {
+    /// block production
+    Block,
+    /// endorsement at the given index
+    Endorsement(usize),
+}
+
+impl std::fmt::Display for DrawKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DrawKind::Block => write!(f, "block"),
+            DrawKind::Endorsement(index) => write!(f, "endorsement (index {})", index),
+        }
+    }
+}
+
+/// Section of an `AddressInfo`, used by `get_addresses` to compute only the requested ones.
+///
+/// `Balance` and `RollCount` are cheap to compute. The other sections are expensive:
+/// `DatastoreKeys` lists all the keys of the address, `DeferredCredits` and `CycleInfos` go through
+/// the proof-of-stake state, `Draws` scans the upcoming selections and `CreatedObjects` walks the storage indexes.
+/// The sections that are not requested are left empty, and the balances and roll counts set to `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddressInfoField {
+    /// `final_balance` and `candidate_balance`
+    Balance,
+    /// `final_roll_count` and `candidate_roll_count`
+    RollCount,
+    /// `final_datastore_keys` and `candidate_datastore_keys`
+    DatastoreKeys,
+    /// `deferred_credits`
+    DeferredCredits,
+    /// `next_block_draws` and `next_endorsement_draws`
+    Draws,
+    /// `created_blocks`, `created_operations` and `created_endorsements`
+    CreatedObjects,
+    /// `cycle_infos`
+    CycleInfos,
+}
+
 /// All you ever dream to know about an address
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AddressInfo {
@@ -17,17 +141,17 @@ pub struct AddressInfo {
     /// the thread the address belongs to
     pub thread: u8,
 
-    /// final balance
-    pub final_balance: Amount,
-    /// final roll count
-    pub final_roll_count: u64,
+    /// final balance, `None` if not requested
+    pub final_balance: Option<Amount>,
+    /// final roll count, `None` if not requested
+    pub final_roll_count: Option<u64>,
     /// final datastore keys
     pub final_datastore_keys: Vec<Vec<u8>>,
 
-    /// candidate balance
-    pub candidate_balance: Amount,
-    /// candidate roll count
-    pub candidate_roll_count: u64,
+    /// candidate balance, `None` if not requested
+    pub candidate_balance: Option<Amount>,
+    /// candidate roll count, `None` if not requested
+    pub candidate_roll_count: Option<u64>,
     /// candidate datastore keys
     pub candidate_datastore_keys: Vec<Vec<u8>>,
 
@@ -53,27 +177,34 @@ pub struct AddressInfo {
 impl std::fmt::Display for AddressInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Address {} (thread {}):", self.address, self.thread)?;
-        writeln!(
-            f,
-            "\tBalance: final={}, candidate={}",
-            self.final_balance, self.candidate_balance
-        )?;
-        writeln!(
-            f,
-            "\tRolls: final={}, candidate={}",
-            self.final_roll_count, self.candidate_roll_count
-        )?;
-        write!(f, "\tLocked coins:")?;
-        if self.deferred_credits.is_empty() {
-            writeln!(f, "0")?;
-        } else {
-            for slot_amount in &self.deferred_credits {
-                writeln!(
-                    f,
-                    "\t\t{} locked coins will be unlocked at slot {}",
-                    slot_amount.amount, slot_amount.slot
-                )?;
-            }
+        if let (Some(final_balance), Some(candidate_balance)) =
+            (self.final_balance, self.candidate_balance)
+        {
+            writeln!(
+                f,
+                "\tBalance: final={}, candidate={}",
+                final_balance, candidate_balance
+            )?;
+        }
+        if let (Some(final_roll_count), Some(candidate_roll_count)) =
+            (self.final_roll_count, self.candidate_roll_count)
+        {
+            writeln!(
+                f,
+                "\tRolls: final={}, candidate={}",
+                final_roll_count, candidate_roll_count
+            )?;
+        }
+        match self.total_deferred_credits() {
+            Ok(total) => writeln!(f, "\tLocked coins: {}", total)?,
+            Err(err) => writeln!(f, "\tLocked coins: {}", err)?,
+        }
+        for slot_amount in &self.deferred_credits {
+            writeln!(
+                f,
+                "\t\t{} locked coins will be unlocked at slot {}",
+                slot_amount.amount, slot_amount.slot
+            )?;
         }
         writeln!(f, "\tCycle infos:")?;
         for cycle_info in &self.cycle_infos {
@@ -102,9 +233,90 @@ impl std::fmt::Display for AddressInfo {
 }
 
 impl AddressInfo {
-    /// Only essential info about an address
-    pub fn compact(&self) -> CompactAddressInfo {
-        CompactAddressInfo {
+    /// Empties the sections that are not in `fields`
+    pub fn retain_fields(&mut self, fields: &[AddressInfoField]) {
+        if !fields.contains(&AddressInfoField::Balance) {
+            self.final_balance = None;
+            self.candidate_balance = None;
+        }
+        if !fields.contains(&AddressInfoField::RollCount) {
+            self.final_roll_count = None;
+            self.candidate_roll_count = None;
+        }
+        if !fields.contains(&AddressInfoField::DatastoreKeys) {
+            self.final_datastore_keys.clear();
+            self.candidate_datastore_keys.clear();
+        }
+        if !fields.contains(&AddressInfoField::DeferredCredits) {
+            self.deferred_credits.clear();
+        }
+        if !fields.contains(&AddressInfoField::Draws) {
+            self.next_block_draws.clear();
+            self.next_endorsement_draws.clear();
+        }
+        if !fields.contains(&AddressInfoField::CreatedObjects) {
+            self.created_blocks.clear();
+            self.created_operations.clear();
+            self.created_endorsements.clear();
+        }
+        if !fields.contains(&AddressInfoField::CycleInfos) {
+            self.cycle_infos.clear();
+        }
+    }
+
+    /// Total amount of the coins locked in deferred credits, zero if there is none.
+    /// Fails if the sum overflows.
+    pub fn total_deferred_credits(&self) -> Result<Amount, ModelsError> {
+        self.deferred_credits
+            .iter()
+            .try_fold(Amount::default(), |total, slot_amount| {
+                total
+                    .checked_add(slot_amount.amount)
+                    .ok_or(ModelsError::AmountOverflowError)
+            })
+    }
+
+    /// Amount of the locked coins unlocked strictly before `slot`, saturating on overflow,
+    /// e.g. the coins unlocked in the next cycle when given the first slot of the cycle after it
+    pub fn deferred_credits_before(&self, slot: Slot) -> Amount {
+        self.deferred_credits
+            .iter()
+            .filter(|slot_amount| slot_amount.slot < slot)
+            .fold(Amount::default(), |total, slot_amount| {
+                total.saturating_add(slot_amount.amount)
+            })
+    }
+
+    /// Signs the compact information of the address along with the current time and the chain id,
+    /// so that the signer can give a verifiable statement of the balance of the address.
+    /// Refuses to sign if the balances or the roll counts were not requested, see `compact`.
+    pub fn attestation(
+        &self,
+        signer: &KeyPair,
+        chain_id: u64,
+    ) -> Result<SignedAttestation, ApiError> {
+        let address_info = self.compact().ok_or_else(|| {
+            ApiError::BadRequest(format!(
+                "cannot attest address {}: its balances and roll counts are required",
+                self.address
+            ))
+        })?;
+        let timestamp = MassaTime::now();
+        let signature = signer
+            .sign(&attestation_hash(&address_info, timestamp, chain_id))
+            .map_err(|err| ApiError::InternalServerError(err.to_string()))?;
+        Ok(SignedAttestation {
+            address_info,
+            timestamp,
+            public_key: signer.get_public_key(),
+            signature,
+        })
+    }
+
+    /// Only essential info about an address.
+    /// `None` if the balances or the roll counts were not requested.
+    pub fn compact(&self) -> Option<CompactAddressInfo> {
+        Some(CompactAddressInfo {
             address: self.address,
             thread: self.thread,
             active_rolls: self
@@ -112,16 +324,76 @@ impl AddressInfo {
                 .last()
                 .and_then(|c| c.active_rolls)
                 .unwrap_or_default(),
-            final_rolls: self.final_roll_count,
-            candidate_rolls: self.candidate_roll_count,
-            final_balance: self.final_balance,
-            candidate_balance: self.candidate_balance,
+            final_rolls: self.final_roll_count?,
+            candidate_rolls: self.candidate_roll_count?,
+            final_balance: self.final_balance?,
+            candidate_balance: self.candidate_balance?,
+        })
+    }
+}
+
+/// Compact address information signed by a node, see `AddressInfo::attestation`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignedAttestation {
+    /// attested address information
+    pub address_info: CompactAddressInfo,
+    /// time at which the attestation was produced
+    pub timestamp: MassaTime,
+    /// public key of the signer
+    pub public_key: PublicKey,
+    /// signature of the address information and the timestamp
+    pub signature: Signature,
+}
+
+impl SignedAttestation {
+    /// Checks that the attestation was signed by `node_public_key` for the chain `chain_id`
+    /// and was not modified since
+    pub fn verify(
+        &self,
+        node_public_key: &PublicKey,
+        chain_id: u64,
+    ) -> Result<(), MassaSignatureError> {
+        if self.public_key != *node_public_key {
+            return Err(MassaSignatureError::SignatureError(format!(
+                "attestation signed by {} instead of {}",
+                self.public_key, node_public_key
+            )));
         }
+        node_public_key.verify_signature(
+            &attestation_hash(&self.address_info, self.timestamp, chain_id),
+            &self.signature,
+        )
     }
 }
 
+/// Domain separation tag of the attestation hashes,
+/// so that an attestation signature cannot be replayed as the signature of another message
+const ATTESTATION_DOMAIN_TAG: &[u8] = b"MASSA_ADDRESS_ATTESTATION";
+
+/// Hash of the attested content
+///
+/// The hashed layout is: domain tag, chain id (varint), binary compact address information
+/// (see `CompactAddressInfoSerializer`), timestamp.
+fn attestation_hash(
+    address_info: &CompactAddressInfo,
+    timestamp: MassaTime,
+    chain_id: u64,
+) -> Hash {
+    let mut bytes = ATTESTATION_DOMAIN_TAG.to_vec();
+    U64VarIntSerializer::new()
+        .serialize(&chain_id, &mut bytes)
+        .expect("serializing a u64 cannot fail");
+    CompactAddressInfoSerializer::new()
+        .serialize(address_info, &mut bytes)
+        .expect("serializing a compact address info cannot fail");
+    MassaTimeSerializer::new()
+        .serialize(&timestamp, &mut bytes)
+        .expect("serializing a timestamp cannot fail");
+    Hash::compute_from(&bytes)
+}
+
 /// Less information about an address
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CompactAddressInfo {
     /// the address
     pub address: Address,
@@ -156,6 +428,140 @@ impl std::fmt::Display for CompactAddressInfo {
     }
 }
 
+/// Binary serializer for `CompactAddressInfo`
+///
+/// The layout is: address, thread (one byte), candidate, final and active roll counts (varints),
+/// final and candidate balances.
+#[derive(Clone)]
+pub struct CompactAddressInfoSerializer {
+    address_serializer: AddressSerializer,
+    u64_serializer: U64VarIntSerializer,
+    amount_serializer: AmountSerializer,
+}
+
+impl CompactAddressInfoSerializer {
+    /// Creates a `CompactAddressInfoSerializer`
+    pub fn new() -> Self {
+        Self {
+            address_serializer: AddressSerializer::new(),
+            u64_serializer: U64VarIntSerializer::new(),
+            amount_serializer: AmountSerializer::new(),
+        }
+    }
+}
+
+impl Default for CompactAddressInfoSerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Serializer<CompactAddressInfo> for CompactAddressInfoSerializer {
+    fn serialize(
+        &self,
+        value: &CompactAddressInfo,
+        buffer: &mut Vec<u8>,
+    ) -> Result<(), SerializeError> {
+        self.address_serializer.serialize(&value.address, buffer)?;
+        buffer.push(value.thread);
+        self.u64_serializer
+            .serialize(&value.candidate_rolls, buffer)?;
+        self.u64_serializer.serialize(&value.final_rolls, buffer)?;
+        self.u64_serializer.serialize(&value.active_rolls, buffer)?;
+        self.amount_serializer
+            .serialize(&value.final_balance, buffer)?;
+        self.amount_serializer
+            .serialize(&value.candidate_balance, buffer)?;
+        Ok(())
+    }
+}
+
+/// Binary deserializer for `CompactAddressInfo`
+#[derive(Clone)]
+pub struct CompactAddressInfoDeserializer {
+    thread_count: u8,
+    address_deserializer: AddressDeserializer,
+    u64_deserializer: U64VarIntDeserializer,
+    amount_deserializer: AmountDeserializer,
+}
+
+impl CompactAddressInfoDeserializer {
+    /// Creates a `CompactAddressInfoDeserializer` accepting threads lower than `thread_count`
+    pub fn new(thread_count: u8) -> Self {
+        Self {
+            thread_count,
+            address_deserializer: AddressDeserializer::new(),
+            u64_deserializer: U64VarIntDeserializer::new(Included(0), Included(u64::MAX)),
+            amount_deserializer: AmountDeserializer::new(
+                Included(Amount::MIN),
+                Included(Amount::MAX),
+            ),
+        }
+    }
+}
+
+impl Deserializer<CompactAddressInfo> for CompactAddressInfoDeserializer {
+    fn deserialize<'a, E: ParseError<&'a [u8]> + ContextError<&'a [u8]>>(
+        &self,
+        buffer: &'a [u8],
+    ) -> IResult<&'a [u8], CompactAddressInfo, E> {
+        context(
+            "Failed CompactAddressInfo deserialization",
+            tuple((
+                context("Failed address deserialization", |input| {
+                    self.address_deserializer.deserialize(input)
+                }),
+                context("Failed thread deserialization", |input: &'a [u8]| {
+                    let (rest, thread) = parse_u8(input)?;
+                    if thread < self.thread_count {
+                        Ok((rest, thread))
+                    } else {
+                        Err(nom::Err::Error(ParseError::from_error_kind(
+                            input,
+                            nom::error::ErrorKind::Verify,
+                        )))
+                    }
+                }),
+                context("Failed candidate_rolls deserialization", |input| {
+                    self.u64_deserializer.deserialize(input)
+                }),
+                context("Failed final_rolls deserialization", |input| {
+                    self.u64_deserializer.deserialize(input)
+                }),
+                context("Failed active_rolls deserialization", |input| {
+                    self.u64_deserializer.deserialize(input)
+                }),
+                context("Failed final_balance deserialization", |input| {
+                    self.amount_deserializer.deserialize(input)
+                }),
+                context("Failed candidate_balance deserialization", |input| {
+                    self.amount_deserializer.deserialize(input)
+                }),
+            )),
+        )
+        .map(
+            |(
+                address,
+                thread,
+                candidate_rolls,
+                final_rolls,
+                active_rolls,
+                final_balance,
+                candidate_balance,
+            )| CompactAddressInfo {
+                address,
+                thread,
+                candidate_rolls,
+                final_rolls,
+                active_rolls,
+                final_balance,
+                candidate_balance,
+            },
+        )
+        .parse(buffer)
+    }
+}
+
 /// filter used when retrieving address informations
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct AddressFilter {
@@ -177,3 +583,148 @@ impl std::fmt::Display for AddressFilter {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn address_info() -> AddressInfo {
+        let address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        AddressInfo {
+            address,
+            thread: address.get_thread(32),
+            final_balance: Some(Amount::from_str("100").unwrap()),
+            final_roll_count: Some(2),
+            final_datastore_keys: Vec::new(),
+            candidate_balance: Some(Amount::from_str("90").unwrap()),
+            candidate_roll_count: Some(3),
+            candidate_datastore_keys: Vec::new(),
+            deferred_credits: Vec::new(),
+            next_block_draws: Vec::new(),
+            next_endorsement_draws: Vec::new(),
+            created_blocks: Vec::new(),
+            created_operations: Vec::new(),
+            created_endorsements: Vec::new(),
+            cycle_infos: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_deferred_credit_totals() {
+        let mut info = address_info();
+        assert_eq!(info.total_deferred_credits().unwrap(), Amount::default());
+        assert_eq!(
+            info.deferred_credits_before(Slot::new(10, 0)),
+            Amount::default()
+        );
+
+        info.deferred_credits = vec![
+            SlotAmount {
+                slot: Slot::new(4, 1),
+                amount: Amount::from_str("10").unwrap(),
+            },
+            SlotAmount {
+                slot: Slot::new(8, 0),
+                amount: Amount::from_str("2.5").unwrap(),
+            },
+        ];
+        assert_eq!(
+            info.total_deferred_credits().unwrap(),
+            Amount::from_str("12.5").unwrap()
+        );
+        assert_eq!(
+            info.deferred_credits_before(Slot::new(8, 0)),
+            Amount::from_str("10").unwrap()
+        );
+        assert_eq!(
+            info.deferred_credits_before(Slot::new(8, 1)),
+            Amount::from_str("12.5").unwrap()
+        );
+
+        info.deferred_credits.push(SlotAmount {
+            slot: Slot::new(9, 0),
+            amount: Amount::MAX,
+        });
+        assert!(matches!(
+            info.total_deferred_credits(),
+            Err(ModelsError::AmountOverflowError)
+        ));
+        assert_eq!(info.deferred_credits_before(Slot::new(10, 0)), Amount::MAX);
+    }
+
+    #[test]
+    fn test_attestation_round_trip() {
+        let node_keypair = KeyPair::generate(0).unwrap();
+        let info = address_info();
+
+        let mut attestation = info.attestation(&node_keypair, 77).unwrap();
+        assert_eq!(
+            Some(attestation.address_info.final_balance),
+            info.final_balance
+        );
+        attestation
+            .verify(&node_keypair.get_public_key(), 77)
+            .unwrap();
+
+        // another node did not sign it
+        let other_keypair = KeyPair::generate(0).unwrap();
+        assert!(attestation
+            .verify(&other_keypair.get_public_key(), 77)
+            .is_err());
+
+        // it was not signed for another chain
+        assert!(attestation
+            .verify(&node_keypair.get_public_key(), 9)
+            .is_err());
+
+        // the attested balance cannot be changed
+        attestation.address_info.final_balance = Amount::from_str("1000").unwrap();
+        assert!(attestation
+            .verify(&node_keypair.get_public_key(), 77)
+            .is_err());
+    }
+
+    #[test]
+    fn test_attestation_refuses_sparse_info() {
+        let node_keypair = KeyPair::generate(0).unwrap();
+        let mut info = address_info();
+        info.retain_fields(&[AddressInfoField::Balance]);
+
+        // the roll counts were not requested, they must not be attested as zero
+        assert!(info.compact().is_none());
+        assert!(matches!(
+            info.attestation(&node_keypair, 77),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_compact_address_info_binary_round_trip() {
+        let address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let info = CompactAddressInfo {
+            address,
+            thread: address.get_thread(32),
+            candidate_rolls: 3,
+            final_rolls: 2,
+            active_rolls: 1,
+            final_balance: Amount::from_str("100").unwrap(),
+            candidate_balance: Amount::from_str("90.5").unwrap(),
+        };
+
+        let mut serialized = Vec::new();
+        CompactAddressInfoSerializer::new()
+            .serialize(&info, &mut serialized)
+            .unwrap();
+        let (rest, deserialized) = CompactAddressInfoDeserializer::new(32)
+            .deserialize::<massa_serialization::DeserializeError>(&serialized)
+            .unwrap();
+        assert!(rest.is_empty());
+        assert_eq!(deserialized, info);
+
+        // the thread must exist
+        assert!(CompactAddressInfoDeserializer::new(info.thread)
+            .deserialize::<massa_serialization::DeserializeError>(&serialized)
+            .is_err());
+    }
+}
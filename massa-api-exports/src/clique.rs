@@ -0,0 +1,150 @@
+// Copyright (c) 2022 MASSA LABS <info@massa.net>
+
+use massa_models::{block_id::BlockId, clique::Clique, prehash::PreHashSet};
+use serde::{Deserialize, Serialize};
+
+use crate::error::ApiError;
+
+/// Cliques of the block graph in a compact form for API transport.
+/// Blocks usually belong to several cliques: their ids are listed once
+/// and the cliques reference them by their index in that list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CompactCliques {
+    /// ids of the blocks of all the cliques, sorted and without duplicates
+    pub block_ids: Vec<BlockId>,
+    /// the cliques, referencing their blocks by index in `block_ids`
+    pub cliques: Vec<CompactClique>,
+}
+
+/// Clique referencing its blocks by index in `CompactCliques::block_ids`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CompactClique {
+    /// sorted indices in `CompactCliques::block_ids` of the blocks in that clique
+    pub block_indices: Vec<u32>,
+    /// Fitness used to compute finality
+    pub fitness: u64,
+    /// True if it is the clique of higher fitness
+    pub is_blockclique: bool,
+}
+
+impl CompactCliques {
+    /// Encodes `cliques` in the compact form
+    pub fn encode(cliques: &[Clique]) -> Self {
+        let mut block_ids: Vec<BlockId> = cliques
+            .iter()
+            .flat_map(|clique| clique.block_ids.iter().copied())
+            .collect();
+        block_ids.sort_unstable();
+        block_ids.dedup();
+        let cliques = cliques
+            .iter()
+            .map(|clique| {
+                let mut block_indices: Vec<u32> = clique
+                    .block_ids
+                    .iter()
+                    .map(|block_id| {
+                        block_ids
+                            .binary_search(block_id)
+                            .expect("clique block absent from the block list")
+                            as u32
+                    })
+                    .collect();
+                block_indices.sort_unstable();
+                CompactClique {
+                    block_indices,
+                    fitness: clique.fitness,
+                    is_blockclique: clique.is_blockclique,
+                }
+            })
+            .collect();
+        CompactCliques { block_ids, cliques }
+    }
+
+    /// Rebuilds the cliques, failing if one of them references an index outside of `block_ids`
+    pub fn decode(&self) -> Result<Vec<Clique>, ApiError> {
+        self.cliques
+            .iter()
+            .map(|clique| {
+                let block_ids = clique
+                    .block_indices
+                    .iter()
+                    .map(|index| {
+                        self.block_ids.get(*index as usize).copied().ok_or_else(|| {
+                            ApiError::InconsistencyError(format!(
+                                "clique references block index {} but only {} blocks are listed",
+                                index,
+                                self.block_ids.len()
+                            ))
+                        })
+                    })
+                    .collect::<Result<PreHashSet<BlockId>, ApiError>>()?;
+                Ok(Clique {
+                    block_ids,
+                    fitness: clique.fitness,
+                    is_blockclique: clique.is_blockclique,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonrpsee::core::__reexports::serde_json;
+
+    fn block_id(name: &str) -> BlockId {
+        BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes()))
+    }
+
+    fn clique(names: &[&str], fitness: u64, is_blockclique: bool) -> Clique {
+        Clique {
+            block_ids: names.iter().map(|name| block_id(name)).collect(),
+            fitness,
+            is_blockclique,
+        }
+    }
+
+    #[test]
+    fn test_compact_cliques_round_trip() {
+        // three forks sharing a common history
+        let common = ["g0", "g1", "a", "b", "c"];
+        let cliques = vec![
+            clique(&[&common[..], &["d1", "e1"]].concat(), 12, true),
+            clique(&[&common[..], &["d2"]].concat(), 10, false),
+            clique(&[&common[..], &["d3", "e3"]].concat(), 11, false),
+            clique(&[], 0, false),
+        ];
+
+        let compact = CompactCliques::encode(&cliques);
+        // the common blocks are listed once
+        assert_eq!(compact.block_ids.len(), 10);
+        assert!(
+            serde_json::to_string(&compact).unwrap().len()
+                < serde_json::to_string(&cliques).unwrap().len()
+        );
+
+        let decoded = compact.decode().unwrap();
+        assert_eq!(decoded.len(), cliques.len());
+        for (decoded, original) in decoded.iter().zip(cliques.iter()) {
+            assert_eq!(decoded.block_ids, original.block_ids);
+            assert_eq!(decoded.fitness, original.fitness);
+            assert_eq!(decoded.is_blockclique, original.is_blockclique);
+        }
+
+        // survives the JSON transport
+        let transported: CompactCliques =
+            serde_json::from_str(&serde_json::to_string(&compact).unwrap()).unwrap();
+        assert_eq!(transported, compact);
+    }
+
+    #[test]
+    fn test_compact_cliques_decode_bad_index() {
+        let mut compact = CompactCliques::encode(&[clique(&["a", "b"], 2, true)]);
+        compact.cliques[0].block_indices.push(2);
+        assert!(matches!(
+            compact.decode(),
+            Err(ApiError::InconsistencyError(_))
+        ));
+    }
+}
@@ -22,6 +22,8 @@ pub struct APIConfig {
     pub bind_api: SocketAddr,
     /// max argument count
     pub max_arguments: u64,
+    /// max number of operations looked up by a query on the operations of an address
+    pub max_ops_per_address_query: u64,
     /// openrpc specification path
     pub openrpc_spec_path: PathBuf,
     /// bootstrap whitelist path
@@ -84,4 +86,6 @@ pub struct APIConfig {
     pub deferred_credits_delta: MassaTime,
     /// minimal fees to include an operation in a block
     pub minimal_fees: Amount,
+    /// whether read queries fail with a "not ready" error until the block graph caught up after startup
+    pub reject_queries_until_ready: bool,
 }
@@ -1,8 +1,10 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
 use massa_models::{
+    address::Address,
     block_id::BlockId,
     endorsement::{EndorsementId, SecureShareEndorsement},
+    slot::{IndexedSlot, Slot},
 };
 use serde::{Deserialize, Serialize};
 
@@ -41,3 +43,32 @@ impl std::fmt::Display for EndorsementInfo {
         Ok(())
     }
 }
+
+/// Query the endorsement draws of a slot range
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EndorsementDrawsQuery {
+    /// first slot of the range (included)
+    pub start: Slot,
+    /// last slot of the range (included)
+    pub end: Slot,
+}
+
+/// An address drawn to create an endorsement
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct EndorsementDraw {
+    /// slot and index of the endorsement
+    pub indexed_slot: IndexedSlot,
+    /// drawn endorser
+    pub address: Address,
+}
+
+impl std::fmt::Display for EndorsementDraw {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Slot {}, index {}: {}",
+            self.indexed_slot.slot, self.indexed_slot.index, self.address
+        )?;
+        Ok(())
+    }
+}
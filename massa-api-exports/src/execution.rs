@@ -1,6 +1,7 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
 use massa_final_state::StateChanges;
+use massa_ledger_exports::{SetOrDelete, SetOrKeep, SetUpdateOrDelete};
 use massa_models::{
     address::Address, amount::Amount, block_id::BlockId, operation::OperationId,
     output_event::SCOutputEvent, slot::Slot,
@@ -32,6 +33,58 @@ pub struct ExecuteReadOnlyResponse {
     pub state_changes: StateChanges,
 }
 
+impl ExecuteReadOnlyResponse {
+    /// Concise description of the execution error, if any.
+    ///
+    /// Known errors (gas exhaustion, missing target, low fee...) are mapped to a short message,
+    /// other errors are returned as is. Returns `None` if the execution succeeded.
+    pub fn friendly_error(&self) -> Option<String> {
+        let error = match &self.result {
+            ReadOnlyResult::Error(error) => error,
+            ReadOnlyResult::Ok(_) => return None,
+        };
+        let lowercase_error = error.to_lowercase();
+        let friendly = if lowercase_error.contains("not enough gas")
+            || lowercase_error.contains("out of gas")
+            || lowercase_error.contains("limit reached")
+            || lowercase_error.contains("instance creation gas cost")
+            || lowercase_error.contains("gas cost of instance creation")
+        {
+            "the execution ran out of gas, try again with a higher max gas"
+        } else if lowercase_error.contains("deadline") || lowercase_error.contains("timeout") {
+            "the execution did not complete in time"
+        } else if lowercase_error.contains("the called smart contract address")
+            && lowercase_error.contains("does not exist")
+        {
+            // raised by the target check of the execution context, other missing
+            // addresses (e.g. in the ledger) keep their raw error
+            "the target smart contract does not exist"
+        } else if lowercase_error.contains("the called address")
+            && lowercase_error.contains("is not a smart contract address")
+        {
+            "the target address is not a smart contract"
+        } else if lowercase_error.contains("fee is too low") {
+            "the fee is below the minimal fees of the node"
+        } else {
+            return Some(error.clone());
+        };
+        Some(friendly.to_string())
+    }
+
+    /// Keeps only the output events emitted by one of the given addresses.
+    ///
+    /// The emitter of an event is the most recent address of its call stack.
+    pub fn retain_events_from(&mut self, emitters: &[Address]) {
+        self.output_events.retain(|event| {
+            event
+                .context
+                .call_stack
+                .back()
+                .map_or(false, |emitter| emitters.contains(emitter))
+        });
+    }
+}
+
 impl Display for ExecuteReadOnlyResponse {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Executed at slot: {}", self.executed_at)?;
@@ -55,6 +108,99 @@ impl Display for ExecuteReadOnlyResponse {
     }
 }
 
+/// Change of a ledger entry made by an execution, in a readable form
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerChange {
+    /// the entry is deleted
+    EntryDeleted,
+    /// the balance is set to a new value
+    Balance(Amount),
+    /// the bytecode is replaced, by one of the given size in bytes
+    Bytecode(usize),
+    /// a datastore entry is written
+    DatastoreWrite {
+        /// key of the entry
+        key: Vec<u8>,
+        /// new value of the entry
+        value: Vec<u8>,
+    },
+    /// a datastore entry is deleted
+    DatastoreDelete {
+        /// key of the entry
+        key: Vec<u8>,
+    },
+}
+
+/// Readable summary of the state changes of an execution.
+///
+/// Ledger changes are detailed. Other changes are only counted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StateChangesSummary {
+    /// the ledger changes as `(address, change)`, sorted by address
+    pub ledger_changes: Vec<(Address, LedgerChange)>,
+    /// number of asynchronous messages added, updated or removed
+    pub async_message_changes: usize,
+    /// number of addresses whose roll count changes
+    pub roll_changes: usize,
+}
+
+impl StateChangesSummary {
+    /// Decodes `changes`.
+    ///
+    /// A newly set entry gives one change per field: its balance, its bytecode and each of its datastore entries.
+    pub fn new(changes: &StateChanges) -> Self {
+        let mut addresses: Vec<&Address> = changes.ledger_changes.0.keys().collect();
+        addresses.sort_unstable();
+        let mut ledger_changes = Vec::new();
+        for address in addresses {
+            match &changes.ledger_changes.0[address] {
+                SetUpdateOrDelete::Set(entry) => {
+                    ledger_changes.push((*address, LedgerChange::Balance(entry.balance)));
+                    ledger_changes.push((*address, LedgerChange::Bytecode(entry.bytecode.0.len())));
+                    ledger_changes.extend(entry.datastore.iter().map(|(key, value)| {
+                        (
+                            *address,
+                            LedgerChange::DatastoreWrite {
+                                key: key.clone(),
+                                value: value.clone(),
+                            },
+                        )
+                    }));
+                }
+                SetUpdateOrDelete::Update(update) => {
+                    if let SetOrKeep::Set(balance) = &update.balance {
+                        ledger_changes.push((*address, LedgerChange::Balance(*balance)));
+                    }
+                    if let SetOrKeep::Set(bytecode) = &update.bytecode {
+                        ledger_changes.push((*address, LedgerChange::Bytecode(bytecode.0.len())));
+                    }
+                    ledger_changes.extend(update.datastore.iter().map(|(key, value)| {
+                        let change = match value {
+                            SetOrDelete::Set(value) => LedgerChange::DatastoreWrite {
+                                key: key.clone(),
+                                value: value.clone(),
+                            },
+                            SetOrDelete::Delete => {
+                                LedgerChange::DatastoreDelete { key: key.clone() }
+                            }
+                        };
+                        (*address, change)
+                    }));
+                }
+                SetUpdateOrDelete::Delete => {
+                    ledger_changes.push((*address, LedgerChange::EntryDeleted));
+                }
+            }
+        }
+        StateChangesSummary {
+            ledger_changes,
+            async_message_changes: changes.async_pool_changes.0.len(),
+            roll_changes: changes.pos_changes.roll_changes.len(),
+        }
+    }
+}
+
 /// read only bytecode execution request
 #[derive(Debug, Deserialize, Clone, Serialize)]
 pub struct ReadOnlyBytecodeExecution {
@@ -68,6 +214,9 @@ pub struct ReadOnlyBytecodeExecution {
     pub operation_datastore: Option<Vec<u8>>,
     /// fee
     pub fee: Option<Amount>,
+    /// if set, only the events emitted by these addresses are returned
+    #[serde(default)]
+    pub only_from: Option<Vec<Address>>,
 }
 
 /// read SC call request
@@ -87,6 +236,9 @@ pub struct ReadOnlyCall {
     pub coins: Option<Amount>,
     /// fee
     pub fee: Option<Amount>,
+    /// if set, only the events emitted by these addresses are returned
+    #[serde(default)]
+    pub only_from: Option<Vec<Address>>,
 }
 
 /// Context of the transfer
@@ -121,3 +273,172 @@ pub struct Transfer {
     /// Context
     pub context: TransferContext,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use massa_ledger_exports::{LedgerEntry, LedgerEntryUpdate};
+    use massa_models::{bytecode::Bytecode, output_event::EventExecutionContext};
+    use massa_signature::KeyPair;
+
+    fn response_with_result(result: ReadOnlyResult) -> ExecuteReadOnlyResponse {
+        ExecuteReadOnlyResponse {
+            executed_at: Slot::new(1, 0),
+            result,
+            output_events: VecDeque::new(),
+            gas_cost: 0,
+            state_changes: StateChanges::default(),
+        }
+    }
+
+    #[test]
+    fn test_friendly_error() {
+        let response = response_with_result(ReadOnlyResult::Ok(Vec::new()));
+        assert_eq!(response.friendly_error(), None);
+
+        let response = response_with_result(ReadOnlyResult::Error(
+            "readonly call failed: VM Error in readonly call context: RuntimeError: Not enough gas, limit reached at: foo".to_string(),
+        ));
+        assert_eq!(
+            response.friendly_error().unwrap(),
+            "the execution ran out of gas, try again with a higher max gas"
+        );
+
+        let response = response_with_result(ReadOnlyResult::Error(
+            "readonly call failed: The called smart contract address AS12 does not exist"
+                .to_string(),
+        ));
+        assert_eq!(
+            response.friendly_error().unwrap(),
+            "the target smart contract does not exist"
+        );
+
+        let raw_error =
+            "readonly call failed: could not set data for address AS12: address does not exist"
+                .to_string();
+        let response = response_with_result(ReadOnlyResult::Error(raw_error.clone()));
+        assert_eq!(response.friendly_error().unwrap(), raw_error);
+
+        let raw_error = "readonly call failed: something unexpected".to_string();
+        let response = response_with_result(ReadOnlyResult::Error(raw_error.clone()));
+        assert_eq!(response.friendly_error().unwrap(), raw_error);
+    }
+
+    fn event_from(call_stack: Vec<Address>, data: &str) -> SCOutputEvent {
+        SCOutputEvent {
+            context: EventExecutionContext {
+                slot: Slot::new(1, 0),
+                block: None,
+                read_only: true,
+                index_in_slot: 0,
+                call_stack: call_stack.into(),
+                origin_operation_id: None,
+                is_final: false,
+                is_error: false,
+            },
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_retain_events_from() {
+        let caller = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let sc_a = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let sc_b = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+
+        let mut response = response_with_result(ReadOnlyResult::Ok(Vec::new()));
+        response.output_events = VecDeque::from(vec![
+            event_from(vec![caller, sc_a], "a1"),
+            event_from(vec![caller, sc_b], "b1"),
+            event_from(vec![caller, sc_a, sc_b], "b2"),
+            event_from(vec![caller, sc_b, sc_a], "a2"),
+            event_from(vec![], "no emitter"),
+        ]);
+
+        response.retain_events_from(&[sc_a]);
+        let kept: Vec<&str> = response
+            .output_events
+            .iter()
+            .map(|event| event.data.as_str())
+            .collect();
+        assert_eq!(kept, vec!["a1", "a2"]);
+
+        response.retain_events_from(&[]);
+        assert!(response.output_events.is_empty());
+    }
+
+    #[test]
+    fn test_state_changes_summary() {
+        let created = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let updated = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let deleted = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+
+        let mut changes = StateChanges::default();
+        changes.ledger_changes.0.insert(
+            created,
+            SetUpdateOrDelete::Set(LedgerEntry {
+                balance: Amount::from_raw(10),
+                bytecode: Bytecode(vec![0; 3]),
+                datastore: [(b"k".to_vec(), b"v".to_vec())].into_iter().collect(),
+            }),
+        );
+        changes.ledger_changes.0.insert(
+            updated,
+            SetUpdateOrDelete::Update(LedgerEntryUpdate {
+                balance: SetOrKeep::Set(Amount::from_raw(5)),
+                bytecode: SetOrKeep::Keep,
+                datastore: [
+                    (b"a".to_vec(), SetOrDelete::Set(b"1".to_vec())),
+                    (b"b".to_vec(), SetOrDelete::Delete),
+                ]
+                .into_iter()
+                .collect(),
+            }),
+        );
+        changes
+            .ledger_changes
+            .0
+            .insert(deleted, SetUpdateOrDelete::Delete);
+        changes.pos_changes.roll_changes.insert(updated, 2);
+
+        let summary = StateChangesSummary::new(&changes);
+        assert_eq!(summary.ledger_changes.len(), 7);
+        assert!(summary
+            .ledger_changes
+            .windows(2)
+            .all(|pair| pair[0].0 <= pair[1].0));
+        let changes_of = |address: Address| -> Vec<LedgerChange> {
+            summary
+                .ledger_changes
+                .iter()
+                .filter(|(addr, _)| *addr == address)
+                .map(|(_, change)| change.clone())
+                .collect()
+        };
+        assert_eq!(
+            changes_of(created),
+            vec![
+                LedgerChange::Balance(Amount::from_raw(10)),
+                LedgerChange::Bytecode(3),
+                LedgerChange::DatastoreWrite {
+                    key: b"k".to_vec(),
+                    value: b"v".to_vec()
+                },
+            ]
+        );
+        assert_eq!(
+            changes_of(updated),
+            vec![
+                LedgerChange::Balance(Amount::from_raw(5)),
+                LedgerChange::DatastoreWrite {
+                    key: b"a".to_vec(),
+                    value: b"1".to_vec()
+                },
+                LedgerChange::DatastoreDelete { key: b"b".to_vec() },
+            ]
+        );
+        assert_eq!(changes_of(deleted), vec![LedgerChange::EntryDeleted]);
+        assert_eq!(summary.async_message_changes, 0);
+        assert_eq!(summary.roll_changes, 1);
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
+use massa_models::address::Address;
 use massa_models::amount::Amount;
-use massa_models::ledger::LedgerData;
+use massa_models::ledger::{LedgerData, LedgerDataExport};
+use massa_models::prehash::PreHashMap;
+use massa_models::slot::Slot;
 
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +19,22 @@ pub struct LedgerInfo {
     pub locked_balance: Amount,
 }
 
+impl LedgerInfo {
+    /// Build the ledger info of an address from a ledger export.
+    /// If the address is missing from the final or candidate data, that side is left empty.
+    pub fn from_export(export: &LedgerDataExport, address: &Address, locked: Amount) -> LedgerInfo {
+        LedgerInfo {
+            final_ledger_info: export.final_data.get(address).copied().unwrap_or_default(),
+            candidate_ledger_info: export
+                .candidate_data
+                .get(address)
+                .copied()
+                .unwrap_or_default(),
+            locked_balance: locked,
+        }
+    }
+}
+
 impl std::fmt::Display for LedgerInfo {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "\tFinal balance: {}", self.final_ledger_info.balance)?;
@@ -28,3 +47,216 @@ impl std::fmt::Display for LedgerInfo {
         Ok(())
     }
 }
+
+/// Address whose balance changes are queried over a range of executed slots
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub struct BalanceChangeDetail {
+    /// address to look for
+    pub address: Address,
+    /// first slot of the range
+    pub start: Slot,
+    /// last slot of the range (included)
+    pub end: Slot,
+}
+
+/// Transfer to simulate
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct TransferSimulationQuery {
+    /// address paying the transfer
+    pub sender_address: Address,
+    /// address receiving the coins
+    pub recipient_address: Address,
+    /// transferred amount
+    pub amount: Amount,
+    /// fee paid by the sender
+    pub fee: Amount,
+}
+
+/// Balances of an address before and after a simulated transfer.
+/// An `after` balance is `None` if the sender cannot pay the transfer on that ledger.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct TransferBalances {
+    /// balance on the final ledger before the transfer
+    pub final_before: Amount,
+    /// balance on the final ledger after the transfer
+    pub final_after: Option<Amount>,
+    /// balance on the candidate ledger before the transfer
+    pub candidate_before: Amount,
+    /// balance on the candidate ledger after the transfer
+    pub candidate_after: Option<Amount>,
+}
+
+/// Projected balances of both sides of a transfer.
+/// Final and candidate balances differ when operations are pending.
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct TransferSimulation {
+    /// sender balances
+    pub sender: TransferBalances,
+    /// recipient balances
+    pub recipient: TransferBalances,
+}
+
+impl TransferSimulation {
+    /// Simulate a transfer on the final and candidate data of a ledger export.
+    /// Addresses missing from a side of the export are considered to have no coins on it.
+    pub fn from_export(export: &LedgerDataExport, query: &TransferSimulationQuery) -> Self {
+        let (final_sender, final_recipient) = Self::project(&export.final_data, query);
+        let (candidate_sender, candidate_recipient) = Self::project(&export.candidate_data, query);
+        TransferSimulation {
+            sender: TransferBalances {
+                final_before: final_sender.0,
+                final_after: final_sender.1,
+                candidate_before: candidate_sender.0,
+                candidate_after: candidate_sender.1,
+            },
+            recipient: TransferBalances {
+                final_before: final_recipient.0,
+                final_after: final_recipient.1,
+                candidate_before: candidate_recipient.0,
+                candidate_after: candidate_recipient.1,
+            },
+        }
+    }
+
+    /// Returns the (before, after) balances of the sender and of the recipient on one ledger
+    #[allow(clippy::type_complexity)]
+    fn project(
+        data: &PreHashMap<Address, LedgerData>,
+        query: &TransferSimulationQuery,
+    ) -> ((Amount, Option<Amount>), (Amount, Option<Amount>)) {
+        let balance = |address: &Address| {
+            data.get(address)
+                .map(|entry| entry.balance)
+                .unwrap_or_default()
+        };
+        let sender_before = balance(&query.sender_address);
+        let recipient_before = balance(&query.recipient_address);
+        let sender_after = query
+            .amount
+            .checked_add(query.fee)
+            .and_then(|spent| sender_before.checked_sub(spent));
+        if query.sender_address == query.recipient_address {
+            // only the fee is spent
+            let after = sender_after.map(|balance| balance.saturating_add(query.amount));
+            return ((sender_before, after), (recipient_before, after));
+        }
+        let recipient_after = sender_after.map(|_| recipient_before.saturating_add(query.amount));
+        (
+            (sender_before, sender_after),
+            (recipient_before, recipient_after),
+        )
+    }
+}
+
+impl std::fmt::Display for TransferBalances {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let display_after = |after: Option<Amount>| match after {
+            Some(balance) => balance.to_string(),
+            None => "insufficient balance".to_string(),
+        };
+        writeln!(
+            f,
+            "\tFinal balance: {} -> {}",
+            self.final_before,
+            display_after(self.final_after)
+        )?;
+        writeln!(
+            f,
+            "\tCandidate balance: {} -> {}",
+            self.candidate_before,
+            display_after(self.candidate_after)
+        )?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for TransferSimulation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Sender:")?;
+        write!(f, "{}", self.sender)?;
+        writeln!(f, "Recipient:")?;
+        write!(f, "{}", self.recipient)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_ledger_info_from_export_final_only() {
+        let address =
+            Address::from_str("AU12hgh5ULW9o8fJE9muLNXhQENaUUswQbxPyDSq8ridnDGu5gRiJ").unwrap();
+        let mut export = LedgerDataExport::default();
+        export.final_data.insert(
+            address,
+            LedgerData {
+                balance: Amount::from_str("1349").unwrap(),
+            },
+        );
+
+        let info = LedgerInfo::from_export(&export, &address, Amount::from_str("10").unwrap());
+        assert_eq!(
+            info.final_ledger_info.balance,
+            Amount::from_str("1349").unwrap()
+        );
+        assert_eq!(info.candidate_ledger_info.balance, Amount::default());
+        assert_eq!(info.locked_balance, Amount::from_str("10").unwrap());
+    }
+
+    #[test]
+    fn test_transfer_simulation_from_export() {
+        let sender =
+            Address::from_str("AU12hgh5ULW9o8fJE9muLNXhQENaUUswQbxPyDSq8ridnDGu5gRiJ").unwrap();
+        let recipient =
+            Address::from_str("AU12htxRWiEm8jDJpJptr6cwEhWNcCSFWstN1MLSa96DDkVM9Y42G").unwrap();
+        let data = |balance: &str| LedgerData {
+            balance: Amount::from_str(balance).unwrap(),
+        };
+        // the sender spent 60 coins in a pending operation
+        let mut export = LedgerDataExport::default();
+        export.final_data.insert(sender, data("100"));
+        export.candidate_data.insert(sender, data("40"));
+        export.candidate_data.insert(recipient, data("5"));
+        let query = TransferSimulationQuery {
+            sender_address: sender,
+            recipient_address: recipient,
+            amount: Amount::from_str("50").unwrap(),
+            fee: Amount::from_str("1").unwrap(),
+        };
+
+        let simulation = TransferSimulation::from_export(&export, &query);
+        assert_eq!(
+            simulation.sender,
+            TransferBalances {
+                final_before: Amount::from_str("100").unwrap(),
+                final_after: Some(Amount::from_str("49").unwrap()),
+                candidate_before: Amount::from_str("40").unwrap(),
+                candidate_after: None,
+            }
+        );
+        assert_eq!(
+            simulation.recipient,
+            TransferBalances {
+                final_before: Amount::zero(),
+                final_after: Some(Amount::from_str("50").unwrap()),
+                candidate_before: Amount::from_str("5").unwrap(),
+                candidate_after: None,
+            }
+        );
+
+        // sending to oneself only costs the fee
+        let query = TransferSimulationQuery {
+            recipient_address: sender,
+            ..query
+        };
+        let simulation = TransferSimulation::from_export(&export, &query);
+        assert_eq!(simulation.sender, simulation.recipient);
+        assert_eq!(
+            simulation.sender.final_after,
+            Some(Amount::from_str("99").unwrap())
+        );
+    }
+}
@@ -5,6 +5,7 @@
 #![warn(missing_docs)]
 #![warn(unused_crate_dependencies)]
 
+use crate::error::ApiError;
 use crate::page::PageRequest;
 use massa_time::MassaTime;
 use serde::{Deserialize, Serialize};
@@ -13,6 +14,8 @@ use serde::{Deserialize, Serialize};
 pub mod address;
 /// block-related structures
 pub mod block;
+/// compact cliques for transport
+pub mod clique;
 /// node configuration
 pub mod config;
 /// datastore serialization / deserialization
@@ -65,6 +68,28 @@ fn display_option_bool(
     }
 }
 
+/// Machine-readable alternative to the `Display` of the structures returned by the API,
+/// for scripts and monitoring tools that should not parse the human oriented output.
+///
+/// The JSON shape is stable and follows the serde representation of the structure:
+/// * every field is present, in declaration order, with the name it has in the structure
+/// * optional fields are never omitted: they are `null` when empty (e.g. `node_ip`, `last_slot`)
+/// * amounts are decimal strings, ids, addresses and keys are their string representation
+/// * enums are externally tagged, unit variants being plain strings
+/// * maps are objects, tuples and sequences are arrays
+pub trait PrettyJson: Serialize {
+    /// Indented JSON representation of the structure
+    fn to_pretty_json(&self) -> Result<String, ApiError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|err| ApiError::InternalServerError(format!("JSON serialization: {}", err)))
+    }
+}
+
+impl PrettyJson for node::NodeStatus {}
+impl PrettyJson for address::AddressInfo {}
+impl PrettyJson for block::BlockInfo {}
+impl PrettyJson for operation::OperationInfo {}
+
 /// Just a wrapper with a optional beginning and end
 #[derive(Debug, Deserialize, Clone, Copy, Serialize)]
 pub struct TimeInterval {
@@ -36,6 +36,10 @@ pub struct NodeStatus {
     pub consensus_stats: ConsensusStats,
     /// pool stats (operation count and endorsement count)
     pub pool_stats: (usize, usize),
+    /// false until a block becomes final after startup (the graph is still catching up), in which case data can be partial
+    pub ready: bool,
+    /// minimal fee currently required for an operation to enter the pool (rises with pool congestion)
+    pub operation_fee_floor: Amount,
     /// network stats
     pub network_stats: NetworkStats,
     /// execution stats
@@ -62,6 +66,9 @@ impl std::fmt::Display for NodeStatus {
         writeln!(f, "Config:\n{}", self.config)?;
         writeln!(f)?;
 
+        if !self.ready {
+            writeln!(f, "Not ready: the block graph is still catching up")?;
+        }
         writeln!(f, "Current time: {}", self.current_time.format_instant())?;
         writeln!(f, "Current cycle: {}", self.current_cycle)?;
         if self.last_slot.is_some() {
@@ -75,6 +82,7 @@ impl std::fmt::Display for NodeStatus {
         writeln!(f, "Pool stats:")?;
         writeln!(f, "\tOperations count: {}", self.pool_stats.0)?;
         writeln!(f, "\tEndorsements count: {}", self.pool_stats.1)?;
+        writeln!(f, "\tOperation fee floor: {}", self.operation_fee_floor)?;
         writeln!(f)?;
 
         writeln!(f, "{}", self.network_stats)?;
@@ -94,3 +102,84 @@ impl std::fmt::Display for NodeStatus {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PrettyJson;
+    use massa_models::stats::{DiscardedBlockCounts, SlotMessageCounters};
+    use massa_signature::KeyPair;
+    use serde_json::Value;
+    use std::str::FromStr;
+
+    fn node_status() -> NodeStatus {
+        let time = MassaTime::from_millis(1_000);
+        NodeStatus {
+            node_id: NodeId::new(KeyPair::generate(0).unwrap().get_public_key()),
+            node_ip: None,
+            version: Version::from_str("TEST.1.0").unwrap(),
+            current_time: time,
+            current_cycle: 0,
+            current_cycle_time: time,
+            next_cycle_time: time,
+            connected_nodes: BTreeMap::new(),
+            last_slot: None,
+            next_slot: Slot::new(0, 0),
+            consensus_stats: ConsensusStats {
+                start_timespan: time,
+                end_timespan: time,
+                final_block_count: 0,
+                stale_block_count: 0,
+                clique_count: 1,
+                average_finality_latency: None,
+                recent_discarded_blocks: DiscardedBlockCounts::default(),
+                slots_since_last_finality: 0,
+                production_rate_per_thread: vec![],
+                endorsement_participation: None,
+                pruned_operation_count: 0,
+            },
+            pool_stats: (0, 0),
+            ready: true,
+            operation_fee_floor: Amount::from_str("0.01").unwrap(),
+            network_stats: NetworkStats {
+                in_connection_count: 0,
+                out_connection_count: 0,
+                known_peer_count: 0,
+                banned_peer_count: 0,
+                active_node_count: 0,
+                message_counters: SlotMessageCounters::default(),
+            },
+            execution_stats: ExecutionStats {
+                time_window_start: time,
+                time_window_end: time,
+                final_block_count: 0,
+                final_executed_operations_count: 0,
+                active_cursor: Slot::new(0, 0),
+                final_cursor: Slot::new(0, 0),
+            },
+            config: CompactConfig::default(),
+            chain_id: 77658377,
+            minimal_fees: Amount::from_str("0.01").unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_node_status_pretty_json() {
+        let json = node_status().to_pretty_json().unwrap();
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let status = value.as_object().unwrap();
+
+        // empty options are kept as explicit nulls
+        assert!(status["node_ip"].is_null());
+        assert!(status["last_slot"].is_null());
+        assert_eq!(status["operation_fee_floor"], Value::from("0.01"));
+        assert_eq!(status["pool_stats"], serde_json::json!([0, 0]));
+
+        // fields come in declaration order
+        let node_ip = json.find("\"node_ip\"").unwrap();
+        let last_slot = json.find("\"last_slot\"").unwrap();
+        let minimal_fees = json.find("\"minimal_fees\"").unwrap();
+        assert!(json.find("\"node_id\"").unwrap() < node_ip);
+        assert!(node_ip < last_slot && last_slot < minimal_fees);
+    }
+}
@@ -1,8 +1,10 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
 use massa_models::{
+    address::Address,
+    amount::Amount,
     block_id::BlockId,
-    operation::{OperationId, SecureShareOperation},
+    operation::{OperationId, OperationType, SecureShareOperation},
 };
 
 use massa_signature::{PublicKey, Signature};
@@ -11,7 +13,7 @@ use serde::{Deserialize, Serialize};
 use crate::{display_if_true, display_option_bool};
 
 /// operation input
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct OperationInput {
     /// The public key of the creator of the TX
     pub creator_public_key: PublicKey,
@@ -39,6 +41,106 @@ pub struct OperationInfo {
     pub operation: SecureShareOperation,
     /// true if the operation execution succeeded, false if failed, None means unknown
     pub op_exec_status: Option<bool>,
+    /// public memo attached to the operation, see `OperationType::memo`
+    #[serde(default)]
+    pub memo: Option<String>,
+    /// signed bytes of the operation along with its signature, only filled when requested
+    #[serde(default)]
+    pub raw: Option<OperationInput>,
+}
+
+/// Query of the operations sent by an address, from the pool and the block graph
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct CombinedOpsQuery {
+    /// creator of the operations
+    pub sender: Address,
+    /// include the operations only waiting in the pool
+    pub include_pool: bool,
+    /// include the final operations
+    pub include_final: bool,
+    /// include the operations in candidate (not yet final) blocks
+    pub include_candidate: bool,
+}
+
+/// Operations sent by an address, as returned by `get_operations_by_sender`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OperationsBySender {
+    /// the operations matching the query
+    pub operations: Vec<OperationInfo>,
+    /// number of known operations created by the address, before the cap and the status filtering
+    pub total_count: u64,
+    /// true if only the most recent `max_ops_per_address_query` operations were looked at
+    pub truncated: bool,
+}
+
+/// Flat view of the kind of an operation along with its key parameters
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub enum OperationKind {
+    /// coins transfer
+    Transaction {
+        /// recipient address
+        recipient_address: Address,
+        /// amount
+        amount: Amount,
+    },
+    /// roll purchase
+    RollBuy {
+        /// roll count
+        roll_count: u64,
+    },
+    /// roll sale
+    RollSell {
+        /// roll count
+        roll_count: u64,
+    },
+    /// call of a function of a stored smart contract
+    CallSC {
+        /// target smart contract address
+        target_addr: Address,
+        /// target function name
+        target_func: String,
+        /// coins transferred to the target
+        coins: Amount,
+    },
+    /// execution of a smart contract bytecode
+    ExecuteSC {
+        /// max amount of coins allowed to be spent by the execution
+        max_coins: Amount,
+    },
+}
+
+impl OperationInfo {
+    /// Get the kind of the operation along with its key parameters
+    pub fn kind(&self) -> OperationKind {
+        match &self.operation.content.op {
+            OperationType::Transaction {
+                recipient_address,
+                amount,
+            } => OperationKind::Transaction {
+                recipient_address: *recipient_address,
+                amount: *amount,
+            },
+            OperationType::RollBuy { roll_count } => OperationKind::RollBuy {
+                roll_count: *roll_count,
+            },
+            OperationType::RollSell { roll_count } => OperationKind::RollSell {
+                roll_count: *roll_count,
+            },
+            OperationType::CallSC {
+                target_addr,
+                target_func,
+                coins,
+                ..
+            } => OperationKind::CallSC {
+                target_addr: *target_addr,
+                target_func: target_func.clone(),
+                coins: *coins,
+            },
+            OperationType::ExecuteSC { max_coins, .. } => OperationKind::ExecuteSC {
+                max_coins: *max_coins,
+            },
+        }
+    }
 }
 
 impl std::fmt::Display for OperationInfo {
@@ -56,6 +158,9 @@ impl std::fmt::Display for OperationInfo {
             ),
             display_option_bool(self.op_exec_status, "succes", "failed", "status unknown")
         )?;
+        if let Some(memo) = &self.memo {
+            writeln!(f, "Memo: {}", memo)?;
+        }
         writeln!(f, "In blocks:")?;
         for block_id in &self.in_blocks {
             writeln!(f, "\t- {}", block_id)?;
@@ -67,8 +172,16 @@ impl std::fmt::Display for OperationInfo {
 
 #[cfg(test)]
 mod tests {
+    use super::{OperationInfo, OperationKind};
     use jsonrpsee::core::__reexports::serde_json::{self, Value};
-    use massa_models::{amount::Amount, operation::OperationType};
+    use massa_models::{
+        address::Address,
+        amount::Amount,
+        config::CHAINID,
+        operation::{Operation, OperationSerializer, OperationType},
+        secure_share::SecureShareContent,
+    };
+    use massa_signature::KeyPair;
     use serial_test::serial;
     use std::collections::BTreeMap;
     use std::str::FromStr;
@@ -107,4 +220,66 @@ mod tests {
         let actual_op: OperationType = serde_json::from_str(&op_json_str).unwrap();
         assert_eq!(actual_op, expected_op);
     }
+
+    #[test]
+    #[serial]
+    fn test_operation_kind() {
+        let keypair = KeyPair::generate(0).unwrap();
+        let recipient_address = Address::from_public_key(&keypair.get_public_key());
+        let make_info = |op: OperationType| {
+            let operation = Operation::new_verifiable(
+                Operation {
+                    fee: Amount::from_str("0.01").unwrap(),
+                    expire_period: 10,
+                    op,
+                },
+                OperationSerializer::new(),
+                &keypair,
+                *CHAINID,
+            )
+            .unwrap();
+            OperationInfo {
+                id: operation.id,
+                in_pool: true,
+                in_blocks: vec![],
+                is_operation_final: None,
+                thread: operation.content_creator_address.get_thread(32),
+                memo: operation.content.op.memo(),
+                operation,
+                op_exec_status: None,
+                raw: None,
+            }
+        };
+
+        let info = make_info(OperationType::Transaction {
+            recipient_address,
+            amount: Amount::from_str("300").unwrap(),
+        });
+        assert_eq!(
+            info.kind(),
+            OperationKind::Transaction {
+                recipient_address,
+                amount: Amount::from_str("300").unwrap(),
+            }
+        );
+
+        let info = make_info(OperationType::RollSell { roll_count: 3 });
+        assert_eq!(info.kind(), OperationKind::RollSell { roll_count: 3 });
+
+        let info = make_info(OperationType::CallSC {
+            target_addr: recipient_address,
+            target_func: "transfer".to_string(),
+            param: vec![1, 2, 3],
+            max_gas: 100_000,
+            coins: Amount::from_str("1").unwrap(),
+        });
+        assert_eq!(
+            info.kind(),
+            OperationKind::CallSC {
+                target_addr: recipient_address,
+                target_func: "transfer".to_string(),
+                coins: Amount::from_str("1").unwrap(),
+            }
+        );
+    }
 }
@@ -1,5 +1,6 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
+use massa_models::amount::Amount;
 use serde::{Deserialize, Serialize};
 
 /// Roll counts
@@ -21,3 +22,33 @@ impl std::fmt::Display for RollsInfo {
         Ok(())
     }
 }
+
+/// Proof-of-stake parameters of the network, for staking tooling
+#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+pub struct PosParams {
+    /// price of a roll
+    pub roll_price: Amount,
+    /// cycle duration in periods
+    pub periods_per_cycle: u64,
+    /// number of threads
+    pub thread_count: u8,
+    /// number of cycles after the cycle of a roll sale at the end of which the price of the sold rolls is credited
+    pub roll_sell_credit_delay_cycles: u64,
+    /// reward for a block creation
+    pub block_reward: Amount,
+}
+
+impl std::fmt::Display for PosParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Roll price: {}", self.roll_price)?;
+        writeln!(f, "Periods per cycle: {}", self.periods_per_cycle)?;
+        writeln!(f, "Thread count: {}", self.thread_count)?;
+        writeln!(
+            f,
+            "Roll sale credit delay: {} cycles",
+            self.roll_sell_credit_delay_cycles
+        )?;
+        writeln!(f, "Block reward: {}", self.block_reward)?;
+        Ok(())
+    }
+}
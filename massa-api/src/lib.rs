@@ -13,20 +13,29 @@ use jsonrpsee::server::{BatchRequestConfig, ServerBuilder, ServerHandle};
 use jsonrpsee::RpcModule;
 use massa_api_exports::execution::Transfer;
 use massa_api_exports::{
-    address::{AddressFilter, AddressInfo},
+    address::{
+        AddressFilter, AddressInfo, AddressInfoField, DrawKind, DrawScheduleQuery,
+        MultiAddressInfo, MultiAddressInfoQuery, PendingRollSalesQuery, RollSellPreview,
+    },
     block::{BlockInfo, BlockSummary},
+    clique::CompactCliques,
     config::APIConfig,
     datastore::{DatastoreEntryInput, DatastoreEntryOutput},
-    endorsement::EndorsementInfo,
+    endorsement::{EndorsementDraw, EndorsementDrawsQuery, EndorsementInfo},
     error::ApiError::WrongAPI,
     execution::{ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall},
+    ledger::{BalanceChangeDetail, TransferSimulation, TransferSimulationQuery},
     node::NodeStatus,
-    operation::{OperationInfo, OperationInput},
+    operation::{CombinedOpsQuery, OperationInfo, OperationInput, OperationsBySender},
     page::{PageRequest, PagedVec},
+    rolls::PosParams,
     TimeInterval,
 };
 use massa_consensus_exports::{ConsensusBroadcasts, ConsensusController};
 use massa_execution_exports::ExecutionController;
+use massa_final_state::FinalStateController;
+use massa_hash::Hash;
+use massa_models::address_history::AddressHistoryBundle;
 use massa_models::clique::Clique;
 use massa_models::composite::PubkeySig;
 use massa_models::node::NodeId;
@@ -34,13 +43,14 @@ use massa_models::operation::OperationId;
 use massa_models::output_event::SCOutputEvent;
 use massa_models::prehash::PreHashSet;
 use massa_models::{
-    address::Address, block::Block, block_id::BlockId, endorsement::EndorsementId,
+    address::Address, amount::Amount, block::Block, block_id::BlockId, endorsement::EndorsementId,
     execution::EventFilter, slot::Slot, version::Version,
 };
 use massa_pool_exports::{PoolBroadcasts, PoolController};
 use massa_pos_exports::SelectorController;
 use massa_protocol_exports::{ProtocolConfig, ProtocolController};
 use massa_storage::Storage;
+use massa_time::MassaTime;
 use massa_versioning::keypair_factory::KeyPairFactory;
 use massa_wallet::Wallet;
 use parking_lot::RwLock;
@@ -102,6 +112,8 @@ pub struct Private {
     pub stop_cv: Arc<(Mutex<bool>, Condvar)>,
     /// User wallet
     pub node_wallet: Arc<RwLock<Wallet>>,
+    /// final state, to access the final ledger
+    pub final_state: Arc<RwLock<dyn FinalStateController>>,
 }
 
 /// API v2 content
@@ -234,6 +246,12 @@ pub trait MassaRpc {
     #[method(name = "node_sign_message")]
     async fn node_sign_message(&self, arg: Vec<u8>) -> RpcResult<PubkeySig>;
 
+    /// Write every final ledger entry to a JSON file on the node's filesystem,
+    /// in the same format as the initial ledger file.
+    /// Returns the number of exported entries.
+    #[method(name = "node_export_ledger")]
+    async fn node_export_ledger(&self, arg: String) -> RpcResult<usize>;
+
     /// Add a vector of new secret(private) keys for the node to use to stake.
     /// No confirmation to expect.
     #[method(name = "add_staking_secret_keys")]
@@ -335,6 +353,18 @@ pub trait MassaRpc {
     #[method(name = "get_cliques")]
     async fn get_cliques(&self) -> RpcResult<Vec<Clique>>;
 
+    /// Get cliques in a compact form, listing each block id once.
+    #[method(name = "get_compact_cliques")]
+    async fn get_compact_cliques(&self) -> RpcResult<CompactCliques>;
+
+    /// Get a fingerprint of the genesis block ids, identifying the network the node runs on.
+    #[method(name = "get_genesis_fingerprint")]
+    async fn get_genesis_fingerprint(&self) -> RpcResult<Hash>;
+
+    /// Get the proof-of-stake parameters of the network: roll price, cycle duration, roll sale credit delay...
+    #[method(name = "get_pos_params")]
+    async fn get_pos_params(&self) -> RpcResult<PosParams>;
+
     /// Returns the active stakers and their active roll counts for the current cycle.
     #[method(name = "get_stakers")]
     async fn get_stakers(
@@ -343,14 +373,42 @@ pub trait MassaRpc {
     ) -> RpcResult<PagedVec<(Address, u64)>>;
 
     /// Returns operation(s) information associated to a given list of operation(s) ID(s).
+    /// If `include_raw` is true, the signed bytes and the signature of each operation are included.
     #[method(name = "get_operations")]
-    async fn get_operations(&self, arg: Vec<OperationId>) -> RpcResult<Vec<OperationInfo>>;
+    async fn get_operations(
+        &self,
+        arg: Vec<OperationId>,
+        include_raw: Option<bool>,
+    ) -> RpcResult<Vec<OperationInfo>>;
+
+    /// Returns the operations created by the given address(es) that are still waiting in the pool,
+    /// i.e. not yet included in a block.
+    #[method(name = "get_pending_operations")]
+    async fn get_pending_operations(&self, arg: Vec<Address>) -> RpcResult<Vec<OperationInfo>>;
+
+    /// Returns the operations created by an address, from the pool and the block graph,
+    /// each one filtered on its most advanced status: final, then candidate, then pool.
+    /// Only the most recent `max_ops_per_address_query` operations are looked at.
+    #[method(name = "get_operations_by_sender")]
+    async fn get_operations_by_sender(
+        &self,
+        arg: CombinedOpsQuery,
+    ) -> RpcResult<OperationsBySender>;
 
     /// Returns endorsement(s) information associated to a given list of endorsement(s) ID(s)
     #[method(name = "get_endorsements")]
     async fn get_endorsements(&self, arg: Vec<EndorsementId>) -> RpcResult<Vec<EndorsementInfo>>;
 
-    /// Returns block(s) information associated to a given list of block(s) ID(s)
+    /// Returns the addresses drawn to create endorsements in a slot range.
+    /// The range must be within the cycles for which draws are available.
+    #[method(name = "get_endorsement_draws")]
+    async fn get_endorsement_draws(
+        &self,
+        arg: EndorsementDrawsQuery,
+    ) -> RpcResult<Vec<EndorsementDraw>>;
+
+    /// Returns block(s) information associated to a given list of block(s) ID(s),
+    /// one entry per ID in the request order, with no content for the unknown blocks
     #[method(name = "get_blocks")]
     async fn get_blocks(&self, arg: Vec<BlockId>) -> RpcResult<Vec<BlockInfo>>;
 
@@ -372,8 +430,66 @@ pub trait MassaRpc {
     ) -> RpcResult<Vec<DatastoreEntryOutput>>;
 
     /// Get addresses.
+    /// If `fields` is given, only these sections of the address info are computed, the others are left empty.
     #[method(name = "get_addresses")]
-    async fn get_addresses(&self, arg: Vec<Address>) -> RpcResult<Vec<AddressInfo>>;
+    async fn get_addresses(
+        &self,
+        arg: Vec<Address>,
+        fields: Option<Vec<AddressInfoField>>,
+    ) -> RpcResult<Vec<AddressInfo>>;
+
+    /// Get the information of many addresses at once, in the order of the query.
+    /// Compact queries only return the balances and roll counts. Unknown addresses get zeroed entries.
+    #[method(name = "get_multi_address_info")]
+    async fn get_multi_address_info(
+        &self,
+        arg: MultiAddressInfoQuery,
+    ) -> RpcResult<MultiAddressInfo>;
+
+    /// Returns the upcoming block and endorsement draws of an address, along with their timestamp.
+    /// The look-ahead is clamped to the draws kept by the selector.
+    #[method(name = "get_draw_schedule")]
+    async fn get_draw_schedule(
+        &self,
+        arg: DrawScheduleQuery,
+    ) -> RpcResult<Vec<(Slot, MassaTime, DrawKind)>>;
+
+    /// Returns the roll sales of an address whose coins are not credited yet,
+    /// as (credit slot, number of rolls sold, credited amount).
+    #[method(name = "get_pending_roll_sales")]
+    async fn get_pending_roll_sales(
+        &self,
+        arg: PendingRollSalesQuery,
+    ) -> RpcResult<Vec<(Slot, u64, Amount)>>;
+
+    /// Previews a roll sale executed in the next slot,
+    /// as (slot at which the coins would be credited, credited amount).
+    #[method(name = "preview_roll_sell")]
+    async fn preview_roll_sell(&self, arg: RollSellPreview) -> RpcResult<(Slot, Amount)>;
+
+    /// Returns the executed operations of a slot range that changed the balance of an address,
+    /// as `(operation id, is credit, amount)`. Requires the `execution-trace` feature.
+    #[method(name = "get_balance_change_detail")]
+    async fn get_balance_change_detail(
+        &self,
+        arg: BalanceChangeDetail,
+    ) -> RpcResult<Vec<(OperationId, bool, Amount)>>;
+
+    /// Simulates a transfer on both the final and the candidate ledgers.
+    #[method(name = "simulate_transfer")]
+    async fn simulate_transfer(
+        &self,
+        arg: TransferSimulationQuery,
+    ) -> RpcResult<TransferSimulation>;
+
+    /// Returns the operations and roll changes of an address in the final blocks retained by the node,
+    /// signed by the node for its chain id if `sign` is true. The final blocks already pruned by the node are not covered.
+    #[method(name = "get_address_history")]
+    async fn get_address_history(
+        &self,
+        address: Address,
+        sign: bool,
+    ) -> RpcResult<AddressHistoryBundle>;
 
     /// Get addresses bytecode.
     #[method(name = "get_addresses_bytecode")]
@@ -5,27 +5,37 @@ use crate::{MassaRpcServer, Private, RpcServer, StopHandle, Value, API};
 use async_trait::async_trait;
 use jsonrpsee::core::{Error as JsonRpseeError, RpcResult};
 use massa_api_exports::{
-    address::{AddressFilter, AddressInfo},
+    address::{
+        AddressFilter, AddressInfo, AddressInfoField, DrawKind, DrawScheduleQuery,
+        MultiAddressInfo, MultiAddressInfoQuery, PendingRollSalesQuery, RollSellPreview,
+    },
     block::{BlockInfo, BlockSummary},
+    clique::CompactCliques,
     config::APIConfig,
     datastore::{DatastoreEntryInput, DatastoreEntryOutput},
-    endorsement::EndorsementInfo,
+    endorsement::{EndorsementDraw, EndorsementDrawsQuery, EndorsementInfo},
     error::ApiError,
     execution::{ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall, Transfer},
+    ledger::{BalanceChangeDetail, TransferSimulation, TransferSimulationQuery},
     node::NodeStatus,
-    operation::{OperationInfo, OperationInput},
+    operation::{CombinedOpsQuery, OperationInfo, OperationInput, OperationsBySender},
     page::{PageRequest, PagedVec},
+    rolls::PosParams,
     ListType, ScrudOperation, TimeInterval,
 };
 use massa_execution_exports::ExecutionController;
+use massa_final_state::FinalStateController;
 use massa_hash::Hash;
+use massa_ledger_exports::write_ledger_file;
 use massa_models::{
-    address::Address, block::Block, block_id::BlockId, clique::Clique, composite::PubkeySig,
-    endorsement::EndorsementId, execution::EventFilter, node::NodeId, operation::OperationId,
-    output_event::SCOutputEvent, prehash::PreHashSet, slot::Slot,
+    address::Address, address_history::AddressHistoryBundle, amount::Amount, block::Block,
+    block_id::BlockId, clique::Clique, composite::PubkeySig, endorsement::EndorsementId,
+    execution::EventFilter, node::NodeId, operation::OperationId, output_event::SCOutputEvent,
+    prehash::PreHashSet, slot::Slot,
 };
 use massa_protocol_exports::{PeerId, ProtocolController};
 use massa_signature::KeyPair;
+use massa_time::MassaTime;
 use massa_wallet::Wallet;
 use parking_lot::RwLock;
 use std::net::{IpAddr, SocketAddr};
@@ -46,6 +56,7 @@ impl API<Private> {
         api_settings: APIConfig,
         stop_cv: Arc<(Mutex<bool>, Condvar)>,
         node_wallet: Arc<RwLock<Wallet>>,
+        final_state: Arc<RwLock<dyn FinalStateController>>,
     ) -> Self {
         API(Private {
             protocol_controller,
@@ -53,6 +64,7 @@ impl API<Private> {
             api_settings,
             stop_cv,
             node_wallet,
+            final_state,
         })
     }
 }
@@ -97,6 +109,17 @@ impl MassaRpcServer for API<Private> {
         })
     }
 
+    async fn node_export_ledger(&self, path: String) -> RpcResult<usize> {
+        // snapshot the ledger under the lock, the file is written after releasing it
+        let entries = self.0.final_state.read().get_ledger().get_every_entry();
+        tokio::task::spawn_blocking(move || write_ledger_file(&PathBuf::from(path), &entries))
+            .await
+            .map_err(|err| ApiError::InternalServerError(err.to_string()))?
+            .map_err(|e| {
+                ApiError::InternalServerError(format!("error exporting ledger: {}", e)).into()
+            })
+    }
+
     async fn add_staking_secret_keys(&self, secret_keys: Vec<String>) -> RpcResult<()> {
         let keypairs = match secret_keys.iter().map(|x| KeyPair::from_str(x)).collect() {
             Ok(keypairs) => keypairs,
@@ -207,18 +230,49 @@ impl MassaRpcServer for API<Private> {
         crate::wrong_api::<Vec<Clique>>()
     }
 
+    async fn get_compact_cliques(&self) -> RpcResult<CompactCliques> {
+        crate::wrong_api::<CompactCliques>()
+    }
+
+    async fn get_genesis_fingerprint(&self) -> RpcResult<Hash> {
+        crate::wrong_api::<Hash>()
+    }
+
+    async fn get_pos_params(&self) -> RpcResult<PosParams> {
+        crate::wrong_api::<PosParams>()
+    }
+
     async fn get_stakers(&self, _: Option<PageRequest>) -> RpcResult<PagedVec<(Address, u64)>> {
         crate::wrong_api::<PagedVec<(Address, u64)>>()
     }
 
-    async fn get_operations(&self, _: Vec<OperationId>) -> RpcResult<Vec<OperationInfo>> {
+    async fn get_operations(
+        &self,
+        _: Vec<OperationId>,
+        _: Option<bool>,
+    ) -> RpcResult<Vec<OperationInfo>> {
         crate::wrong_api::<Vec<OperationInfo>>()
     }
 
+    async fn get_pending_operations(&self, _: Vec<Address>) -> RpcResult<Vec<OperationInfo>> {
+        crate::wrong_api::<Vec<OperationInfo>>()
+    }
+
+    async fn get_operations_by_sender(&self, _: CombinedOpsQuery) -> RpcResult<OperationsBySender> {
+        crate::wrong_api::<OperationsBySender>()
+    }
+
     async fn get_endorsements(&self, _: Vec<EndorsementId>) -> RpcResult<Vec<EndorsementInfo>> {
         crate::wrong_api::<Vec<EndorsementInfo>>()
     }
 
+    async fn get_endorsement_draws(
+        &self,
+        _: EndorsementDrawsQuery,
+    ) -> RpcResult<Vec<EndorsementDraw>> {
+        crate::wrong_api::<Vec<EndorsementDraw>>()
+    }
+
     async fn get_blocks(&self, _: Vec<BlockId>) -> RpcResult<Vec<BlockInfo>> {
         crate::wrong_api::<Vec<BlockInfo>>()
     }
@@ -238,10 +292,54 @@ impl MassaRpcServer for API<Private> {
         crate::wrong_api()
     }
 
-    async fn get_addresses(&self, _: Vec<Address>) -> RpcResult<Vec<AddressInfo>> {
+    async fn get_addresses(
+        &self,
+        _: Vec<Address>,
+        _: Option<Vec<AddressInfoField>>,
+    ) -> RpcResult<Vec<AddressInfo>> {
         crate::wrong_api::<Vec<AddressInfo>>()
     }
 
+    async fn get_multi_address_info(
+        &self,
+        _: MultiAddressInfoQuery,
+    ) -> RpcResult<MultiAddressInfo> {
+        crate::wrong_api::<MultiAddressInfo>()
+    }
+
+    async fn get_draw_schedule(
+        &self,
+        _: DrawScheduleQuery,
+    ) -> RpcResult<Vec<(Slot, MassaTime, DrawKind)>> {
+        crate::wrong_api::<Vec<(Slot, MassaTime, DrawKind)>>()
+    }
+
+    async fn get_pending_roll_sales(
+        &self,
+        _: PendingRollSalesQuery,
+    ) -> RpcResult<Vec<(Slot, u64, Amount)>> {
+        crate::wrong_api::<Vec<(Slot, u64, Amount)>>()
+    }
+
+    async fn preview_roll_sell(&self, _: RollSellPreview) -> RpcResult<(Slot, Amount)> {
+        crate::wrong_api::<(Slot, Amount)>()
+    }
+
+    async fn get_balance_change_detail(
+        &self,
+        _: BalanceChangeDetail,
+    ) -> RpcResult<Vec<(OperationId, bool, Amount)>> {
+        crate::wrong_api::<Vec<(OperationId, bool, Amount)>>()
+    }
+
+    async fn simulate_transfer(&self, _: TransferSimulationQuery) -> RpcResult<TransferSimulation> {
+        crate::wrong_api::<TransferSimulation>()
+    }
+
+    async fn get_address_history(&self, _: Address, _: bool) -> RpcResult<AddressHistoryBundle> {
+        crate::wrong_api::<AddressHistoryBundle>()
+    }
+
     async fn get_addresses_bytecode(&self, _: Vec<AddressFilter>) -> RpcResult<Vec<Vec<u8>>> {
         crate::wrong_api::<Vec<Vec<u8>>>()
     }
@@ -6,41 +6,51 @@ use async_trait::async_trait;
 use itertools::{izip, Itertools};
 use jsonrpsee::core::{Error as JsonRpseeError, RpcResult};
 use massa_api_exports::{
-    address::{AddressFilter, AddressInfo},
+    address::{
+        AddressFilter, AddressInfo, AddressInfoField, CompactAddressInfo, DrawKind,
+        DrawScheduleQuery, MultiAddressInfo, MultiAddressInfoQuery, PendingRollSalesQuery,
+        RollSellPreview,
+    },
     block::{BlockInfo, BlockInfoContent, BlockSummary},
+    clique::CompactCliques,
     config::APIConfig,
     datastore::{DatastoreEntryInput, DatastoreEntryOutput},
-    endorsement::EndorsementInfo,
+    endorsement::{EndorsementDraw, EndorsementDrawsQuery, EndorsementInfo},
     error::ApiError,
     execution::{
         ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall, ReadOnlyResult, Transfer,
     },
+    ledger::{BalanceChangeDetail, TransferSimulation, TransferSimulationQuery},
     node::NodeStatus,
-    operation::{OperationInfo, OperationInput},
+    operation::{CombinedOpsQuery, OperationInfo, OperationInput, OperationsBySender},
     page::{PageRequest, PagedVec},
+    rolls::PosParams,
     slot::SlotAmount,
     TimeInterval,
 };
 use massa_consensus_exports::block_status::DiscardReason;
 use massa_consensus_exports::ConsensusController;
 use massa_execution_exports::{
-    ExecutionController, ExecutionQueryRequest, ExecutionQueryRequestItem,
+    ExecutionAddressInfo, ExecutionController, ExecutionQueryRequest, ExecutionQueryRequestItem,
     ExecutionQueryResponseItem, ExecutionStackElement, ReadOnlyExecutionRequest,
     ReadOnlyExecutionTarget,
 };
+use massa_hash::Hash;
 use massa_models::{
     address::Address,
+    address_history::AddressHistoryBundle,
     amount::Amount,
     block::{Block, BlockGraphStatus},
     block_id::BlockId,
     clique::Clique,
     composite::PubkeySig,
-    config::CompactConfig,
+    config::{CompactConfig, BLOCK_REWARD, ROLL_PRICE, ROLL_SELL_CREDIT_DELAY_CYCLES},
     datastore::DatastoreDeserializer,
     endorsement::EndorsementId,
     endorsement::SecureShareEndorsement,
     error::ModelsError,
     execution::EventFilter,
+    ledger::{LedgerData, LedgerDataExport},
     node::NodeId,
     operation::OperationDeserializer,
     operation::OperationId,
@@ -95,6 +105,44 @@ impl API<Public> {
             keypair_factory: KeyPairFactory { mip_store },
         })
     }
+
+    /// Fails if queries are rejected until the graph is loaded and it is not loaded yet
+    fn check_ready(&self) -> RpcResult<()> {
+        if self.0.api_settings.reject_queries_until_ready && !self.0.consensus_controller.is_ready()
+        {
+            return Err(ApiError::NotReady.into());
+        }
+        Ok(())
+    }
+
+    /// Get the operations created by `addr` that the pool carries and that no known block includes yet.
+    /// Unlike the at-risk operations, which are included in blocks of a minority fork, these are in no block at all.
+    pub fn pending_operations_by(&self, addr: &Address) -> Vec<OperationId> {
+        let mut created_operations: Vec<OperationId> = self
+            .0
+            .storage
+            .read_operations()
+            .get_operations_created_by(addr)
+            .map(|ids| ids.iter().copied().collect())
+            .unwrap_or_default();
+        {
+            let read_blocks = self.0.storage.read_blocks();
+            created_operations.retain(|id| {
+                read_blocks
+                    .get_blocks_by_operation(id)
+                    .map_or(true, |blocks| blocks.is_empty())
+            });
+        }
+        let in_pool = self
+            .0
+            .pool_command_sender
+            .contains_operations(&created_operations);
+        created_operations
+            .into_iter()
+            .zip(in_pool)
+            .filter_map(|(id, in_pool)| in_pool.then_some(id))
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -119,6 +167,10 @@ impl MassaRpcServer for API<Public> {
         crate::wrong_api::<PubkeySig>()
     }
 
+    async fn node_export_ledger(&self, _: String) -> RpcResult<usize> {
+        crate::wrong_api::<usize>()
+    }
+
     async fn add_staking_secret_keys(&self, _: Vec<String>) -> RpcResult<()> {
         crate::wrong_api::<()>()
     }
@@ -232,6 +284,7 @@ impl MassaRpcServer for API<Public> {
             bytecode,
             operation_datastore,
             fee,
+            only_from,
         } in reqs
         {
             let address = if let Some(addr) = address {
@@ -303,7 +356,7 @@ impl MassaRpcServer for API<Public> {
             let result = self.0.execution_controller.execute_readonly_request(req);
 
             // map result
-            let result = ExecuteReadOnlyResponse {
+            let mut result = ExecuteReadOnlyResponse {
                 executed_at: result
                     .as_ref()
                     .map_or_else(|_| Slot::new(0, 0), |v| v.out.slot),
@@ -317,6 +370,9 @@ impl MassaRpcServer for API<Public> {
                     .map_or_else(|_| Default::default(), |v| v.out.events.clone().0),
                 state_changes: result.map_or_else(|_| Default::default(), |v| v.out.state_changes),
             };
+            if let Some(emitters) = &only_from {
+                result.retain_events_from(emitters);
+            }
 
             res.push(result);
         }
@@ -343,6 +399,7 @@ impl MassaRpcServer for API<Public> {
             caller_address,
             coins,
             fee,
+            only_from,
         } in reqs
         {
             let caller_address = if let Some(addr) = caller_address {
@@ -404,7 +461,7 @@ impl MassaRpcServer for API<Public> {
             let result = self.0.execution_controller.execute_readonly_request(req);
 
             // map result
-            let result = ExecuteReadOnlyResponse {
+            let mut result = ExecuteReadOnlyResponse {
                 executed_at: result
                     .as_ref()
                     .map_or_else(|_| Slot::new(0, 0), |v| v.out.slot),
@@ -418,6 +475,9 @@ impl MassaRpcServer for API<Public> {
                     .map_or_else(|_| Default::default(), |v| v.out.events.clone().0),
                 state_changes: result.map_or_else(|_| Default::default(), |v| v.out.state_changes),
             };
+            if let Some(emitters) = &only_from {
+                result.retain_events_from(emitters);
+            }
 
             res.push(result);
         }
@@ -486,6 +546,7 @@ impl MassaRpcServer for API<Public> {
             self.0.pool_command_sender.get_operation_count(),
             self.0.pool_command_sender.get_endorsement_count(),
         );
+        let operation_fee_floor = self.0.pool_command_sender.get_operation_fee_floor();
 
         let next_slot_result = last_slot
             .unwrap_or_else(|| Slot::new(0, 0))
@@ -552,6 +613,8 @@ impl MassaRpcServer for API<Public> {
             consensus_stats,
             network_stats,
             pool_stats,
+            operation_fee_floor,
+            ready: self.0.consensus_controller.is_ready(),
             config,
             current_cycle,
             chain_id: self.0.api_settings.chain_id,
@@ -561,9 +624,43 @@ impl MassaRpcServer for API<Public> {
 
     /// get cliques
     async fn get_cliques(&self) -> RpcResult<Vec<Clique>> {
+        self.check_ready()?;
         Ok(self.0.consensus_controller.get_cliques())
     }
 
+    /// get cliques in a compact form
+    async fn get_compact_cliques(&self) -> RpcResult<CompactCliques> {
+        self.check_ready()?;
+        Ok(CompactCliques::encode(
+            &self.0.consensus_controller.get_cliques(),
+        ))
+    }
+
+    async fn get_genesis_fingerprint(&self) -> RpcResult<Hash> {
+        Ok(self.0.consensus_controller.get_genesis_fingerprint())
+    }
+
+    async fn get_pos_params(&self) -> RpcResult<PosParams> {
+        let api_settings = &self.0.api_settings;
+        let params = PosParams {
+            roll_price: ROLL_PRICE,
+            periods_per_cycle: api_settings.periods_per_cycle,
+            thread_count: api_settings.thread_count,
+            roll_sell_credit_delay_cycles: ROLL_SELL_CREDIT_DELAY_CYCLES,
+            block_reward: BLOCK_REWARD,
+        };
+        // staking tooling divides by those values
+        if params.roll_price.is_zero() || params.periods_per_cycle == 0 || params.thread_count == 0
+        {
+            return Err(ApiError::InconsistencyError(format!(
+                "invalid proof-of-stake parameters: {:?}",
+                params
+            ))
+            .into());
+        }
+        Ok(params)
+    }
+
     /// get stakers
     async fn get_stakers(
         &self,
@@ -608,7 +705,11 @@ impl MassaRpcServer for API<Public> {
     async fn get_operations(
         &self,
         operations_ids: Vec<OperationId>,
+        include_raw: Option<bool>,
     ) -> RpcResult<Vec<OperationInfo>> {
+        self.check_ready()?;
+        let include_raw = include_raw.unwrap_or(false);
+
         // get the operations and the list of blocks that contain them from storage
         let secure_share_operations: Vec<SecureShareOperation> = {
             let read_ops = self.0.storage.read_operations();
@@ -675,6 +776,11 @@ impl MassaRpcServer for API<Public> {
         for (id, (operation, in_blocks), in_pool, is_operation_final, op_exec_status) in
             zipped_iterator
         {
+            let raw = include_raw.then(|| OperationInput {
+                creator_public_key: operation.content_creator_pub_key,
+                signature: operation.signature,
+                serialized_content: operation.serialized_data.clone(),
+            });
             #[cfg(feature = "execution-trace")]
             {
                 let mut transfer = None;
@@ -690,9 +796,11 @@ impl MassaRpcServer for API<Public> {
                     thread: operation
                         .content_creator_address
                         .get_thread(api_cfg.thread_count),
+                    memo: operation.content.op.memo(),
                     operation,
                     in_blocks: in_blocks.into_iter().collect(),
                     op_exec_status,
+                    raw,
                 });
             }
             #[cfg(not(feature = "execution-trace"))]
@@ -704,9 +812,11 @@ impl MassaRpcServer for API<Public> {
                     thread: operation
                         .content_creator_address
                         .get_thread(api_cfg.thread_count),
+                    memo: operation.content.op.memo(),
                     operation,
                     in_blocks: in_blocks.into_iter().collect(),
                     op_exec_status,
+                    raw,
                 });
             }
         }
@@ -715,11 +825,82 @@ impl MassaRpcServer for API<Public> {
         Ok(res)
     }
 
+    /// get pending operations
+    async fn get_pending_operations(
+        &self,
+        addresses: Vec<Address>,
+    ) -> RpcResult<Vec<OperationInfo>> {
+        let max_arguments = self.0.api_settings.max_arguments;
+        if addresses.len() as u64 > max_arguments {
+            return Err(ApiError::BadRequest("too many arguments".into()).into());
+        }
+
+        // the operations of the addresses that are carried by the pool and not yet included in a block
+        let pending: Vec<OperationId> = addresses
+            .iter()
+            .flat_map(|address| self.pending_operations_by(address))
+            .collect::<PreHashSet<OperationId>>()
+            .into_iter()
+            .take(max_arguments as usize)
+            .collect();
+
+        let mut res = self.get_operations(pending, None).await?;
+        res.retain(|op| op.in_pool);
+        Ok(res)
+    }
+
+    /// get the operations of a sender from the pool and the block graph
+    async fn get_operations_by_sender(
+        &self,
+        query: CombinedOpsQuery,
+    ) -> RpcResult<OperationsBySender> {
+        // the creator index of the storage covers both the pool and the block graph, without duplicates
+        let (created_operations, total_count) = {
+            let read_ops = self.0.storage.read_operations();
+            let mut created: Vec<(u64, OperationId)> = read_ops
+                .get_operations_created_by(&query.sender)
+                .map(|ids| {
+                    ids.iter()
+                        .filter_map(|id| read_ops.get(id).map(|op| (op.content.expire_period, *id)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let total_count = created.len();
+            // keep the most recent operations
+            created.sort_unstable_by(|a, b| b.cmp(a));
+            created.truncate(self.0.api_settings.max_ops_per_address_query as usize);
+            let ids: Vec<OperationId> = created.into_iter().map(|(_, id)| id).collect();
+            (ids, total_count)
+        };
+        let truncated = created_operations.len() < total_count;
+
+        let mut res = Vec::with_capacity(created_operations.len());
+        for ids in created_operations.chunks(self.0.api_settings.max_arguments.max(1) as usize) {
+            res.extend(self.get_operations(ids.to_vec(), None).await?);
+        }
+        res.retain(|op| {
+            if op.is_operation_final == Some(true) {
+                query.include_final
+            } else if !op.in_blocks.is_empty() || op.is_operation_final == Some(false) {
+                query.include_candidate
+            } else {
+                op.in_pool && query.include_pool
+            }
+        });
+        res.sort_unstable_by_key(|op| (op.operation.content.expire_period, op.id));
+        Ok(OperationsBySender {
+            operations: res,
+            total_count: total_count as u64,
+            truncated,
+        })
+    }
+
     /// get endorsements
     async fn get_endorsements(
         &self,
         mut endorsement_ids: Vec<EndorsementId>,
     ) -> RpcResult<Vec<EndorsementInfo>> {
+        self.check_ready()?;
         if endorsement_ids.len() as u64 > self.0.api_settings.max_arguments {
             return Err(ApiError::BadRequest("too many arguments".into()).into());
         }
@@ -809,20 +990,73 @@ impl MassaRpcServer for API<Public> {
         Ok(res)
     }
 
-    /// get blocks
-    /// Returns only active blocks are returned
-    async fn get_blocks(&self, mut ids: Vec<BlockId>) -> RpcResult<Vec<BlockInfo>> {
-        let mut blocks: Vec<Block> = Vec::with_capacity(ids.len());
+    /// get endorsement draws
+    async fn get_endorsement_draws(
+        &self,
+        query: EndorsementDrawsQuery,
+    ) -> RpcResult<Vec<EndorsementDraw>> {
+        if query.start > query.end {
+            return Err(ApiError::BadRequest("start slot is after end slot".into()).into());
+        }
+        if query.end.period.saturating_sub(query.start.period)
+            > self.0.api_settings.draw_lookahead_period_count
         {
-            let block_storage_lock = self.0.storage.read_blocks();
-            ids.retain(|id| {
-                if let Some(wrapped_block) = block_storage_lock.get(id) {
-                    blocks.push(wrapped_block.content.clone());
-                    return true;
-                };
-                false
-            });
+            return Err(ApiError::BadRequest(format!(
+                "slot range is too large, at most {} periods can be queried",
+                self.0.api_settings.draw_lookahead_period_count
+            ))
+            .into());
         }
+
+        let selections = self
+            .0
+            .selector_controller
+            .get_available_selections_in_range(query.start..=query.end, None)
+            .map_err(|e| ApiError::InconsistencyError(e.to_string()))?;
+
+        // the selector only returns the draws of the cycles it keeps in cache
+        if !selections.contains_key(&query.start) || !selections.contains_key(&query.end) {
+            return Err(ApiError::BadRequest(
+                "slot range is not within the cycles for which draws are available".into(),
+            )
+            .into());
+        }
+
+        let res = selections
+            .into_iter()
+            .flat_map(|(slot, selection)| {
+                selection
+                    .endorsements
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, address)| EndorsementDraw {
+                        indexed_slot: IndexedSlot { slot, index },
+                        address,
+                    })
+            })
+            .collect();
+        Ok(res)
+    }
+
+    /// get blocks
+    /// Returns one entry per requested id, in the request order, with no content for the blocks absent from storage
+    async fn get_blocks(&self, ids: Vec<BlockId>) -> RpcResult<Vec<BlockInfo>> {
+        self.check_ready()?;
+        if ids.len() as u64 > self.0.api_settings.max_arguments {
+            return Err(ApiError::BadRequest("too many arguments".into()).into());
+        }
+
+        // get the blocks from storage, unknown blocks are kept as `None` to preserve the order
+        let blocks: Vec<Option<Block>> = {
+            let block_storage_lock = self.0.storage.read_blocks();
+            ids.iter()
+                .map(|id| {
+                    block_storage_lock
+                        .get(id)
+                        .map(|wrapped_block| wrapped_block.content.clone())
+                })
+                .collect()
+        };
         let block_statuses = self.0.consensus_controller.get_block_statuses(&ids);
         let res = ids
             .into_iter()
@@ -830,13 +1064,13 @@ impl MassaRpcServer for API<Public> {
             .zip(block_statuses)
             .map(|((id, content), graph_status)| BlockInfo {
                 id,
-                content: Some(BlockInfoContent {
+                content: content.map(|block| BlockInfoContent {
                     is_final: graph_status == BlockGraphStatus::Final,
                     is_in_blockclique: graph_status == BlockGraphStatus::ActiveInBlockclique,
                     is_candidate: graph_status == BlockGraphStatus::ActiveInBlockclique
                         || graph_status == BlockGraphStatus::ActiveInAlternativeCliques,
                     is_discarded: graph_status == BlockGraphStatus::Discarded,
-                    block: content,
+                    block,
                 }),
             })
             .collect();
@@ -844,6 +1078,7 @@ impl MassaRpcServer for API<Public> {
     }
 
     async fn get_blockclique_block_by_slot(&self, slot: Slot) -> RpcResult<Option<Block>> {
+        self.check_ready()?;
         let block_id_option = self
             .0
             .consensus_controller
@@ -866,6 +1101,7 @@ impl MassaRpcServer for API<Public> {
     /// gets an interval of the block graph from consensus, with time filtering
     /// time filtering is done consensus-side to prevent communication overhead
     async fn get_graph_interval(&self, time: TimeInterval) -> RpcResult<Vec<BlockSummary>> {
+        self.check_ready()?;
         let api_settings = self.0.api_settings.clone();
 
         // filter blocks from graph_export
@@ -947,9 +1183,21 @@ impl MassaRpcServer for API<Public> {
     }
 
     /// get addresses
-    async fn get_addresses(&self, addresses: Vec<Address>) -> RpcResult<Vec<AddressInfo>> {
+    async fn get_addresses(
+        &self,
+        addresses: Vec<Address>,
+        fields: Option<Vec<AddressInfoField>>,
+    ) -> RpcResult<Vec<AddressInfo>> {
+        self.check_ready()?;
+
+        // all the sections are computed unless some are explicitly requested
+        let wanted = |field: AddressInfoField| fields.as_ref().map_or(true, |f| f.contains(&field));
+        let with_created_objects = wanted(AddressInfoField::CreatedObjects);
+
         // get info from storage about which blocks the addresses have created
-        let created_blocks: Vec<PreHashSet<BlockId>> = {
+        let created_blocks: Vec<PreHashSet<BlockId>> = if !with_created_objects {
+            vec![PreHashSet::default(); addresses.len()]
+        } else {
             let lck = self.0.storage.read_blocks();
             addresses
                 .iter()
@@ -962,7 +1210,9 @@ impl MassaRpcServer for API<Public> {
         };
 
         // get info from storage about which operations the addresses have created
-        let created_operations: Vec<PreHashSet<OperationId>> = {
+        let created_operations: Vec<PreHashSet<OperationId>> = if !with_created_objects {
+            vec![PreHashSet::default(); addresses.len()]
+        } else {
             let lck = self.0.storage.read_operations();
             addresses
                 .iter()
@@ -975,7 +1225,9 @@ impl MassaRpcServer for API<Public> {
         };
 
         // get info from storage about which endorsements the addresses have created
-        let created_endorsements: Vec<PreHashSet<EndorsementId>> = {
+        let created_endorsements: Vec<PreHashSet<EndorsementId>> = if !with_created_objects {
+            vec![PreHashSet::default(); addresses.len()]
+        } else {
             let lck = self.0.storage.read_endorsements();
             addresses
                 .iter()
@@ -997,14 +1249,35 @@ impl MassaRpcServer for API<Public> {
             bound_ts,
         );
 
-        // get execution info
-        let execution_infos = self.0.execution_controller.get_addresses_infos(
-            &addresses,
-            std::ops::Bound::Included(deferred_credit_max_slot),
-        );
+        // get execution info, only the balances being cheap to get on their own
+        let execution_infos = if wanted(AddressInfoField::RollCount)
+            || wanted(AddressInfoField::DatastoreKeys)
+            || wanted(AddressInfoField::DeferredCredits)
+            || wanted(AddressInfoField::CycleInfos)
+        {
+            self.0.execution_controller.get_addresses_infos(
+                &addresses,
+                std::ops::Bound::Included(deferred_credit_max_slot),
+            )
+        } else if wanted(AddressInfoField::Balance) {
+            self.0
+                .execution_controller
+                .get_final_and_candidate_balance(&addresses)
+                .into_iter()
+                .map(|(final_balance, candidate_balance)| ExecutionAddressInfo {
+                    final_balance: final_balance.unwrap_or_default(),
+                    candidate_balance: candidate_balance.unwrap_or_default(),
+                    ..Default::default()
+                })
+                .collect()
+        } else {
+            vec![ExecutionAddressInfo::default(); addresses.len()]
+        };
 
         // get future draws from selector
-        let selection_draws = {
+        let selection_draws = if !wanted(AddressInfoField::Draws) {
+            vec![(Vec::new(), Vec::new()); addresses.len()]
+        } else {
             let cur_slot = timeslots::get_current_latest_block_slot(
                 self.0.api_settings.thread_count,
                 self.0.api_settings.t0,
@@ -1075,16 +1348,16 @@ impl MassaRpcServer for API<Public> {
                 thread: address.get_thread(self.0.api_settings.thread_count),
 
                 // final execution info
-                final_balance: execution_infos.final_balance,
-                final_roll_count: execution_infos.final_roll_count,
+                final_balance: Some(execution_infos.final_balance),
+                final_roll_count: Some(execution_infos.final_roll_count),
                 final_datastore_keys: execution_infos
                     .final_datastore_keys
                     .into_iter()
                     .collect::<Vec<_>>(),
 
                 // candidate execution info
-                candidate_balance: execution_infos.candidate_balance,
-                candidate_roll_count: execution_infos.candidate_roll_count,
+                candidate_balance: Some(execution_infos.candidate_balance),
+                candidate_roll_count: Some(execution_infos.candidate_roll_count),
                 candidate_datastore_keys: execution_infos
                     .candidate_datastore_keys
                     .into_iter()
@@ -1111,9 +1384,276 @@ impl MassaRpcServer for API<Public> {
             });
         }
 
+        // sections computed along with the requested ones are not returned either
+        if let Some(fields) = fields {
+            res.iter_mut().for_each(|info| info.retain_fields(&fields));
+        }
+
         Ok(res)
     }
 
+    /// get the information of many addresses, compact queries only computing the balances and roll counts
+    async fn get_multi_address_info(
+        &self,
+        query: MultiAddressInfoQuery,
+    ) -> RpcResult<MultiAddressInfo> {
+        if !query.compact {
+            return Ok(MultiAddressInfo::Full(
+                self.get_addresses(query.addresses, None).await?,
+            ));
+        }
+        // one entry per address, zeroed for the unknown ones
+        let balances = self
+            .0
+            .execution_controller
+            .get_addresses_balances(&query.addresses);
+        Ok(MultiAddressInfo::Compact(
+            query
+                .addresses
+                .iter()
+                .zip(balances)
+                .map(|(address, balances)| CompactAddressInfo {
+                    address: *address,
+                    thread: address.get_thread(self.0.api_settings.thread_count),
+                    active_rolls: balances.active_rolls.unwrap_or_default(),
+                    final_rolls: balances.final_roll_count,
+                    candidate_rolls: balances.candidate_roll_count,
+                    final_balance: balances.final_balance,
+                    candidate_balance: balances.candidate_balance,
+                })
+                .collect(),
+        ))
+    }
+
+    /// get the upcoming draws of an address
+    async fn get_draw_schedule(
+        &self,
+        query: DrawScheduleQuery,
+    ) -> RpcResult<Vec<(Slot, MassaTime, DrawKind)>> {
+        let api_settings = &self.0.api_settings;
+        let thread_count = api_settings.thread_count as u64;
+
+        let cur_slot = timeslots::get_current_latest_block_slot(
+            api_settings.thread_count,
+            api_settings.t0,
+            api_settings.genesis_timestamp,
+        )
+        .map_err(ApiError::ModelsError)?
+        .unwrap_or_else(|| Slot::new(0, 0));
+
+        // the selector only keeps the draws of a few cycles: clamp to the usual draw look-ahead
+        let slots_ahead = query.slots_ahead.min(
+            api_settings
+                .draw_lookahead_period_count
+                .saturating_mul(thread_count),
+        );
+        let end_index = cur_slot
+            .period
+            .saturating_mul(thread_count)
+            .saturating_add(cur_slot.thread as u64)
+            .saturating_add(slots_ahead);
+        let end_slot = Slot::new(end_index / thread_count, (end_index % thread_count) as u8);
+
+        let selections = self
+            .0
+            .selector_controller
+            .get_available_selections_in_range(
+                cur_slot..=end_slot,
+                Some(&std::iter::once(query.address).collect()),
+            )
+            .map_err(|e| ApiError::InconsistencyError(e.to_string()))?;
+
+        let mut res = Vec::new();
+        for (slot, selection) in selections {
+            let timestamp = timeslots::get_block_slot_timestamp(
+                api_settings.thread_count,
+                api_settings.t0,
+                api_settings.genesis_timestamp,
+                slot,
+            )
+            .map_err(ApiError::ModelsError)?;
+            if selection.producer == query.address {
+                res.push((slot, timestamp, DrawKind::Block));
+            }
+            for (index, endorser) in selection.endorsements.iter().enumerate() {
+                if *endorser == query.address {
+                    res.push((slot, timestamp, DrawKind::Endorsement(index)));
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// get the roll sales of an address that are waiting for their deferred credit
+    async fn get_pending_roll_sales(
+        &self,
+        query: PendingRollSalesQuery,
+    ) -> RpcResult<Vec<(Slot, u64, Amount)>> {
+        // deferred credits are only scheduled by roll sales,
+        // the number of rolls is rounded down in case the credit was slashed
+        let res = self
+            .0
+            .execution_controller
+            .get_addresses_infos(&[query.address], std::ops::Bound::Unbounded)
+            .into_iter()
+            .flat_map(|info| info.future_deferred_credits)
+            .map(|(slot, amount)| (slot, amount.checked_div(ROLL_PRICE).unwrap_or(0), amount))
+            .collect();
+        Ok(res)
+    }
+
+    async fn preview_roll_sell(&self, preview: RollSellPreview) -> RpcResult<(Slot, Amount)> {
+        let api_settings = &self.0.api_settings;
+
+        let owned_rolls = self
+            .0
+            .execution_controller
+            .get_addresses_infos(&[preview.address], std::ops::Bound::Unbounded)
+            .first()
+            .map_or(0, |info| info.candidate_roll_count);
+        if preview.roll_count == 0 || preview.roll_count > owned_rolls {
+            return Err(ApiError::BadRequest(format!(
+                "{} cannot sell {} rolls, it owns {}",
+                preview.address, preview.roll_count, owned_rolls
+            ))
+            .into());
+        }
+
+        // the sale is assumed to be executed in the next slot
+        let sell_slot = match get_latest_block_slot_at_timestamp(
+            api_settings.thread_count,
+            api_settings.t0,
+            api_settings.genesis_timestamp,
+            MassaTime::now(),
+        ) {
+            Ok(Some(cur_slot)) => cur_slot
+                .get_next_slot(api_settings.thread_count)
+                .map_err(ApiError::ModelsError)?,
+            Ok(None) => Slot::new(0, 0),
+            Err(e) => return Err(ApiError::ModelsError(e).into()),
+        };
+        let sell_slot = std::cmp::max(sell_slot, Slot::new(api_settings.last_start_period, 0));
+
+        let credit_slot = sell_slot
+            .get_roll_sell_credit_slot(api_settings.periods_per_cycle, api_settings.thread_count)
+            .map_err(ApiError::ModelsError)?;
+        let amount = ROLL_PRICE
+            .checked_mul_u64(preview.roll_count)
+            .ok_or_else(|| ApiError::BadRequest("roll sale amount overflow".to_string()))?;
+        Ok((credit_slot, amount))
+    }
+
+    #[cfg(feature = "execution-trace")]
+    async fn get_balance_change_detail(
+        &self,
+        arg: BalanceChangeDetail,
+    ) -> RpcResult<Vec<(OperationId, bool, Amount)>> {
+        let thread_count = self.0.api_settings.thread_count;
+        let slot_count = arg
+            .end
+            .slots_since(&arg.start, thread_count)
+            .map_err(|_| ApiError::BadRequest("start slot is after end slot".to_string()))?
+            .saturating_add(1);
+        if slot_count > self.0.api_settings.max_arguments {
+            return Err(ApiError::BadRequest(format!(
+                "too many slots received. Only a maximum of {} slots are accepted per request",
+                self.0.api_settings.max_arguments
+            ))
+            .into());
+        }
+
+        // net change of the balance of the address per operation, as (credited, debited)
+        let mut changes: Vec<(OperationId, Amount, Amount)> = Vec::new();
+        let mut slot = arg.start;
+        loop {
+            let transfers = self
+                .0
+                .execution_controller
+                .get_transfers_for_slot(slot)
+                .unwrap_or_default();
+            for transfer in transfers {
+                let mut credit = Amount::zero();
+                let mut debit = Amount::zero();
+                if transfer.from == arg.address {
+                    // the fee is paid even if the operation failed
+                    debit = transfer.fee;
+                    if transfer.succeed {
+                        debit = debit.saturating_add(transfer.amount);
+                    }
+                }
+                if transfer.to == arg.address && transfer.succeed {
+                    credit = transfer.effective_received_amount;
+                }
+                if credit != debit {
+                    changes.push((transfer.op_id, credit, debit));
+                }
+            }
+            if slot == arg.end {
+                break;
+            }
+            slot = slot
+                .get_next_slot(thread_count)
+                .map_err(ApiError::ModelsError)?;
+        }
+        Ok(changes
+            .into_iter()
+            .map(|(op_id, credit, debit)| {
+                if credit > debit {
+                    (op_id, true, credit.saturating_sub(debit))
+                } else {
+                    (op_id, false, debit.saturating_sub(credit))
+                }
+            })
+            .collect())
+    }
+
+    #[cfg(not(feature = "execution-trace"))]
+    async fn get_balance_change_detail(
+        &self,
+        _: BalanceChangeDetail,
+    ) -> RpcResult<Vec<(OperationId, bool, Amount)>> {
+        RpcResult::Err(ApiError::BadRequest("feature execution-trace is not enabled".into()).into())
+    }
+
+    async fn simulate_transfer(
+        &self,
+        query: TransferSimulationQuery,
+    ) -> RpcResult<TransferSimulation> {
+        let addresses = [query.sender_address, query.recipient_address];
+        let mut export = LedgerDataExport::default();
+        for (address, (final_balance, candidate_balance)) in addresses.iter().zip(
+            self.0
+                .execution_controller
+                .get_final_and_candidate_balance(&addresses),
+        ) {
+            if let Some(balance) = final_balance {
+                export.final_data.insert(*address, LedgerData { balance });
+            }
+            if let Some(balance) = candidate_balance {
+                export
+                    .candidate_data
+                    .insert(*address, LedgerData { balance });
+            }
+        }
+        Ok(TransferSimulation::from_export(&export, &query))
+    }
+
+    /// get the history of an address, optionally signed by the node
+    async fn get_address_history(
+        &self,
+        address: Address,
+        sign: bool,
+    ) -> RpcResult<AddressHistoryBundle> {
+        self.check_ready()?;
+        let history = self.0.consensus_controller.get_address_history(address);
+        AddressHistoryBundle::new(
+            history,
+            sign.then_some(&self.0.api_settings.keypair),
+            self.0.api_settings.chain_id,
+        )
+        .map_err(|e| ApiError::ModelsError(e).into())
+    }
+
     /// get addresses bytecode
     async fn get_addresses_bytecode(&self, args: Vec<AddressFilter>) -> RpcResult<Vec<Vec<u8>>> {
         let queries = args
@@ -8,7 +8,7 @@ use massa_api_exports::config::APIConfig;
 use massa_consensus_exports::{ConsensusBroadcasts, MockConsensusController};
 use massa_execution_exports::{GasCosts, MockExecutionController};
 use massa_models::amount::Amount;
-use massa_models::config::CHAINID;
+use massa_models::config::{CHAINID, OPERATION_VALIDITY_PERIODS};
 use massa_models::{
     config::{
         BASE_OPERATION_GAS_COST, ENDORSEMENT_COUNT, GENESIS_TIMESTAMP, MAX_DATASTORE_VALUE_LENGTH,
@@ -39,6 +39,7 @@ pub(crate) fn get_apiv2_server(addr: &SocketAddr) -> (API<ApiV2>, APIConfig) {
         bind_api: *addr,
         draw_lookahead_period_count: 10,
         max_arguments: 128,
+        max_ops_per_address_query: 1000,
         openrpc_spec_path: "base_config/openrpc.json".parse().unwrap(),
         bootstrap_whitelist_path: "base_config/bootstrap_whitelist.json".parse().unwrap(),
         bootstrap_blacklist_path: "base_config/bootstrap_blacklist.json".parse().unwrap(),
@@ -70,6 +71,7 @@ pub(crate) fn get_apiv2_server(addr: &SocketAddr) -> (API<ApiV2>, APIConfig) {
         chain_id: *CHAINID,
         deferred_credits_delta: MassaTime::from_millis(24 * 3600 * 2),
         minimal_fees: Amount::zero(),
+        reject_queries_until_ready: false,
     };
 
     // let shared_storage: massa_storage::Storage = massa_storage::Storage::create_root();
@@ -115,6 +117,7 @@ pub(crate) fn start_public_api(addr: SocketAddr) -> (API<Public>, APIConfig) {
         bind_api: "[::]:0".parse().unwrap(),
         draw_lookahead_period_count: 10,
         max_arguments: 128,
+        max_ops_per_address_query: 1000,
         openrpc_spec_path: "base_config/openrpc.json".parse().unwrap(),
         bootstrap_whitelist_path: "base_config/bootstrap_whitelist.json".parse().unwrap(),
         bootstrap_blacklist_path: "base_config/bootstrap_blacklist.json".parse().unwrap(),
@@ -146,6 +149,7 @@ pub(crate) fn start_public_api(addr: SocketAddr) -> (API<Public>, APIConfig) {
         chain_id: *CHAINID,
         deferred_credits_delta: MassaTime::from_millis(24 * 3600 * 2),
         minimal_fees: Amount::zero(),
+        reject_queries_until_ready: false,
     };
 
     let shared_storage: massa_storage::Storage = massa_storage::Storage::create_root();
@@ -194,6 +198,7 @@ pub(crate) fn start_public_api(addr: SocketAddr) -> (API<Public>, APIConfig) {
             operation_batch_proc_period: MassaTime::from_millis(200),
             asked_operations_buffer_capacity: 10000,
             operation_announcement_interval: MassaTime::from_millis(150),
+            eager_local_operations_propagation: false,
             max_operations_per_message: 1024,
             max_operations_per_block: 5000,
             thread_count: 32,
@@ -204,6 +209,9 @@ pub(crate) fn start_public_api(addr: SocketAddr) -> (API<Public>, APIConfig) {
             t0: MassaTime::from_millis(16000),
             max_ops_kept_for_propagation: 10000,
             max_operations_propagation_time: MassaTime::from_millis(30000),
+            operation_validity_periods: OPERATION_VALIDITY_PERIODS,
+            max_future_validity_periods: 10_000_000,
+            max_signature_verification_threads: None,
             max_endorsements_propagation_time: MassaTime::from_millis(60000),
             initial_peers: NamedTempFile::new()
                 .expect("cannot create temp file")
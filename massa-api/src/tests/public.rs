@@ -13,45 +13,57 @@ use jsonrpsee::{
     rpc_params,
 };
 use massa_api_exports::{
-    address::{AddressFilter, AddressInfo},
+    address::{
+        AddressFilter, AddressInfo, AddressInfoField, DrawKind, DrawScheduleQuery,
+        MultiAddressInfo, MultiAddressInfoQuery, PendingRollSalesQuery, RollSellPreview,
+    },
     block::{BlockInfo, BlockSummary},
+    clique::CompactCliques,
     datastore::{DatastoreEntryInput, DatastoreEntryOutput},
-    endorsement::EndorsementInfo,
+    endorsement::{EndorsementDraw, EndorsementDrawsQuery, EndorsementInfo},
     execution::{ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall},
-    operation::{OperationInfo, OperationInput},
+    ledger::{TransferBalances, TransferSimulation, TransferSimulationQuery},
+    operation::{CombinedOpsQuery, OperationInfo, OperationInput, OperationsBySender},
+    rolls::PosParams,
     TimeInterval,
 };
 use massa_consensus_exports::{
     block_graph_export::BlockGraphExport, block_status::ExportCompiledBlock,
     MockConsensusController,
 };
+use massa_hash::Hash;
 use massa_pool_exports::MockPoolController;
-use massa_pos_exports::MockSelectorController;
+use massa_pos_exports::{MockSelectorController, Selection};
 
 use crate::{tests::mock::start_public_api, RpcServer};
 use massa_execution_exports::{
-    ExecutionAddressInfo, ExecutionQueryResponse, ExecutionQueryResponseItem,
-    MockExecutionController, ReadOnlyExecutionOutput,
+    ExecutionAddressBalances, ExecutionAddressInfo, ExecutionQueryResponse,
+    ExecutionQueryResponseItem, MockExecutionController, ReadOnlyExecutionOutput,
 };
 use massa_models::{
     address::Address,
+    address_history::{AddressHistory, AddressHistoryBundle},
     amount::Amount,
     block::{Block, BlockGraphStatus},
+    block_id::BlockId,
     bytecode::Bytecode,
     clique::Clique,
+    config::{CHAINID, PERIODS_PER_CYCLE, THREAD_COUNT},
     endorsement::EndorsementId,
     execution::EventFilter,
     node::NodeId,
     operation::OperationId,
     output_event::SCOutputEvent,
     prehash::{CapacityAllocator, PreHashMap},
+    secure_share::Id,
     slot::Slot,
     stats::{ConsensusStats, ExecutionStats, NetworkStats},
 };
 use massa_protocol_exports::{
     test_exports::tools::{
-        create_block, create_call_sc_op_with_too_much_gas, create_endorsement,
-        create_execute_sc_op_with_too_much_gas, create_operation_with_expire_period,
+        create_block, create_block_with_operations, create_call_sc_op_with_too_much_gas,
+        create_endorsement, create_execute_sc_op_with_too_much_gas,
+        create_operation_with_expire_period,
     },
     MockProtocolController,
 };
@@ -77,6 +89,7 @@ async fn get_status() {
     });
 
     let mut consensus_ctrl = MockConsensusController::new();
+    consensus_ctrl.expect_is_ready().returning(|| true);
     consensus_ctrl.expect_get_stats().returning(|| {
         Ok(ConsensusStats {
             start_timespan: MassaTime::now(),
@@ -84,6 +97,12 @@ async fn get_status() {
             final_block_count: 50,
             stale_block_count: 40,
             clique_count: 30,
+            average_finality_latency: None,
+            recent_discarded_blocks: Default::default(),
+            slots_since_last_finality: 0,
+            production_rate_per_thread: vec![],
+            endorsement_participation: None,
+            pruned_operation_count: 0,
         })
     });
 
@@ -96,6 +115,7 @@ async fn get_status() {
                 known_peer_count: 6,
                 banned_peer_count: 0,
                 active_node_count: 15,
+                message_counters: Default::default(),
             },
             HashMap::new(),
         ))
@@ -104,6 +124,9 @@ async fn get_status() {
     let mut pool_ctrl = MockPoolController::new();
     pool_ctrl.expect_get_operation_count().returning(|| 1024);
     pool_ctrl.expect_get_endorsement_count().returning(|| 2048);
+    pool_ctrl
+        .expect_get_operation_fee_floor()
+        .returning(Amount::zero);
 
     api_public.0.pool_command_sender = Box::new(pool_ctrl);
     api_public.0.protocol_controller = Box::new(protocol_ctrl);
@@ -130,6 +153,7 @@ async fn get_status() {
     assert_eq!(response.config.thread_count, 32);
     // Chain id == 77 for Node in sandbox mode otherwise it is always greater
     assert!(response.chain_id >= 77);
+    assert!(response.ready);
 
     api_public_handle.stop().await;
 }
@@ -166,6 +190,206 @@ async fn get_cliques() {
     api_public_handle.stop().await;
 }
 
+#[tokio::test]
+async fn get_compact_cliques() {
+    let addr: SocketAddr = "[::]:5064".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let block_id = |name: &str| BlockId::generate_from_hash(Hash::compute_from(name.as_bytes()));
+    // two forks sharing the block "a"
+    let cliques = vec![
+        Clique {
+            block_ids: vec![block_id("a"), block_id("b")].into_iter().collect(),
+            fitness: 2,
+            is_blockclique: true,
+        },
+        Clique {
+            block_ids: vec![block_id("a"), block_id("c")].into_iter().collect(),
+            fitness: 1,
+            is_blockclique: false,
+        },
+    ];
+    let mut consensus_ctrl = MockConsensusController::new();
+    let returned = cliques.clone();
+    consensus_ctrl
+        .expect_get_cliques()
+        .returning(move || returned.clone());
+
+    api_public.0.consensus_controller = Box::new(consensus_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+    let response: CompactCliques = client
+        .request("get_compact_cliques", rpc_params![])
+        .await
+        .unwrap();
+
+    assert_eq!(response.block_ids.len(), 3);
+    let decoded = response.decode().unwrap();
+    assert_eq!(decoded.len(), 2);
+    for (decoded, original) in decoded.iter().zip(cliques.iter()) {
+        assert_eq!(decoded.block_ids, original.block_ids);
+        assert_eq!(decoded.fitness, original.fitness);
+        assert_eq!(decoded.is_blockclique, original.is_blockclique);
+    }
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_address_history() {
+    let addr: SocketAddr = "[::]:5065".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let keypair = KeyPair::generate(0).unwrap();
+    let address = Address::from_public_key(&keypair.get_public_key());
+    let op_id = OperationId::new(Hash::compute_from(b"roll buy"));
+    let history = AddressHistory {
+        address,
+        covered_slots: Some((Slot::new(1, 0), Slot::new(4, 1))),
+        operations: vec![(op_id, Slot::new(2, 0))],
+        roll_changes: vec![(op_id, 1, true)],
+    };
+    let mut consensus_ctrl = MockConsensusController::new();
+    let returned = history.clone();
+    consensus_ctrl
+        .expect_get_address_history()
+        .returning(move |_| returned.clone());
+    let node_public_key = api_public.0.api_settings.keypair.get_public_key();
+    let chain_id = api_public.0.api_settings.chain_id;
+    api_public.0.consensus_controller = Box::new(consensus_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+    let signed: AddressHistoryBundle = client
+        .request("get_address_history", rpc_params![address, true])
+        .await
+        .unwrap();
+    assert_eq!(signed.history, history);
+    assert_eq!(signed.verify(chain_id).unwrap(), Some(node_public_key));
+
+    let unsigned: AddressHistoryBundle = client
+        .request("get_address_history", rpc_params![address, false])
+        .await
+        .unwrap();
+    assert!(unsigned.signature.is_none());
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_cliques_not_ready() {
+    let addr: SocketAddr = "[::]:5060".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    // the graph is still catching up
+    let mut consensus_ctrl = MockConsensusController::new();
+    consensus_ctrl.expect_is_ready().returning(|| false);
+    consensus_ctrl
+        .expect_get_cliques()
+        .returning(|| vec![Clique::default()]);
+
+    api_public.0.consensus_controller = Box::new(consensus_ctrl);
+    api_public.0.api_settings.reject_queries_until_ready = true;
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+    let response: Result<Vec<Clique>, Error> = client.request("get_cliques", rpc_params![]).await;
+
+    assert!(response.unwrap_err().to_string().contains("Node not ready"));
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_genesis_fingerprint() {
+    let addr: SocketAddr = "[::]:5058".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let fingerprint = Hash::compute_from(b"genesis");
+    let mut consensus_ctrl = MockConsensusController::new();
+    consensus_ctrl
+        .expect_get_genesis_fingerprint()
+        .returning(move || fingerprint);
+
+    api_public.0.consensus_controller = Box::new(consensus_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+    let response: Hash = client
+        .request("get_genesis_fingerprint", rpc_params![])
+        .await
+        .unwrap();
+
+    assert_eq!(response, fingerprint);
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_pos_params() {
+    let addr: SocketAddr = "[::]:5061".parse().unwrap();
+    let (api_public, config) = start_public_api(addr);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+    let response: PosParams = client
+        .request("get_pos_params", rpc_params![])
+        .await
+        .unwrap();
+
+    assert_eq!(response.thread_count, config.thread_count);
+    assert_eq!(response.periods_per_cycle, config.periods_per_cycle);
+    assert!(!response.roll_price.is_zero());
+
+    api_public_handle.stop().await;
+}
+
 #[tokio::test]
 async fn get_operations() {
     let addr: SocketAddr = "[::]:5003".parse().unwrap();
@@ -210,6 +434,316 @@ async fn get_operations() {
     api_public_handle.stop().await;
 }
 
+#[tokio::test]
+async fn get_operations_with_raw() {
+    let addr: SocketAddr = "[::]:5062".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+    let keypair = KeyPair::generate(0).unwrap();
+    let op = create_operation_with_expire_period(&keypair, 500000);
+
+    api_public.0.storage.store_operations(vec![op.clone()]);
+
+    let mut pool_ctrl = MockPoolController::new();
+    pool_ctrl
+        .expect_contains_operations()
+        .returning(|ids| ids.iter().map(|_id| true).collect());
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl
+        .expect_get_ops_exec_status()
+        .returning(|op| op.iter().map(|_op| (Some(true), Some(true))).collect());
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+    api_public.0.pool_command_sender = Box::new(pool_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    // raw fields are not included by default
+    let response: Vec<OperationInfo> = client
+        .request("get_operations", rpc_params![vec![op.id]])
+        .await
+        .unwrap();
+    assert_eq!(response.len(), 1);
+    assert!(response[0].raw.is_none());
+
+    let response: Vec<OperationInfo> = client
+        .request("get_operations", rpc_params![vec![op.id], true])
+        .await
+        .unwrap();
+    assert_eq!(response.len(), 1);
+    let raw = response[0].raw.clone().expect("raw fields were requested");
+    assert_eq!(raw.creator_public_key, keypair.get_public_key());
+
+    // the signature can be checked from the returned fields only
+    let mut hash_data = Vec::new();
+    hash_data.extend(CHAINID.to_be_bytes());
+    hash_data.extend(raw.creator_public_key.to_bytes());
+    hash_data.extend(&raw.serialized_content);
+    raw.creator_public_key
+        .verify_signature(&Hash::compute_from(&hash_data), &raw.signature)
+        .unwrap();
+
+    // tampered bytes do not verify
+    let mut tampered = raw.serialized_content.clone();
+    tampered[0] ^= 1;
+    let mut hash_data = Vec::new();
+    hash_data.extend(CHAINID.to_be_bytes());
+    hash_data.extend(raw.creator_public_key.to_bytes());
+    hash_data.extend(&tampered);
+    assert!(raw
+        .creator_public_key
+        .verify_signature(&Hash::compute_from(&hash_data), &raw.signature)
+        .is_err());
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_pending_operations() {
+    let addr: SocketAddr = "[::]:5050".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+    let keypair = KeyPair::generate(0).unwrap();
+    let pending_op = create_operation_with_expire_period(&keypair, 500000);
+    let included_op = create_operation_with_expire_period(&keypair, 500001);
+    let other_op = create_operation_with_expire_period(&KeyPair::generate(0).unwrap(), 500000);
+
+    api_public.0.storage.store_operations(vec![
+        pending_op.clone(),
+        included_op.clone(),
+        other_op.clone(),
+    ]);
+
+    let pending_id = pending_op.id;
+    let mut pool_ctrl = MockPoolController::new();
+    pool_ctrl
+        .expect_contains_operations()
+        .returning(move |ids| ids.iter().map(|id| *id == pending_id).collect());
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl
+        .expect_get_ops_exec_status()
+        .returning(|op| op.iter().map(|_op| (None, None)).collect());
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+    api_public.0.pool_command_sender = Box::new(pool_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+    let params = rpc_params![vec![Address::from_public_key(&keypair.get_public_key())]];
+    let response: Vec<OperationInfo> = client
+        .request("get_pending_operations", params)
+        .await
+        .unwrap();
+
+    assert_eq!(response.len(), 1);
+    assert_eq!(response[0].id, pending_op.id);
+    assert!(response[0].in_pool);
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn pending_operations_by() {
+    let addr: SocketAddr = "[::]:5066".parse().unwrap();
+    let (mut api_public, _config) = start_public_api(addr);
+    let keypair = KeyPair::generate(0).unwrap();
+    let pooled_op = create_operation_with_expire_period(&keypair, 500000);
+    let included_op = create_operation_with_expire_period(&keypair, 500001);
+    let dropped_op = create_operation_with_expire_period(&keypair, 500002);
+
+    api_public.0.storage.store_operations(vec![
+        pooled_op.clone(),
+        included_op.clone(),
+        dropped_op.clone(),
+    ]);
+    let block = create_block_with_operations(&keypair, Slot::new(1, 0), vec![included_op.clone()]);
+    api_public.0.storage.store_block(block);
+
+    // the pool still carries the included operation, but not the dropped one
+    let dropped_id = dropped_op.id;
+    let mut pool_ctrl = MockPoolController::new();
+    pool_ctrl
+        .expect_contains_operations()
+        .returning(move |ids| ids.iter().map(|id| *id != dropped_id).collect());
+    api_public.0.pool_command_sender = Box::new(pool_ctrl);
+
+    let sender = Address::from_public_key(&keypair.get_public_key());
+    assert_eq!(
+        api_public.pending_operations_by(&sender),
+        vec![pooled_op.id]
+    );
+    let other = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    assert!(api_public.pending_operations_by(&other).is_empty());
+}
+
+#[tokio::test]
+async fn get_operations_by_sender() {
+    let addr: SocketAddr = "[::]:5053".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+    let keypair = KeyPair::generate(0).unwrap();
+    let pool_op = create_operation_with_expire_period(&keypair, 500000);
+    let candidate_op = create_operation_with_expire_period(&keypair, 500001);
+    let final_op = create_operation_with_expire_period(&keypair, 500002);
+    let other_op = create_operation_with_expire_period(&KeyPair::generate(0).unwrap(), 500000);
+
+    api_public.0.storage.store_operations(vec![
+        pool_op.clone(),
+        candidate_op.clone(),
+        final_op.clone(),
+        other_op.clone(),
+    ]);
+
+    // the pool still carries the candidate operation
+    let (pool_id, candidate_id, final_id) = (pool_op.id, candidate_op.id, final_op.id);
+    let mut pool_ctrl = MockPoolController::new();
+    pool_ctrl
+        .expect_contains_operations()
+        .returning(move |ids| {
+            ids.iter()
+                .map(|id| *id == pool_id || *id == candidate_id)
+                .collect()
+        });
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl
+        .expect_get_ops_exec_status()
+        .returning(move |ops| {
+            ops.iter()
+                .map(|id| {
+                    if *id == final_id {
+                        (Some(true), Some(true))
+                    } else if *id == candidate_id {
+                        (Some(true), None)
+                    } else {
+                        (None, None)
+                    }
+                })
+                .collect()
+        });
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+    api_public.0.pool_command_sender = Box::new(pool_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+    let sender = Address::from_public_key(&keypair.get_public_key());
+
+    let params = rpc_params![CombinedOpsQuery {
+        sender,
+        include_pool: true,
+        include_final: true,
+        include_candidate: true,
+    }];
+    let response: OperationsBySender = client
+        .request("get_operations_by_sender", params)
+        .await
+        .unwrap();
+    assert_eq!(response.total_count, 3);
+    assert!(!response.truncated);
+    let ids: Vec<OperationId> = response.operations.iter().map(|op| op.id).collect();
+    assert_eq!(ids, vec![pool_op.id, candidate_op.id, final_op.id]);
+
+    // the candidate operation is not reported as a pool one
+    let params = rpc_params![CombinedOpsQuery {
+        sender,
+        include_pool: true,
+        include_final: true,
+        include_candidate: false,
+    }];
+    let response: OperationsBySender = client
+        .request("get_operations_by_sender", params)
+        .await
+        .unwrap();
+    assert_eq!(response.total_count, 3);
+    assert!(!response.truncated);
+    let ids: Vec<OperationId> = response.operations.iter().map(|op| op.id).collect();
+    assert_eq!(ids, vec![pool_op.id, final_op.id]);
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_operations_by_sender_capped() {
+    let addr: SocketAddr = "[::]:5054".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+    api_public.0.api_settings.max_ops_per_address_query = 2;
+    let keypair = KeyPair::generate(0).unwrap();
+    let ops: Vec<_> = (0..4)
+        .map(|i| create_operation_with_expire_period(&keypair, 500000 + i))
+        .collect();
+    api_public.0.storage.store_operations(ops.clone());
+
+    let mut pool_ctrl = MockPoolController::new();
+    pool_ctrl
+        .expect_contains_operations()
+        .returning(|ids| vec![true; ids.len()]);
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl
+        .expect_get_ops_exec_status()
+        .returning(|ops| vec![(None, None); ops.len()]);
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+    api_public.0.pool_command_sender = Box::new(pool_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let params = rpc_params![CombinedOpsQuery {
+        sender: Address::from_public_key(&keypair.get_public_key()),
+        include_pool: true,
+        include_final: true,
+        include_candidate: true,
+    }];
+    let response: OperationsBySender = client
+        .request("get_operations_by_sender", params)
+        .await
+        .unwrap();
+
+    // only the two most recent operations are returned
+    assert_eq!(response.total_count, 4);
+    assert!(response.truncated);
+    let ids: Vec<OperationId> = response.operations.iter().map(|op| op.id).collect();
+    assert_eq!(ids, vec![ops[2].id, ops[3].id]);
+
+    api_public_handle.stop().await;
+}
+
 #[tokio::test]
 async fn get_endorsements() {
     let addr: SocketAddr = "[::]:5005".parse().unwrap();
@@ -309,6 +843,19 @@ async fn get_blocks() {
 
     assert_eq!(response[0].id, block.id);
 
+    // unknown blocks are returned without content, in the requested order
+    let unknown_block = create_block(&KeyPair::generate(0).unwrap());
+    let response: Vec<BlockInfo> = client
+        .request("get_blocks", rpc_params![vec![unknown_block.id, block.id]])
+        .await
+        .unwrap();
+
+    assert_eq!(response.len(), 2);
+    assert_eq!(response[0].id, unknown_block.id);
+    assert!(response[0].content.is_none());
+    assert_eq!(response[1].id, block.id);
+    assert!(response[1].content.as_ref().unwrap().is_final);
+
     api_public_handle.stop().await;
 }
 
@@ -727,7 +1274,8 @@ async fn execute_read_only_bytecode() {
             Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap()
         ),
         operation_datastore: None,
-        fee: None
+        fee: None,
+        only_from: None,
     }]];
     let response: Result<Vec<ExecuteReadOnlyResponse>, Error> = client
         .request("execute_read_only_bytecode", params.clone())
@@ -741,6 +1289,7 @@ async fn execute_read_only_bytecode() {
         address: None,
         operation_datastore: None,
         fee: None,
+        only_from: None,
     }]];
     let response: Result<Vec<ExecuteReadOnlyResponse>, Error> = client
         .request("execute_read_only_bytecode", params.clone())
@@ -753,7 +1302,8 @@ async fn execute_read_only_bytecode() {
         bytecode: "hi".as_bytes().to_vec(),
         address: None,
         operation_datastore: Some("hi".as_bytes().to_vec()),
-        fee: None
+        fee: None,
+        only_from: None,
     }]];
     let response: Result<Vec<ExecuteReadOnlyResponse>, Error> = client
         .request("execute_read_only_bytecode", params.clone())
@@ -823,6 +1373,7 @@ async fn execute_read_only_call() {
         caller_address: None,
         fee: None,
         coins: None,
+        only_from: None,
     }]];
     let response: Vec<ExecuteReadOnlyResponse> = client
         .request("execute_read_only_call", params.clone())
@@ -879,16 +1430,184 @@ async fn get_addresses() {
         client.request("get_addresses", params.clone()).await;
     assert!(response.unwrap_err().to_string().contains("Invalid params"));
 
-    let params = rpc_params![vec![Address::from_str(
-        "AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x"
-    )
-    .unwrap()]];
-    let response: Vec<AddressInfo> = client
-        .request("get_addresses", params.clone())
+    let params = rpc_params![vec![Address::from_str(
+        "AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x"
+    )
+    .unwrap()]];
+    let response: Vec<AddressInfo> = client
+        .request("get_addresses", params.clone())
+        .await
+        .unwrap();
+
+    assert!(response.len() == 1);
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_addresses_balance_only() {
+    let addr: SocketAddr = "[::]:5063".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    // only the balances are fetched: the full execution info and the selector are not queried
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl.expect_get_addresses_infos().never();
+    exec_ctrl
+        .expect_get_final_and_candidate_balance()
+        .returning(|a| {
+            a.iter()
+                .map(|_addr| {
+                    (
+                        Some(Amount::from_str("80000").unwrap()),
+                        Some(Amount::from_str("100000").unwrap()),
+                    )
+                })
+                .collect()
+        });
+    let mut selector_ctrl = MockSelectorController::new();
+    selector_ctrl
+        .expect_get_available_selections_in_range()
+        .never();
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+    api_public.0.selector_controller = Box::new(selector_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let address =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+    let response: Vec<AddressInfo> = client
+        .request(
+            "get_addresses",
+            rpc_params![vec![address], vec![AddressInfoField::Balance]],
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.len(), 1);
+    let info = &response[0];
+    assert_eq!(info.address, address);
+    assert_eq!(info.final_balance, Some(Amount::from_str("80000").unwrap()));
+    assert_eq!(
+        info.candidate_balance,
+        Some(Amount::from_str("100000").unwrap())
+    );
+    // not requested: left empty rather than reported as zero
+    assert_eq!(info.final_roll_count, None);
+    assert_eq!(info.candidate_roll_count, None);
+    assert!(info.next_block_draws.is_empty());
+    assert!(info.created_blocks.is_empty());
+    assert!(info.cycle_infos.is_empty());
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_multi_address_info() {
+    let addr: SocketAddr = "[::]:5067".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let known = Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+    let unknown = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+
+    // the execution has no entry for the unknown address
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl
+        .expect_get_addresses_balances()
+        .returning(move |a| {
+            a.iter()
+                .map(|addr| {
+                    if *addr != known {
+                        return ExecutionAddressBalances::default();
+                    }
+                    ExecutionAddressBalances {
+                        candidate_balance: Amount::from_str("100000").unwrap(),
+                        final_balance: Amount::from_str("80000").unwrap(),
+                        final_roll_count: 5,
+                        candidate_roll_count: 6,
+                        active_rolls: Some(4),
+                    }
+                })
+                .collect()
+        });
+    // only the full query loads the datastore keys and deferred credits
+    exec_ctrl
+        .expect_get_addresses_infos()
+        .returning(|a, _s| vec![ExecutionAddressInfo::default(); a.len()]);
+    let mut selector_ctrl = MockSelectorController::new();
+    selector_ctrl
+        .expect_get_available_selections_in_range()
+        .returning(|_range, _addrs| Ok(BTreeMap::new()));
+
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+    api_public.0.selector_controller = Box::new(selector_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    // the unknown address is kept, in the order of the query, with zeroed balances and rolls
+    let response: MultiAddressInfo = client
+        .request(
+            "get_multi_address_info",
+            rpc_params![MultiAddressInfoQuery {
+                addresses: vec![unknown, known],
+                compact: true,
+            }],
+        )
         .await
         .unwrap();
+    let MultiAddressInfo::Compact(infos) = response else {
+        panic!("expected compact address infos");
+    };
+    assert_eq!(infos.len(), 2);
+    assert_eq!(infos[0].address, unknown);
+    assert_eq!(infos[0].final_balance, Amount::zero());
+    assert_eq!(infos[0].candidate_rolls, 0);
+    assert_eq!(infos[1].address, known);
+    assert_eq!(
+        infos[1].candidate_balance,
+        Amount::from_str("100000").unwrap()
+    );
+    assert_eq!(infos[1].final_rolls, 5);
+    assert_eq!(infos[1].candidate_rolls, 6);
+    assert_eq!(infos[1].active_rolls, 4);
 
-    assert!(response.len() == 1);
+    let response: MultiAddressInfo = client
+        .request(
+            "get_multi_address_info",
+            rpc_params![MultiAddressInfoQuery {
+                addresses: vec![known, unknown],
+                compact: false,
+            }],
+        )
+        .await
+        .unwrap();
+    let MultiAddressInfo::Full(infos) = response else {
+        panic!("expected full address infos");
+    };
+    assert_eq!(
+        infos.iter().map(|info| info.address).collect::<Vec<_>>(),
+        vec![known, unknown]
+    );
 
     api_public_handle.stop().await;
 }
@@ -1345,3 +2064,502 @@ async fn get_stakers() {
 
     api_public_handle.stop().await;
 }
+
+#[tokio::test]
+async fn get_endorsement_draws() {
+    let addr: SocketAddr = "[::]:5051".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let draw_addr =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+
+    // the selector only knows the draws of the first 5 periods
+    let mut selector_ctrl = MockSelectorController::new();
+    selector_ctrl
+        .expect_get_available_selections_in_range()
+        .returning(move |range, _addrs| {
+            let mut res = BTreeMap::new();
+            for period in range.start().period..=range.end().period.min(4) {
+                for thread in 0..2 {
+                    let slot = Slot::new(period, thread);
+                    if range.contains(&slot) {
+                        res.insert(
+                            slot,
+                            Selection {
+                                endorsements: vec![draw_addr; 2],
+                                producer: draw_addr,
+                            },
+                        );
+                    }
+                }
+            }
+            Ok(res)
+        });
+
+    api_public.0.selector_controller = Box::new(selector_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let params = rpc_params![EndorsementDrawsQuery {
+        start: Slot::new(1, 1),
+        end: Slot::new(2, 0),
+    }];
+    let response: Vec<EndorsementDraw> = client
+        .request("get_endorsement_draws", params)
+        .await
+        .unwrap();
+    assert_eq!(response.len(), 4);
+    assert_eq!(response[0].indexed_slot.slot, Slot::new(1, 1));
+    assert_eq!(response[1].indexed_slot.index, 1);
+    assert_eq!(response[3].indexed_slot.slot, Slot::new(2, 0));
+    assert!(response.iter().all(|draw| draw.address == draw_addr));
+
+    // start after end
+    let params = rpc_params![EndorsementDrawsQuery {
+        start: Slot::new(2, 0),
+        end: Slot::new(1, 0),
+    }];
+    let response: Result<Vec<EndorsementDraw>, Error> =
+        client.request("get_endorsement_draws", params).await;
+    assert!(response.is_err());
+
+    // range outside of the cached cycles
+    let params = rpc_params![EndorsementDrawsQuery {
+        start: Slot::new(3, 0),
+        end: Slot::new(8, 0),
+    }];
+    let response: Result<Vec<EndorsementDraw>, Error> =
+        client.request("get_endorsement_draws", params).await;
+    assert!(response.is_err());
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_draw_schedule() {
+    let addr: SocketAddr = "[::]:5052".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let staker =
+        Address::from_str("AU12dG5xP1RDEB5ocdHkymNVvvSJmUL9BgHwCksDowqmGWxfpm93x").unwrap();
+    let other = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+
+    let mut selector_ctrl = MockSelectorController::new();
+    selector_ctrl
+        .expect_get_available_selections_in_range()
+        .returning(move |range, _addrs| {
+            // the look-ahead is clamped to draw_lookahead_period_count periods
+            assert!(
+                range
+                    .end()
+                    .slots_since(range.start(), THREAD_COUNT)
+                    .unwrap()
+                    <= 10 * THREAD_COUNT as u64
+            );
+            let next_slot = range.start().get_next_slot(THREAD_COUNT).unwrap();
+            let mut endorsements = vec![other; 16];
+            endorsements[3] = staker;
+            Ok(BTreeMap::from([
+                (
+                    *range.start(),
+                    Selection {
+                        endorsements: vec![other; 16],
+                        producer: staker,
+                    },
+                ),
+                (
+                    next_slot,
+                    Selection {
+                        endorsements,
+                        producer: other,
+                    },
+                ),
+            ]))
+        });
+
+    api_public.0.selector_controller = Box::new(selector_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let params = rpc_params![DrawScheduleQuery {
+        address: staker,
+        slots_ahead: 1_000_000,
+    }];
+    let response: Vec<(Slot, MassaTime, DrawKind)> =
+        client.request("get_draw_schedule", params).await.unwrap();
+
+    assert_eq!(response.len(), 2);
+    assert_eq!(response[0].2, DrawKind::Block);
+    assert_eq!(response[1].2, DrawKind::Endorsement(3));
+    assert_eq!(
+        response[1].0,
+        response[0].0.get_next_slot(THREAD_COUNT).unwrap()
+    );
+    assert!(response[0].1 < response[1].1);
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn get_pending_roll_sales() {
+    let addr: SocketAddr = "[::]:5055".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let seller = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    let idle = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl
+        .expect_get_addresses_infos()
+        .returning(move |a, _s| {
+            a.iter()
+                .map(|addr| ExecutionAddressInfo {
+                    candidate_balance: Amount::zero(),
+                    final_balance: Amount::zero(),
+                    final_roll_count: 0,
+                    final_datastore_keys: std::collections::BTreeSet::new(),
+                    candidate_roll_count: 0,
+                    candidate_datastore_keys: std::collections::BTreeSet::new(),
+                    future_deferred_credits: if *addr == seller {
+                        BTreeMap::from([
+                            (Slot::new(10, 3), Amount::from_str("300").unwrap()),
+                            (Slot::new(12, 0), Amount::from_str("100").unwrap()),
+                        ])
+                    } else {
+                        BTreeMap::new()
+                    },
+                    cycle_infos: vec![],
+                })
+                .collect()
+        });
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let response: Vec<(Slot, u64, Amount)> = client
+        .request(
+            "get_pending_roll_sales",
+            rpc_params![PendingRollSalesQuery { address: seller }],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response,
+        vec![
+            (Slot::new(10, 3), 3, Amount::from_str("300").unwrap()),
+            (Slot::new(12, 0), 1, Amount::from_str("100").unwrap()),
+        ]
+    );
+
+    let response: Vec<(Slot, u64, Amount)> = client
+        .request(
+            "get_pending_roll_sales",
+            rpc_params![PendingRollSalesQuery { address: idle }],
+        )
+        .await
+        .unwrap();
+    assert!(response.is_empty());
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn preview_roll_sell() {
+    let addr: SocketAddr = "[::]:5056".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let seller = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl
+        .expect_get_addresses_infos()
+        .returning(move |a, _s| {
+            a.iter()
+                .map(|addr| ExecutionAddressInfo {
+                    candidate_balance: Amount::zero(),
+                    final_balance: Amount::zero(),
+                    final_roll_count: 5,
+                    final_datastore_keys: std::collections::BTreeSet::new(),
+                    candidate_roll_count: if *addr == seller { 5 } else { 0 },
+                    candidate_datastore_keys: std::collections::BTreeSet::new(),
+                    future_deferred_credits: BTreeMap::new(),
+                    cycle_infos: vec![],
+                })
+                .collect()
+        });
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let (credit_slot, amount): (Slot, Amount) = client
+        .request(
+            "preview_roll_sell",
+            rpc_params![RollSellPreview {
+                address: seller,
+                roll_count: 2
+            }],
+        )
+        .await
+        .unwrap();
+    assert!(credit_slot.is_last_of_cycle(PERIODS_PER_CYCLE, THREAD_COUNT));
+    assert!(credit_slot.get_cycle(PERIODS_PER_CYCLE) >= 3);
+    assert_eq!(amount, Amount::from_str("200").unwrap());
+
+    // cannot sell more than the candidate rolls, nor nothing
+    for roll_count in [6, 0] {
+        let response: Result<(Slot, Amount), _> = client
+            .request(
+                "preview_roll_sell",
+                rpc_params![RollSellPreview {
+                    address: seller,
+                    roll_count
+                }],
+            )
+            .await;
+        assert!(response.is_err());
+    }
+
+    api_public_handle.stop().await;
+}
+
+#[tokio::test]
+async fn simulate_transfer() {
+    let addr: SocketAddr = "[::]:5057".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let sender = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    let recipient = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+
+    // a pending operation already spent 30 coins of the sender on the candidate ledger
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl
+        .expect_get_final_and_candidate_balance()
+        .returning(move |addresses| {
+            addresses
+                .iter()
+                .map(|address| {
+                    if *address == sender {
+                        (
+                            Some(Amount::from_str("100").unwrap()),
+                            Some(Amount::from_str("70").unwrap()),
+                        )
+                    } else {
+                        (None, None)
+                    }
+                })
+                .collect()
+        });
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    let simulation: TransferSimulation = client
+        .request(
+            "simulate_transfer",
+            rpc_params![TransferSimulationQuery {
+                sender_address: sender,
+                recipient_address: recipient,
+                amount: Amount::from_str("20").unwrap(),
+                fee: Amount::from_str("1").unwrap(),
+            }],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        simulation.sender,
+        TransferBalances {
+            final_before: Amount::from_str("100").unwrap(),
+            final_after: Some(Amount::from_str("79").unwrap()),
+            candidate_before: Amount::from_str("70").unwrap(),
+            candidate_after: Some(Amount::from_str("49").unwrap()),
+        }
+    );
+    assert_eq!(
+        simulation.recipient,
+        TransferBalances {
+            final_before: Amount::zero(),
+            final_after: Some(Amount::from_str("20").unwrap()),
+            candidate_before: Amount::zero(),
+            candidate_after: Some(Amount::from_str("20").unwrap()),
+        }
+    );
+
+    api_public_handle.stop().await;
+}
+
+#[cfg(feature = "execution-trace")]
+#[tokio::test]
+async fn get_balance_change_detail() {
+    use massa_api_exports::ledger::BalanceChangeDetail;
+
+    let addr: SocketAddr = "[::]:5059".parse().unwrap();
+    let (mut api_public, config) = start_public_api(addr);
+
+    let address_a = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    let address_b = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    let transfer = |op: &[u8], from: Address, to: Address, amount: &str, succeed: bool| {
+        massa_execution_exports::Transfer {
+            from,
+            to,
+            amount: Amount::from_str(amount).unwrap(),
+            effective_received_amount: Amount::from_str(amount).unwrap(),
+            op_id: OperationId::new(Hash::compute_from(op)),
+            succeed,
+            fee: Amount::from_str("1").unwrap(),
+        }
+    };
+    // a sends 10 coins to b, then b sends 4 coins back in the next slot of the same thread,
+    // then a call from a with 5 coins fails: only its fee is paid
+    let a_to_b = transfer(b"a to b", address_a, address_b, "10", true);
+    let b_to_a = transfer(b"b to a", address_b, address_a, "4", true);
+    let failed_call = transfer(b"failed call", address_a, address_b, "5", false);
+    let slot_transfers = vec![
+        (Slot::new(3, 0), vec![a_to_b.clone()]),
+        (Slot::new(3, 1), vec![b_to_a.clone(), failed_call.clone()]),
+        (
+            Slot::new(4, 0),
+            vec![transfer(b"later", address_b, address_a, "7", true)],
+        ),
+    ];
+
+    let mut exec_ctrl = MockExecutionController::new();
+    exec_ctrl
+        .expect_get_transfers_for_slot()
+        .returning(move |slot| {
+            slot_transfers
+                .iter()
+                .find(|(transfer_slot, _)| *transfer_slot == slot)
+                .map(|(_, transfers)| transfers.clone())
+        });
+    api_public.0.execution_controller = Box::new(exec_ctrl);
+
+    let api_public_handle = api_public
+        .serve(&addr, &config)
+        .await
+        .expect("failed to start PUBLIC API");
+
+    let client = HttpClientBuilder::default()
+        .build(format!(
+            "http://localhost:{}",
+            addr.to_string().split(':').last().unwrap()
+        ))
+        .unwrap();
+
+    // the range stops before the transfer of the slot (4, 0)
+    let response: Vec<(OperationId, bool, Amount)> = client
+        .request(
+            "get_balance_change_detail",
+            rpc_params![BalanceChangeDetail {
+                address: address_a,
+                start: Slot::new(3, 0),
+                end: Slot::new(3, THREAD_COUNT - 1),
+            }],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response,
+        vec![
+            (a_to_b.op_id, false, Amount::from_str("11").unwrap()),
+            (b_to_a.op_id, true, Amount::from_str("4").unwrap()),
+            (failed_call.op_id, false, Amount::from_str("1").unwrap()),
+        ]
+    );
+
+    // the recipient of a failed call is not credited
+    let response: Vec<(OperationId, bool, Amount)> = client
+        .request(
+            "get_balance_change_detail",
+            rpc_params![BalanceChangeDetail {
+                address: address_b,
+                start: Slot::new(3, 1),
+                end: Slot::new(3, 1),
+            }],
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        response,
+        vec![(b_to_a.op_id, false, Amount::from_str("5").unwrap())]
+    );
+
+    // an address that is not affected by the range
+    let other_address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    let response: Vec<(OperationId, bool, Amount)> = client
+        .request(
+            "get_balance_change_detail",
+            rpc_params![BalanceChangeDetail {
+                address: other_address,
+                start: Slot::new(3, 0),
+                end: Slot::new(4, 0),
+            }],
+        )
+        .await
+        .unwrap();
+    assert!(response.is_empty());
+
+    // the start of the range is after its end
+    let response: Result<Vec<(OperationId, bool, Amount)>, _> = client
+        .request(
+            "get_balance_change_detail",
+            rpc_params![BalanceChangeDetail {
+                address: address_a,
+                start: Slot::new(4, 0),
+                end: Slot::new(3, 0),
+            }],
+        )
+        .await;
+    assert!(response.is_err());
+
+    api_public_handle.stop().await;
+}
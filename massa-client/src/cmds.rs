@@ -1,36 +1,68 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
 use crate::display::Output;
+use crate::settings::SETTINGS;
 use crate::{client_warning, rpc_error};
 use anyhow::{anyhow, bail, Result};
 use console::style;
 use massa_api_exports::{
-    address::{AddressInfo, CompactAddressInfo},
+    address::{AddressInfo, CompactAddressInfo, RollSellPreview},
+    block::{BlockInfo, BlockSummary},
     datastore::DatastoreEntryInput,
-    execution::{ReadOnlyBytecodeExecution, ReadOnlyCall},
+    execution::{
+        ExecuteReadOnlyResponse, LedgerChange, ReadOnlyBytecodeExecution, ReadOnlyCall,
+        StateChangesSummary,
+    },
+    ledger::TransferSimulationQuery,
+    node::NodeStatus,
     operation::OperationInput,
+    rolls::PosParams,
+    TimeInterval,
 };
+use massa_consensus_exports::bootstrapable_graph::BootstrapableGraphDeserializer;
+use massa_hash::Hash;
+use massa_models::block::BlockDeserializerArgs;
+use massa_models::config::CompactConfig;
 use massa_models::node::NodeId;
 use massa_models::prehash::PreHashMap;
-use massa_models::timeslots::get_current_latest_block_slot;
+use massa_models::timeslots::{
+    get_block_slot_timestamp, get_current_latest_block_slot, get_latest_block_slot_at_timestamp,
+};
 use massa_models::{
     address::Address,
+    address_history::AddressHistoryBundle,
     amount::Amount,
     block_id::BlockId,
+    clique::Clique,
+    config::{
+        ENDORSEMENT_COUNT, MAX_BOOTSTRAP_BLOCKS, MAX_DATASTORE_VALUE_LENGTH,
+        MAX_DENUNCIATIONS_PER_BLOCK_HEADER, MAX_FUNCTION_NAME_LENGTH, MAX_OPERATIONS_PER_BLOCK,
+        MAX_OPERATION_DATASTORE_ENTRY_COUNT, MAX_OPERATION_DATASTORE_KEY_LENGTH,
+        MAX_OPERATION_DATASTORE_VALUE_LENGTH, MAX_OPERATION_MEMO_LENGTH, MAX_PARAMETERS_SIZE,
+        OPERATION_MEMO_DATASTORE_KEY, THREAD_COUNT,
+    },
     endorsement::EndorsementId,
+    error::ModelsError,
     execution::EventFilter,
-    operation::{Operation, OperationId, OperationType},
+    operation::{
+        Operation, OperationDeserializer, OperationId, OperationType, SecureShareOperation,
+    },
+    secure_share::SecureShareDeserializer,
     slot::Slot,
+    stats::{ConsensusStats, NetworkStats},
 };
-use massa_sdk::Client;
+use massa_sdk::{Client, ClientConfig, HttpConfig, RpcClient};
+use massa_serialization::{DeserializeError, Deserializer};
 use massa_signature::KeyPair;
+use massa_time::MassaTime;
 use massa_wallet::Wallet;
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::cmp::max;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Write as _;
 use std::fmt::{Debug, Display};
-use std::net::IpAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::path::PathBuf;
 use std::str::FromStr;
 use strum::{EnumMessage, EnumProperty, IntoEnumIterator};
@@ -93,6 +125,13 @@ pub enum Command {
     )]
     node_stop,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "FilePath", pwd_not_needed = "true"),
+        message = "writes the final ledger to a JSON file on the node's machine, in the initial ledger format"
+    )]
+    node_export_ledger,
+
     #[strum(
         ascii_case_insensitive,
         props(pwd_not_needed = "true"),
@@ -149,6 +188,41 @@ pub enum Command {
     )]
     get_status,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(pwd_not_needed = "true"),
+        message = "show the nodes the node is connected to (node id, ip, in/out), sorted by node id"
+    )]
+    peers,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[ExpectedFingerprint]", pwd_not_needed = "true"),
+        message = "show the fingerprint of the genesis blocks of the node, identifying its network. If a fingerprint is given, check that it matches"
+    )]
+    get_genesis_fingerprint,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(pwd_not_needed = "true"),
+        message = "show the proof-of-stake parameters of the network: roll price, cycle duration, roll sale credit delay..."
+    )]
+    get_pos_params,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Address or RollCount", pwd_not_needed = "true"),
+        message = "estimate the staking rewards per cycle of the active rolls of an address, or of a number of rolls bought in addition to the current stake distribution"
+    )]
+    roll_roi,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Ip Port", pwd_not_needed = "true"),
+        message = "compare the cycle, last slot and per-thread finality of the node with the ones of the node whose public API is at the given address"
+    )]
+    compare_status,
+
     #[strum(
         ascii_case_insensitive,
         props(args = "Address1 Address2 ...", pwd_not_needed = "true"),
@@ -170,6 +244,13 @@ pub enum Command {
     )]
     get_blocks,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "CursorFilePath (optional)", pwd_not_needed = "true"),
+        message = "stream the new final blocks as NDJSON, resuming from the cursor file if one is given (stop with Ctrl-C)"
+    )]
+    stream_finals,
+
     #[strum(
         ascii_case_insensitive,
         props(args = "EndorsementId1 EndorsementId2 ...", pwd_not_needed = "true"),
@@ -184,6 +265,13 @@ pub enum Command {
     )]
     get_operations,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Address", pwd_not_needed = "true"),
+        message = "show the operations sent by an address that are still waiting in the pool"
+    )]
+    get_pending_operations,
+
     #[strum(
         ascii_case_insensitive,
         props(
@@ -217,7 +305,8 @@ pub enum Command {
 
     #[strum(
         ascii_case_insensitive,
-        message = "generate a secret key and add it into the wallet"
+        props(args = "[--all-threads]"),
+        message = "generate a secret key and add it into the wallet, or one key per thread with --all-threads"
     )]
     wallet_generate_secret_key,
 
@@ -256,17 +345,55 @@ pub enum Command {
     )]
     sell_rolls,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(
+            args = "SenderAddress ReceiverAddress Amount Fee",
+            pwd_not_needed = "true"
+        ),
+        message = "show the final and candidate balances of both sides before and after a transfer, nothing is sent"
+    )]
+    simulate_transfer,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Address RollCount", pwd_not_needed = "true"),
+        message = "show when and how many coins would be credited back if the address sold rolls in the next slot"
+    )]
+    preview_roll_sell,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Address Path", pwd_not_needed = "true"),
+        message = "export to a file the operations, balance changes and roll changes of an address, signed by the node (only covers the final blocks the node did not prune yet)"
+    )]
+    export_address_history,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Path", pwd_not_needed = "true"),
+        message = "check the signature of an address history exported with export_address_history"
+    )]
+    verify_address_history,
+
     #[strum(
         ascii_case_insensitive,
         props(args = "SenderAddress ReceiverAddress Amount Fee"),
-        message = "send coins from a wallet address"
+        message = "send coins from a wallet address, transfers cannot carry a memo: use execute_smart_contract --memo to attach one to an operation"
     )]
     send_transaction,
 
     #[strum(
         ascii_case_insensitive,
-        props(args = "SenderAddress PathToBytecode MaxGas MaxCoins Fee"),
-        message = "create and send an operation containing byte code"
+        props(args = "OperationId MaxRetries"),
+        message = "watch an operation until it is final and re-submit it with a fresh validity window when it expires without having been included, at most MaxRetries times (stop with Ctrl-C)"
+    )]
+    keep_alive_operation,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "SenderAddress PathToBytecode MaxGas MaxCoins Fee [--memo Text]"),
+        message = "create and send an operation containing byte code, with an optional public memo (UTF-8, at most 256 bytes)"
     )]
     execute_smart_contract,
 
@@ -279,21 +406,59 @@ pub enum Command {
 
     #[strum(
         ascii_case_insensitive,
-        props(args = "PathToBytecode MaxGas Address Fee", pwd_not_needed = "true"),
-        message = "execute byte code, address is optional, fee is optional. Nothing is really executed on chain"
+        props(
+            args = "PathToBytecode MaxGas Address Fee [--events-from Address1,Address2,...] [--show-state-changes]",
+            pwd_not_needed = "true"
+        ),
+        message = "execute byte code, address is optional, fee is optional, --events-from only keeps the events emitted by the given addresses, --show-state-changes lists the ledger changes the execution would make, in non-interactive mode these options go after `--`. Nothing is really executed on chain"
     )]
     read_only_execute_smart_contract,
 
     #[strum(
         ascii_case_insensitive,
         props(
-            args = "TargetAddress TargetFunction Parameter MaxGas SenderAddress Coins Fee",
+            args = "TargetAddress TargetFunction Parameter MaxGas SenderAddress Coins Fee [--events-from Address1,Address2,...] [--show-state-changes]",
             pwd_not_needed = "true"
         ),
-        message = "call a smart contract function, sender address, coins and fee are optional. Nothing is really executed on chain"
+        message = "call a smart contract function, sender address, coins and fee are optional, --events-from only keeps the events emitted by the given addresses, --show-state-changes lists the ledger changes the execution would make, in non-interactive mode these options go after `--`. Nothing is really executed on chain"
     )]
     read_only_call,
 
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "Address", pwd_not_needed = "true"),
+        message = "check an address and print it in its canonical form (surrounding whitespace and prefix case are fixed)"
+    )]
+    normalize_address,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "PathToBootstrapGraph [PathToContext]", pwd_not_needed = "true"),
+        message = "load a serialized bootstrap graph and print a summary of its content, without starting a node. The optional JSON context file overrides the MainNet deserialization limits (see base_config/deserialization_context.json)"
+    )]
+    bootstrap_info,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "PathToOperation [PathToContext]", pwd_not_needed = "true"),
+        message = "decode a serialized signed operation and check its signature, without a node. The optional JSON context file overrides the MainNet deserialization limits (see base_config/deserialization_context.json)"
+    )]
+    decode_operation,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[IntervalMs]", pwd_not_needed = "true"),
+        message = "print the node stats in the Prometheus text format. If an interval is given, print a new snapshot every IntervalMs until Ctrl-C"
+    )]
+    metrics,
+
+    #[strum(
+        ascii_case_insensitive,
+        props(args = "[MinPolls] [PollIntervalMs]", pwd_not_needed = "true"),
+        message = "poll the cliques until Ctrl-C and alert when several of them compete for MinPolls polls in a row (defaults: 3 polls, every 1000 ms)"
+    )]
+    watch_forks,
+
     #[strum(
         ascii_case_insensitive,
         props(pwd_not_needed = "true"),
@@ -370,7 +535,9 @@ impl ExtendedWallet {
                         x.address,
                         ExtendedWalletEntry {
                             keypair: keypair.clone(),
-                            address_info: x.compact(),
+                            address_info: x.compact().ok_or_else(|| {
+                                anyhow!("incomplete information for address {}", x.address)
+                            })?,
                             show_keys,
                         },
                     ))
@@ -380,6 +547,326 @@ impl ExtendedWallet {
     }
 }
 
+/// Read-only execution response along with its decoded state changes, for `--show-state-changes`
+#[derive(Debug, Serialize)]
+pub struct ReadOnlyStateChanges {
+    /// the response of the node
+    #[serde(flatten)]
+    pub response: ExecuteReadOnlyResponse,
+    /// the decoded state changes of the execution
+    pub decoded_state_changes: StateChangesSummary,
+}
+
+/// Maximum number of ledger changes listed by `--show-state-changes`, the others are only counted
+pub(crate) const MAX_DISPLAYED_STATE_CHANGES: usize = 50;
+
+/// Addresses generated in the wallet by `wallet_generate_secret_key --all-threads`
+#[derive(Debug, Serialize)]
+pub struct GeneratedAddresses {
+    /// generated addresses by thread
+    pub addresses: BTreeMap<u8, Address>,
+    /// threads for which no address could be generated
+    pub missing_threads: Vec<u8>,
+}
+
+/// Estimated staking rewards of some active rolls over a cycle, as computed by `roll_roi`
+#[derive(Debug, Serialize)]
+pub struct RollRoiEstimate {
+    /// active rolls of the staker
+    pub rolls: u64,
+    /// active rolls of all the stakers, the staker included
+    pub total_rolls: u64,
+    /// share of the active rolls held by the staker, between 0 and 1
+    pub roll_share: f64,
+    /// price of the rolls of the staker
+    pub stake_value: Amount,
+    /// expected number of blocks created by the staker per cycle
+    pub expected_blocks_per_cycle: f64,
+    /// expected number of endorsements created by the staker per cycle
+    pub expected_endorsements_per_cycle: f64,
+    /// approximate reward of the staker per cycle
+    pub reward_per_cycle: Amount,
+    /// reward per cycle relative to the stake value, in percent
+    pub reward_per_cycle_percent: f64,
+}
+
+/// Nodes the node is connected to
+#[derive(Debug, Serialize)]
+pub struct ConnectedPeers {
+    /// connected nodes sorted by node id: ip address and true if the connection is outgoing
+    pub connected_nodes: BTreeMap<NodeId, (IpAddr, bool)>,
+}
+
+/// Coins credited back by a roll sale
+#[derive(Debug, Serialize)]
+pub struct RollSellCredit {
+    /// slot at which the coins are credited
+    pub credit_slot: Slot,
+    /// credited amount
+    pub amount: Amount,
+}
+
+/// Parameters needed to deserialize network objects without asking a node.
+///
+/// The defaults are the MainNet values:
+/// * thread count: 32
+/// * max operations per block: 5000
+/// * endorsement count: 16
+/// * max denunciations per block header: 128
+/// * max bootstrap blocks: 1000000
+/// * max datastore value length: 10000000
+/// * max function name length: 65535
+/// * max parameters size: 10000000
+/// * max operation datastore entry count: 128
+/// * max operation datastore key length: 255
+/// * max operation datastore value length: 500000
+///
+/// The chain id defaults to the one of the client (77658377 on MainNet).
+/// A JSON file only needs to contain the fields that differ from the defaults.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeserializationContext {
+    /// chain id, `None` to use the one of the client
+    pub chain_id: Option<u64>,
+    /// number of threads
+    pub thread_count: u8,
+    /// maximum number of operations per block
+    pub max_operations_per_block: u32,
+    /// number of endorsements per block
+    pub endorsement_count: u32,
+    /// maximum number of denunciations per block header
+    pub max_denunciations_per_block_header: u32,
+    /// maximum number of blocks in a bootstrap graph
+    pub max_bootstrap_blocks: u32,
+    /// maximum length of a datastore value
+    pub max_datastore_value_length: u64,
+    /// maximum length of a called function name
+    pub max_function_name_length: u16,
+    /// maximum size of the parameters of a call
+    pub max_parameters_size: u32,
+    /// maximum number of entries in the datastore of an operation
+    pub max_op_datastore_entry_count: u64,
+    /// maximum length of a key in the datastore of an operation
+    pub max_op_datastore_key_length: u8,
+    /// maximum length of a value in the datastore of an operation
+    pub max_op_datastore_value_length: u64,
+}
+
+impl Default for DeserializationContext {
+    fn default() -> Self {
+        DeserializationContext {
+            chain_id: None,
+            thread_count: THREAD_COUNT,
+            max_operations_per_block: MAX_OPERATIONS_PER_BLOCK,
+            endorsement_count: ENDORSEMENT_COUNT,
+            max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
+            max_bootstrap_blocks: MAX_BOOTSTRAP_BLOCKS,
+            max_datastore_value_length: MAX_DATASTORE_VALUE_LENGTH,
+            max_function_name_length: MAX_FUNCTION_NAME_LENGTH,
+            max_parameters_size: MAX_PARAMETERS_SIZE,
+            max_op_datastore_entry_count: MAX_OPERATION_DATASTORE_ENTRY_COUNT,
+            max_op_datastore_key_length: MAX_OPERATION_DATASTORE_KEY_LENGTH,
+            max_op_datastore_value_length: MAX_OPERATION_DATASTORE_VALUE_LENGTH,
+        }
+    }
+}
+
+impl DeserializationContext {
+    /// Reads a context from a JSON file, or returns the MainNet context if no file is given
+    async fn load(path: Option<&String>) -> Result<Self> {
+        match path {
+            Some(path) => {
+                let data = get_file_as_byte_vec(&PathBuf::from(path)).await?;
+                serde_json::from_slice(&data)
+                    .map_err(|e| anyhow!("invalid deserialization context {}: {}", path, e))
+            }
+            None => Ok(DeserializationContext::default()),
+        }
+    }
+}
+
+/// Deserializes a signed operation and checks its signature
+fn decode_operation(
+    data: &[u8],
+    context: &DeserializationContext,
+    chain_id: u64,
+) -> Result<SecureShareOperation> {
+    let deserializer = SecureShareDeserializer::new(
+        OperationDeserializer::new(
+            context.max_datastore_value_length,
+            context.max_function_name_length,
+            context.max_parameters_size,
+            context.max_op_datastore_entry_count,
+            context.max_op_datastore_key_length,
+            context.max_op_datastore_value_length,
+        ),
+        chain_id,
+    );
+    let (rest, operation): (&[u8], SecureShareOperation) = deserializer
+        .deserialize::<DeserializeError>(data)
+        .map_err(|e| anyhow!("invalid operation: {}", e))?;
+    if !rest.is_empty() {
+        bail!(
+            "invalid operation: {} trailing bytes after the operation",
+            rest.len()
+        );
+    }
+    operation.verify_signature().map_err(|e| {
+        anyhow!(
+            "invalid operation signature, check the chain id of the context: {}",
+            e
+        )
+    })?;
+    Ok(operation)
+}
+
+/// Summary of the content of a serialized bootstrap graph
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct BootstrapGraphSummary {
+    /// number of blocks in the graph
+    pub block_count: usize,
+    /// number of blocks marked as final
+    pub final_block_count: usize,
+    /// highest period of the final blocks of each thread, `None` if the thread has no final block
+    pub latest_final_periods: Vec<Option<u64>>,
+    /// size of the serialized graph in bytes
+    pub serialized_size: usize,
+}
+
+impl BootstrapGraphSummary {
+    /// Deserializes a bootstrap graph and summarizes it
+    fn from_bytes(data: &[u8], context: &DeserializationContext, chain_id: u64) -> Result<Self> {
+        let deserializer = BootstrapableGraphDeserializer::new(
+            BlockDeserializerArgs {
+                thread_count: context.thread_count,
+                max_operations_per_block: context.max_operations_per_block,
+                endorsement_count: context.endorsement_count,
+                max_denunciations_per_block_header: context.max_denunciations_per_block_header,
+                last_start_period: None,
+                chain_id,
+            },
+            context.max_bootstrap_blocks,
+        );
+        let (rest, graph) = deserializer
+            .deserialize::<DeserializeError>(data)
+            .map_err(|e| anyhow!("invalid bootstrap graph: {}", e))?;
+        if !rest.is_empty() {
+            bail!(
+                "invalid bootstrap graph: {} trailing bytes after the graph",
+                rest.len()
+            );
+        }
+
+        let mut latest_final_periods = vec![None; context.thread_count as usize];
+        let mut final_block_count = 0;
+        for export_block in graph.final_blocks.iter().filter(|b| b.is_final) {
+            final_block_count += 1;
+            let slot = export_block.block.content.header.content.slot;
+            let latest: &mut Option<u64> = &mut latest_final_periods[slot.thread as usize];
+            *latest = Some(latest.map_or(slot.period, |p| max(p, slot.period)));
+        }
+        Ok(BootstrapGraphSummary {
+            block_count: graph.final_blocks.len(),
+            final_block_count,
+            latest_final_periods,
+            serialized_size: data.len(),
+        })
+    }
+}
+
+/// A clique competing with others during a fork
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct CompetingClique {
+    /// true if it is the clique of higher fitness
+    pub is_blockclique: bool,
+    /// fitness of the clique
+    pub fitness: u64,
+    /// blocks of the clique that are not shared by all the cliques
+    pub block_ids: Vec<BlockId>,
+}
+
+/// Event printed by `watch_forks`
+#[derive(Debug, Serialize, PartialEq, Eq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum ForkEvent {
+    /// several cliques have been competing for `polls` polls in a row
+    Fork {
+        polls: u64,
+        cliques: Vec<CompetingClique>,
+    },
+    /// a single clique is left after a reported fork that lasted `polls` polls
+    Resolved { polls: u64 },
+}
+
+/// Event printed by `keep_alive_operation`
+#[derive(Debug, Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum KeepAliveEvent {
+    /// the operation expired without being included and was sent again
+    Resubmitted {
+        expired_id: OperationId,
+        resubmitted_id: OperationId,
+        retry: u64,
+    },
+    /// the operation is final
+    Final { operation_id: OperationId },
+}
+
+/// Follows the number of cliques over successive polls
+struct ForkWatcher {
+    /// number of polls in a row with several cliques before a fork is reported
+    min_polls: u64,
+    /// number of polls in a row with several cliques so far
+    fork_polls: u64,
+}
+
+impl ForkWatcher {
+    fn new(min_polls: u64) -> Self {
+        ForkWatcher {
+            min_polls,
+            fork_polls: 0,
+        }
+    }
+
+    /// Takes the cliques of a new poll into account, returning the event to report if any
+    fn observe(&mut self, cliques: &[Clique]) -> Option<ForkEvent> {
+        if cliques.len() <= 1 {
+            let polls = std::mem::take(&mut self.fork_polls);
+            return (polls >= self.min_polls).then_some(ForkEvent::Resolved { polls });
+        }
+        self.fork_polls += 1;
+        if self.fork_polls != self.min_polls {
+            return None;
+        }
+        let shared: HashSet<&BlockId> = cliques[0]
+            .block_ids
+            .iter()
+            .filter(|id| cliques[1..].iter().all(|c| c.block_ids.contains(*id)))
+            .collect();
+        let cliques = cliques
+            .iter()
+            .map(|clique| {
+                let mut block_ids: Vec<BlockId> = clique
+                    .block_ids
+                    .iter()
+                    .filter(|id| !shared.contains(id))
+                    .copied()
+                    .collect();
+                block_ids.sort_unstable();
+                CompetingClique {
+                    is_blockclique: clique.is_blockclique,
+                    fitness: clique.fitness,
+                    block_ids,
+                }
+            })
+            .collect();
+        Some(ForkEvent::Fork {
+            polls: self.fork_polls,
+            cliques,
+        })
+    }
+}
+
 impl Display for ExtendedWallet {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if self.0.is_empty() {
@@ -524,6 +1011,23 @@ impl Command {
                 Ok(Box::new(()))
             }
 
+            Command::node_export_ledger => {
+                if parameters.len() != 1 {
+                    bail!("wrong number of parameters");
+                }
+                match client
+                    .private
+                    .node_export_ledger(parameters[0].clone())
+                    .await
+                {
+                    Ok(entry_count) => Ok(Box::new(format!(
+                        "{} ledger entries exported to {}",
+                        entry_count, parameters[0]
+                    ))),
+                    Err(e) => rpc_error!(e),
+                }
+            }
+
             Command::node_get_staking_addresses => {
                 match client.private.get_staking_addresses().await {
                     Ok(staking_addresses) => Ok(Box::new(staking_addresses)),
@@ -565,10 +1069,101 @@ impl Command {
             }
 
             Command::get_status => match client.public.get_status().await {
-                Ok(node_status) => Ok(Box::new(node_status)),
+                Ok(node_status) => {
+                    if !json {
+                        warn_clock_drift(&node_status);
+                    }
+                    Ok(Box::new(node_status))
+                }
                 Err(e) => rpc_error!(e),
             },
 
+            Command::peers => match client.public.get_status().await {
+                Ok(node_status) => Ok(Box::new(ConnectedPeers {
+                    connected_nodes: node_status.connected_nodes,
+                })),
+                Err(e) => rpc_error!(e),
+            },
+
+            Command::get_genesis_fingerprint => {
+                if parameters.len() > 1 {
+                    bail!("wrong number of parameters");
+                }
+                let expected = match parameters.first() {
+                    Some(param) => Some(param.parse::<Hash>()?),
+                    None => None,
+                };
+                let fingerprint = match client.public.get_genesis_fingerprint().await {
+                    Ok(fingerprint) => fingerprint,
+                    Err(e) => rpc_error!(e),
+                };
+                match expected {
+                    Some(expected) if expected != fingerprint => bail!(
+                        "genesis fingerprint {} does not match the expected {}: the node runs on another network",
+                        fingerprint,
+                        expected
+                    ),
+                    Some(_) => Ok(Box::new(format!(
+                        "{} (matches the expected fingerprint)",
+                        fingerprint
+                    ))),
+                    None => Ok(Box::new(fingerprint.to_string())),
+                }
+            }
+
+            Command::get_pos_params => {
+                if !parameters.is_empty() {
+                    bail!("wrong number of parameters");
+                }
+                match client.public.get_pos_params().await {
+                    Ok(params) => Ok(Box::new(params)),
+                    Err(e) => rpc_error!(e),
+                }
+            }
+
+            Command::roll_roi => {
+                if parameters.len() != 1 {
+                    bail!("wrong number of parameters");
+                }
+                let params = match client.public.get_pos_params().await {
+                    Ok(params) => params,
+                    Err(e) => rpc_error!(e),
+                };
+                let stakers = match client.public.get_stakers().await {
+                    Ok(stakers) => stakers,
+                    Err(e) => rpc_error!(e),
+                };
+                let total_rolls: u64 = stakers.iter().map(|(_, rolls)| *rolls).sum();
+                let (rolls, total_rolls) = match parameters[0].parse::<Address>() {
+                    Ok(address) => (
+                        stakers
+                            .iter()
+                            .find(|(staker, _)| *staker == address)
+                            .map_or(0, |(_, rolls)| *rolls),
+                        total_rolls,
+                    ),
+                    Err(_) => {
+                        let rolls = parameters[0].parse::<u64>().map_err(|_| {
+                            anyhow!("expected an address or a roll count, got {}", parameters[0])
+                        })?;
+                        (rolls, total_rolls.saturating_add(rolls))
+                    }
+                };
+                match estimate_roll_roi(rolls, total_rolls, &params, ENDORSEMENT_COUNT) {
+                    Some(estimate) => Ok(Box::new(estimate)),
+                    None => bail!("there are no active rolls in the current cycle"),
+                }
+            }
+
+            Command::compare_status => {
+                if parameters.len() != 2 {
+                    bail!("wrong number of parameters");
+                }
+                let ip = parameters[0].parse::<IpAddr>()?;
+                let port = parameters[1].parse::<u16>()?;
+                compare_status(client, ip, port).await
+            }
+
             Command::get_addresses => {
                 let addresses = parse_vec::<Address>(parameters)?;
                 match client.public.get_addresses(addresses).await {
@@ -599,11 +1194,24 @@ impl Command {
                 }
                 let block_ids = parse_vec::<BlockId>(parameters)?;
                 match client.public.get_blocks(block_ids).await {
-                    Ok(blocks_info) => Ok(Box::new(blocks_info)),
+                    Ok(blocks_info) => {
+                        if !json {
+                            warn_block_fill(client, &blocks_info).await;
+                        }
+                        Ok(Box::new(blocks_info))
+                    }
                     Err(e) => rpc_error!(e),
                 }
             }
 
+            Command::stream_finals => {
+                if parameters.len() > 1 {
+                    bail!("wrong number of parameters");
+                }
+                let cursor_path = parameters.first().map(PathBuf::from);
+                stream_finals(client, cursor_path).await
+            }
+
             Command::get_endorsements => {
                 let endorsements = parse_vec::<EndorsementId>(parameters)?;
                 match client.public.get_endorsements(endorsements).await {
@@ -620,6 +1228,17 @@ impl Command {
                 }
             }
 
+            Command::get_pending_operations => {
+                if parameters.len() != 1 {
+                    bail!("wrong number of parameters");
+                }
+                let address = parameters[0].parse::<Address>()?;
+                match client.public.get_pending_operations(vec![address]).await {
+                    Ok(operations_info) => Ok(Box::new(operations_info)),
+                    Err(e) => rpc_error!(e),
+                }
+            }
+
             Command::get_filtered_sc_output_event => {
                 let p_list: [&str; 7] = [
                     "start",
@@ -785,6 +1404,33 @@ impl Command {
                 //       This will allow to generate keypair on hardware ledger / computer without internet...
 
                 let keypair_version: u64 = 0;
+
+                match parameters {
+                    [] => {}
+                    [flag] if flag == "--all-threads" => {
+                        let keypairs = generate_keypairs_per_thread(
+                            keypair_version,
+                            THREAD_COUNT,
+                            MAX_KEY_GENERATION_ATTEMPTS_PER_THREAD,
+                        )?;
+                        let missing_threads: Vec<u8> = (0..THREAD_COUNT)
+                            .filter(|thread| !keypairs.contains_key(thread))
+                            .collect();
+                        let threads: Vec<u8> = keypairs.keys().copied().collect();
+                        let addresses = wallet.add_keypairs(keypairs.into_values().collect())?;
+                        let generated = GeneratedAddresses {
+                            addresses: threads.into_iter().zip(addresses).collect(),
+                            missing_threads,
+                        };
+                        if json {
+                            return Ok(Box::new(generated));
+                        }
+                        generated.pretty_print();
+                        return Ok(Box::new(()));
+                    }
+                    _ => bail!("wrong parameters, expected nothing or --all-threads"),
+                }
+
                 let key = KeyPair::generate(keypair_version).expect("Unable to generate key pair");
 
                 let ad = wallet.add_keypairs(vec![key])?[0];
@@ -868,7 +1514,7 @@ impl Command {
                             {
                                 match addresses_info.get(0) {
                                     Some(info) => {
-                                        if info.candidate_balance < total {
+                                        if info.candidate_balance.unwrap_or_default() < total {
                                             client_warning!("this operation may be rejected due to insufficient balance");
                                         }
                                     }
@@ -913,8 +1559,8 @@ impl Command {
                     if let Ok(addresses_info) = client.public.get_addresses(vec![addr]).await {
                         match addresses_info.get(0) {
                             Some(info) => {
-                                if info.candidate_balance < fee
-                                    || roll_count > info.candidate_roll_count
+                                if info.candidate_balance.unwrap_or_default() < fee
+                                    || roll_count > info.candidate_roll_count.unwrap_or_default()
                                 {
                                     client_warning!("this operation may be rejected due to insufficient balance or roll count");
                                 }
@@ -935,6 +1581,98 @@ impl Command {
                 .await
             }
 
+            Command::simulate_transfer => {
+                if parameters.len() != 4 {
+                    bail!("wrong number of parameters");
+                }
+                let query = TransferSimulationQuery {
+                    sender_address: parameters[0].parse::<Address>()?,
+                    recipient_address: parameters[1].parse::<Address>()?,
+                    amount: parameters[2].parse::<Amount>()?,
+                    fee: parameters[3].parse::<Amount>()?,
+                };
+                match client.public.simulate_transfer(query).await {
+                    Ok(simulation) => Ok(Box::new(simulation)),
+                    Err(e) => rpc_error!(e),
+                }
+            }
+
+            Command::preview_roll_sell => {
+                if parameters.len() != 2 {
+                    bail!("wrong number of parameters");
+                }
+                let address = parameters[0].parse::<Address>()?;
+                let roll_count = parameters[1].parse::<u64>()?;
+                match client
+                    .public
+                    .preview_roll_sell(RollSellPreview {
+                        address,
+                        roll_count,
+                    })
+                    .await
+                {
+                    Ok((credit_slot, amount)) => Ok(Box::new(RollSellCredit {
+                        credit_slot,
+                        amount,
+                    })),
+                    Err(e) => rpc_error!(e),
+                }
+            }
+
+            Command::export_address_history => {
+                if parameters.len() != 2 {
+                    bail!("wrong number of parameters");
+                }
+                let address = parameters[0].parse::<Address>()?;
+                let path = PathBuf::from(&parameters[1]);
+                let bundle = match client.public.get_address_history(address, true).await {
+                    Ok(bundle) => bundle,
+                    Err(e) => rpc_error!(e),
+                };
+                // do not store a bundle that would fail the verification later on
+                bundle.verify(client.chain_id)?;
+                tokio::fs::write(&path, serde_json::to_string(&bundle)?).await?;
+                let covered = match bundle.history.covered_slots {
+                    Some((first, last)) => format!("final slots {} to {}", first, last),
+                    None => "no final slot".to_string(),
+                };
+                Ok(Box::new(format!(
+                    "exported {} operations of {} covering {} to {}",
+                    bundle.history.operations.len(),
+                    address,
+                    covered,
+                    path.display()
+                )))
+            }
+
+            Command::verify_address_history => {
+                if parameters.len() != 1 {
+                    bail!("wrong number of parameters");
+                }
+                let path = PathBuf::from(&parameters[0]);
+                let bundle: AddressHistoryBundle =
+                    serde_json::from_str(&tokio::fs::read_to_string(&path).await?)?;
+                match bundle.verify(client.chain_id)? {
+                    Some(public_key) => Ok(Box::new(format!(
+                        "valid history of {} signed by node {}",
+                        bundle.history.address,
+                        NodeId::new(public_key)
+                    ))),
+                    None => bail!("the history of {} is not signed", bundle.history.address),
+                }
+            }
+
+            Command::keep_alive_operation => {
+                let wallet = wallet_opt.as_mut().unwrap();
+
+                if parameters.len() != 2 {
+                    bail!("wrong number of parameters");
+                }
+                let op_id = parameters[0].parse::<OperationId>()?;
+                let max_retries = parameters[1].parse::<u64>()?;
+                keep_alive_operation(client, wallet, op_id, max_retries, json).await
+            }
+
             Command::send_transaction => {
                 let wallet = wallet_opt.as_mut().unwrap();
 
@@ -950,7 +1688,7 @@ impl Command {
                     if let Ok(addresses_info) = client.public.get_addresses(vec![addr]).await {
                         match addresses_info.get(0) {
                             Some(info) => {
-                                if info.candidate_balance < fee {
+                                if info.candidate_balance.unwrap_or_default() < fee {
                                     client_warning!("this operation may be rejected due to insufficient balance");
                                 }
                             }
@@ -974,6 +1712,110 @@ impl Command {
                 )
                 .await
             }
+            Command::normalize_address => {
+                if parameters.len() != 1 {
+                    bail!("wrong number of parameters");
+                }
+                let address = normalize_address(&parameters[0])?;
+                Ok(Box::new(address.to_string()))
+            }
+
+            Command::bootstrap_info => {
+                if parameters.is_empty() || parameters.len() > 2 {
+                    bail!("wrong number of parameters");
+                }
+                let path = PathBuf::from(&parameters[0]);
+                let data = get_file_as_byte_vec(&path).await?;
+                let context = DeserializationContext::load(parameters.get(1)).await?;
+                let summary = BootstrapGraphSummary::from_bytes(
+                    &data,
+                    &context,
+                    context.chain_id.unwrap_or(client.chain_id),
+                )?;
+                Ok(Box::new(summary))
+            }
+
+            Command::decode_operation => {
+                if parameters.is_empty() || parameters.len() > 2 {
+                    bail!("wrong number of parameters");
+                }
+                let path = PathBuf::from(&parameters[0]);
+                let data = get_file_as_byte_vec(&path).await?;
+                let context = DeserializationContext::load(parameters.get(1)).await?;
+                let operation =
+                    decode_operation(&data, &context, context.chain_id.unwrap_or(client.chain_id))?;
+                Ok(Box::new(operation))
+            }
+
+            Command::metrics => {
+                if parameters.len() > 1 {
+                    bail!("wrong number of parameters");
+                }
+                let interval = match parameters.first() {
+                    Some(p) => Some(std::time::Duration::from_millis(p.parse::<u64>()?)),
+                    None => None,
+                };
+                loop {
+                    let status = match client.public.get_status().await {
+                        Ok(node_status) => node_status,
+                        Err(e) => rpc_error!(e),
+                    };
+                    let metrics = prometheus_metrics(
+                        &status.consensus_stats,
+                        status.pool_stats,
+                        &status.network_stats,
+                    )?;
+                    let Some(interval) = interval else {
+                        return Ok(Box::new(metrics));
+                    };
+                    if json {
+                        println!("{}", serde_json::to_string(&metrics)?);
+                    } else {
+                        println!("{}", metrics);
+                    }
+                    tokio::select! {
+                        _ = tokio::signal::ctrl_c() => break,
+                        _ = tokio::time::sleep(interval) => {}
+                    }
+                }
+                Ok(Box::new(()))
+            }
+
+            Command::watch_forks => {
+                if parameters.len() > 2 {
+                    bail!("wrong number of parameters");
+                }
+                let min_polls = match parameters.first() {
+                    Some(p) => p.parse::<u64>()?,
+                    None => 3,
+                };
+                if min_polls == 0 {
+                    bail!("MinPolls must be at least 1");
+                }
+                let poll_interval = match parameters.get(1) {
+                    Some(p) => std::time::Duration::from_millis(p.parse::<u64>()?),
+                    None => std::time::Duration::from_millis(1000),
+                };
+                if !json {
+                    println!("Watching forks, press Ctrl-C to stop");
+                }
+                let mut watcher = ForkWatcher::new(min_polls);
+                loop {
+                    let cliques = match client.public.get_cliques().await {
+                        Ok(cliques) => cliques,
+                        Err(e) => rpc_error!(e),
+                    };
+                    if let Some(event) = watcher.observe(&cliques) {
+                        print_event(&event, json)?;
+                    }
+                    tokio::select! {
+                        _ = tokio::signal::ctrl_c() => break,
+                        _ = tokio::time::sleep(poll_interval) => {}
+                    }
+                }
+                Ok(Box::new(()))
+            }
+
             Command::when_moon => {
                 let res = "At night 🌔.";
                 if !json {
@@ -984,6 +1826,7 @@ impl Command {
             Command::execute_smart_contract => {
                 let wallet = wallet_opt.as_mut().unwrap();
 
+                let (parameters, memo) = take_option(parameters, "--memo")?;
                 if parameters.len() != 5 {
                     bail!("wrong number of parameters");
                 }
@@ -996,7 +1839,9 @@ impl Command {
                     if let Ok(addresses_info) = client.public.get_addresses(vec![addr]).await {
                         match addresses_info.get(0) {
                             Some(info) => {
-                                if info.candidate_balance < fee.saturating_add(max_coins) {
+                                if info.candidate_balance.unwrap_or_default()
+                                    < fee.saturating_add(max_coins)
+                                {
                                     client_warning!("this operation may be rejected due to insufficient balance");
                                 }
                             }
@@ -1016,7 +1861,17 @@ impl Command {
                         client_warning!("bytecode size exceeded the maximum size of a block, operation will be rejected");
                     }
                 }
-                let datastore = BTreeMap::new();
+                let mut datastore = BTreeMap::new();
+                if let Some(memo) = memo {
+                    if memo.len() > MAX_OPERATION_MEMO_LENGTH {
+                        bail!(
+                            "memo is {} bytes long, the maximum is {} bytes",
+                            memo.len(),
+                            MAX_OPERATION_MEMO_LENGTH
+                        );
+                    }
+                    datastore.insert(OPERATION_MEMO_DATASTORE_KEY.to_vec(), memo.into_bytes());
+                }
 
                 send_operation(
                     client,
@@ -1054,7 +1909,7 @@ impl Command {
                             {
                                 match addresses_info.get(0) {
                                     Some(info) => {
-                                        if info.candidate_balance < total {
+                                        if info.candidate_balance.unwrap_or_default() < total {
                                             client_warning!("this operation may be rejected due to insufficient balance");
                                         }
                                     }
@@ -1103,6 +1958,9 @@ impl Command {
                 }
             }
             Command::read_only_execute_smart_contract => {
+                let (parameters, show_state_changes) =
+                    take_flag(parameters, "--show-state-changes");
+                let (parameters, only_from) = take_events_from(&parameters)?;
                 if parameters.len() < 2 || parameters.len() > 4 {
                     bail!("wrong number of parameters");
                 }
@@ -1126,14 +1984,18 @@ impl Command {
                         address,
                         operation_datastore: None, // TODO - #3072
                         fee,
+                        only_from,
                     })
                     .await
                 {
-                    Ok(res) => Ok(Box::new(res)),
+                    Ok(res) => Ok(read_only_output(res, show_state_changes)),
                     Err(e) => rpc_error!(e),
                 }
             }
             Command::read_only_call => {
+                let (parameters, show_state_changes) =
+                    take_flag(parameters, "--show-state-changes");
+                let (parameters, only_from) = take_events_from(&parameters)?;
                 if parameters.len() < 4 || parameters.len() > 7 {
                     bail!("wrong number of parameters");
                 }
@@ -1162,10 +2024,11 @@ impl Command {
                         max_gas,
                         coins,
                         fee,
+                        only_from,
                     })
                     .await
                 {
-                    Ok(res) => Ok(Box::new(res)),
+                    Ok(res) => Ok(read_only_output(res, show_state_changes)),
                     Err(e) => rpc_error!(e),
                 }
             }
@@ -1373,21 +2236,10 @@ async fn send_operation(
         ));
     }
 
-    let slot = get_current_latest_block_slot(
-        status.config.thread_count,
-        status.config.t0,
-        status.config.genesis_timestamp,
-    )?
-    .unwrap_or_else(|| Slot::new(0, 0));
-    let mut expire_period = slot.period + status.config.operation_validity_periods;
-    if slot.thread >= addr.get_thread(status.config.thread_count) {
-        expire_period += 1;
-    };
-
     let op = wallet.create_operation(
         Operation {
             fee,
-            expire_period,
+            expire_period: fresh_expire_period(&status.config, &addr)?,
             op,
         },
         addr,
@@ -1412,6 +2264,413 @@ async fn send_operation(
     }
 }
 
+/// expire period giving a new operation of `sender` the full validity window
+fn fresh_expire_period(config: &CompactConfig, sender: &Address) -> Result<u64> {
+    let slot =
+        get_current_latest_block_slot(config.thread_count, config.t0, config.genesis_timestamp)?
+            .unwrap_or_else(|| Slot::new(0, 0));
+    let mut expire_period = slot.period + config.operation_validity_periods;
+    if slot.thread >= sender.get_thread(config.thread_count) {
+        expire_period += 1;
+    };
+    Ok(expire_period)
+}
+
+/// true if the thread of an operation is final past its expire period,
+/// so that the operation can no longer be included in a block
+fn can_no_longer_be_included(
+    expire_period: u64,
+    thread: u8,
+    last_final_periods: &[Option<u64>],
+) -> bool {
+    last_final_periods
+        .get(thread as usize)
+        .copied()
+        .flatten()
+        .map_or(false, |final_period| final_period >= expire_period)
+}
+
+/// watch an operation until it is final, re-submitting it with a fresh validity window
+/// each time it expires without having been included, at most `max_retries` times
+async fn keep_alive_operation(
+    client: &Client,
+    wallet: &Wallet,
+    op_id: OperationId,
+    max_retries: u64,
+    json: bool,
+) -> Result<Box<dyn Output>> {
+    let config = match client.public.get_status().await {
+        Ok(node_status) => node_status.config,
+        Err(e) => rpc_error!(e),
+    };
+    let (operation, sender, mut seen_in_block) =
+        match client.public.get_operations(vec![op_id]).await {
+            Ok(infos) => match infos.into_iter().next() {
+                Some(info) => (
+                    info.operation.content,
+                    info.operation.content_creator_address,
+                    !info.in_blocks.is_empty() || info.is_operation_final.is_some(),
+                ),
+                None => bail!("operation {} is unknown to the node", op_id),
+            },
+            Err(e) => rpc_error!(e),
+        };
+    if wallet.find_associated_keypair(&sender).is_none() {
+        bail!(
+            "the sender {} of the operation is not in the wallet",
+            sender
+        );
+    }
+    // keep stdout for the events
+    eprintln!(
+        "{}: a re-submitted operation is a new operation. It is only sent once the previous one can no longer be included, \
+        but if the node is desynchronized or on a fork the previous one may still land and both would be executed",
+        style("WARNING").yellow()
+    );
+
+    let thread = sender.get_thread(config.thread_count);
+    let mut current_id = op_id;
+    let mut expire_period = operation.expire_period;
+    let mut retries = 0;
+    let mut poll_interval = tokio::time::interval(config.t0.to_duration());
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(Box::new(())),
+            _ = poll_interval.tick() => {}
+        }
+
+        let info = match client.public.get_operations(vec![current_id]).await {
+            Ok(infos) => infos.into_iter().next(),
+            Err(e) => rpc_error!(e),
+        };
+        match info {
+            Some(info) => {
+                if info.is_operation_final == Some(true) {
+                    print_event(
+                        &KeepAliveEvent::Final {
+                            operation_id: current_id,
+                        },
+                        json,
+                    )?;
+                    return Ok(Box::new(()));
+                }
+                if !info.in_blocks.is_empty() || info.is_operation_final.is_some() {
+                    seen_in_block = true;
+                }
+                if info.in_pool || !info.in_blocks.is_empty() {
+                    continue;
+                }
+            }
+            // the node forgets both expired operations and old final ones:
+            // an operation that was included may have become final since the last poll
+            None if seen_in_block => bail!(
+                "operation {} was included in a block and is no longer known to the node, it may be final: it is not re-submitted",
+                current_id
+            ),
+            None => {}
+        }
+        let last_final_periods =
+            get_last_final_periods(&client.public, config.thread_count).await?;
+        if !can_no_longer_be_included(expire_period, thread, &last_final_periods) {
+            continue;
+        }
+        if retries >= max_retries {
+            bail!(
+                "operation {} expired and the {} allowed re-submissions were done",
+                current_id,
+                max_retries
+            );
+        }
+
+        retries += 1;
+        seen_in_block = false;
+        expire_period = fresh_expire_period(&config, &sender)?;
+        let op = wallet.create_operation(
+            Operation {
+                fee: operation.fee,
+                expire_period,
+                op: operation.op.clone(),
+            },
+            sender,
+        )?;
+        let resubmitted_id = match client
+            .public
+            .send_operations(vec![OperationInput {
+                creator_public_key: op.content_creator_pub_key,
+                serialized_content: op.serialized_data,
+                signature: op.signature,
+            }])
+            .await
+        {
+            Ok(ids) => ids
+                .into_iter()
+                .next()
+                .ok_or_else(|| anyhow!("the node did not return the re-submitted operation id"))?,
+            Err(e) => rpc_error!(e),
+        };
+        print_event(
+            &KeepAliveEvent::Resubmitted {
+                expired_id: current_id,
+                resubmitted_id,
+                retry: retries,
+            },
+            json,
+        )?;
+        current_id = resubmitted_id;
+    }
+}
+
+/// prints an event of a watching command, as a JSON line in json mode
+fn print_event<T: Output>(event: &T, json: bool) -> Result<()> {
+    if json {
+        println!("{}", serde_json::to_string(event)?);
+    } else {
+        event.pretty_print();
+    }
+    Ok(())
+}
+
+/// poll the node for blocks that became final and print their summaries as NDJSON
+///
+/// The cursor holds the period of the last streamed final block of each thread.
+/// It is saved to `cursor_path` after each batch so that a restart resumes from it.
+async fn stream_finals(client: &Client, cursor_path: Option<PathBuf>) -> Result<Box<dyn Output>> {
+    let config = match client.public.get_status().await {
+        Ok(node_status) => node_status.config,
+        Err(e) => rpc_error!(e),
+    };
+    let mut cursor: Vec<Option<u64>> = match &cursor_path {
+        Some(path) if path.exists() => {
+            serde_json::from_str(&tokio::fs::read_to_string(path).await?)?
+        }
+        _ => vec![None; config.thread_count as usize],
+    };
+    if cursor.len() != config.thread_count as usize {
+        bail!("the cursor file does not match the thread count of the node");
+    }
+
+    let mut poll_interval = tokio::time::interval(config.t0.to_duration());
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => return Ok(Box::new(())),
+            _ = poll_interval.tick() => {}
+        }
+
+        // only ask for the blocks after the least advanced thread of the cursor
+        let start = match cursor
+            .iter()
+            .enumerate()
+            .map(|(thread, period)| period.map(|period| Slot::new(period, thread as u8)))
+            .collect::<Option<Vec<Slot>>>()
+            .and_then(|slots| slots.into_iter().min())
+        {
+            Some(slot) => Some(get_block_slot_timestamp(
+                config.thread_count,
+                config.t0,
+                config.genesis_timestamp,
+                slot,
+            )?),
+            None => None,
+        };
+        let mut finals: Vec<BlockSummary> = match client
+            .public
+            .get_graph_interval(TimeInterval { start, end: None })
+            .await
+        {
+            Ok(blocks) => blocks
+                .into_iter()
+                .filter(|block| {
+                    block.is_final
+                        && cursor[block.slot.thread as usize]
+                            .map_or(true, |period| block.slot.period > period)
+                })
+                .collect(),
+            Err(e) => {
+                // keep stdout for the stream itself
+                eprintln!("{}: {}", style("WARNING").yellow(), e);
+                continue;
+            }
+        };
+        if finals.is_empty() {
+            continue;
+        }
+
+        finals.sort_unstable_by_key(|block| block.slot);
+        for block in finals {
+            println!("{}", serde_json::to_string(&block)?);
+            cursor[block.slot.thread as usize] = Some(block.slot.period);
+        }
+        if let Some(path) = &cursor_path {
+            tokio::fs::write(path, serde_json::to_string(&cursor)?).await?;
+        }
+    }
+}
+
+/// latest final period of each thread among the blocks known by the node's graph
+async fn get_last_final_periods(rpc: &RpcClient, thread_count: u8) -> Result<Vec<Option<u64>>> {
+    let blocks = match rpc
+        .get_graph_interval(TimeInterval {
+            start: None,
+            end: None,
+        })
+        .await
+    {
+        Ok(blocks) => blocks,
+        Err(e) => rpc_error!(e),
+    };
+    let mut periods = vec![None; thread_count as usize];
+    for block in blocks.into_iter().filter(|block| block.is_final) {
+        if let Some(period) = periods.get_mut(block.slot.thread as usize) {
+            *period = max(*period, Some(block.slot.period));
+        }
+    }
+    Ok(periods)
+}
+
+/// compare the status of the node with the one of a remote node, highlighting the finality gaps
+async fn compare_status(client: &Client, ip: IpAddr, port: u16) -> Result<Box<dyn Output>> {
+    let http_config = HttpConfig {
+        client_config: ClientConfig {
+            max_request_body_size: SETTINGS.client.max_request_body_size,
+            request_timeout: SETTINGS.client.request_timeout,
+            max_concurrent_requests: SETTINGS.client.max_concurrent_requests,
+            certificate_store: SETTINGS.client.certificate_store.clone(),
+            id_kind: SETTINGS.client.id_kind.clone(),
+            max_log_length: SETTINGS.client.max_log_length,
+            headers: SETTINGS.client.headers.clone(),
+        },
+        enabled: SETTINGS.client.http.enabled,
+    };
+    let remote = RpcClient::from_url(
+        &format!("http://{}", SocketAddr::new(ip, port)),
+        &http_config,
+    )
+    .await;
+
+    let local_status = match client.public.get_status().await {
+        Ok(node_status) => node_status,
+        Err(e) => rpc_error!(e),
+    };
+    let remote_status = match remote.get_status().await {
+        Ok(node_status) => node_status,
+        Err(e) => rpc_error!(e),
+    };
+    let thread_count = local_status.config.thread_count;
+    if remote_status.config.thread_count != thread_count {
+        bail!("the remote node does not have the same thread count as the local node");
+    }
+    let local_finals = get_last_final_periods(&client.public, thread_count).await?;
+    let remote_finals = get_last_final_periods(&remote, thread_count).await?;
+
+    let display_slot = |slot: Option<Slot>| slot.map_or("none".to_string(), |s| s.to_string());
+    let display_period = |period: Option<u64>| period.map_or("none".to_string(), |p| p.to_string());
+    let mut res = String::new();
+    writeln!(res, "\t\tlocal\tremote")?;
+    writeln!(
+        res,
+        "Current cycle:\t{}\t{}",
+        local_status.current_cycle, remote_status.current_cycle
+    )?;
+    writeln!(
+        res,
+        "Last slot:\t{}\t{}",
+        display_slot(local_status.last_slot),
+        display_slot(remote_status.last_slot)
+    )?;
+    writeln!(res, "Last final period per thread:")?;
+    for (thread, (local, remote)) in local_finals.iter().zip(remote_finals.iter()).enumerate() {
+        let line = format!(
+            "\tThread {}:\t{}\t{}",
+            thread,
+            display_period(*local),
+            display_period(*remote)
+        );
+        let lagging = match (local, remote) {
+            (Some(local), Some(remote)) => {
+                local.abs_diff(*remote) > SETTINGS.client.finality_lag_warning_periods
+            }
+            _ => true,
+        };
+        if lagging {
+            writeln!(res, "{}", style(line).red())?;
+        } else {
+            writeln!(res, "{}", line)?;
+        }
+    }
+    Ok(Box::new(res))
+}
+
+/// Compares the slot clock of the client with the last slot reported by the node,
+/// and warns if they drift apart by more than `clock_drift_warning_threshold`.
+fn warn_clock_drift(status: &NodeStatus) {
+    let drift = match slot_clock_drift(&status.config, status.last_slot, MassaTime::now()) {
+        Ok(Some(drift)) => drift,
+        Ok(None) => return,
+        Err(e) => {
+            client_warning!(format!("could not check the local clock: {}", e));
+            return;
+        }
+    };
+    if drift.unsigned_abs() > SETTINGS.client.clock_drift_warning_threshold.as_millis() {
+        client_warning!(format!(
+            "the local clock is {:.1} seconds {} the slot clock of the node, check the time settings of this machine",
+            drift.unsigned_abs() as f64 / 1000.0,
+            if drift > 0 { "ahead of" } else { "behind" }
+        ));
+    }
+}
+
+/// Drift in milliseconds between the slot expected at `local_now` and the last slot reported by the node,
+/// positive if the local clock is ahead. The precision is one slot (`t0 / thread_count`).
+///
+/// Returns `None` if the node or the client is before genesis.
+fn slot_clock_drift(
+    config: &CompactConfig,
+    node_last_slot: Option<Slot>,
+    local_now: MassaTime,
+) -> Result<Option<i64>> {
+    let Some(node_slot) = node_last_slot else {
+        return Ok(None);
+    };
+    let Some(local_slot) = get_latest_block_slot_at_timestamp(
+        config.thread_count,
+        config.t0,
+        config.genesis_timestamp,
+        local_now,
+    )?
+    else {
+        return Ok(None);
+    };
+    let slot_index = |slot: Slot| {
+        i128::from(slot.period) * i128::from(config.thread_count) + i128::from(slot.thread)
+    };
+    let slot_duration = i128::from(config.t0.as_millis()) / i128::from(config.thread_count);
+    let drift = (slot_index(local_slot) - slot_index(node_slot)) * slot_duration;
+    Ok(Some(i64::try_from(drift)?))
+}
+
+/// warn about the blocks that are filled above the configured ratio of the maximum block size
+async fn warn_block_fill(client: &Client, blocks_info: &[BlockInfo]) {
+    let max_block_size = match client.public.get_status().await {
+        Ok(node_status) => node_status.config.max_block_size as u64,
+        Err(_) => return,
+    };
+    for (block_id, content) in blocks_info
+        .iter()
+        .filter_map(|info| info.content.as_ref().map(|content| (info.id, content)))
+    {
+        if let Ok(ratio) = content.block.fill_ratio(max_block_size) {
+            if ratio > SETTINGS.client.block_fill_warning_ratio {
+                client_warning!(format!(
+                    "block {} is filled at {:.1}% of the maximum block size",
+                    block_id,
+                    ratio * 100.0
+                ));
+            }
+        }
+    }
+}
+
 /// TODO: ugly utilities functions
 /// takes a slice of string and makes it into a `Vec<T>`
 pub fn parse_vec<T: std::str::FromStr>(args: &[String]) -> anyhow::Result<Vec<T>, anyhow::Error>
@@ -1426,6 +2685,376 @@ where
         .collect()
 }
 
+/// Number of keys generated at most to find an address of a given thread
+const MAX_KEY_GENERATION_ATTEMPTS_PER_THREAD: u32 = 1000;
+
+/// Generates one keypair whose address belongs to each thread of `0..thread_count`.
+/// A thread is left out if no matching address was found after `max_attempts_per_thread` keys.
+/// Keys generated while looking for a thread are used for the other threads they land in.
+fn generate_keypairs_per_thread(
+    keypair_version: u64,
+    thread_count: u8,
+    max_attempts_per_thread: u32,
+) -> Result<BTreeMap<u8, KeyPair>> {
+    let mut keypairs = BTreeMap::new();
+    for thread in 0..thread_count {
+        let mut attempts = 0;
+        while !keypairs.contains_key(&thread) && attempts < max_attempts_per_thread {
+            let keypair = KeyPair::generate(keypair_version)?;
+            let key_thread =
+                Address::from_public_key(&keypair.get_public_key()).get_thread(thread_count);
+            keypairs.entry(key_thread).or_insert(keypair);
+            attempts += 1;
+        }
+    }
+    Ok(keypairs)
+}
+
+/// Removes a `--name value` option from the parameters of a command,
+/// returning the remaining parameters and the value of the option.
+fn take_option(parameters: &[String], name: &str) -> Result<(Vec<String>, Option<String>)> {
+    let Some(pos) = parameters.iter().position(|p| p == name) else {
+        return Ok((parameters.to_vec(), None));
+    };
+    let Some(value) = parameters.get(pos + 1).cloned() else {
+        bail!("missing value for option {}", name);
+    };
+    let mut remaining = parameters.to_vec();
+    remaining.drain(pos..=pos + 1);
+    Ok((remaining, Some(value)))
+}
+
+/// Removes the `name` flag from the parameters, returning the remaining parameters and whether the flag was present.
+fn take_flag(parameters: &[String], name: &str) -> (Vec<String>, bool) {
+    let mut remaining = parameters.to_vec();
+    let len = remaining.len();
+    remaining.retain(|p| p != name);
+    let present = remaining.len() != len;
+    (remaining, present)
+}
+
+/// Output of a read-only command, with its decoded state changes if `show_state_changes` is set
+fn read_only_output(
+    response: ExecuteReadOnlyResponse,
+    show_state_changes: bool,
+) -> Box<dyn Output> {
+    if show_state_changes {
+        let decoded_state_changes = StateChangesSummary::new(&response.state_changes);
+        Box::new(ReadOnlyStateChanges {
+            response,
+            decoded_state_changes,
+        })
+    } else {
+        Box::new(response)
+    }
+}
+
+/// Bytes as a quoted string if they are printable UTF-8, as a byte list otherwise.
+/// Values longer than 64 bytes are cut, with their full size.
+fn readable_bytes(bytes: &[u8]) -> String {
+    const MAX_LEN: usize = 64;
+    let shown = &bytes[..bytes.len().min(MAX_LEN)];
+    let mut readable = match std::str::from_utf8(shown) {
+        Ok(s) if !s.chars().any(char::is_control) => format!("{:?}", s),
+        _ => format!("{:?}", shown),
+    };
+    if bytes.len() > MAX_LEN {
+        readable.push_str(&format!("... ({} bytes)", bytes.len()));
+    }
+    readable
+}
+
+/// Formats the state changes of a read-only execution as a table with one ledger change per row.
+/// Only the first `max_rows` ledger changes are listed, the remaining ones are counted.
+pub(crate) fn format_state_changes(summary: &StateChangesSummary, max_rows: usize) -> String {
+    let mut table = format!(
+        "State changes: {} ledger changes, {} asynchronous message changes, {} roll count changes\n",
+        summary.ledger_changes.len(),
+        summary.async_message_changes,
+        summary.roll_changes
+    );
+    if summary.ledger_changes.is_empty() {
+        return table;
+    }
+    let rows: Vec<(String, &str, String)> = summary
+        .ledger_changes
+        .iter()
+        .take(max_rows)
+        .map(|(address, change)| {
+            let (kind, detail) = match change {
+                LedgerChange::EntryDeleted => ("entry deleted", String::new()),
+                LedgerChange::Balance(balance) => ("balance", balance.to_string()),
+                LedgerChange::Bytecode(size) => ("bytecode", format!("{} bytes", size)),
+                LedgerChange::DatastoreWrite { key, value } => (
+                    "datastore write",
+                    format!("{} = {}", readable_bytes(key), readable_bytes(value)),
+                ),
+                LedgerChange::DatastoreDelete { key } => ("datastore delete", readable_bytes(key)),
+            };
+            (address.to_string(), kind, detail)
+        })
+        .collect();
+    let address_width = rows.iter().map(|row| row.0.len()).max().unwrap_or(0);
+    let kind_width = rows.iter().map(|row| row.1.len()).max().unwrap_or(0);
+    for (address, kind, detail) in rows {
+        table.push_str(&format!(
+            "{:<address_width$}  {:<kind_width$}  {}\n",
+            address, kind, detail
+        ));
+    }
+    if summary.ledger_changes.len() > max_rows {
+        table.push_str(&format!(
+            "... and {} more ledger changes\n",
+            summary.ledger_changes.len() - max_rows
+        ));
+    }
+    table
+}
+
+/// Removes the `--events-from Address1,Address2,...` option from the parameters of a read-only command,
+/// returning the remaining parameters and the parsed addresses.
+fn take_events_from(parameters: &[String]) -> Result<(Vec<String>, Option<Vec<Address>>)> {
+    let (remaining, addresses) = take_option(parameters, "--events-from")?;
+    let only_from = addresses
+        .map(|addresses| {
+            addresses
+                .split(',')
+                .map(|addr| {
+                    addr.parse::<Address>()
+                        .map_err(|e| anyhow!("failed to parse \"{}\" due to: {}", addr, e))
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .transpose()?;
+    Ok((remaining, only_from))
+}
+
+/// Formats node stats in the Prometheus text exposition format.
+///
+/// Metric names are prefixed with `massa_` and end with their unit when they have one.
+/// The node computes most of its stats over sliding windows, so they are exported as gauges.
+/// The totals since the node started are exported as counters, whose names end with `_total`.
+/// Node identity is not a label: it is left to the labels of the scrape target.
+/// The only labels are `thread` (thread index) and `reason` (`stale` or `invalid` for discards).
+/// A stat without a value, like the finality latency before any block became final, is omitted.
+///
+/// Exported metrics:
+/// * `massa_cliques`: number of active cliques
+/// * `massa_final_blocks`, `massa_stale_blocks`: blocks that became final or stale over the stats time span
+/// * `massa_finality_latency_slots`: average number of slots for a block to become final
+/// * `massa_slots_since_last_finality`: slots since the latest final periods last advanced
+/// * `massa_discarded_blocks{reason}`: blocks discarded over the discard stats window
+/// * `massa_blocks_per_period{thread}`: average number of blocks produced per period in each thread
+/// * `massa_endorsement_participation`: fraction of the endorsement draws of the current cycle that were filled
+/// * `massa_pruned_operations_total`: operations of the final blocks pruned from the block graph since the node started
+/// * `massa_pool_operations`, `massa_pool_endorsements`: number of items in the pool
+/// * `massa_in_connections`, `massa_out_connections`: connections to other nodes
+/// * `massa_known_peers`, `massa_banned_peers`, `massa_active_nodes`: peers known by the node
+fn prometheus_metrics(
+    consensus: &ConsensusStats,
+    pool_stats: (usize, usize),
+    network: &NetworkStats,
+) -> Result<String, std::fmt::Error> {
+    fn metric(
+        res: &mut String,
+        name: &str,
+        kind: &str,
+        help: &str,
+        samples: &[(String, f64)],
+    ) -> std::fmt::Result {
+        if samples.is_empty() {
+            return Ok(());
+        }
+        writeln!(res, "# HELP massa_{} {}", name, help)?;
+        writeln!(res, "# TYPE massa_{} {}", name, kind)?;
+        for (labels, value) in samples {
+            writeln!(res, "massa_{}{} {}", name, labels, value)?;
+        }
+        Ok(())
+    }
+    fn gauge(
+        res: &mut String,
+        name: &str,
+        help: &str,
+        samples: &[(String, f64)],
+    ) -> std::fmt::Result {
+        metric(res, name, "gauge", help, samples)
+    }
+    let single = |value: f64| vec![(String::new(), value)];
+
+    let mut res = String::new();
+    gauge(
+        &mut res,
+        "cliques",
+        "Number of active cliques.",
+        &single(consensus.clique_count as f64),
+    )?;
+    gauge(
+        &mut res,
+        "final_blocks",
+        "Blocks that became final over the stats time span.",
+        &single(consensus.final_block_count as f64),
+    )?;
+    gauge(
+        &mut res,
+        "stale_blocks",
+        "Blocks that became stale over the stats time span.",
+        &single(consensus.stale_block_count as f64),
+    )?;
+    gauge(
+        &mut res,
+        "finality_latency_slots",
+        "Average number of slots between a block becoming active and becoming final.",
+        &consensus
+            .average_finality_latency
+            .map_or(Vec::new(), single),
+    )?;
+    gauge(
+        &mut res,
+        "slots_since_last_finality",
+        "Slots elapsed since the latest final periods last advanced.",
+        &single(consensus.slots_since_last_finality as f64),
+    )?;
+    gauge(
+        &mut res,
+        "discarded_blocks",
+        "Blocks discarded over the discard stats window, by reason.",
+        &[
+            (
+                "{reason=\"stale\"}".to_string(),
+                consensus.recent_discarded_blocks.stale as f64,
+            ),
+            (
+                "{reason=\"invalid\"}".to_string(),
+                consensus.recent_discarded_blocks.invalid as f64,
+            ),
+        ],
+    )?;
+    gauge(
+        &mut res,
+        "blocks_per_period",
+        "Average number of blocks produced per period, by thread.",
+        &consensus
+            .production_rate_per_thread
+            .iter()
+            .enumerate()
+            .map(|(thread, rate)| (format!("{{thread=\"{}\"}}", thread), *rate))
+            .collect::<Vec<_>>(),
+    )?;
+    gauge(
+        &mut res,
+        "endorsement_participation",
+        "Fraction of the endorsement draws of the current cycle that were filled.",
+        &consensus
+            .endorsement_participation
+            .map_or(Vec::new(), single),
+    )?;
+    metric(
+        &mut res,
+        "pruned_operations_total",
+        "counter",
+        "Operations of the final blocks pruned from the block graph since the node started.",
+        &single(consensus.pruned_operation_count as f64),
+    )?;
+    gauge(
+        &mut res,
+        "pool_operations",
+        "Operations in the pool.",
+        &single(pool_stats.0 as f64),
+    )?;
+    gauge(
+        &mut res,
+        "pool_endorsements",
+        "Endorsements in the pool.",
+        &single(pool_stats.1 as f64),
+    )?;
+    gauge(
+        &mut res,
+        "in_connections",
+        "Incoming connections.",
+        &single(network.in_connection_count as f64),
+    )?;
+    gauge(
+        &mut res,
+        "out_connections",
+        "Outgoing connections.",
+        &single(network.out_connection_count as f64),
+    )?;
+    gauge(
+        &mut res,
+        "known_peers",
+        "Peers known by the node.",
+        &single(network.known_peer_count as f64),
+    )?;
+    gauge(
+        &mut res,
+        "banned_peers",
+        "Peers banned by the node.",
+        &single(network.banned_peer_count as f64),
+    )?;
+    gauge(
+        &mut res,
+        "active_nodes",
+        "Nodes the node is connected to.",
+        &single(network.active_node_count as f64),
+    )?;
+    Ok(res)
+}
+
+/// Estimates the rewards per cycle of `rolls` active rolls out of `total_rolls`.
+///
+/// This assumes that the draws follow the roll shares exactly, that every drawn block and endorsement
+/// is created and included, and that the reward of each slot, `block_reward`, is shared between the
+/// block and endorsement creators in proportion to their draws.
+/// Returns `None` if there are no active rolls at all.
+fn estimate_roll_roi(
+    rolls: u64,
+    total_rolls: u64,
+    params: &PosParams,
+    endorsement_count: u32,
+) -> Option<RollRoiEstimate> {
+    if total_rolls == 0 {
+        return None;
+    }
+    let slots_per_cycle = params
+        .periods_per_cycle
+        .saturating_mul(params.thread_count as u64);
+    let roll_share = rolls as f64 / total_rolls as f64;
+    let reward_raw = params.block_reward.to_raw() as u128 * slots_per_cycle as u128 * rolls as u128
+        / total_rolls as u128;
+    let reward_per_cycle = Amount::from_raw(u64::try_from(reward_raw).unwrap_or(u64::MAX));
+    let stake_value = params.roll_price.saturating_mul_u64(rolls);
+    let reward_per_cycle_percent = if stake_value.is_zero() {
+        0.0
+    } else {
+        reward_per_cycle.to_raw() as f64 / stake_value.to_raw() as f64 * 100.0
+    };
+    Some(RollRoiEstimate {
+        rolls,
+        total_rolls,
+        roll_share,
+        stake_value,
+        expected_blocks_per_cycle: roll_share * slots_per_cycle as f64,
+        expected_endorsements_per_cycle: roll_share
+            * slots_per_cycle as f64
+            * endorsement_count as f64,
+        reward_per_cycle,
+        reward_per_cycle_percent,
+    })
+}
+
+/// Parses an address pasted by a user: surrounding whitespace is trimmed
+/// and the case of the `AU`/`AS` prefix is fixed, the base58 part being case sensitive.
+fn normalize_address(input: &str) -> Result<Address, ModelsError> {
+    let trimmed = input.trim();
+    let prefix_len = trimmed
+        .char_indices()
+        .nth(2)
+        .map_or(trimmed.len(), |(idx, _)| idx);
+    let (prefix, rest) = trimmed.split_at(prefix_len);
+    Address::from_str(&format!("{}{}", prefix.to_ascii_uppercase(), rest))
+}
+
 /// reads a file
 async fn get_file_as_byte_vec(filename: &std::path::Path) -> Result<Vec<u8>> {
     Ok(tokio::fs::read(filename).await?)
@@ -1455,3 +3084,396 @@ pub fn help() {
     println!("HELP of Massa client (list of available commands):");
     Command::iter().map(|c| c.help()).collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        can_no_longer_be_included, decode_operation, estimate_roll_roi, format_state_changes,
+        generate_keypairs_per_thread, normalize_address, prometheus_metrics, slot_clock_drift,
+        take_flag, BootstrapGraphSummary, CompetingClique, DeserializationContext, ForkEvent,
+        ForkWatcher,
+    };
+    use massa_api_exports::{
+        execution::{LedgerChange, StateChangesSummary},
+        rolls::PosParams,
+    };
+    use massa_consensus_exports::bootstrapable_graph::{
+        BootstrapableGraph, BootstrapableGraphSerializer,
+    };
+    use massa_models::config::{CHAINID, THREAD_COUNT};
+    use massa_models::{
+        address::Address,
+        amount::Amount,
+        block_id::BlockId,
+        clique::Clique,
+        config::CompactConfig,
+        error::ModelsError,
+        operation::{Operation, OperationSerializer, OperationType},
+        secure_share::{SecureShareContent, SecureShareSerializer},
+        slot::Slot,
+        stats::{ConsensusStats, DiscardedBlockCounts, NetworkStats},
+    };
+    use massa_serialization::Serializer;
+    use massa_signature::KeyPair;
+    use massa_time::MassaTime;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_generate_keypairs_per_thread() {
+        let keypairs = generate_keypairs_per_thread(0, THREAD_COUNT, 1000).unwrap();
+        assert_eq!(keypairs.len(), THREAD_COUNT as usize);
+        for (thread, keypair) in &keypairs {
+            assert_eq!(
+                Address::from_public_key(&keypair.get_public_key()).get_thread(THREAD_COUNT),
+                *thread
+            );
+        }
+
+        // with a single attempt per thread, at most one key is generated for each thread
+        let keypairs = generate_keypairs_per_thread(0, THREAD_COUNT, 1).unwrap();
+        assert!(!keypairs.is_empty() && keypairs.len() <= THREAD_COUNT as usize);
+    }
+
+    #[test]
+    fn test_estimate_roll_roi() {
+        // 128 periods of 32 threads: 4096 slots per cycle
+        let params = PosParams {
+            roll_price: Amount::from_str("100").unwrap(),
+            periods_per_cycle: 128,
+            thread_count: 32,
+            roll_sell_credit_delay_cycles: 3,
+            block_reward: Amount::from_str("1").unwrap(),
+        };
+        assert!(estimate_roll_roi(10, 0, &params, 16).is_none());
+
+        // a quarter of the rolls
+        let estimate = estimate_roll_roi(10, 40, &params, 16).unwrap();
+        assert_eq!(estimate.roll_share, 0.25);
+        assert_eq!(estimate.stake_value, Amount::from_str("1000").unwrap());
+        assert_eq!(estimate.expected_blocks_per_cycle, 1024.0);
+        assert_eq!(estimate.expected_endorsements_per_cycle, 16384.0);
+        assert_eq!(estimate.reward_per_cycle, Amount::from_str("1024").unwrap());
+        assert!((estimate.reward_per_cycle_percent - 102.4).abs() < 1e-9);
+
+        // no rolls, no rewards
+        let estimate = estimate_roll_roi(0, 40, &params, 16).unwrap();
+        assert_eq!(estimate.reward_per_cycle, Amount::zero());
+        assert_eq!(estimate.reward_per_cycle_percent, 0.0);
+    }
+
+    #[test]
+    fn test_normalize_address() {
+        let address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let canonical = address.to_string();
+
+        assert_eq!(normalize_address(&canonical).unwrap(), address);
+        assert_eq!(
+            normalize_address(&format!(" \t{}\n", canonical)).unwrap(),
+            address
+        );
+        assert_eq!(
+            normalize_address(&format!("au{}", &canonical[2..])).unwrap(),
+            address
+        );
+
+        // the base58 part is case sensitive
+        assert!(matches!(
+            normalize_address(&canonical.to_lowercase()),
+            Err(ModelsError::AddressParseError(_))
+        ));
+        assert!(normalize_address("").is_err());
+        assert!(normalize_address("A").is_err());
+    }
+
+    #[test]
+    fn test_slot_clock_drift() {
+        // 2 threads, one slot every 500ms
+        let config = CompactConfig {
+            genesis_timestamp: MassaTime::from_millis(1_000_000),
+            thread_count: 2,
+            t0: MassaTime::from_millis(1000),
+            ..Default::default()
+        };
+        // local time is in slot (10, 1)
+        let local_now = MassaTime::from_millis(1_000_000 + 10_700);
+
+        let drift = |node_slot| slot_clock_drift(&config, node_slot, local_now).unwrap();
+        assert_eq!(drift(Some(Slot::new(10, 1))), Some(0));
+        assert_eq!(drift(Some(Slot::new(8, 0))), Some(2500));
+        assert_eq!(drift(Some(Slot::new(12, 0))), Some(-1500));
+        assert_eq!(drift(None), None);
+
+        // the client is before genesis
+        assert_eq!(
+            slot_clock_drift(&config, Some(Slot::new(0, 0)), MassaTime::from_millis(0)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bootstrap_graph_summary() {
+        let context = DeserializationContext::default();
+        let mut data = Vec::new();
+        BootstrapableGraphSerializer::new()
+            .serialize(
+                &BootstrapableGraph {
+                    final_blocks: Vec::new(),
+                },
+                &mut data,
+            )
+            .unwrap();
+
+        assert_eq!(
+            BootstrapGraphSummary::from_bytes(&data, &context, *CHAINID).unwrap(),
+            BootstrapGraphSummary {
+                block_count: 0,
+                final_block_count: 0,
+                latest_final_periods: vec![None; THREAD_COUNT as usize],
+                serialized_size: data.len(),
+            }
+        );
+
+        // truncated or padded files are rejected
+        assert!(BootstrapGraphSummary::from_bytes(&[], &context, *CHAINID).is_err());
+        data.push(0);
+        assert!(BootstrapGraphSummary::from_bytes(&data, &context, *CHAINID).is_err());
+    }
+
+    #[test]
+    fn test_deserialization_context() {
+        // the documented MainNet file only differs from the defaults by its explicit chain id
+        let mainnet: DeserializationContext =
+            serde_json::from_str(include_str!("../base_config/deserialization_context.json"))
+                .unwrap();
+        assert_eq!(
+            mainnet,
+            DeserializationContext {
+                chain_id: Some(77658377),
+                ..Default::default()
+            }
+        );
+
+        // missing fields keep their defaults, unknown ones are rejected
+        let custom: DeserializationContext =
+            serde_json::from_str(r#"{"chain_id": 77, "max_parameters_size": 10}"#).unwrap();
+        assert_eq!(custom.chain_id, Some(77));
+        assert_eq!(custom.max_parameters_size, 10);
+        assert_eq!(custom.thread_count, THREAD_COUNT);
+        assert!(serde_json::from_str::<DeserializationContext>(r#"{"threads": 2}"#).is_err());
+
+        // an operation is decoded with the context limits and the chain id it was signed for
+        let keypair = KeyPair::generate(0).unwrap();
+        let operation = Operation::new_verifiable(
+            Operation {
+                fee: Amount::from_str("1").unwrap(),
+                expire_period: 10,
+                op: OperationType::CallSC {
+                    target_addr: Address::from_public_key(&keypair.get_public_key()),
+                    target_func: "f".to_string(),
+                    param: vec![0; 100],
+                    max_gas: 1_000_000,
+                    coins: Amount::zero(),
+                },
+            },
+            OperationSerializer::new(),
+            &keypair,
+            77,
+        )
+        .unwrap();
+        let mut data = Vec::new();
+        SecureShareSerializer::new()
+            .serialize(&operation, &mut data)
+            .unwrap();
+        let context = DeserializationContext::default();
+        assert_eq!(decode_operation(&data, &context, 77).unwrap(), operation);
+        // wrong chain id
+        assert!(decode_operation(&data, &context, 78).is_err());
+        // parameters over the limit of the custom context
+        assert!(decode_operation(&data, &custom, 77).is_err());
+        // trailing bytes
+        data.push(0);
+        assert!(decode_operation(&data, &context, 77).is_err());
+    }
+
+    #[test]
+    fn test_fork_watcher() {
+        let block_id = |name: &str| {
+            BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes()))
+        };
+        let clique = |names: &[&str], fitness, is_blockclique| Clique {
+            block_ids: names.iter().map(|name| block_id(name)).collect(),
+            fitness,
+            is_blockclique,
+        };
+        let single = vec![clique(&["a", "b"], 10, true)];
+        let forked = vec![
+            clique(&["a", "b", "c"], 12, true),
+            clique(&["a", "b", "d"], 11, false),
+        ];
+
+        let mut watcher = ForkWatcher::new(2);
+        assert_eq!(watcher.observe(&single), None);
+        // a short fork is not reported
+        assert_eq!(watcher.observe(&forked), None);
+        assert_eq!(watcher.observe(&single), None);
+
+        assert_eq!(watcher.observe(&forked), None);
+        assert_eq!(
+            watcher.observe(&forked),
+            Some(ForkEvent::Fork {
+                polls: 2,
+                cliques: vec![
+                    CompetingClique {
+                        is_blockclique: true,
+                        fitness: 12,
+                        block_ids: vec![block_id("c")],
+                    },
+                    CompetingClique {
+                        is_blockclique: false,
+                        fitness: 11,
+                        block_ids: vec![block_id("d")],
+                    },
+                ],
+            })
+        );
+        // the fork is reported once
+        assert_eq!(watcher.observe(&forked), None);
+        assert_eq!(
+            watcher.observe(&single),
+            Some(ForkEvent::Resolved { polls: 3 })
+        );
+        assert_eq!(watcher.observe(&single), None);
+    }
+
+    #[test]
+    fn test_show_state_changes() {
+        let parameters: Vec<String> = ["AS1", "--show-state-changes", "main", "1000"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        let (remaining, present) = take_flag(&parameters, "--show-state-changes");
+        assert!(present);
+        assert_eq!(remaining, vec!["AS1", "main", "1000"]);
+        let (remaining, present) = take_flag(&remaining, "--show-state-changes");
+        assert!(!present);
+        assert_eq!(remaining.len(), 3);
+
+        let address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let mut ledger_changes = vec![
+            (
+                address,
+                LedgerChange::Balance(Amount::from_str("1.5").unwrap()),
+            ),
+            (
+                address,
+                LedgerChange::DatastoreWrite {
+                    key: b"owner".to_vec(),
+                    value: vec![0, 159, 146],
+                },
+            ),
+        ];
+        ledger_changes
+            .extend((0..10u8).map(|i| (address, LedgerChange::DatastoreDelete { key: vec![i] })));
+        let summary = StateChangesSummary {
+            ledger_changes,
+            async_message_changes: 1,
+            roll_changes: 0,
+        };
+        let table = format_state_changes(&summary, 3);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(
+            lines[0],
+            "State changes: 12 ledger changes, 1 asynchronous message changes, 0 roll count changes"
+        );
+        assert_eq!(lines.len(), 5);
+        assert!(lines[1].contains("balance") && lines[1].ends_with("1.5"));
+        assert!(lines[2].ends_with("\"owner\" = [0, 159, 146]"));
+        assert!(lines[3].ends_with("[0]"));
+        assert_eq!(lines[4], "... and 9 more ledger changes");
+
+        // an empty change set only gives the counts
+        assert_eq!(
+            format_state_changes(&StateChangesSummary::default(), 3)
+                .lines()
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_can_no_longer_be_included() {
+        let last_final_periods = vec![Some(10), None, Some(12)];
+        assert!(!can_no_longer_be_included(11, 0, &last_final_periods));
+        assert!(can_no_longer_be_included(10, 0, &last_final_periods));
+        assert!(can_no_longer_be_included(11, 2, &last_final_periods));
+        // nothing is known to be final in the thread
+        assert!(!can_no_longer_be_included(0, 1, &last_final_periods));
+        assert!(!can_no_longer_be_included(0, 3, &last_final_periods));
+    }
+
+    #[test]
+    fn test_prometheus_metrics() {
+        let mut consensus = ConsensusStats {
+            start_timespan: MassaTime::from_millis(0),
+            end_timespan: MassaTime::from_millis(60000),
+            final_block_count: 120,
+            stale_block_count: 3,
+            clique_count: 2,
+            average_finality_latency: Some(4.5),
+            recent_discarded_blocks: DiscardedBlockCounts {
+                stale: 3,
+                invalid: 1,
+            },
+            slots_since_last_finality: 0,
+            production_rate_per_thread: vec![1.0, 0.75],
+            endorsement_participation: Some(0.5),
+            pruned_operation_count: 40,
+        };
+        let network = NetworkStats {
+            in_connection_count: 5,
+            out_connection_count: 8,
+            known_peer_count: 100,
+            banned_peer_count: 2,
+            active_node_count: 13,
+            message_counters: Default::default(),
+        };
+
+        let metrics = prometheus_metrics(&consensus, (1500, 32), &network).unwrap();
+        let lines: Vec<&str> = metrics.lines().collect();
+        for expected in [
+            "# HELP massa_cliques Number of active cliques.",
+            "# TYPE massa_cliques gauge",
+            "massa_cliques 2",
+            "massa_finality_latency_slots 4.5",
+            "massa_discarded_blocks{reason=\"stale\"} 3",
+            "massa_discarded_blocks{reason=\"invalid\"} 1",
+            "massa_blocks_per_period{thread=\"0\"} 1",
+            "massa_blocks_per_period{thread=\"1\"} 0.75",
+            "massa_endorsement_participation 0.5",
+            "# TYPE massa_pruned_operations_total counter",
+            "massa_pruned_operations_total 40",
+            "massa_pool_operations 1500",
+            "massa_pool_endorsements 32",
+            "massa_active_nodes 13",
+        ] {
+            assert!(lines.contains(&expected), "missing line: {}", expected);
+        }
+        // every sample belongs to a declared gauge or counter
+        for line in lines.iter().filter(|line| !line.starts_with('#')) {
+            let name = line.split(['{', ' ']).next().unwrap();
+            assert!(
+                lines.contains(&format!("# TYPE {} gauge", name).as_str())
+                    || lines.contains(&format!("# TYPE {} counter", name).as_str())
+            );
+        }
+
+        // stats without a value are omitted
+        consensus.average_finality_latency = None;
+        consensus.production_rate_per_thread = Vec::new();
+        consensus.endorsement_participation = None;
+        let metrics = prometheus_metrics(&consensus, (0, 0), &network).unwrap();
+        assert!(!metrics.contains("massa_finality_latency_slots"));
+        assert!(!metrics.contains("massa_blocks_per_period"));
+        assert!(!metrics.contains("massa_endorsement_participation"));
+    }
+}
@@ -1,22 +1,40 @@
 // Copyright (c) 2023 MASSA LABS <info@massa.net>
 
-use crate::cmds::ExtendedWallet;
+use crate::cmds::{
+    format_state_changes, BootstrapGraphSummary, ConnectedPeers, ExtendedWallet, ForkEvent,
+    GeneratedAddresses, KeepAliveEvent, ReadOnlyStateChanges, RollRoiEstimate, RollSellCredit,
+    MAX_DISPLAYED_STATE_CHANGES,
+};
 use console::style;
 use erased_serde::{Serialize, Serializer};
 use massa_api_exports::{
-    address::AddressInfo, block::BlockInfo, datastore::DatastoreEntryOutput,
-    endorsement::EndorsementInfo, execution::ExecuteReadOnlyResponse, node::NodeStatus,
+    address::AddressInfo,
+    block::BlockInfo,
+    datastore::DatastoreEntryOutput,
+    endorsement::EndorsementInfo,
+    execution::{ExecuteReadOnlyResponse, ReadOnlyResult},
+    ledger::TransferSimulation,
+    node::NodeStatus,
     operation::OperationInfo,
+    rolls::PosParams,
 };
 use massa_models::composite::PubkeySig;
 use massa_models::output_event::SCOutputEvent;
 use massa_models::prehash::PreHashSet;
 use massa_models::stats::{ConsensusStats, ExecutionStats, NetworkStats};
-use massa_models::{address::Address, config::CompactConfig, operation::OperationId};
+use massa_models::{
+    address::Address,
+    config::CompactConfig,
+    operation::{OperationId, SecureShareOperation},
+};
 use massa_signature::{KeyPair, PublicKey};
 use massa_wallet::Wallet;
 use std::net::IpAddr;
 use std::str;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Set when the client was started with `--verbose`: raw errors are displayed instead of their summaries
+pub(crate) static VERBOSE: AtomicBool = AtomicBool::new(false);
 
 #[macro_export]
 /// Display the MASSA logo on the CLI with fancy colors
@@ -168,6 +186,180 @@ impl Output for ExtendedWallet {
     }
 }
 
+impl Output for TransferSimulation {
+    fn pretty_print(&self) {
+        print!("{}", self);
+    }
+}
+
+impl Output for PosParams {
+    fn pretty_print(&self) {
+        print!("{}", self);
+    }
+}
+
+impl Output for SecureShareOperation {
+    fn pretty_print(&self) {
+        print!("{}", self);
+    }
+}
+
+impl Output for RollRoiEstimate {
+    fn pretty_print(&self) {
+        println!(
+            "Estimate for {} active rolls out of {} ({:.4}% of the rolls):",
+            Style::Protocol.style(self.rolls),
+            Style::Protocol.style(self.total_rolls),
+            self.roll_share * 100.0
+        );
+        println!("\tStake value: {}", Style::Coins.style(self.stake_value));
+        println!(
+            "\tExpected blocks per cycle: {}",
+            Style::Block.style(format!("{:.2}", self.expected_blocks_per_cycle))
+        );
+        println!(
+            "\tExpected endorsements per cycle: {}",
+            Style::Block.style(format!("{:.2}", self.expected_endorsements_per_cycle))
+        );
+        println!(
+            "\tApproximate reward per cycle: {} ({:.4}% of the stake value)",
+            Style::Coins.style(self.reward_per_cycle),
+            self.reward_per_cycle_percent
+        );
+        println!(
+            "{}",
+            Style::Unknown.style("This assumes that the draws follow the roll shares exactly, that every drawn block and endorsement is created and included, and that the stake distribution does not change. Actual rewards vary from one cycle to the next.")
+        );
+    }
+}
+
+impl Output for RollSellCredit {
+    fn pretty_print(&self) {
+        println!(
+            "{} coins would be credited back at slot {}",
+            Style::Coins.style(self.amount),
+            Style::Pending.style(self.credit_slot)
+        );
+    }
+}
+
+impl Output for KeepAliveEvent {
+    fn pretty_print(&self) {
+        match self {
+            KeepAliveEvent::Resubmitted {
+                expired_id,
+                resubmitted_id,
+                retry,
+            } => println!(
+                "Operation {} expired, re-submitted as {} (retry {})",
+                Style::Id.style(expired_id),
+                Style::Id.style(resubmitted_id),
+                Style::Pending.style(retry)
+            ),
+            KeepAliveEvent::Final { operation_id } => println!(
+                "Operation {} is {}",
+                Style::Id.style(operation_id),
+                Style::Finished.style("final")
+            ),
+        }
+    }
+}
+
+impl Output for ForkEvent {
+    fn pretty_print(&self) {
+        match self {
+            ForkEvent::Fork { polls, cliques } => {
+                println!(
+                    "{}",
+                    Style::Bad.style(format!(
+                        "Fork: {} cliques competing for {} polls",
+                        cliques.len(),
+                        polls
+                    ))
+                );
+                for clique in cliques {
+                    let kind = if clique.is_blockclique {
+                        "blockclique"
+                    } else {
+                        "clique"
+                    };
+                    println!(
+                        "\t{} (fitness {}):",
+                        kind,
+                        Style::Protocol.style(clique.fitness)
+                    );
+                    for block_id in &clique.block_ids {
+                        println!("\t\t{}", Style::Id.style(block_id));
+                    }
+                }
+            }
+            ForkEvent::Resolved { polls } => println!(
+                "{}",
+                Style::Good.style(format!("Fork resolved after {} polls", polls))
+            ),
+        }
+    }
+}
+
+impl Output for GeneratedAddresses {
+    fn pretty_print(&self) {
+        for (thread, address) in &self.addresses {
+            println!(
+                "Generated {} address in thread {} and added it to the wallet",
+                Style::Wallet.style(address),
+                Style::Protocol.style(thread)
+            );
+        }
+        if !self.missing_threads.is_empty() {
+            println!(
+                "{}",
+                Style::Bad.style(format!(
+                    "No address could be generated in threads {:?}, try again to fill them",
+                    self.missing_threads
+                ))
+            );
+        }
+    }
+}
+
+impl Output for ConnectedPeers {
+    fn pretty_print(&self) {
+        for (node_id, (ip_addr, is_outgoing)) in &self.connected_nodes {
+            println!(
+                "{}, {}, {}",
+                Style::Id.style(node_id),
+                Style::Protocol.style(ip_addr),
+                if *is_outgoing { "out" } else { "in" }
+            );
+        }
+        println!(
+            "Total: {} connected nodes",
+            Style::Protocol.style(self.connected_nodes.len())
+        );
+    }
+}
+
+impl Output for BootstrapGraphSummary {
+    fn pretty_print(&self) {
+        println!("Blocks: {}", Style::Protocol.style(self.block_count));
+        println!(
+            "Final blocks: {}",
+            Style::Protocol.style(self.final_block_count)
+        );
+        println!("Latest final periods:");
+        for (thread, period) in self.latest_final_periods.iter().enumerate() {
+            match period {
+                Some(period) => println!("\tthread {}: {}", thread, Style::Finished.style(period)),
+                None => println!("\tthread {}: {}", thread, Style::Unknown.style("none")),
+            }
+        }
+        println!(
+            "Serialized size: {} bytes",
+            Style::Protocol.style(self.serialized_size)
+        );
+    }
+}
+
 impl Output for Vec<(Address, PublicKey)> {
     fn pretty_print(&self) {
         match self.len() {
@@ -231,6 +423,12 @@ impl Output for NodeStatus {
         self.config.pretty_print();
         println!();
 
+        if !self.ready {
+            println!(
+                "{}",
+                Style::Bad.style("Not ready: the block graph is still catching up")
+            );
+        }
         println!("Current time: {}", self.current_time.format_instant());
         println!(
             "Current cycle: {}",
@@ -256,6 +454,10 @@ impl Output for NodeStatus {
             "\tEndorsements count: {}",
             Style::Protocol.style(self.pool_stats.1)
         );
+        println!(
+            "\tOperation fee floor: {}",
+            Style::Coins.style(self.operation_fee_floor)
+        );
         println!();
 
         self.network_stats.pretty_print();
@@ -331,6 +533,23 @@ impl Output for NetworkStats {
             "\tActive nodes: {}",
             Style::Good.style(self.active_node_count)
         );
+        let counters = &self.message_counters;
+        if let Some(slot) = counters.current_slot {
+            println!(
+                "\tMessages in/out during current slot {}: {}/{}",
+                Style::Protocol.style(slot),
+                Style::Protocol.style(counters.current_received),
+                Style::Protocol.style(counters.current_sent)
+            );
+        }
+        if let Some(slot) = counters.previous_slot {
+            println!(
+                "\tMessages in/out during previous slot {}: {}/{}",
+                Style::Protocol.style(slot),
+                Style::Protocol.style(counters.previous_received),
+                Style::Protocol.style(counters.previous_sent)
+            );
+        }
     }
 }
 
@@ -390,6 +609,43 @@ impl Output for ConsensusStats {
             "\tClique count: {}",
             Style::Protocol.style(self.clique_count)
         );
+        if let Some(latency) = self.average_finality_latency {
+            println!(
+                "\tAverage finality latency: {} slots",
+                Style::Block.style(format!("{:.2}", latency))
+            );
+        }
+        println!(
+            "\tRecently discarded blocks: {} stale, {} invalid",
+            Style::Block.style(self.recent_discarded_blocks.stale),
+            Style::Block.style(self.recent_discarded_blocks.invalid)
+        );
+        println!(
+            "\tSlots since last finality: {}",
+            Style::Block.style(self.slots_since_last_finality)
+        );
+        if !self.production_rate_per_thread.is_empty() {
+            println!(
+                "\tProduction rate per thread: {}",
+                Style::Block.style(
+                    self.production_rate_per_thread
+                        .iter()
+                        .map(|rate| format!("{:.2}", rate))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            );
+        }
+        if let Some(participation) = self.endorsement_participation {
+            println!(
+                "\tEndorsement participation: {}",
+                Style::Block.style(format!("{:.2}%", participation * 100.0))
+            );
+        }
+        println!(
+            "\tPruned operation count: {}",
+            Style::Block.style(self.pruned_operation_count)
+        );
     }
 }
 
@@ -418,20 +674,28 @@ impl Output for Vec<AddressInfo> {
                 Style::Wallet.style(info.address),
                 Style::Protocol.style(info.thread),
             );
-            println!(
-                "\tBalance: {}={}, {}={}",
-                Style::Finished.style("final"),
-                Style::Coins.style(info.final_balance),
-                Style::Pending.style("candidate"),
-                Style::Coins.style(info.candidate_balance),
-            );
-            println!(
-                "\tRolls: {}={}, {}={}",
-                Style::Finished.style("final"),
-                Style::Protocol.style(info.final_roll_count),
-                Style::Pending.style("candidate"),
-                Style::Protocol.style(info.candidate_roll_count),
-            );
+            if let (Some(final_balance), Some(candidate_balance)) =
+                (info.final_balance, info.candidate_balance)
+            {
+                println!(
+                    "\tBalance: {}={}, {}={}",
+                    Style::Finished.style("final"),
+                    Style::Coins.style(final_balance),
+                    Style::Pending.style("candidate"),
+                    Style::Coins.style(candidate_balance),
+                );
+            }
+            if let (Some(final_roll_count), Some(candidate_roll_count)) =
+                (info.final_roll_count, info.candidate_roll_count)
+            {
+                println!(
+                    "\tRolls: {}={}, {}={}",
+                    Style::Finished.style("final"),
+                    Style::Protocol.style(final_roll_count),
+                    Style::Pending.style("candidate"),
+                    Style::Protocol.style(candidate_roll_count),
+                );
+            }
 
             print!("\tLocked coins:");
             if info.deferred_credits.is_empty() {
@@ -595,6 +859,23 @@ impl Output for PubkeySig {
 
 impl Output for ExecuteReadOnlyResponse {
     fn pretty_print(&self) {
-        println!("{}", self);
+        match self.friendly_error() {
+            Some(friendly_error) if !VERBOSE.load(Ordering::Relaxed) => {
+                let mut friendly_response = self.clone();
+                friendly_response.result = ReadOnlyResult::Error(friendly_error);
+                println!("{}", friendly_response);
+            }
+            _ => println!("{}", self),
+        }
+    }
+}
+
+impl Output for ReadOnlyStateChanges {
+    fn pretty_print(&self) {
+        self.response.pretty_print();
+        print!(
+            "{}",
+            format_state_changes(&self.decoded_state_changes, MAX_DISPLAYED_STATE_CHANGES)
+        );
     }
 }
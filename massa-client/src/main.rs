@@ -2,15 +2,16 @@
 //! Massa stateless CLI
 #![warn(missing_docs)]
 #![warn(unused_crate_dependencies)]
+use crate::display::VERBOSE;
 use crate::settings::SETTINGS;
 use anyhow::Result;
 use clap::{crate_version, Parser};
 use cmds::Command;
 use console::style;
-use dialoguer::Password;
+use dialoguer::{Confirm, Password};
 use is_terminal::IsTerminal;
 use massa_sdk::{Client, ClientConfig, HttpConfig};
-use massa_wallet::Wallet;
+use massa_wallet::{Wallet, WalletError};
 use serde::Serialize;
 use std::env;
 use std::net::IpAddr;
@@ -50,6 +51,8 @@ struct Args {
     #[arg(name = "COMMAND", default_value = "help")]
     command: Command,
     /// Optional command parameter (as a JSON string)
+    ///
+    /// Command options like `--events-from` go after `--`, so that they are not taken as client flags.
     #[arg(name = "PARAMETERS")]
     parameters: Vec<String>,
     /// Path of wallet folder
@@ -61,6 +64,9 @@ struct Args {
     #[arg(short = 'p', long = "pwd")]
     /// Wallet password
     password: Option<String>,
+    /// Display raw errors instead of their summaries
+    #[arg(short = 'v', long = "verbose")]
+    verbose: bool,
 }
 
 #[derive(Serialize)]
@@ -85,6 +91,36 @@ pub(crate) fn ask_password(wallet_path: &Path) -> String {
     }
 }
 
+/// Open the wallet, enabling the backup of its key files according to the settings.
+/// If a key file is corrupted and has a backup, offers to restore it
+pub(crate) fn open_wallet(wallet_path: &Path, password: String, chain_id: u64) -> Result<Wallet> {
+    let mut restored = false;
+    loop {
+        match Wallet::new(wallet_path.to_path_buf(), password.clone(), chain_id) {
+            Ok(mut wallet) => {
+                wallet.set_backup(SETTINGS.client.wallet_backup);
+                return Ok(wallet);
+            }
+            Err(WalletError::CorruptedFileError(file_path, err))
+                if !restored
+                    && Wallet::has_backup(&file_path)
+                    && std::io::stdin().is_terminal() =>
+            {
+                println!("Wallet file {} is corrupted: {}", file_path.display(), err);
+                if !Confirm::new()
+                    .with_prompt("Restore it from its backup?")
+                    .interact()?
+                {
+                    return Err(WalletError::CorruptedFileError(file_path, err).into());
+                }
+                Wallet::restore_backup(&file_path)?;
+                restored = true;
+            }
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
 fn main() -> anyhow::Result<()> {
     let args = Args::parse();
     let tokio_rt = tokio::runtime::Builder::new_multi_thread()
@@ -101,6 +137,8 @@ fn main() -> anyhow::Result<()> {
 }
 
 async fn run(args: Args) -> Result<()> {
+    VERBOSE.store(args.verbose, Ordering::Relaxed);
+
     let client_config = ClientConfig {
         max_request_body_size: SETTINGS.client.max_request_body_size,
         request_timeout: SETTINGS.client.request_timeout,
@@ -183,7 +221,7 @@ async fn run(args: Args) -> Result<()> {
                     _ => ask_password(&args.wallet),
                 };
 
-                let wallet = Wallet::new(args.wallet, password, chain_id)?;
+                let wallet = open_wallet(&args.wallet, password, chain_id)?;
                 Some(wallet)
             }
             false => None,
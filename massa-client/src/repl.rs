@@ -1,13 +1,12 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
-use crate::ask_password;
 use crate::cmds::Command;
 use crate::massa_fancy_ascii_art_logo;
 use crate::settings::SETTINGS;
+use crate::{ask_password, open_wallet};
 use anyhow::Result;
 use console::style;
 use massa_sdk::Client;
-use massa_wallet::Wallet;
 use rustyline::completion::{Completer, FilenameCompleter, Pair};
 use rustyline::error::ReadlineError;
 use rustyline::validate::MatchingBracketValidator;
@@ -135,11 +134,7 @@ pub(crate) async fn run(
                                     _ => ask_password(wallet_path),
                                 };
 
-                            let wallet = match Wallet::new(
-                                wallet_path.to_path_buf(),
-                                password,
-                                client.chain_id,
-                            ) {
+                            let wallet = match open_wallet(wallet_path, password, client.chain_id) {
                                 Ok(wallet) => wallet,
                                 Err(e) => {
                                     println!("Could not open wallet: {}", e);
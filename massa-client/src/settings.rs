@@ -40,6 +40,10 @@ pub struct ClientSettings {
     pub id_kind: String,
     pub max_log_length: u32,
     pub headers: Vec<(String, String)>,
+    pub block_fill_warning_ratio: f64,
+    pub finality_lag_warning_periods: u64,
+    pub clock_drift_warning_threshold: MassaTime,
+    pub wallet_backup: bool,
     pub http: HttpSettings,
 }
 
@@ -0,0 +1,46 @@
+use crate::{cmds::Command, Args};
+use clap::Parser;
+
+#[test]
+fn test_command_options_are_parameters() {
+    let args = Args::try_parse_from([
+        "massa-client",
+        "read_only_call",
+        "AS12",
+        "main",
+        "",
+        "1000000",
+        "--",
+        "--events-from",
+        "AS12,AS13",
+        "--show-state-changes",
+    ])
+    .unwrap();
+    assert!(matches!(args.command, Command::read_only_call));
+    assert_eq!(
+        args.parameters,
+        vec![
+            "AS12",
+            "main",
+            "",
+            "1000000",
+            "--events-from",
+            "AS12,AS13",
+            "--show-state-changes"
+        ]
+    );
+}
+
+#[test]
+fn test_global_flags_after_command() {
+    let args = Args::try_parse_from(["massa-client", "get_status", "-j"]).unwrap();
+    assert!(matches!(args.command, Command::get_status));
+    assert!(args.json);
+    assert!(args.parameters.is_empty());
+
+    let args =
+        Args::try_parse_from(["massa-client", "wallet_info", "-p", "secret", "--json"]).unwrap();
+    assert_eq!(args.password.as_deref(), Some("secret"));
+    assert!(args.json);
+    assert!(args.parameters.is_empty());
+}
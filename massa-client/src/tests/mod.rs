@@ -1,3 +1,5 @@
 // TODO: re-enable this test, currently trying to fix it in #2275
 // mod scenarios;
 mod tools;
+
+mod args;
@@ -88,6 +88,18 @@ impl StorageOrBlock {
         }
     }
 
+    /// Run `f` on the underlying block without cloning it.
+    /// The storage lock, if any, is held while `f` runs.
+    pub fn with_block<R>(&self, block_id: &BlockId, f: impl FnOnce(&SecureShareBlock) -> R) -> R {
+        match self {
+            StorageOrBlock::Storage(storage) => f(storage
+                .read_blocks()
+                .get(block_id)
+                .expect("block absent from its own storage")),
+            StorageOrBlock::Block(block) => f(block),
+        }
+    }
+
     /// Convert any StorageOrBlock variant into a StorageOrBlock::Block variant.
     /// This effectively drops the operations of the block.
     pub fn strip_to_block(&mut self, block_id: &BlockId) {
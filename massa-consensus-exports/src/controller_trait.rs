@@ -1,5 +1,11 @@
 use crate::block_graph_export::BlockGraphExport;
 use crate::{bootstrapable_graph::BootstrapableGraph, error::ConsensusError};
+use massa_hash::Hash;
+use massa_models::address::Address;
+use massa_models::address_history::AddressHistory;
+use massa_models::amount::Amount;
+use massa_models::endorsement::EndorsementId;
+use massa_models::operation::OperationId;
 use massa_models::prehash::PreHashSet;
 use massa_models::streaming_step::StreamingStep;
 use massa_models::{
@@ -7,6 +13,7 @@ use massa_models::{
     secure_share::SecureShare, slot::Slot, stats::ConsensusStats,
 };
 use massa_storage::Storage;
+use std::collections::HashSet;
 
 #[cfg(feature = "test-exports")]
 use std::sync::Arc;
@@ -43,6 +50,165 @@ pub trait ConsensusController: Send + Sync {
     /// The list of cliques
     fn get_cliques(&self) -> Vec<Clique>;
 
+    /// Get the lowest common ancestor of two active blocks, used to diagnose forks
+    ///
+    /// # Arguments
+    /// * `a`, `b`: the blocks to connect
+    ///
+    /// # Returns
+    /// The common ancestor and the blocks on the paths going from it to `a` and `b`
+    fn get_common_ancestry(
+        &self,
+        a: BlockId,
+        b: BlockId,
+    ) -> Result<(BlockId, PreHashSet<BlockId>), ConsensusError>;
+
+    /// Get the addresses that produced blocks in a cycle, used for participation analytics.
+    /// Only the blocks still retained by the graph are covered.
+    ///
+    /// # Arguments
+    /// * `cycle`: the cycle to look at
+    ///
+    /// # Returns
+    /// The creators of the active or final blocks of that cycle
+    fn get_producers_in_cycle(&self, cycle: u64) -> HashSet<Address>;
+
+    /// Get the blocks waiting for a dependency, used to prioritize the fetching of missing blocks
+    ///
+    /// # Arguments
+    /// * `dep`: the missing dependency
+    ///
+    /// # Returns
+    /// The blocks whose unsatisfied dependencies contain `dep`, oldest first
+    fn get_blocks_waiting_on(&self, dep: BlockId) -> Vec<BlockId>;
+
+    /// Get the operations of the final blocks in a slot range, used for reconciliation
+    ///
+    /// # Arguments
+    /// * `start`: first slot of the range
+    /// * `end`: last slot of the range (included)
+    ///
+    /// # Returns
+    /// The operations of the final blocks retained in the graph with a slot in the range, in slot order
+    fn get_final_operations_in_range(&self, start: Slot, end: Slot) -> Vec<OperationId>;
+
+    /// Get the operations that declare a balance change of an address between two periods, used for reconciliation
+    ///
+    /// # Arguments
+    /// * `address`: the address whose balance changed
+    /// * `from_period`: period of the first balance snapshot (excluded)
+    /// * `to_period`: period of the second balance snapshot (included)
+    ///
+    /// # Returns
+    /// The operations of the final blocks retained in the graph that declare a balance change of `address`,
+    /// with the declared amount (the maximal spending for the sender) and whether it is a credit, in slot order
+    fn get_balance_delta_operations(
+        &self,
+        address: Address,
+        from_period: u64,
+        to_period: u64,
+    ) -> Vec<(OperationId, Amount, bool)>;
+
+    /// Get the blocks endorsed by an address, used for endorser activity views
+    ///
+    /// # Arguments
+    /// * `address`: the endorser address
+    ///
+    /// # Returns
+    /// The endorsements created by `address` in the headers of the active blocks, with the including block, in slot order
+    fn get_endorsed_blocks_by(&self, address: Address) -> Vec<(EndorsementId, BlockId)>;
+
+    /// Get the descendants of a block down to a generation limit, used to render partial subtrees
+    ///
+    /// # Arguments
+    /// * `block_id`: the block to start from
+    /// * `max_gen`: maximum number of generations below `block_id`
+    ///
+    /// # Returns
+    /// The active descendants of the block within `max_gen` generations, the block itself excluded
+    fn get_descendants_within(&self, block_id: BlockId, max_gen: usize) -> PreHashSet<BlockId>;
+
+    /// Get the validity range of an operation known by the node, without holding the operation
+    ///
+    /// # Arguments
+    /// * `op_id`: the operation to look at
+    ///
+    /// # Returns
+    /// The first and last periods during which the operation can be included in a block,
+    /// `None` if the operation is neither in the pool nor in a stored block
+    fn get_operation_validity_range(&self, op_id: OperationId) -> Option<(u64, u64)>;
+
+    /// Get the earliest slot in which an operation known by the node could be included, used to predict inclusion timing
+    ///
+    /// # Arguments
+    /// * `op_id`: the operation to look at
+    ///
+    /// # Returns
+    /// The first slot of the thread of the sender, after both the best parent of that thread and the current slot, within the validity range of the operation.
+    /// `None` if the operation is unknown, expires before that slot, or is already included in a blockclique or final block
+    fn get_earliest_inclusion_slot(&self, op_id: OperationId) -> Option<Slot>;
+
+    /// Get the operations included in the active blocks of all the cliques, used for mempool reconciliation
+    ///
+    /// # Returns
+    /// The union of the operations of the active blocks, whether final or not, whatever their clique
+    fn get_all_active_operations(&self) -> PreHashSet<OperationId>;
+
+    /// Get the creator of a block without fetching the whole block, used for attribution
+    ///
+    /// # Arguments
+    /// * `block_id`: the block to look at
+    ///
+    /// # Returns
+    /// The address of the creator of the block, `None` if the block is not active
+    fn get_block_creator(&self, block_id: BlockId) -> Option<Address>;
+
+    /// Get the blocks created by an address, used for validator monitoring
+    ///
+    /// # Arguments
+    /// * `address`: the block creator to look for
+    ///
+    /// # Returns
+    /// The active and final blocks created by `address` in all the cliques, with their slot and status, in slot order
+    fn get_blocks_by_creator(&self, address: Address) -> Vec<(BlockId, Slot, BlockGraphStatus)>;
+
+    /// Get the addresses that bought or sold rolls in a block, used by staking indexers
+    ///
+    /// # Arguments
+    /// * `block_id`: the block to look into
+    ///
+    /// # Returns
+    /// The creators of the roll buy and roll sell operations of the block,
+    /// `None` if the block is not active or its operations were already dropped
+    fn get_roll_changed_addresses(&self, block_id: BlockId) -> Option<PreHashSet<Address>>;
+
+    /// Get the history of an address in one go, used to produce account statements
+    ///
+    /// # Arguments
+    /// * `address`: the address to look at
+    ///
+    /// # Returns
+    /// The operations and roll changes of `address` in the final blocks retained in the graph, in slot order.
+    /// The final blocks that were already pruned are not covered
+    fn get_address_history(&self, address: Address) -> AddressHistory;
+
+    /// Get the operations most likely to be reverted, so that wallets can warn about them
+    ///
+    /// # Returns
+    /// The operations of the active non-final blocks outside of the blockclique,
+    /// excluding the ones also included in a blockclique or final block
+    fn get_at_risk_operations(&self) -> PreHashSet<OperationId>;
+
+    /// Check whether the graph caught up after startup: a block became final since then.
+    /// Before that, the graph may be partial and queries can return inconsistent data.
+    fn is_ready(&self) -> bool;
+
+    /// Get a fingerprint of the genesis block ids, to check which network the node runs on
+    ///
+    /// # Returns
+    /// The hash of the sorted genesis block ids
+    fn get_genesis_fingerprint(&self) -> Hash;
+
     /// Get a part of the graph to send to a node for it to setup its graph.
     /// Used for bootstrap.
     ///
@@ -98,6 +264,16 @@ pub trait ConsensusController: Send + Sync {
     /// The block id of the latest block in the thread of the given slot and before this slot
     fn get_latest_blockclique_block_at_slot(&self, slot: Slot) -> BlockId;
 
+    /// Get the chain of blocks from the block including an operation back to genesis, used for provenance display
+    ///
+    /// # Arguments
+    /// * `op_id`: the operation to look at
+    ///
+    /// # Returns
+    /// The block including the operation, preferably a final one, then its ancestors in its thread down to genesis,
+    /// or down to the first ancestor pruned from the graph. `None` if no active block includes the operation
+    fn get_operation_lineage(&self, op_id: OperationId) -> Option<Vec<BlockId>>;
+
     /// Register a block in the graph
     ///
     /// # Arguments
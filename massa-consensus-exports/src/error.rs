@@ -45,6 +45,10 @@ pub enum ConsensusError {
     ProtocolError(#[from] ProtocolError),
     /// Invalid transition {0}
     InvalidTransition(String),
+    /// Invalid bootstrap graph: {0}
+    InvalidBootstrapGraph(String),
+    /// ancestry walk exceeded the maximum of {0} blocks
+    AncestryWalkLimitExceeded(usize),
 }
 
 /// Internal error
@@ -54,4 +54,16 @@ pub struct ConsensusConfig {
     pub last_start_period: u64,
     /// chain id
     pub chain_id: u64,
+    /// maximum number of blocks visited when walking the ancestry of a block
+    pub max_ancestry_walk_blocks: usize,
+    /// log the number of slots each block spent active before becoming final
+    pub log_finality_latency: bool,
+    /// number of periods over which block discards are counted in the stats
+    pub discard_stats_window_periods: u64,
+    /// discard the incoming blocks whose operation list does not match the `operation_merkle_root` of their header,
+    /// before any other check
+    pub strict_operation_merkle_root: bool,
+    /// maximum number of missing dependencies asked to protocol at once, the ones most blocks wait on first.
+    /// Must not be 0, or no dependency would ever be asked.
+    pub max_wishlist_fetches: usize,
 }
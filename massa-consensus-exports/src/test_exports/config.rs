@@ -37,6 +37,12 @@ impl Default for ConsensusConfig {
             broadcast_filled_blocks_channel_capacity: 128,
             last_start_period: 0,
             chain_id: *CHAINID,
+            max_ancestry_walk_blocks: 10000,
+            log_finality_latency: false,
+            discard_stats_window_periods: 225,
+            // the test blocks are built with a placeholder operation merkle root
+            strict_operation_merkle_root: false,
+            max_wishlist_fetches: 1000,
         }
     }
 }
@@ -5,11 +5,16 @@ use massa_consensus_exports::{
     bootstrapable_graph::BootstrapableGraph, error::ConsensusError,
     export_active_block::ExportActiveBlock, ConsensusController,
 };
+use massa_hash::Hash;
 use massa_models::{
+    address::Address,
+    address_history::AddressHistory,
+    amount::Amount,
     block::{BlockGraphStatus, FilledBlock},
     block_header::BlockHeader,
     block_id::BlockId,
     clique::Clique,
+    endorsement::EndorsementId,
     operation::{Operation, OperationId},
     prehash::PreHashSet,
     secure_share::SecureShare,
@@ -19,6 +24,7 @@ use massa_models::{
 };
 use massa_storage::Storage;
 use parking_lot::RwLock;
+use std::collections::HashSet;
 use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
@@ -99,6 +105,158 @@ impl ConsensusController for ConsensusControllerImpl {
         self.shared_state.read().max_cliques.clone()
     }
 
+    /// Get the lowest common ancestor of two active blocks.
+    ///
+    /// # Returns:
+    /// The common ancestor and the blocks on the paths going from it to `a` and `b`
+    fn get_common_ancestry(
+        &self,
+        a: BlockId,
+        b: BlockId,
+    ) -> Result<(BlockId, PreHashSet<BlockId>), ConsensusError> {
+        self.shared_state.read().common_ancestry(a, b)
+    }
+
+    /// Get a fingerprint of the genesis block ids
+    ///
+    /// # Returns:
+    /// The hash of the sorted genesis block ids
+    fn get_genesis_fingerprint(&self) -> Hash {
+        self.shared_state.read().genesis_fingerprint()
+    }
+
+    /// Check whether the graph caught up after startup, i.e. a block became final since then.
+    fn is_ready(&self) -> bool {
+        self.shared_state.read().ready
+    }
+
+    /// Get the addresses that produced the retained blocks of a cycle.
+    ///
+    /// # Returns:
+    /// The creators of the active or final blocks of that cycle
+    fn get_producers_in_cycle(&self, cycle: u64) -> HashSet<Address> {
+        self.shared_state.read().producers_in_cycle(cycle)
+    }
+
+    /// Get the blocks waiting for a dependency.
+    ///
+    /// # Returns:
+    /// The blocks whose unsatisfied dependencies contain `dep`, oldest first
+    fn get_blocks_waiting_on(&self, dep: BlockId) -> Vec<BlockId> {
+        self.shared_state.read().blocks_waiting_on(&dep)
+    }
+
+    /// Get the operations of the final blocks in a slot range.
+    ///
+    /// # Returns:
+    /// The operations of the final blocks retained in the graph with a slot in `start..=end`, in slot order
+    fn get_final_operations_in_range(&self, start: Slot, end: Slot) -> Vec<OperationId> {
+        self.shared_state
+            .read()
+            .final_operations_in_range(start, end)
+    }
+
+    /// Get the operations that changed the balance of an address between two periods.
+    ///
+    /// # Returns:
+    /// The operations of the final blocks retained in the graph with a period in `from_period + 1..=to_period`
+    /// that changed the balance of `address`, with the amount of the change and whether it is a credit, in slot order
+    fn get_balance_delta_operations(
+        &self,
+        address: Address,
+        from_period: u64,
+        to_period: u64,
+    ) -> Vec<(OperationId, Amount, bool)> {
+        self.shared_state
+            .read()
+            .balance_delta_operations(&address, from_period, to_period)
+    }
+
+    /// Get the blocks endorsed by an address.
+    ///
+    /// # Returns:
+    /// The endorsements created by `address` in the headers of the active blocks, with the including block, in slot order
+    fn get_endorsed_blocks_by(&self, address: Address) -> Vec<(EndorsementId, BlockId)> {
+        self.shared_state.read().endorsed_blocks_by(&address)
+    }
+
+    /// Get the descendants of a block down to a generation limit.
+    ///
+    /// # Returns:
+    /// The active descendants of the block within `max_gen` generations, the block itself excluded
+    fn get_descendants_within(&self, block_id: BlockId, max_gen: usize) -> PreHashSet<BlockId> {
+        self.shared_state
+            .read()
+            .descendants_within(&block_id, max_gen)
+    }
+
+    /// Get the validity range of an operation known by the node.
+    ///
+    /// # Returns:
+    /// The first and last periods during which the operation can be included in a block, `None` if it is unknown
+    fn get_operation_validity_range(&self, op_id: OperationId) -> Option<(u64, u64)> {
+        self.shared_state.read().operation_validity_range(&op_id)
+    }
+
+    /// Get the earliest slot in which an operation known by the node could be included.
+    ///
+    /// # Returns:
+    /// The first slot of the thread of the sender, after the best parent of that thread, within the validity range of the operation.
+    /// `None` if the operation is unknown, expires before that slot, or is already included in a blockclique or final block
+    fn get_earliest_inclusion_slot(&self, op_id: OperationId) -> Option<Slot> {
+        self.shared_state.read().earliest_inclusion_slot(&op_id)
+    }
+
+    /// Get the operations included in the active blocks of all the cliques.
+    ///
+    /// # Returns:
+    /// The union of the operations of the active blocks, whether final or not, whatever their clique
+    fn get_all_active_operations(&self) -> PreHashSet<OperationId> {
+        self.shared_state.read().all_active_operations()
+    }
+
+    /// Get the creator of a block.
+    ///
+    /// # Returns:
+    /// The address of the creator of the block, `None` if the block is not active
+    fn get_block_creator(&self, block_id: BlockId) -> Option<Address> {
+        self.shared_state.read().get_block_creator(&block_id)
+    }
+
+    /// Get the blocks created by an address.
+    ///
+    /// # Returns:
+    /// The active and final blocks created by `address` in all the cliques, with their slot and status, in slot order
+    fn get_blocks_by_creator(&self, address: Address) -> Vec<(BlockId, Slot, BlockGraphStatus)> {
+        self.shared_state.read().blocks_by_creator(&address)
+    }
+
+    /// Get the addresses that bought or sold rolls in a block.
+    ///
+    /// # Returns:
+    /// The creators of the roll buy and roll sell operations of the block,
+    /// `None` if the block is not active or its operations were already dropped
+    fn get_roll_changed_addresses(&self, block_id: BlockId) -> Option<PreHashSet<Address>> {
+        self.shared_state.read().roll_changed_addresses(&block_id)
+    }
+
+    /// Get the history of an address in one go.
+    ///
+    /// # Returns:
+    /// The operations, balance changes and roll changes of `address` in the final blocks retained in the graph, in slot order
+    fn get_address_history(&self, address: Address) -> AddressHistory {
+        self.shared_state.read().address_history(&address)
+    }
+
+    /// Get the operations most likely to be reverted.
+    ///
+    /// # Returns:
+    /// The operations of the active non-final blocks outside of the blockclique,
+    /// excluding the ones also included in a blockclique or final block
+    fn get_at_risk_operations(&self) -> PreHashSet<OperationId> {
+        self.shared_state.read().at_risk_operations()
+    }
+
     /// Get a part of the graph to send to a node so that he can setup his graph.
     /// Used for bootstrap.
     ///
@@ -230,6 +388,15 @@ impl ConsensusController for ConsensusControllerImpl {
             .get_latest_blockclique_block_at_slot(&slot)
     }
 
+    /// Get the chain of blocks from the block including an operation back to genesis.
+    ///
+    /// # Returns:
+    /// The block including the operation, preferably a final one, then its ancestors in its thread down to genesis,
+    /// or down to the first ancestor pruned from the graph. `None` if no active block includes the operation
+    fn get_operation_lineage(&self, op_id: OperationId) -> Option<Vec<BlockId>> {
+        self.shared_state.read().operation_lineage(&op_id)
+    }
+
     fn register_block(&self, block_id: BlockId, slot: Slot, block_storage: Storage, created: bool) {
         if self.broadcast_enabled {
             if let Some(verifiable_block) = block_storage.read_blocks().get(&block_id) {
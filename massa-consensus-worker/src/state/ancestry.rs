@@ -0,0 +1,181 @@
+// Copyright (c) 2022 MASSA LABS <info@massa.net>
+
+//! This file is responsible for finding the common ancestry of two blocks
+
+use massa_consensus_exports::error::ConsensusError;
+use massa_models::{
+    block_id::BlockId,
+    prehash::{PreHashMap, PreHashSet},
+    slot::Slot,
+};
+
+/// Lists a block and all its known ancestors along with their slot and parents.
+///
+/// The walk stops at the blocks for which `get_parents` returns `None` (pruned or unknown blocks),
+/// and fails if more than `max_blocks` blocks are listed.
+fn list_known_ancestors<F>(
+    block_id: BlockId,
+    max_blocks: usize,
+    get_parents: &F,
+) -> Result<PreHashMap<BlockId, (Slot, Vec<BlockId>)>, ConsensusError>
+where
+    F: Fn(&BlockId) -> Option<(Slot, Vec<BlockId>)>,
+{
+    let mut ancestors = PreHashMap::default();
+    let root = get_parents(&block_id)
+        .ok_or_else(|| ConsensusError::MissingBlock(format!("block {} is not active", block_id)))?;
+    let mut to_visit = root.1.clone();
+    ancestors.insert(block_id, root);
+    while let Some(visit_id) = to_visit.pop() {
+        if ancestors.contains_key(&visit_id) {
+            continue; // already visited
+        }
+        if let Some((slot, parents)) = get_parents(&visit_id) {
+            if ancestors.len() >= max_blocks {
+                return Err(ConsensusError::AncestryWalkLimitExceeded(max_blocks));
+            }
+            to_visit.extend(parents.iter().copied());
+            ancestors.insert(visit_id, (slot, parents));
+        }
+    }
+    Ok(ancestors)
+}
+
+/// Computes the lowest common ancestor of two blocks, along with the blocks lying on the paths
+/// going from that ancestor to either block (the ancestor itself excluded).
+///
+/// # Arguments
+/// * `a`, `b`: the blocks to connect
+/// * `max_blocks`: maximum number of ancestors listed for each block
+/// * `get_parents`: returns the slot and parents of a block, or `None` if the block is not known
+pub fn compute_common_ancestry<F>(
+    a: BlockId,
+    b: BlockId,
+    max_blocks: usize,
+    get_parents: F,
+) -> Result<(BlockId, PreHashSet<BlockId>), ConsensusError>
+where
+    F: Fn(&BlockId) -> Option<(Slot, Vec<BlockId>)>,
+{
+    let ancestors_a = list_known_ancestors(a, max_blocks, &get_parents)?;
+    let ancestors_b = list_known_ancestors(b, max_blocks, &get_parents)?;
+
+    // parents always have a lower slot than their children,
+    // so the common ancestor with the highest slot is the lowest one
+    let (common_id, _) = ancestors_a
+        .iter()
+        .filter(|(id, _)| ancestors_b.contains_key(id))
+        .map(|(id, (slot, _))| (*id, *slot))
+        .max_by_key(|(id, slot)| (*slot, *id))
+        .ok_or_else(|| {
+            ConsensusError::ContainerInconsistency(format!(
+                "blocks {} and {} have no known common ancestor",
+                a, b
+            ))
+        })?;
+
+    // keep the blocks that descend from the common ancestor, visiting parents before children
+    let mut candidates: Vec<(Slot, BlockId, &Vec<BlockId>)> = ancestors_a
+        .iter()
+        .chain(ancestors_b.iter())
+        .map(|(id, (slot, parents))| (*slot, *id, parents))
+        .collect();
+    candidates.sort_unstable_by_key(|(slot, id, _)| (*slot, *id));
+    candidates.dedup_by_key(|(_, id, _)| *id);
+    let mut between = PreHashSet::<BlockId>::default();
+    for (_, id, parents) in candidates {
+        if parents
+            .iter()
+            .any(|parent| *parent == common_id || between.contains(parent))
+        {
+            between.insert(id);
+        }
+    }
+
+    Ok((common_id, between))
+}
+
+/// Tests
+
+#[cfg(test)]
+mod tests {
+    use crate::state::ancestry::compute_common_ancestry;
+    use massa_consensus_exports::error::ConsensusError;
+    use massa_models::{
+        block_id::BlockId,
+        prehash::{PreHashMap, PreHashSet},
+        slot::Slot,
+    };
+
+    fn block_id(name: &str) -> BlockId {
+        BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes()))
+    }
+
+    #[test]
+    fn test_compute_common_ancestry() {
+        // two threads:
+        //   g0 (0,0)   g1 (0,1)
+        //   a1 (1,0) -> [g0, g1]
+        //   b1 (1,1) -> [a1, g1]
+        //   c1 (1,1) -> [a1, g1]   (competes with b1)
+        //   b2 (2,0) -> [a1, b1]
+        //   c2 (2,1) -> [a1, c1]
+        let mut graph: PreHashMap<BlockId, (Slot, Vec<BlockId>)> = PreHashMap::default();
+        graph.insert(block_id("g0"), (Slot::new(0, 0), vec![]));
+        graph.insert(block_id("g1"), (Slot::new(0, 1), vec![]));
+        graph.insert(
+            block_id("a1"),
+            (Slot::new(1, 0), vec![block_id("g0"), block_id("g1")]),
+        );
+        graph.insert(
+            block_id("b1"),
+            (Slot::new(1, 1), vec![block_id("a1"), block_id("g1")]),
+        );
+        graph.insert(
+            block_id("c1"),
+            (Slot::new(1, 1), vec![block_id("a1"), block_id("g1")]),
+        );
+        graph.insert(
+            block_id("b2"),
+            (Slot::new(2, 0), vec![block_id("a1"), block_id("b1")]),
+        );
+        graph.insert(
+            block_id("c2"),
+            (Slot::new(2, 1), vec![block_id("a1"), block_id("c1")]),
+        );
+        let get_parents = |id: &BlockId| graph.get(id).cloned();
+
+        // diverging tips meet at a1
+        let (common, between) =
+            compute_common_ancestry(block_id("b2"), block_id("c2"), 100, get_parents).unwrap();
+        assert_eq!(common, block_id("a1"));
+        let expected: PreHashSet<BlockId> = ["b1", "b2", "c1", "c2"]
+            .iter()
+            .map(|name| block_id(name))
+            .collect();
+        assert_eq!(between, expected);
+
+        // a block is its own common ancestor with one of its descendants
+        let (common, between) =
+            compute_common_ancestry(block_id("a1"), block_id("b2"), 100, get_parents).unwrap();
+        assert_eq!(common, block_id("a1"));
+        let expected: PreHashSet<BlockId> =
+            ["b1", "b2"].iter().map(|name| block_id(name)).collect();
+        assert_eq!(between, expected);
+
+        // genesis blocks of different threads share no ancestor
+        assert!(compute_common_ancestry(block_id("g0"), block_id("g1"), 100, get_parents).is_err());
+
+        // unknown blocks are reported
+        assert!(
+            compute_common_ancestry(block_id("unknown"), block_id("b2"), 100, get_parents).is_err()
+        );
+
+        // the walk is bounded: b2 has 5 known ancestors including itself
+        assert!(matches!(
+            compute_common_ancestry(block_id("b2"), block_id("c2"), 4, get_parents),
+            Err(ConsensusError::AncestryWalkLimitExceeded(4))
+        ));
+        assert!(compute_common_ancestry(block_id("b2"), block_id("c2"), 5, get_parents).is_ok());
+    }
+}
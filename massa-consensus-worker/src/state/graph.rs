@@ -1,19 +1,32 @@
-use std::collections::VecDeque;
+use std::collections::{HashSet, VecDeque};
 
 use massa_consensus_exports::{
-    block_status::{BlockStatus, DiscardReason},
+    block_status::{BlockStatus, DiscardReason, StorageOrBlock},
     error::ConsensusError,
 };
+use massa_hash::Hash;
 use massa_logging::massa_trace;
 use massa_models::{
+    active_block::ActiveBlock,
+    address::Address,
+    address_history::AddressHistory,
+    amount::Amount,
+    block::BlockGraphStatus,
     block_id::{BlockId, BlockIdSerializer},
     clique::Clique,
+    config::{MAX_GI_HEAD_SIZE, ROLL_PRICE},
+    endorsement::EndorsementId,
+    operation::{OperationId, OperationType},
     prehash::PreHashSet,
     slot::Slot,
 };
 use massa_serialization::Serializer;
+use massa_storage::Storage;
+use massa_time::MassaTime;
+use tracing::{info, warn};
 
-use super::ConsensusState;
+use super::{ancestry::compute_common_ancestry, stats::finality_latency_slots, ConsensusState};
+use crate::worker::init::compute_genesis_fingerprint;
 
 impl ConsensusState {
     pub fn insert_parents_descendants(
@@ -76,17 +89,223 @@ impl ConsensusState {
         }
     }
 
+    /// Gets the lowest common ancestor of two active blocks,
+    /// along with the active blocks on the paths going from that ancestor to both blocks.
+    pub fn common_ancestry(
+        &self,
+        a: BlockId,
+        b: BlockId,
+    ) -> Result<(BlockId, PreHashSet<BlockId>), ConsensusError> {
+        compute_common_ancestry(
+            a,
+            b,
+            self.config.max_ancestry_walk_blocks,
+            |block_id| match self.blocks_state.get(block_id) {
+                Some(BlockStatus::Active { a_block, .. }) => Some((
+                    a_block.slot,
+                    a_block.parents.iter().map(|(id, _)| *id).collect(),
+                )),
+                _ => None,
+            },
+        )
+    }
+
+    /// Gets the active descendants of a block that are at most `max_gen` generations below it.
+    /// The block itself is not included.
+    pub fn descendants_within(&self, block_id: &BlockId, max_gen: usize) -> PreHashSet<BlockId> {
+        bounded_descendants(*block_id, max_gen, |id| match self.blocks_state.get(id) {
+            Some(BlockStatus::Active { a_block, .. }) => Some(
+                a_block
+                    .children
+                    .iter()
+                    .flat_map(|thread_children| thread_children.keys().copied())
+                    .collect(),
+            ),
+            _ => None,
+        })
+    }
+
+    /// Gets a fingerprint of the genesis blocks, identifying the network the node runs on
+    pub fn genesis_fingerprint(&self) -> Hash {
+        compute_genesis_fingerprint(&self.genesis_hashes)
+    }
+
+    /// Gets the addresses that created the active or final blocks of the given cycle.
+    ///
+    /// Only the blocks still retained in the graph are covered:
+    /// final blocks that were pruned are not taken into account.
+    pub fn producers_in_cycle(&self, cycle: u64) -> HashSet<Address> {
+        cycle_producers(
+            self.blocks_state
+                .iter()
+                .filter_map(|(_, status)| match status {
+                    BlockStatus::Active { a_block, .. } => Some(a_block.as_ref()),
+                    _ => None,
+                }),
+            cycle,
+            self.config.periods_per_cycle,
+        )
+    }
+
+    /// Lists the active and final blocks created by `addr` in all the cliques, with their slot and status, in slot order.
+    /// The creators are the ones recorded from the block headers when the blocks became active.
+    pub fn blocks_by_creator(&self, addr: &Address) -> Vec<(BlockId, Slot, BlockGraphStatus)> {
+        blocks_created_by(
+            self.blocks_state
+                .iter()
+                .filter_map(|(_, status)| match status {
+                    BlockStatus::Active { a_block, .. } => Some(a_block.as_ref()),
+                    _ => None,
+                }),
+            addr,
+        )
+        .into_iter()
+        .map(|(block_id, slot)| (block_id, slot, self.get_block_status(&block_id)))
+        .collect()
+    }
+
+    /// Lists the blocks waiting for the given dependency, oldest waiters first.
+    /// Helps choosing which missing block to fetch first.
+    pub fn blocks_waiting_on(&self, dep: &BlockId) -> Vec<BlockId> {
+        dependency_waiters(
+            self.blocks_state
+                .waiting_for_dependencies_blocks()
+                .iter()
+                .filter_map(|block_id| match self.blocks_state.get(block_id) {
+                    Some(BlockStatus::WaitingForDependencies {
+                        unsatisfied_dependencies,
+                        sequence_number,
+                        ..
+                    }) => Some((*block_id, unsatisfied_dependencies, *sequence_number)),
+                    _ => None,
+                }),
+            dep,
+        )
+    }
+
+    /// Lists the operations of the final blocks whose slot is within `start..=end`, in slot order.
+    /// Only the final blocks still retained in the graph are covered:
+    /// the operations of pruned blocks have to be reconciled from the final state instead.
+    pub fn final_operations_in_range(&self, start: Slot, end: Slot) -> Vec<OperationId> {
+        final_operations_in_slot_range(
+            self.blocks_state
+                .iter()
+                .filter_map(|(_, status)| match status {
+                    BlockStatus::Active {
+                        a_block,
+                        storage_or_block,
+                    } => Some((a_block.as_ref(), storage_or_block)),
+                    _ => None,
+                }),
+            start,
+            end,
+        )
+    }
+
+    /// Lists the operations of the final blocks of periods `from_period + 1..=to_period` that declare a balance change of `addr`,
+    /// with the declared amount and whether it is a credit, in slot order.
+    ///
+    /// The amounts are declared by the operations (see `SecureShareOperation::get_ledger_changes`), they are not executed changes:
+    /// senders are accounted for their maximal spending, so the amounts exceed the actual balance change of `addr`
+    /// when an execution spends less than its maximum or fails.
+    /// The operations of the final blocks that were already pruned or stripped of their operations are missing.
+    pub fn balance_delta_operations(
+        &self,
+        addr: &Address,
+        from_period: u64,
+        to_period: u64,
+    ) -> Vec<(OperationId, Amount, bool)> {
+        declared_balance_changes(
+            self.blocks_state
+                .iter()
+                .filter_map(|(_, status)| match status {
+                    BlockStatus::Active {
+                        a_block,
+                        storage_or_block,
+                    } => Some((a_block.as_ref(), storage_or_block)),
+                    _ => None,
+                }),
+            addr,
+            from_period,
+            to_period,
+        )
+    }
+
+    /// Builds the statement of the activity of `addr` in the final blocks retained in the graph:
+    /// its operations and roll changes, in slot order.
+    /// The final blocks that were already pruned or stripped of their operations are not covered.
+    pub fn address_history(&self, addr: &Address) -> AddressHistory {
+        address_activity(
+            self.blocks_state
+                .iter()
+                .filter_map(|(_, status)| match status {
+                    BlockStatus::Active {
+                        a_block,
+                        storage_or_block,
+                    } => Some((a_block.as_ref(), storage_or_block)),
+                    _ => None,
+                }),
+            addr,
+        )
+    }
+
+    /// Lists the addresses that bought or sold rolls in the active block `block_id`.
+    /// Rolls lost to denunciation slashing are not covered.
+    /// Returns `None` if the block is not active or its operations were already dropped from RAM.
+    pub fn roll_changed_addresses(&self, block_id: &BlockId) -> Option<PreHashSet<Address>> {
+        match self.blocks_state.get(block_id) {
+            Some(BlockStatus::Active {
+                storage_or_block: StorageOrBlock::Storage(storage),
+                ..
+            }) => Some(roll_operation_senders(storage, block_id)),
+            _ => None,
+        }
+    }
+
+    /// Lists the active blocks whose header includes an endorsement created by `addr`,
+    /// along with the id of that endorsement, in slot order.
+    pub fn endorsed_blocks_by(&self, addr: &Address) -> Vec<(EndorsementId, BlockId)> {
+        endorsements_by_address(
+            self.blocks_state
+                .iter()
+                .filter_map(|(_, status)| match status {
+                    BlockStatus::Active {
+                        a_block,
+                        storage_or_block,
+                    } => Some((a_block.as_ref(), storage_or_block)),
+                    _ => None,
+                }),
+            addr,
+        )
+    }
+
+    /// Lists the operations of the active non-final blocks that are outside of the blockclique,
+    /// unless they are also included in a blockclique or final block.
+    /// Those operations are the most likely to be reverted.
+    pub fn at_risk_operations(&self) -> PreHashSet<OperationId> {
+        let blockclique = self.get_blockclique();
+        operations_outside_blockclique(
+            self.blocks_state
+                .iter()
+                .filter_map(|(_, status)| match status {
+                    BlockStatus::Active {
+                        a_block,
+                        storage_or_block,
+                    } => Some((a_block.as_ref(), storage_or_block)),
+                    _ => None,
+                }),
+            &blockclique,
+        )
+    }
+
     pub fn compute_fitness_find_blockclique(
         &mut self,
         add_block_id: &BlockId,
     ) -> Result<usize, ConsensusError> {
-        let block_id_serializer = BlockIdSerializer::new();
-        let mut blockclique_i = 0usize;
-        let mut max_clique_fitness = (0u64, num::BigInt::default());
-        for (clique_i, clique) in self.max_cliques.iter_mut().enumerate() {
+        let mut clique_keys = Vec::with_capacity(self.max_cliques.len());
+        for clique in self.max_cliques.iter_mut() {
             clique.fitness = 0;
             clique.is_blockclique = false;
-            let mut sum_hash = num::BigInt::default();
             for block_h in clique.block_ids.iter() {
                 let fitness = match self.blocks_state.get(block_h) {
                     Some(BlockStatus::Active { a_block, .. }) => a_block.fitness,
@@ -96,36 +315,38 @@ impl ConsensusState {
                     .fitness
                     .checked_add(fitness)
                     .ok_or(ConsensusError::FitnessOverflow)?;
-                let mut bytes = Vec::new();
-                block_id_serializer
-                    .serialize(block_h, &mut bytes)
-                    .map_err(|err| ConsensusError::SerializationError(err.to_string()))?;
-                sum_hash -= num::BigInt::from_bytes_be(num::bigint::Sign::Plus, &bytes);
-            }
-            let cur_fit = (clique.fitness, sum_hash);
-            if cur_fit > max_clique_fitness {
-                blockclique_i = clique_i;
-                max_clique_fitness = cur_fit;
             }
+            clique_keys.push(blockclique_key(clique.fitness, clique.block_ids.iter())?);
         }
+        let blockclique_i = select_blockclique(clique_keys);
         self.max_cliques[blockclique_i].is_blockclique = true;
         Ok(blockclique_i)
     }
 
     pub fn list_stale_blocks(&self, fitness_threshold: u64) -> PreHashSet<BlockId> {
-        // iterate from largest to smallest to minimize reallocations
-        let mut indices: Vec<usize> = (0..self.max_cliques.len()).collect();
-        indices.sort_unstable_by_key(|&i| std::cmp::Reverse(self.max_cliques[i].block_ids.len()));
-        let mut high_set = PreHashSet::<BlockId>::default();
-        let mut low_set = PreHashSet::<BlockId>::default();
-        for clique_i in indices.into_iter() {
-            if self.max_cliques[clique_i].fitness >= fitness_threshold {
-                high_set.extend(&self.max_cliques[clique_i].block_ids);
-            } else {
-                low_set.extend(&self.max_cliques[clique_i].block_ids);
-            }
+        stale_blocks_of_cliques(&self.max_cliques, fitness_threshold)
+    }
+
+    /// Raises `fitness_threshold` when `gi_head` holds more than `MAX_GI_HEAD_SIZE` blocks,
+    /// so that the lowest-fitness forks are discarded as stale before reaching the usual threshold.
+    /// This bounds the cost of the clique computation under sustained equivocation.
+    pub fn bounded_gi_head_fitness_threshold(&self, fitness_threshold: u64) -> u64 {
+        let threshold = gi_head_bounded_fitness_threshold(
+            &self.max_cliques,
+            self.gi_head.len(),
+            MAX_GI_HEAD_SIZE,
+            fitness_threshold,
+        );
+        if threshold > fitness_threshold {
+            warn!(
+                "gi_head holds {} blocks (max {}): discarding the forks of fitness below {} instead of {}",
+                self.gi_head.len(),
+                MAX_GI_HEAD_SIZE,
+                threshold,
+                fitness_threshold
+            );
         }
-        &low_set - &high_set
+        threshold
     }
 
     pub fn remove_block(&mut self, add_block_id: &BlockId, block_id: &BlockId) {
@@ -330,6 +551,23 @@ impl ConsensusState {
                 }
                 // update new final blocks list
                 self.new_final_blocks.insert(block_id);
+                // note the finality latency
+                if let Some(active_time) = self.active_block_times.remove(&block_id) {
+                    let now = MassaTime::now();
+                    let latency = finality_latency_slots(
+                        active_time,
+                        now,
+                        self.config.t0,
+                        self.config.thread_count,
+                    );
+                    if self.config.log_finality_latency {
+                        info!(
+                            "block {} at slot {} became final {} slots after becoming active",
+                            block_id, final_block.slot, latency
+                        );
+                    }
+                    self.finality_latency_stats.push_back((now, latency));
+                }
             } else {
                 return Err(ConsensusError::ContainerInconsistency(format!("inconsistency inside block statuses updating final blocks adding {} - block {} is missing", add_block_id, block_id)));
             }
@@ -337,3 +575,1054 @@ impl ConsensusState {
         Ok(())
     }
 }
+
+/// Collects the creators of the given blocks whose slot belongs to `cycle`.
+fn cycle_producers<'a>(
+    blocks: impl Iterator<Item = &'a ActiveBlock>,
+    cycle: u64,
+    periods_per_cycle: u64,
+) -> HashSet<Address> {
+    blocks
+        .filter(|a_block| a_block.slot.get_cycle(periods_per_cycle) == cycle)
+        .map(|a_block| a_block.creator_address)
+        .collect()
+}
+
+/// Lists the given blocks created by `addr` along with their slot, sorted by slot.
+fn blocks_created_by<'a>(
+    blocks: impl Iterator<Item = &'a ActiveBlock>,
+    addr: &Address,
+) -> Vec<(BlockId, Slot)> {
+    let mut created: Vec<(BlockId, Slot)> = blocks
+        .filter(|a_block| a_block.creator_address == *addr)
+        .map(|a_block| (a_block.block_id, a_block.slot))
+        .collect();
+    // blocks competing for the same slot are ordered by id
+    created.sort_unstable_by_key(|(block_id, slot)| (*slot, *block_id));
+    created
+}
+
+/// Lists the blocks whose unsatisfied dependencies contain `dep`, sorted by sequence number.
+///
+/// A header waits for its own block, so `dep` itself is not listed.
+fn dependency_waiters<'a>(
+    waiting: impl Iterator<Item = (BlockId, &'a PreHashSet<BlockId>, u64)>,
+    dep: &BlockId,
+) -> Vec<BlockId> {
+    let mut waiters: Vec<(u64, BlockId)> = waiting
+        .filter(|(block_id, unsatisfied_dependencies, _)| {
+            block_id != dep && unsatisfied_dependencies.contains(dep)
+        })
+        .map(|(block_id, _, sequence_number)| (sequence_number, block_id))
+        .collect();
+    waiters.sort_unstable();
+    waiters.into_iter().map(|(_, block_id)| block_id).collect()
+}
+
+/// Lists the operations of the final blocks whose slot is within `start..=end`, in slot order.
+fn final_operations_in_slot_range<'a>(
+    blocks: impl Iterator<Item = (&'a ActiveBlock, &'a StorageOrBlock)>,
+    start: Slot,
+    end: Slot,
+) -> Vec<OperationId> {
+    let mut final_blocks: Vec<(Slot, &BlockId, &StorageOrBlock)> = blocks
+        .filter(|(a_block, _)| a_block.is_final && a_block.slot >= start && a_block.slot <= end)
+        .map(|(a_block, storage_or_block)| (a_block.slot, &a_block.block_id, storage_or_block))
+        .collect();
+    final_blocks.sort_unstable_by_key(|(slot, _, _)| *slot);
+    final_blocks
+        .into_iter()
+        .flat_map(|(_, block_id, storage_or_block)| {
+            storage_or_block.with_block(block_id, |block| block.content.operations.clone())
+        })
+        .collect()
+}
+
+/// Lists the operations of the given final blocks of periods `from_period + 1..=to_period`
+/// that declare a balance change of `addr`, as `(operation, declared amount, is_credit)`, in slot order.
+///
+/// The block creator is credited the fees. Blocks without their operations are skipped.
+fn declared_balance_changes<'a>(
+    blocks: impl Iterator<Item = (&'a ActiveBlock, &'a StorageOrBlock)>,
+    addr: &Address,
+    from_period: u64,
+    to_period: u64,
+) -> Vec<(OperationId, Amount, bool)> {
+    let mut final_blocks: Vec<(&ActiveBlock, &Storage)> = blocks
+        .filter(|(a_block, _)| {
+            a_block.is_final
+                && a_block.slot.period > from_period
+                && a_block.slot.period <= to_period
+        })
+        .filter_map(|(a_block, storage_or_block)| match storage_or_block {
+            StorageOrBlock::Storage(storage) => Some((a_block, storage)),
+            StorageOrBlock::Block(_) => None,
+        })
+        .collect();
+    final_blocks.sort_unstable_by_key(|(a_block, _)| a_block.slot);
+    let mut changes = Vec::new();
+    for (a_block, storage) in final_blocks {
+        let op_ids = match storage.read_blocks().get(&a_block.block_id) {
+            Some(block) => block.content.operations.clone(),
+            None => continue,
+        };
+        let operations = storage.read_operations();
+        for op_id in op_ids {
+            // an operation whose changes overflow cannot have been executed
+            let change = operations
+                .get(&op_id)
+                .and_then(|op| {
+                    op.get_ledger_changes(&a_block.creator_address, ROLL_PRICE)
+                        .ok()
+                })
+                .and_then(|ledger_changes| ledger_changes.0.get(addr).cloned());
+            if let Some(change) = change {
+                if !change.balance_delta.is_zero() {
+                    changes.push((op_id, change.balance_delta, change.balance_increment));
+                }
+            }
+        }
+    }
+    changes
+}
+
+/// Builds the statement of the activity of `addr` in the given final blocks, in slot order.
+/// Blocks without their operations are skipped.
+fn address_activity<'a>(
+    blocks: impl Iterator<Item = (&'a ActiveBlock, &'a StorageOrBlock)>,
+    addr: &Address,
+) -> AddressHistory {
+    let mut final_blocks: Vec<(&ActiveBlock, &StorageOrBlock)> = blocks
+        .filter(|(a_block, storage_or_block)| {
+            a_block.is_final && matches!(storage_or_block, StorageOrBlock::Storage(_))
+        })
+        .collect();
+    final_blocks.sort_unstable_by_key(|(a_block, _)| a_block.slot);
+    let balance_changed: PreHashSet<OperationId> =
+        declared_balance_changes(final_blocks.iter().copied(), addr, 0, u64::MAX)
+            .into_iter()
+            .map(|(op_id, _, _)| op_id)
+            .collect();
+
+    let mut operations = Vec::new();
+    let mut roll_changes = Vec::new();
+    for (a_block, storage_or_block) in &final_blocks {
+        let StorageOrBlock::Storage(storage) = storage_or_block else {
+            continue;
+        };
+        let op_ids = match storage.read_blocks().get(&a_block.block_id) {
+            Some(block) => block.content.operations.clone(),
+            None => continue,
+        };
+        let stored_operations = storage.read_operations();
+        for op_id in op_ids {
+            let Some(op) = stored_operations.get(&op_id) else {
+                continue;
+            };
+            let created = op.content_creator_address == *addr;
+            if created || balance_changed.contains(&op_id) {
+                operations.push((op_id, a_block.slot));
+            }
+            if created {
+                match op.content.op {
+                    OperationType::RollBuy { roll_count } => {
+                        roll_changes.push((op_id, roll_count, true))
+                    }
+                    OperationType::RollSell { roll_count } => {
+                        roll_changes.push((op_id, roll_count, false))
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    AddressHistory {
+        address: *addr,
+        covered_slots: final_blocks
+            .first()
+            .zip(final_blocks.last())
+            .map(|((first, _), (last, _))| (first.slot, last.slot)),
+        operations,
+        roll_changes,
+    }
+}
+
+/// Lists the creators of the roll buy and roll sell operations of the block `block_id` held in `storage`.
+fn roll_operation_senders(storage: &Storage, block_id: &BlockId) -> PreHashSet<Address> {
+    let op_ids = storage
+        .read_blocks()
+        .get(block_id)
+        .expect("block absent from its own storage")
+        .content
+        .operations
+        .clone();
+    let operations = storage.read_operations();
+    op_ids
+        .iter()
+        .filter_map(|op_id| operations.get(op_id))
+        .filter(|op| {
+            matches!(
+                op.content.op,
+                OperationType::RollBuy { .. } | OperationType::RollSell { .. }
+            )
+        })
+        .map(|op| op.content_creator_address)
+        .collect()
+}
+
+/// Lists the endorsements created by `addr` in the headers of the given blocks, in slot order.
+fn endorsements_by_address<'a>(
+    blocks: impl Iterator<Item = (&'a ActiveBlock, &'a StorageOrBlock)>,
+    addr: &Address,
+) -> Vec<(EndorsementId, BlockId)> {
+    let mut endorsed: Vec<(Slot, EndorsementId, BlockId)> = blocks
+        .flat_map(|(a_block, storage_or_block)| {
+            storage_or_block.with_block(&a_block.block_id, |block| {
+                block
+                    .content
+                    .header
+                    .content
+                    .endorsements
+                    .iter()
+                    .filter(|endorsement| endorsement.content_creator_address == *addr)
+                    .map(|endorsement| (a_block.slot, endorsement.id, a_block.block_id))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    endorsed.sort_unstable_by_key(|(slot, _, _)| *slot);
+    endorsed
+        .into_iter()
+        .map(|(_, endorsement_id, block_id)| (endorsement_id, block_id))
+        .collect()
+}
+
+/// Lists the descendants of `block_id` that are at most `max_gen` generations below it,
+/// walking the graph one generation at a time.
+/// `get_children` returns the children of a block, or `None` if the block is not known.
+fn bounded_descendants<F>(block_id: BlockId, max_gen: usize, get_children: F) -> PreHashSet<BlockId>
+where
+    F: Fn(&BlockId) -> Option<Vec<BlockId>>,
+{
+    let mut result = PreHashSet::<BlockId>::default();
+    let mut generation = vec![block_id];
+    for _ in 0..max_gen {
+        let mut next_generation = Vec::new();
+        for id in generation {
+            for child in get_children(&id).unwrap_or_default() {
+                // a block reached through several paths is only walked once, at its lowest depth
+                if child != block_id && result.insert(child) {
+                    next_generation.push(child);
+                }
+            }
+        }
+        if next_generation.is_empty() {
+            break;
+        }
+        generation = next_generation;
+    }
+    result
+}
+
+/// Lists the operations of the non-final blocks that are not in `blockclique`,
+/// excluding the ones also included in a final or blockclique block.
+fn operations_outside_blockclique<'a>(
+    blocks: impl Iterator<Item = (&'a ActiveBlock, &'a StorageOrBlock)>,
+    blockclique: &PreHashSet<BlockId>,
+) -> PreHashSet<OperationId> {
+    let mut at_risk = PreHashSet::<OperationId>::default();
+    let mut safe = PreHashSet::<OperationId>::default();
+    for (a_block, storage_or_block) in blocks {
+        let target = if a_block.is_final || blockclique.contains(&a_block.block_id) {
+            &mut safe
+        } else {
+            &mut at_risk
+        };
+        storage_or_block.with_block(&a_block.block_id, |block| {
+            target.extend(block.content.operations.iter().copied())
+        });
+    }
+    at_risk.retain(|op_id| !safe.contains(op_id));
+    at_risk
+}
+
+/// Key on which cliques are compared to choose the blockclique: `(fitness, -hash_sum)`,
+/// where `hash_sum` is the sum of the serialized ids of the blocks of the clique, read as big-endian integers.
+///
+/// The clique of highest fitness wins. Among cliques of equal fitness, the one with the lowest hash sum wins.
+/// The result only depends on the set of blocks, so every node makes the same choice.
+pub(crate) fn blockclique_key<'a>(
+    fitness: u64,
+    block_ids: impl Iterator<Item = &'a BlockId>,
+) -> Result<(u64, num::BigInt), ConsensusError> {
+    let block_id_serializer = BlockIdSerializer::new();
+    let mut sum_hash = num::BigInt::default();
+    for block_id in block_ids {
+        let mut bytes = Vec::new();
+        block_id_serializer
+            .serialize(block_id, &mut bytes)
+            .map_err(|err| ConsensusError::SerializationError(err.to_string()))?;
+        sum_hash -= num::BigInt::from_bytes_be(num::bigint::Sign::Plus, &bytes);
+    }
+    Ok((fitness, sum_hash))
+}
+
+/// Index of the blockclique among cliques of keys `clique_keys` (see `blockclique_key`): the first clique of highest key.
+/// Defaults to the first clique if every clique has a zero fitness.
+pub(crate) fn select_blockclique(
+    clique_keys: impl IntoIterator<Item = (u64, num::BigInt)>,
+) -> usize {
+    let mut blockclique_i = 0usize;
+    let mut max_key = (0u64, num::BigInt::default());
+    for (clique_i, key) in clique_keys.into_iter().enumerate() {
+        if key > max_key {
+            blockclique_i = clique_i;
+            max_key = key;
+        }
+    }
+    blockclique_i
+}
+
+/// Lists the blocks that only belong to cliques of fitness below `fitness_threshold`.
+fn stale_blocks_of_cliques(cliques: &[Clique], fitness_threshold: u64) -> PreHashSet<BlockId> {
+    // iterate from largest to smallest to minimize reallocations
+    let mut indices: Vec<usize> = (0..cliques.len()).collect();
+    indices.sort_unstable_by_key(|&i| std::cmp::Reverse(cliques[i].block_ids.len()));
+    let mut high_set = PreHashSet::<BlockId>::default();
+    let mut low_set = PreHashSet::<BlockId>::default();
+    for clique_i in indices.into_iter() {
+        if cliques[clique_i].fitness >= fitness_threshold {
+            high_set.extend(&cliques[clique_i].block_ids);
+        } else {
+            low_set.extend(&cliques[clique_i].block_ids);
+        }
+    }
+    &low_set - &high_set
+}
+
+/// Computes the fitness threshold under which cliques are discarded so that at most
+/// `max_gi_head_size` blocks remain in the incompatibility graph.
+///
+/// The threshold is raised one clique fitness at a time, lowest first, and never above the fitness
+/// of the blockclique. Returns `fitness_threshold` unchanged if `gi_head_size` is within the limit.
+fn gi_head_bounded_fitness_threshold(
+    cliques: &[Clique],
+    gi_head_size: usize,
+    max_gi_head_size: usize,
+    fitness_threshold: u64,
+) -> u64 {
+    if gi_head_size <= max_gi_head_size {
+        return fitness_threshold;
+    }
+    let Some(blockclique_fitness) = cliques.iter().find(|c| c.is_blockclique).map(|c| c.fitness)
+    else {
+        return fitness_threshold;
+    };
+    let mut fitnesses: Vec<u64> = cliques
+        .iter()
+        .map(|c| c.fitness)
+        .filter(|fitness| *fitness >= fitness_threshold && *fitness < blockclique_fitness)
+        .collect();
+    fitnesses.sort_unstable();
+    fitnesses.dedup();
+    let mut threshold = fitness_threshold;
+    for fitness in fitnesses {
+        threshold = fitness + 1;
+        let stale_count = stale_blocks_of_cliques(cliques, threshold).len();
+        if gi_head_size.saturating_sub(stale_count) <= max_gi_head_size {
+            break;
+        }
+    }
+    threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        address_activity, blockclique_key, blocks_created_by, bounded_descendants, cycle_producers,
+        declared_balance_changes, dependency_waiters, endorsements_by_address,
+        final_operations_in_slot_range, gi_head_bounded_fitness_threshold,
+        operations_outside_blockclique, roll_operation_senders, select_blockclique,
+    };
+    use massa_consensus_exports::block_status::StorageOrBlock;
+    use massa_models::{
+        active_block::ActiveBlock,
+        address::Address,
+        amount::Amount,
+        block::{Block, BlockSerializer, SecureShareBlock},
+        block_header::{BlockHeader, BlockHeaderSerializer},
+        block_id::{BlockId, BlockIdSerializer},
+        clique::Clique,
+        config::CHAINID,
+        endorsement::{Endorsement, EndorsementSerializer, SecureShareEndorsement},
+        operation::{
+            Operation, OperationId, OperationSerializer, OperationType, SecureShareOperation,
+        },
+        prehash::PreHashSet,
+        secure_share::SecureShareContent,
+        slot::Slot,
+    };
+    use massa_serialization::Serializer;
+    use massa_signature::KeyPair;
+    use massa_storage::Storage;
+    use std::collections::{HashMap, HashSet};
+
+    fn active_block(name: &str, slot: Slot, creator_address: Address) -> ActiveBlock {
+        ActiveBlock {
+            creator_address,
+            block_id: BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes())),
+            parents: Vec::new(),
+            children: Vec::new(),
+            descendants: Default::default(),
+            is_final: false,
+            slot,
+            fitness: 1,
+            same_thread_parent_creator: None,
+        }
+    }
+
+    #[test]
+    fn test_cycle_producers() {
+        let periods_per_cycle = 4;
+        let alice = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let bob = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let carol = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        // cycle 0 spans periods 0 to 3, cycle 1 spans periods 4 to 7
+        let blocks = vec![
+            active_block("a", Slot::new(1, 0), alice),
+            active_block("b", Slot::new(3, 1), bob),
+            active_block("c", Slot::new(3, 0), alice),
+            active_block("d", Slot::new(4, 0), carol),
+            active_block("e", Slot::new(7, 1), alice),
+        ];
+
+        assert_eq!(
+            cycle_producers(blocks.iter(), 0, periods_per_cycle),
+            HashSet::from([alice, bob])
+        );
+        assert_eq!(
+            cycle_producers(blocks.iter(), 1, periods_per_cycle),
+            HashSet::from([alice, carol])
+        );
+        assert!(cycle_producers(blocks.iter(), 2, periods_per_cycle).is_empty());
+    }
+
+    #[test]
+    fn test_blocks_created_by() {
+        let alice = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let bob = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let id = |name: &str| {
+            BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes()))
+        };
+        // alice produces in both threads, and twice at slot (3, 1) in competing cliques
+        let blocks = vec![
+            active_block("a3", Slot::new(3, 1), alice),
+            active_block("b1", Slot::new(1, 0), bob),
+            active_block("a1", Slot::new(2, 0), alice),
+            active_block("a3bis", Slot::new(3, 1), alice),
+            active_block("a2", Slot::new(2, 1), alice),
+            active_block("b2", Slot::new(4, 0), bob),
+        ];
+
+        let mut competing = vec![(id("a3"), Slot::new(3, 1)), (id("a3bis"), Slot::new(3, 1))];
+        competing.sort_unstable_by_key(|(block_id, _)| *block_id);
+        let mut expected = vec![(id("a1"), Slot::new(2, 0)), (id("a2"), Slot::new(2, 1))];
+        expected.extend(competing);
+        assert_eq!(blocks_created_by(blocks.iter(), &alice), expected);
+        assert_eq!(
+            blocks_created_by(blocks.iter(), &bob),
+            vec![(id("b1"), Slot::new(1, 0)), (id("b2"), Slot::new(4, 0))]
+        );
+        let carol = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        assert!(blocks_created_by(blocks.iter(), &carol).is_empty());
+    }
+
+    #[test]
+    fn test_dependency_waiters() {
+        let id = |name: &str| {
+            BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes()))
+        };
+        let deps = |names: &[&str]| names.iter().map(|name| id(name)).collect::<PreHashSet<_>>();
+        // (block, unsatisfied dependencies, sequence number)
+        let waiting = vec![
+            (id("w1"), deps(&["missing", "other"]), 3),
+            (id("w2"), deps(&["other"]), 1),
+            (id("w3"), deps(&["missing"]), 0),
+            (id("w4"), deps(&["missing", "w3"]), 7),
+            // a header waiting for its own block
+            (id("missing"), deps(&["missing"]), 2),
+        ];
+        let iter = || {
+            waiting
+                .iter()
+                .map(|(block_id, deps, seq)| (*block_id, deps, *seq))
+        };
+
+        assert_eq!(
+            dependency_waiters(iter(), &id("missing")),
+            vec![id("w3"), id("w1"), id("w4")]
+        );
+        assert_eq!(
+            dependency_waiters(iter(), &id("other")),
+            vec![id("w2"), id("w1")]
+        );
+        assert!(dependency_waiters(iter(), &id("unknown")).is_empty());
+    }
+
+    /// Builds an active block created by `creator`, holding only its header and its list of operations
+    fn bare_block(
+        creator: &KeyPair,
+        slot: Slot,
+        is_final: bool,
+        operations: Vec<OperationId>,
+        endorsements: Vec<SecureShareEndorsement>,
+    ) -> (ActiveBlock, StorageOrBlock) {
+        let secured_block = signed_block(creator, slot, operations, endorsements);
+        let mut a_block = active_block("", slot, secured_block.content_creator_address);
+        a_block.block_id = secured_block.id;
+        a_block.is_final = is_final;
+        (a_block, StorageOrBlock::Block(Box::new(secured_block)))
+    }
+
+    /// Builds a block of `creator` at `slot`, with a header signed by the same key
+    fn signed_block(
+        creator: &KeyPair,
+        slot: Slot,
+        operations: Vec<OperationId>,
+        endorsements: Vec<SecureShareEndorsement>,
+    ) -> SecureShareBlock {
+        let header = BlockHeader::new_verifiable(
+            BlockHeader {
+                current_version: 0,
+                announced_version: None,
+                slot,
+                parents: Vec::new(),
+                operation_merkle_root: massa_hash::Hash::compute_from(&[]),
+                endorsements,
+                denunciations: Vec::new(),
+            },
+            BlockHeaderSerializer::new(),
+            creator,
+            *CHAINID,
+        )
+        .unwrap();
+        Block::new_verifiable(
+            Block { header, operations },
+            BlockSerializer::new(),
+            creator,
+            *CHAINID,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_final_operations_in_slot_range() {
+        let keypair = KeyPair::generate(0).unwrap();
+        let op_id = |name: &str| OperationId::new(massa_hash::Hash::compute_from(name.as_bytes()));
+        let block = |slot: Slot, is_final: bool, ops: &[&str]| {
+            bare_block(
+                &keypair,
+                slot,
+                is_final,
+                ops.iter().map(|name| op_id(name)).collect(),
+                Vec::new(),
+            )
+        };
+        // listed out of slot order, over two threads
+        let blocks = vec![
+            block(Slot::new(2, 1), true, &["op4"]),
+            block(Slot::new(1, 0), true, &["op1", "op2"]),
+            block(Slot::new(1, 1), true, &["op3"]),
+            block(Slot::new(2, 0), false, &["op5"]),
+            block(Slot::new(3, 0), true, &["op6"]),
+            block(Slot::new(0, 1), true, &["op0"]),
+        ];
+        let iter = || blocks.iter().map(|(a_block, block)| (a_block, block));
+
+        assert_eq!(
+            final_operations_in_slot_range(iter(), Slot::new(1, 0), Slot::new(2, 1)),
+            vec![op_id("op1"), op_id("op2"), op_id("op3"), op_id("op4")]
+        );
+        assert_eq!(
+            final_operations_in_slot_range(iter(), Slot::new(0, 0), Slot::new(10, 0)).len(),
+            6
+        );
+        assert!(
+            final_operations_in_slot_range(iter(), Slot::new(2, 0), Slot::new(2, 0)).is_empty()
+        );
+    }
+
+    fn signed_operation(sender: &KeyPair, fee: u64, op: OperationType) -> SecureShareOperation {
+        Operation::new_verifiable(
+            Operation {
+                fee: Amount::const_init(fee, 0),
+                expire_period: 10,
+                op,
+            },
+            OperationSerializer::new(),
+            sender,
+            *CHAINID,
+        )
+        .unwrap()
+    }
+
+    /// Builds an active block holding `ops` in its storage
+    fn stored_block(
+        creator: &KeyPair,
+        slot: Slot,
+        is_final: bool,
+        ops: Vec<SecureShareOperation>,
+    ) -> (ActiveBlock, StorageOrBlock) {
+        let secured_block = signed_block(
+            creator,
+            slot,
+            ops.iter().map(|op| op.id).collect(),
+            Vec::new(),
+        );
+        let mut a_block = active_block("", slot, secured_block.content_creator_address);
+        a_block.block_id = secured_block.id;
+        a_block.is_final = is_final;
+        let mut storage = Storage::create_root();
+        storage.store_operations(ops);
+        storage.store_block(secured_block);
+        (a_block, StorageOrBlock::Storage(storage))
+    }
+
+    #[test]
+    fn test_declared_balance_changes() {
+        let alice = KeyPair::generate(0).unwrap();
+        let bob = KeyPair::generate(0).unwrap();
+        let carol = KeyPair::generate(0).unwrap();
+        let bob_address = Address::from_public_key(&bob.get_public_key());
+        let transfer = |sender: &KeyPair, recipient: &KeyPair, amount: u64| {
+            signed_operation(
+                sender,
+                1,
+                OperationType::Transaction {
+                    recipient_address: Address::from_public_key(&recipient.get_public_key()),
+                    amount: Amount::const_init(amount, 0),
+                },
+            )
+        };
+
+        let received = transfer(&alice, &bob, 10);
+        // bob created the block, so they get their own fee back
+        let sent = transfer(&bob, &alice, 3);
+        let unrelated = transfer(&alice, &carol, 5);
+        let roll_buy = signed_operation(&bob, 2, OperationType::RollBuy { roll_count: 1 });
+        let fee_earned = transfer(&carol, &alice, 7);
+        let later = transfer(&bob, &carol, 30);
+        let blocks = vec![
+            stored_block(&carol, Slot::new(3, 0), true, vec![roll_buy.clone()]),
+            stored_block(
+                &carol,
+                Slot::new(1, 0),
+                true,
+                vec![received.clone(), unrelated],
+            ),
+            stored_block(&bob, Slot::new(2, 1), true, vec![fee_earned.clone()]),
+            stored_block(&bob, Slot::new(2, 0), true, vec![sent.clone()]),
+            // not final
+            stored_block(
+                &carol,
+                Slot::new(3, 1),
+                false,
+                vec![transfer(&bob, &carol, 1)],
+            ),
+            // outside of the range
+            stored_block(
+                &carol,
+                Slot::new(0, 1),
+                true,
+                vec![transfer(&alice, &bob, 20)],
+            ),
+            stored_block(&carol, Slot::new(4, 0), true, vec![later.clone()]),
+        ];
+        let iter = || blocks.iter().map(|(a_block, storage)| (a_block, storage));
+
+        assert_eq!(
+            declared_balance_changes(iter(), &bob_address, 0, 3),
+            vec![
+                (received.id, Amount::const_init(10, 0), true),
+                (sent.id, Amount::const_init(3, 0), false),
+                (fee_earned.id, Amount::const_init(1, 0), true),
+                (roll_buy.id, Amount::const_init(102, 0), false),
+            ]
+        );
+        assert_eq!(
+            declared_balance_changes(iter(), &bob_address, 3, 10),
+            vec![(later.id, Amount::const_init(31, 0), false)]
+        );
+        assert!(declared_balance_changes(iter(), &bob_address, 5, 10).is_empty());
+    }
+
+    #[test]
+    fn test_address_activity() {
+        let alice = KeyPair::generate(0).unwrap();
+        let bob = KeyPair::generate(0).unwrap();
+        let carol = KeyPair::generate(0).unwrap();
+        let bob_address = Address::from_public_key(&bob.get_public_key());
+        let received = signed_operation(
+            &alice,
+            1,
+            OperationType::Transaction {
+                recipient_address: bob_address,
+                amount: Amount::const_init(10, 0),
+            },
+        );
+        let roll_buy = signed_operation(&bob, 2, OperationType::RollBuy { roll_count: 3 });
+        let roll_sell = signed_operation(&bob, 0, OperationType::RollSell { roll_count: 1 });
+        let unrelated = signed_operation(
+            &alice,
+            1,
+            OperationType::Transaction {
+                recipient_address: Address::from_public_key(&carol.get_public_key()),
+                amount: Amount::const_init(5, 0),
+            },
+        );
+        let mut stripped = stored_block(
+            &carol,
+            Slot::new(5, 0),
+            true,
+            vec![signed_operation(
+                &bob,
+                3,
+                OperationType::RollSell { roll_count: 2 },
+            )],
+        );
+        stripped.1.strip_to_block(&stripped.0.block_id);
+        let blocks = vec![
+            stored_block(&carol, Slot::new(3, 0), true, vec![roll_sell.clone()]),
+            stored_block(
+                &carol,
+                Slot::new(1, 0),
+                true,
+                vec![received.clone(), unrelated],
+            ),
+            stored_block(&carol, Slot::new(2, 0), true, vec![roll_buy.clone()]),
+            // not final
+            stored_block(
+                &carol,
+                Slot::new(4, 0),
+                false,
+                vec![signed_operation(
+                    &bob,
+                    1,
+                    OperationType::RollBuy { roll_count: 1 },
+                )],
+            ),
+            // operations already dropped
+            stripped,
+        ];
+
+        let history = address_activity(
+            blocks.iter().map(|(a_block, storage)| (a_block, storage)),
+            &bob_address,
+        );
+        assert_eq!(history.address, bob_address);
+        assert_eq!(
+            history.covered_slots,
+            Some((Slot::new(1, 0), Slot::new(3, 0)))
+        );
+        assert_eq!(
+            history.operations,
+            vec![
+                (received.id, Slot::new(1, 0)),
+                (roll_buy.id, Slot::new(2, 0)),
+                (roll_sell.id, Slot::new(3, 0)),
+            ]
+        );
+        assert_eq!(
+            history.roll_changes,
+            vec![(roll_buy.id, 3, true), (roll_sell.id, 1, false)]
+        );
+    }
+
+    #[test]
+    fn test_roll_operation_senders() {
+        let keypairs: Vec<KeyPair> = (0..4).map(|_| KeyPair::generate(0).unwrap()).collect();
+        let address = |i: usize| Address::from_public_key(&keypairs[i].get_public_key());
+        let (a_block, storage_or_block) = stored_block(
+            &keypairs[0],
+            Slot::new(1, 0),
+            false,
+            vec![
+                signed_operation(&keypairs[0], 1, OperationType::RollBuy { roll_count: 2 }),
+                signed_operation(&keypairs[1], 1, OperationType::RollSell { roll_count: 1 }),
+                // a second roll operation of the same address
+                signed_operation(&keypairs[1], 2, OperationType::RollBuy { roll_count: 3 }),
+                // the recipient of a transaction keeps its rolls
+                signed_operation(
+                    &keypairs[2],
+                    1,
+                    OperationType::Transaction {
+                        recipient_address: address(3),
+                        amount: Amount::const_init(10, 0),
+                    },
+                ),
+            ],
+        );
+        let StorageOrBlock::Storage(storage) = &storage_or_block else {
+            panic!("the block was built with its operations");
+        };
+
+        assert_eq!(
+            roll_operation_senders(storage, &a_block.block_id),
+            [address(0), address(1)].into_iter().collect()
+        );
+        let (a_block, storage_or_block) =
+            stored_block(&keypairs[0], Slot::new(2, 0), false, Vec::new());
+        let StorageOrBlock::Storage(storage) = &storage_or_block else {
+            panic!("the block was built with its operations");
+        };
+        assert!(roll_operation_senders(storage, &a_block.block_id).is_empty());
+    }
+
+    #[test]
+    fn test_bounded_descendants() {
+        let id = |name: &str| {
+            BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes()))
+        };
+        // root -> a1, b1
+        // a1 -> a2 ; b1 -> a2, b2
+        // a2 -> a3 ; b2 -> b3
+        // a3 -> a4
+        let children: HashMap<BlockId, Vec<BlockId>> = [
+            ("root", vec!["a1", "b1"]),
+            ("a1", vec!["a2"]),
+            ("b1", vec!["a2", "b2"]),
+            ("a2", vec!["a3"]),
+            ("b2", vec!["b3"]),
+            ("a3", vec!["a4"]),
+            ("b3", vec![]),
+            ("a4", vec![]),
+        ]
+        .into_iter()
+        .map(|(parent, children)| (id(parent), children.into_iter().map(id).collect()))
+        .collect();
+        let get_children = |block_id: &BlockId| children.get(block_id).cloned();
+        let names = |names: &[&str]| names.iter().map(|name| id(name)).collect::<PreHashSet<_>>();
+
+        assert!(bounded_descendants(id("root"), 0, get_children).is_empty());
+        assert_eq!(
+            bounded_descendants(id("root"), 1, get_children),
+            names(&["a1", "b1"])
+        );
+        assert_eq!(
+            bounded_descendants(id("root"), 2, get_children),
+            names(&["a1", "b1", "a2", "b2"])
+        );
+        assert_eq!(
+            bounded_descendants(id("root"), 3, get_children),
+            names(&["a1", "b1", "a2", "b2", "a3", "b3"])
+        );
+        // the limit can exceed the depth of the subtree
+        assert_eq!(
+            bounded_descendants(id("root"), 100, get_children),
+            names(&["a1", "b1", "a2", "b2", "a3", "b3", "a4"])
+        );
+        assert_eq!(
+            bounded_descendants(id("b1"), 1, get_children),
+            names(&["a2", "b2"])
+        );
+        assert!(bounded_descendants(id("unknown"), 3, get_children).is_empty());
+    }
+
+    #[test]
+    fn test_gi_head_bounded_fitness_threshold() {
+        let id = |name: &str| {
+            BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes()))
+        };
+        let clique = |names: &[&str], fitness: u64, is_blockclique: bool| Clique {
+            block_ids: names.iter().map(|name| id(name)).collect(),
+            fitness,
+            is_blockclique,
+        };
+        // 7 blocks in gi_head: a and b are shared, x, y and z are in competing forks
+        let mut cliques = vec![
+            clique(&["a", "b", "c", "d"], 40, true),
+            clique(&["a", "b", "x"], 25, false),
+            clique(&["a", "b", "y", "z"], 30, false),
+        ];
+
+        // within the limit, the usual threshold applies
+        assert_eq!(gi_head_bounded_fitness_threshold(&cliques, 7, 10, 20), 20);
+        assert_eq!(gi_head_bounded_fitness_threshold(&cliques, 7, 7, 20), 20);
+
+        // the lowest-fitness fork is discarded first
+        assert_eq!(gi_head_bounded_fitness_threshold(&cliques, 7, 6, 20), 26);
+
+        // then the next ones until gi_head fits
+        assert_eq!(gi_head_bounded_fitness_threshold(&cliques, 7, 5, 20), 31);
+
+        // the blockclique is never discarded
+        assert_eq!(gi_head_bounded_fitness_threshold(&cliques, 7, 2, 20), 31);
+
+        // forks already below the usual threshold are left to the usual pruning
+        assert_eq!(gi_head_bounded_fitness_threshold(&cliques, 7, 6, 28), 31);
+
+        // nothing to do without a known blockclique
+        cliques[0].is_blockclique = false;
+        assert_eq!(gi_head_bounded_fitness_threshold(&cliques, 7, 2, 20), 20);
+    }
+
+    #[test]
+    fn test_blockclique_tie_break() {
+        let id = |name: &str| {
+            BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes()))
+        };
+        let hash_sum = |ids: &[BlockId]| {
+            let mut sum = num::BigInt::default();
+            for block_id in ids {
+                let mut bytes = Vec::new();
+                BlockIdSerializer::new()
+                    .serialize(block_id, &mut bytes)
+                    .unwrap();
+                sum += num::BigInt::from_bytes_be(num::bigint::Sign::Plus, &bytes);
+            }
+            sum
+        };
+        // two forks of equal fitness sharing a and b
+        let fork_x = vec![id("a"), id("b"), id("x")];
+        let fork_y = vec![id("a"), id("b"), id("y")];
+        let key_x = blockclique_key(30, fork_x.iter()).unwrap();
+        let key_y = blockclique_key(30, fork_y.iter()).unwrap();
+        assert_eq!(key_x, (30, -hash_sum(&fork_x)));
+        assert_ne!(key_x, key_y);
+        let (lowest_sum, highest_sum) = if hash_sum(&fork_x) < hash_sum(&fork_y) {
+            (key_x.clone(), key_y.clone())
+        } else {
+            (key_y.clone(), key_x.clone())
+        };
+
+        // the lowest hash sum wins, whatever the order of the cliques
+        assert_eq!(
+            select_blockclique(vec![lowest_sum.clone(), highest_sum.clone()]),
+            0
+        );
+        assert_eq!(
+            select_blockclique(vec![highest_sum.clone(), lowest_sum.clone()]),
+            1
+        );
+        // the block order within a clique does not matter
+        let reversed: Vec<BlockId> = fork_x.iter().rev().copied().collect();
+        assert_eq!(blockclique_key(30, reversed.iter()).unwrap(), key_x);
+
+        // fitness comes first
+        let fitter = blockclique_key(31, fork_y.iter().chain(fork_x.iter())).unwrap();
+        assert_eq!(
+            select_blockclique(vec![lowest_sum.clone(), fitter, highest_sum]),
+            1
+        );
+
+        // identical keys keep the first clique
+        assert_eq!(select_blockclique(vec![key_x.clone(), key_x]), 0);
+    }
+
+    #[test]
+    fn test_operations_outside_blockclique() {
+        let keypair = KeyPair::generate(0).unwrap();
+        let op_id = |name: &str| OperationId::new(massa_hash::Hash::compute_from(name.as_bytes()));
+        let block = |slot: Slot, is_final: bool, ops: &[&str]| {
+            bare_block(
+                &keypair,
+                slot,
+                is_final,
+                ops.iter().map(|name| op_id(name)).collect(),
+                Vec::new(),
+            )
+        };
+        // a fork at slot (2, 0): "clique" is in the blockclique, "fork" is not.
+        // "shared" is included on both sides, "forked" only in the losing branch.
+        let blocks = vec![
+            block(Slot::new(1, 0), true, &["final"]),
+            block(Slot::new(2, 0), false, &["clique", "shared"]),
+            block(Slot::new(2, 0), false, &["forked", "shared"]),
+            block(Slot::new(3, 0), false, &["fork_child", "final"]),
+        ];
+        let blockclique: PreHashSet<BlockId> = [blocks[0].0.block_id, blocks[1].0.block_id]
+            .into_iter()
+            .collect();
+        let iter = || blocks.iter().map(|(a_block, block)| (a_block, block));
+
+        let expected: PreHashSet<OperationId> =
+            [op_id("forked"), op_id("fork_child")].into_iter().collect();
+        assert_eq!(
+            operations_outside_blockclique(iter(), &blockclique),
+            expected
+        );
+
+        // once the fork wins, the other branch is at risk
+        let blockclique: PreHashSet<BlockId> = [
+            blocks[0].0.block_id,
+            blocks[2].0.block_id,
+            blocks[3].0.block_id,
+        ]
+        .into_iter()
+        .collect();
+        let expected: PreHashSet<OperationId> = [op_id("clique")].into_iter().collect();
+        assert_eq!(
+            operations_outside_blockclique(iter(), &blockclique),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_endorsements_by_address() {
+        let block_keypair = KeyPair::generate(0).unwrap();
+        let endorser = KeyPair::generate(0).unwrap();
+        let other = KeyPair::generate(0).unwrap();
+        let endorser_address = Address::from_public_key(&endorser.get_public_key());
+        let endorsement = |keypair: &KeyPair, slot: Slot, index: u32| {
+            Endorsement::new_verifiable(
+                Endorsement {
+                    slot,
+                    index,
+                    endorsed_block: BlockId::generate_from_hash(massa_hash::Hash::compute_from(
+                        "parent".as_bytes(),
+                    )),
+                },
+                EndorsementSerializer::new(),
+                keypair,
+                *CHAINID,
+            )
+            .unwrap()
+        };
+        let block = |slot: Slot, endorsements: Vec<SecureShareEndorsement>| {
+            bare_block(&block_keypair, slot, false, Vec::new(), endorsements)
+        };
+        let endorsement_1 = endorsement(&endorser, Slot::new(1, 0), 0);
+        let endorsement_2 = endorsement(&endorser, Slot::new(3, 1), 2);
+        // listed out of slot order, with endorsements of another address
+        let blocks = vec![
+            block(
+                Slot::new(3, 1),
+                vec![
+                    endorsement(&other, Slot::new(3, 1), 0),
+                    endorsement_2.clone(),
+                ],
+            ),
+            block(
+                Slot::new(2, 0),
+                vec![endorsement(&other, Slot::new(2, 0), 0)],
+            ),
+            block(Slot::new(1, 0), vec![endorsement_1.clone()]),
+            block(Slot::new(4, 0), Vec::new()),
+        ];
+        let iter = || blocks.iter().map(|(a_block, block)| (a_block, block));
+
+        assert_eq!(
+            endorsements_by_address(iter(), &endorser_address),
+            vec![
+                (endorsement_1.id, blocks[2].0.block_id),
+                (endorsement_2.id, blocks[0].0.block_id),
+            ]
+        );
+        assert!(endorsements_by_address(
+            iter(),
+            &Address::from_public_key(&block_keypair.get_public_key())
+        )
+        .is_empty());
+    }
+}
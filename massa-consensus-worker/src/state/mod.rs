@@ -18,22 +18,28 @@ use massa_models::{
     block_header::SecuredHeader,
     block_id::BlockId,
     clique::Clique,
+    operation::OperationId,
     prehash::{CapacityAllocator, PreHashMap, PreHashSet},
     slot::Slot,
+    timeslots::get_latest_block_slot_at_timestamp,
 };
 use massa_storage::Storage;
 use massa_time::MassaTime;
 use tracing::debug;
 
-use self::blocks_state::BlocksState;
+use self::{
+    blocks_state::BlocksState,
+    stats::{DiscardHistory, FinalityProgress},
+};
 
+mod ancestry;
 pub mod blocks_state;
 mod clique_computation;
 mod graph;
 mod process;
 mod process_commands;
 mod prune;
-mod stats;
+pub(crate) mod stats;
 mod tick;
 mod verifications;
 
@@ -78,6 +84,18 @@ pub struct ConsensusState {
     pub protocol_blocks: VecDeque<(MassaTime, BlockId)>,
     /// Stale block timestamp
     pub stale_block_stats: VecDeque<MassaTime>,
+    /// Stale and invalid blocks discarded over the last `discard_stats_window_periods` periods
+    pub discard_history: DiscardHistory,
+    /// Slot at which the latest final periods last advanced
+    pub finality_progress: FinalityProgress,
+    /// Number of operations of the final blocks pruned from the graph since the node started
+    pub pruned_operation_count: u64,
+    /// Whether a block became final since startup, meaning that the graph caught up after bootstrap
+    pub ready: bool,
+    /// Time at which each non-final active block was added to the graph
+    pub active_block_times: PreHashMap<BlockId, MassaTime>,
+    /// Finality latency stats `(time of finalization, slots elapsed since the block became active)`
+    pub finality_latency_stats: VecDeque<(MassaTime, u64)>,
     /// the time span considered for stats
     pub stats_history_timespan: MassaTime,
     /// the time span considered for desynchronization detection
@@ -120,10 +138,150 @@ impl ConsensusState {
         })
     }
 
+    /// Get the first and last periods during which an operation can be included in a block.
+    /// The operation is looked up in the shared storage, which holds the operations of the pool and of the blocks.
+    pub fn operation_validity_range(&self, op_id: &OperationId) -> Option<(u64, u64)> {
+        let range = self
+            .storage
+            .read_operations()
+            .get(op_id)?
+            .get_validity_range(self.config.operation_validity_periods);
+        Some((*range.start(), *range.end()))
+    }
+
+    /// Get the earliest slot in which a block built on the current best parents could include an operation:
+    /// the first slot of the thread of the sender that is both after its best parent and after the current slot,
+    /// and whose period is within the validity range of the operation.
+    ///
+    /// Returns `None` if the operation is unknown, if it expires before that slot,
+    /// or if it is already included in a blockclique or final block.
+    pub fn earliest_inclusion_slot(&self, op_id: &OperationId) -> Option<Slot> {
+        let (thread, validity_range) = {
+            let operations = self.storage.read_operations();
+            let operation = operations.get(op_id)?;
+            (
+                operation
+                    .content_creator_address
+                    .get_thread(self.config.thread_count),
+                operation.get_validity_range(self.config.operation_validity_periods),
+            )
+        };
+        let blockclique = self.get_blockclique();
+        let included = self
+            .storage
+            .read_blocks()
+            .get_blocks_by_operation(op_id)
+            .map_or(false, |block_ids| {
+                block_ids.iter().any(|block_id| {
+                    blockclique.contains(block_id)
+                        || matches!(
+                            self.blocks_state.get(block_id),
+                            Some(BlockStatus::Active { a_block, .. }) if a_block.is_final
+                        )
+                })
+            });
+        if included {
+            return None;
+        }
+        // first period of the thread of the sender whose slot is strictly after the current slot
+        let next_period = get_latest_block_slot_at_timestamp(
+            self.config.thread_count,
+            self.config.t0,
+            self.config.genesis_timestamp,
+            MassaTime::now(),
+        )
+        .ok()
+        .flatten()
+        .map_or(0, |current_slot| {
+            if thread > current_slot.thread {
+                current_slot.period
+            } else {
+                current_slot.period.saturating_add(1)
+            }
+        });
+        let period = self.best_parents[thread as usize]
+            .1
+            .saturating_add(1)
+            .max(next_period)
+            .max(*validity_range.start());
+        validity_range
+            .contains(&period)
+            .then_some(Slot::new(period, thread))
+    }
+
+    /// Get the operations included in any active block, whatever its clique
+    pub fn all_active_operations(&self) -> PreHashSet<OperationId> {
+        let mut operations = PreHashSet::default();
+        for block_id in self.blocks_state.active_blocks() {
+            match self.blocks_state.get(block_id) {
+                Some(BlockStatus::Active {
+                    storage_or_block: StorageOrBlock::Storage(storage),
+                    ..
+                }) => {
+                    if let Some(block) = storage.read_blocks().get(block_id) {
+                        operations.extend(block.content.operations.iter().copied());
+                    }
+                }
+                Some(BlockStatus::Active {
+                    storage_or_block: StorageOrBlock::Block(block),
+                    ..
+                }) => operations.extend(block.content.operations.iter().copied()),
+                _ => {}
+            }
+        }
+        operations
+    }
+
+    /// Get the address of the creator of an active block, `None` if the block is not active
+    pub fn get_block_creator(&self, block_id: &BlockId) -> Option<Address> {
+        match self.blocks_state.get(block_id) {
+            Some(BlockStatus::Active { a_block, .. }) => Some(a_block.creator_address),
+            _ => None,
+        }
+    }
+
     pub fn get_clique_count(&self) -> usize {
         self.max_cliques.len()
     }
 
+    /// Get the chain of blocks from the block including an operation back to genesis,
+    /// following the parents in the thread of that block.
+    /// A final block including the operation is preferred to the other active ones.
+    /// The chain stops at the first ancestor that was already pruned from the graph.
+    ///
+    /// Returns `None` if no active block includes the operation.
+    pub fn operation_lineage(&self, op_id: &OperationId) -> Option<Vec<BlockId>> {
+        let containing_blocks: Vec<BlockId> = self
+            .storage
+            .read_blocks()
+            .get_blocks_by_operation(op_id)?
+            .iter()
+            .copied()
+            .collect();
+        let (_, block_id) = containing_blocks
+            .into_iter()
+            .filter_map(|block_id| {
+                self.get_full_active_block(&block_id)
+                    .map(|(a_block, _)| (a_block.is_final, block_id))
+            })
+            .max()?;
+
+        let mut lineage = Vec::new();
+        let mut current = Some(block_id);
+        while let Some(block_id) = current {
+            let Some((a_block, _)) = self.get_full_active_block(&block_id) else {
+                break;
+            };
+            lineage.push(block_id);
+            // genesis blocks have no parents
+            current = a_block
+                .parents
+                .get(a_block.slot.thread as usize)
+                .map(|(parent_id, _)| *parent_id);
+        }
+        Some(lineage)
+    }
+
     /// get the blockclique (or final) block ID at a given slot, if any
     pub fn get_blockclique_block_at_slot(&self, slot: &Slot) -> Option<BlockId> {
         // List all blocks at this slot.
@@ -522,6 +680,40 @@ impl ConsensusState {
         Ok(wishlist)
     }
 
+    /// Get at most `max` blocks of `wishlist`, as returned by `get_block_wishlist`,
+    /// ranked by the number of blocks waiting on them, the most waited on first.
+    /// Ties are broken by block id.
+    pub fn get_prioritized_wishlist(
+        &self,
+        wishlist: &PreHashMap<BlockId, Option<SecuredHeader>>,
+        max: usize,
+    ) -> Vec<BlockId> {
+        let mut waiting_counts: PreHashMap<BlockId, usize> =
+            wishlist.keys().map(|block_id| (*block_id, 0)).collect();
+        for block_id in self.blocks_state.waiting_for_dependencies_blocks().iter() {
+            if let Some(BlockStatus::WaitingForDependencies {
+                unsatisfied_dependencies,
+                ..
+            }) = self.blocks_state.get(block_id)
+            {
+                for dependency in unsatisfied_dependencies.iter() {
+                    if let Some(count) = waiting_counts.get_mut(dependency) {
+                        *count += 1;
+                    }
+                }
+            }
+        }
+        let mut ranked: Vec<(BlockId, usize)> = waiting_counts.into_iter().collect();
+        ranked.sort_unstable_by(|(id_a, count_a), (id_b, count_b)| {
+            count_b.cmp(count_a).then_with(|| id_a.cmp(id_b))
+        });
+        ranked
+            .into_iter()
+            .take(max)
+            .map(|(block_id, _)| block_id)
+            .collect()
+    }
+
     /// Gets a block and all its descendants
     ///
     /// # Argument
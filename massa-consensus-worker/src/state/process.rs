@@ -150,40 +150,44 @@ impl ConsensusState {
                 massa_trace!("consensus.block_graph.process.incoming_block", {
                     "block_id": block_id
                 });
-                let header_check_outcome =
-                    if let Some(BlockStatus::Incoming(HeaderOrBlock::Block {
-                        slot: _,
-                        storage,
-                        ..
-                    })) = self.blocks_state.get(&block_id)
+                let header_check_outcome = if let Some(BlockStatus::Incoming(
+                    HeaderOrBlock::Block {
+                        slot: _, storage, ..
+                    },
+                )) = self.blocks_state.get(&block_id)
+                {
+                    let stored_block = storage
+                        .read_blocks()
+                        .get(&block_id)
+                        .cloned()
+                        .expect("incoming block not found in storage");
+                    let res = if self.config.strict_operation_merkle_root
+                        && !stored_block.content.verify_operation_merkle_root()
                     {
-                        let stored_block = storage
-                            .read_blocks()
-                            .get(&block_id)
-                            .cloned()
-                            .expect("incoming block not found in storage");
-                        let res = self.check_header(
-                            &block_id,
-                            &stored_block.content.header,
-                            current_slot,
-                        );
-                        match &res {
-                            HeaderCheckOutcome::Discard(reason) => {
-                                self.maybe_note_attack_attempt(reason, &block_id)
-                            }
-                            _ => {
-                                if self.detect_multistake(&stored_block.content.header) {
-                                    return Ok(BTreeSet::new());
-                                }
-                            }
-                        }
-                        res
+                        HeaderCheckOutcome::Discard(DiscardReason::Invalid(
+                                "the operations of the block do not match the operation merkle root of its header"
+                                    .to_string(),
+                            ))
                     } else {
-                        panic!(
-                            "inconsistency inside block statuses removing incoming block {}",
-                            block_id
-                        )
+                        self.check_header(&block_id, &stored_block.content.header, current_slot)
                     };
+                    match &res {
+                        HeaderCheckOutcome::Discard(reason) => {
+                            self.maybe_note_attack_attempt(reason, &block_id)
+                        }
+                        _ => {
+                            if self.detect_multistake(&stored_block.content.header) {
+                                return Ok(BTreeSet::new());
+                            }
+                        }
+                    }
+                    res
+                } else {
+                    panic!(
+                        "inconsistency inside block statuses removing incoming block {}",
+                        block_id
+                    )
+                };
                 let mut block_infos = None;
                 let sequence_number = self.blocks_state.sequence_counter();
                 self.blocks_state
@@ -239,6 +243,7 @@ impl ConsensusState {
                                     ))
                                 }
                                 HeaderCheckOutcome::Discard(reason) => {
+                                    self.discard_history.record(header.content.slot, &reason);
                                     if reason == DiscardReason::Stale {
                                         self.new_stale_blocks.insert(
                                             block_id,
@@ -429,6 +434,10 @@ impl ConsensusState {
             "block_id": add_block_id
         });
 
+        // note when the block became active, to measure its finality latency
+        self.active_block_times
+            .insert(add_block_id, MassaTime::now());
+
         // add as child to parents
         // add as descendant to ancestors. Note: descendants are never removed.
         self.insert_parents_descendants(
@@ -501,7 +510,7 @@ impl ConsensusState {
 
         // compute clique fitnesses and find blockclique
         massa_trace!("consensus.block_graph.add_block_to_graph.compute_clique_fitnesses_and_find_blockclique", {});
-        // note: clique_fitnesses is pair (fitness, -hash_sum) where the second parameter is negative for sorting
+        // note: cliques are compared on (fitness, -hash_sum), see `blockclique_key`
         let position_blockclique = self.compute_fitness_find_blockclique(&add_block_id)?;
 
         // update best parents
@@ -535,6 +544,7 @@ impl ConsensusState {
         let fitness_threshold = self.max_cliques[position_blockclique]
             .fitness
             .saturating_sub(self.config.delta_f0);
+        let fitness_threshold = self.bounded_gi_head_fitness_threshold(fitness_threshold);
         let stale_blocks = self.list_stale_blocks(fitness_threshold);
         self.max_cliques.retain(|c| c.fitness >= fitness_threshold);
         // mark stale blocks
@@ -740,6 +750,11 @@ impl ConsensusState {
             }
             self.final_block_stats.extend(final_block_stats);
 
+            // the graph caught up with the network once a block becomes final after startup
+            if !final_block_slots.is_empty() {
+                self.ready = true;
+            }
+
             // add stale blocks to stats
             let new_stale_block_ids_creators_slots = mem::take(&mut self.new_stale_blocks);
             let timestamp = MassaTime::now();
@@ -753,7 +768,15 @@ impl ConsensusState {
         self.notify_execution(final_block_slots);
 
         // notify protocol of block wishlist
-        let new_wishlist = self.get_block_wishlist()?;
+        let mut new_wishlist = self.get_block_wishlist()?;
+        // do not flood the network: the other dependencies are wished once the most awaited ones arrive
+        if new_wishlist.len() > self.config.max_wishlist_fetches {
+            let prioritized: PreHashSet<BlockId> = self
+                .get_prioritized_wishlist(&new_wishlist, self.config.max_wishlist_fetches)
+                .into_iter()
+                .collect();
+            new_wishlist.retain(|block_id, _| prioritized.contains(block_id));
+        }
         let new_blocks: PreHashMap<BlockId, Option<SecuredHeader>> = new_wishlist
             .iter()
             .filter_map(|(id, header)| {
@@ -161,6 +161,7 @@ impl ConsensusState {
         let reason = DiscardReason::Invalid("invalid".to_string());
         self.maybe_note_attack_attempt(&reason, block_id);
         massa_trace!("consensus.block_graph.process.invalid_block", {"block_id": block_id, "reason": reason});
+        self.discard_history.record(header.content.slot, &reason);
         let sequence_number = self.blocks_state.sequence_counter();
         self.blocks_state.transition_map(block_id, |_, _| {
             Some(BlockStatus::Discarded {
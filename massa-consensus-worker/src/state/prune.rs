@@ -1,7 +1,7 @@
 use core::panic;
 
 use massa_consensus_exports::{
-    block_status::{BlockStatus, DiscardReason, HeaderOrBlock},
+    block_status::{BlockStatus, DiscardReason, HeaderOrBlock, StorageOrBlock},
     error::ConsensusError,
 };
 use massa_logging::massa_trace;
@@ -15,6 +15,17 @@ use tracing::debug;
 
 use super::ConsensusState;
 
+/// Number of operations included in a block, whether the block still holds them or was stripped to their ids
+fn block_operation_count(block_id: &BlockId, storage_or_block: &StorageOrBlock) -> u64 {
+    match storage_or_block {
+        StorageOrBlock::Storage(storage) => storage
+            .read_blocks()
+            .get(block_id)
+            .map_or(0, |block| block.content.operations.len() as u64),
+        StorageOrBlock::Block(block) => block.content.operations.len() as u64,
+    }
+}
+
 impl ConsensusState {
     /// prune active blocks and return final blocks, return discarded final blocks
     fn prune_active(&mut self) -> Result<PreHashMap<BlockId, ActiveBlock>, ConsensusError> {
@@ -54,6 +65,7 @@ impl ConsensusState {
 
         // remove unused final active blocks
         let mut discarded_finals: PreHashMap<BlockId, ActiveBlock> = PreHashMap::default();
+        let mut discarded_operation_count = 0u64;
         let to_remove: Vec<BlockId> = self
             .blocks_state
             .active_blocks()
@@ -66,7 +78,7 @@ impl ConsensusState {
                 if let Some(
                     BlockStatus::Active {
                         a_block: discarded_active,
-                        ..
+                        storage_or_block,
                     }
                 ) = block_status {
                     // remove from parent's children
@@ -86,6 +98,7 @@ impl ConsensusState {
                     let block_creator = discarded_active.creator_address;
                     let block_parents = discarded_active.parents.iter().map(|(p, _)| *p).collect();
                     discarded_finals.insert(discard_active_h, *discarded_active);
+                    discarded_operation_count += block_operation_count(&discard_active_h, &storage_or_block);
 
                     // mark as final
                     Some(BlockStatus::Discarded {
@@ -100,6 +113,9 @@ impl ConsensusState {
                 }
             });
         }
+        self.pruned_operation_count = self
+            .pruned_operation_count
+            .saturating_add(discarded_operation_count);
         Ok(discarded_finals)
     }
 
@@ -296,6 +312,7 @@ impl ConsensusState {
                     };
                     massa_trace!("consensus.block_graph.prune_waiting_for_dependencies", {"hash": block_id, "reason": reason_opt});
                     if let Some(reason) = reason_opt {
+                        self.discard_history.record(header.content.slot, &reason);
                         // add to stats if reason is Stale
                         if reason == DiscardReason::Stale {
                             self.new_stale_blocks.insert(
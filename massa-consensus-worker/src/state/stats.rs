@@ -1,8 +1,19 @@
 use super::ConsensusState;
-use massa_consensus_exports::error::ConsensusError;
-use massa_models::stats::ConsensusStats;
+use massa_consensus_exports::{
+    block_status::{BlockStatus, DiscardReason, StorageOrBlock},
+    error::ConsensusError,
+};
+use massa_models::{
+    block_id::BlockId,
+    slot::Slot,
+    stats::{ConsensusStats, DiscardedBlockCounts},
+    timeslots::get_latest_block_slot_at_timestamp,
+};
 use massa_time::MassaTime;
-use std::cmp::max;
+use std::{
+    cmp::max,
+    collections::{BTreeMap, HashSet},
+};
 
 #[cfg(not(feature = "sandbox"))]
 use tracing::warn;
@@ -29,26 +40,128 @@ impl ConsensusState {
             .filter(|t| **t >= timespan_start && **t < timespan_end)
             .count() as u64;
         let clique_count = self.get_clique_count() as u64;
+        let average_finality_latency = average_finality_latency(
+            self.finality_latency_stats
+                .iter()
+                .filter(|(t, _)| *t >= timespan_start && *t < timespan_end)
+                .map(|(_, latency)| *latency),
+        );
+        let current_slot = get_latest_block_slot_at_timestamp(
+            self.config.thread_count,
+            self.config.t0,
+            self.config.genesis_timestamp,
+            timespan_end,
+        )?;
+        let slots_since_last_finality = current_slot.map_or(0, |current_slot| {
+            self.slots_since_last_finality(current_slot)
+        });
+        let stats_periods = self
+            .config
+            .stats_timespan
+            .checked_div_time(self.config.t0)
+            .unwrap_or(0);
+        let production_rate_per_thread =
+            self.production_rate_per_thread(current_slot, stats_periods);
+        let endorsement_participation = current_slot.and_then(|current_slot| {
+            self.endorsement_participation(current_slot.get_cycle(self.config.periods_per_cycle))
+        });
         Ok(ConsensusStats {
             final_block_count,
             stale_block_count,
             clique_count,
+            average_finality_latency,
+            recent_discarded_blocks: self.discard_history.counts(),
+            slots_since_last_finality,
+            production_rate_per_thread,
+            endorsement_participation,
+            pruned_operation_count: self.pruned_operation_count,
             start_timespan: timespan_start,
             end_timespan: timespan_end,
         })
     }
 
     /// Must be called each tick to update stats. Will detect if a desynchronization happened
-    pub fn stats_tick(&mut self) -> Result<(), ConsensusError> {
+    pub fn stats_tick(&mut self, current_slot: Slot) -> Result<(), ConsensusError> {
         #[cfg(not(feature = "sandbox"))]
         {
             self.check_desync()?;
         }
         // prune stats
         self.prune_stats()?;
+        self.discard_history.prune(current_slot);
+        self.finality_progress
+            .observe(&self.latest_final_blocks_periods, current_slot);
         Ok(())
     }
 
+    /// Average number of blocks produced per period in each thread over the `window_periods` periods
+    /// up to `current_slot`, computed from the slots of the active and final blocks in memory.
+    /// A thread far below 1 signals missed draws or offline stakers.
+    pub fn production_rate_per_thread(
+        &self,
+        current_slot: Option<Slot>,
+        window_periods: u64,
+    ) -> Vec<f64> {
+        let Some(current_slot) = current_slot else {
+            return vec![0.0; self.config.thread_count as usize];
+        };
+        production_rate_per_thread(
+            self.blocks_state
+                .iter()
+                .filter_map(|(_, status)| match status {
+                    BlockStatus::Active { a_block, .. } => Some(a_block.slot),
+                    _ => None,
+                }),
+            self.config.thread_count,
+            current_slot.period,
+            window_periods,
+        )
+    }
+
+    /// Fraction of the endorsement draws of `cycle` that were filled, computed from the headers
+    /// of the final and blockclique blocks in memory: slots without such a block are not counted.
+    /// Low participation signals offline endorsers. Returns `None` if no block of the cycle is known.
+    pub fn endorsement_participation(&self, cycle: u64) -> Option<f64> {
+        let blockclique = self.get_blockclique();
+        endorsement_participation(
+            self.blocks_state
+                .iter()
+                .filter_map(|(block_id, status)| match status {
+                    BlockStatus::Active {
+                        a_block,
+                        storage_or_block,
+                    } if !a_block.parents.is_empty()
+                        && (a_block.is_final || blockclique.contains(block_id)) =>
+                    {
+                        let endorsement_count = match storage_or_block {
+                            StorageOrBlock::Storage(storage) => storage
+                                .read_blocks()
+                                .get(block_id)?
+                                .content
+                                .header
+                                .content
+                                .endorsements
+                                .len(),
+                            StorageOrBlock::Block(block) => {
+                                block.content.header.content.endorsements.len()
+                            }
+                        };
+                        Some((a_block.slot, endorsement_count))
+                    }
+                    _ => None,
+                }),
+            cycle,
+            self.config.periods_per_cycle,
+            self.config.endorsement_count,
+        )
+    }
+
+    /// Number of slots elapsed between the last time the latest final periods advanced and `current_slot`
+    pub fn slots_since_last_finality(&self, current_slot: Slot) -> u64 {
+        self.finality_progress
+            .slots_since_advance(current_slot, self.config.thread_count)
+    }
+
     #[cfg(not(feature = "sandbox"))]
     /// Helper function for stats_tick. Checks if there are any final blocks is coming from protocol
     /// if none => we are probably desync
@@ -105,6 +218,352 @@ impl ConsensusState {
                 break;
             }
         }
+        while let Some((t, _)) = self.finality_latency_stats.front() {
+            if t < &start_time {
+                self.finality_latency_stats.pop_front();
+            } else {
+                break;
+            }
+        }
+        // blocks that stayed active that long are discarded rather than finalized
+        self.active_block_times.retain(|_, t| *t >= start_time);
         Ok(())
     }
 }
+
+/// Stale and invalid block discards, keyed by the slot of the discarded block.
+///
+/// Only the discards of blocks from the last `window_periods` periods are kept.
+#[derive(Debug, Clone)]
+pub struct DiscardHistory {
+    window_periods: u64,
+    per_slot: BTreeMap<Slot, DiscardedBlockCounts>,
+}
+
+impl DiscardHistory {
+    pub fn new(window_periods: u64) -> Self {
+        DiscardHistory {
+            window_periods,
+            per_slot: BTreeMap::new(),
+        }
+    }
+
+    /// Count a discarded block. Blocks discarded because they are final are not counted.
+    pub fn record(&mut self, slot: Slot, reason: &DiscardReason) {
+        let counts = match reason {
+            DiscardReason::Stale => DiscardedBlockCounts {
+                stale: 1,
+                invalid: 0,
+            },
+            DiscardReason::Invalid(_) => DiscardedBlockCounts {
+                stale: 0,
+                invalid: 1,
+            },
+            DiscardReason::Final => return,
+        };
+        let entry = self.per_slot.entry(slot).or_default();
+        entry.stale = entry.stale.saturating_add(counts.stale);
+        entry.invalid = entry.invalid.saturating_add(counts.invalid);
+    }
+
+    /// Forget the discards of blocks that are older than the window ending at `current_slot`
+    pub fn prune(&mut self, current_slot: Slot) {
+        let oldest_kept = Slot::new(
+            current_slot.period.saturating_sub(self.window_periods),
+            current_slot.thread,
+        );
+        self.per_slot = self.per_slot.split_off(&oldest_kept);
+    }
+
+    /// Total discards over the window
+    pub fn counts(&self) -> DiscardedBlockCounts {
+        self.per_slot
+            .values()
+            .fold(DiscardedBlockCounts::default(), |acc, counts| {
+                DiscardedBlockCounts {
+                    stale: acc.stale.saturating_add(counts.stale),
+                    invalid: acc.invalid.saturating_add(counts.invalid),
+                }
+            })
+    }
+}
+
+/// Slot at which the latest final periods last advanced.
+#[derive(Debug, Clone, Default)]
+pub struct FinalityProgress {
+    last_final_periods: Vec<u64>,
+    last_advance_slot: Option<Slot>,
+}
+
+impl FinalityProgress {
+    /// Record the latest final period of each thread as seen at `current_slot`
+    pub fn observe(&mut self, latest_final_blocks_periods: &[(BlockId, u64)], current_slot: Slot) {
+        let final_periods: Vec<u64> = latest_final_blocks_periods
+            .iter()
+            .map(|(_, period)| *period)
+            .collect();
+        if self.last_advance_slot.is_none() || final_periods != self.last_final_periods {
+            self.last_final_periods = final_periods;
+            self.last_advance_slot = Some(current_slot);
+        }
+    }
+
+    /// Number of slots elapsed since the last advance, 0 if nothing was observed yet
+    pub fn slots_since_advance(&self, current_slot: Slot, thread_count: u8) -> u64 {
+        self.last_advance_slot
+            .and_then(|slot| current_slot.slots_since(&slot, thread_count).ok())
+            .unwrap_or(0)
+    }
+}
+
+/// Number of slots elapsed between the moment a block became active and the moment it became final.
+///
+/// The duration is expressed in slots of any thread, i.e. in units of `t0 / thread_count`.
+pub(crate) fn finality_latency_slots(
+    active_time: MassaTime,
+    final_time: MassaTime,
+    t0: MassaTime,
+    thread_count: u8,
+) -> u64 {
+    let elapsed = final_time.saturating_sub(active_time);
+    t0.checked_div_u64(thread_count as u64)
+        .and_then(|slot_duration| elapsed.checked_div_time(slot_duration))
+        .unwrap_or(0)
+}
+
+/// Number of distinct slots produced per period in each thread, over the window of `window_periods`
+/// periods ending at `last_period` included. The window is shortened if it would start before genesis.
+pub(crate) fn production_rate_per_thread(
+    slots: impl Iterator<Item = Slot>,
+    thread_count: u8,
+    last_period: u64,
+    window_periods: u64,
+) -> Vec<f64> {
+    let first_period = last_period.saturating_add(1).saturating_sub(window_periods);
+    let period_count = last_period.saturating_add(1).saturating_sub(first_period);
+    let mut rates = vec![0.0; thread_count as usize];
+    if period_count == 0 {
+        return rates;
+    }
+    // several blocks at the same slot (equivocations, forks) count as one production
+    let produced: HashSet<Slot> = slots
+        .filter(|slot| slot.period >= first_period && slot.period <= last_period)
+        .collect();
+    for slot in produced {
+        if let Some(rate) = rates.get_mut(slot.thread as usize) {
+            *rate += 1.0;
+        }
+    }
+    for rate in rates.iter_mut() {
+        *rate /= period_count as f64;
+    }
+    rates
+}
+
+/// Fraction of the `endorsement_count` endorsement draws of each given block slot of `cycle`
+/// that were filled, from the number of endorsements included in the block. `None` if there are no such blocks.
+pub(crate) fn endorsement_participation(
+    blocks: impl Iterator<Item = (Slot, usize)>,
+    cycle: u64,
+    periods_per_cycle: u64,
+    endorsement_count: u32,
+) -> Option<f64> {
+    let (block_count, filled) = blocks
+        .filter(|(slot, _)| slot.get_cycle(periods_per_cycle) == cycle)
+        .fold((0u64, 0u64), |(block_count, filled), (_, endorsements)| {
+            (block_count + 1, filled.saturating_add(endorsements as u64))
+        });
+    let expected = block_count.saturating_mul(endorsement_count as u64);
+    if expected == 0 {
+        None
+    } else {
+        Some(filled as f64 / expected as f64)
+    }
+}
+
+/// Average of the given finality latencies, `None` if there are none.
+pub(crate) fn average_finality_latency(latencies: impl Iterator<Item = u64>) -> Option<f64> {
+    let (count, sum) = latencies.fold((0u64, 0u64), |(count, sum), latency| {
+        (count + 1, sum.saturating_add(latency))
+    });
+    if count == 0 {
+        None
+    } else {
+        Some(sum as f64 / count as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        average_finality_latency, endorsement_participation, finality_latency_slots,
+        production_rate_per_thread, DiscardHistory, FinalityProgress,
+    };
+    use massa_consensus_exports::block_status::DiscardReason;
+    use massa_models::{block_id::BlockId, slot::Slot, stats::DiscardedBlockCounts};
+    use massa_time::MassaTime;
+
+    #[test]
+    fn test_finality_latency_slots() {
+        let t0 = MassaTime::from_millis(16000);
+        let active = MassaTime::from_millis(1_000_000);
+        // 32 threads: one slot every 500ms
+        assert_eq!(
+            finality_latency_slots(active, MassaTime::from_millis(1_005_200), t0, 32),
+            10
+        );
+        assert_eq!(finality_latency_slots(active, active, t0, 32), 0);
+        // clock going backwards does not underflow
+        assert_eq!(
+            finality_latency_slots(active, MassaTime::from_millis(0), t0, 32),
+            0
+        );
+    }
+
+    #[test]
+    fn test_average_finality_latency() {
+        assert_eq!(average_finality_latency(std::iter::empty()), None);
+        assert_eq!(
+            average_finality_latency(vec![4, 6, 11].into_iter()),
+            Some(7.0)
+        );
+    }
+
+    #[test]
+    fn test_endorsement_participation() {
+        // cycles of 4 periods, 16 endorsements per block
+        let blocks = vec![
+            // cycle 0: all endorsements but 4 of the 32 draws
+            (Slot::new(1, 0), 16),
+            (Slot::new(2, 1), 12),
+            // cycle 1: one block fully endorsed, one not endorsed at all
+            (Slot::new(4, 0), 16),
+            (Slot::new(7, 1), 0),
+        ];
+        assert_eq!(
+            endorsement_participation(blocks.clone().into_iter(), 0, 4, 16),
+            Some(0.875)
+        );
+        assert_eq!(
+            endorsement_participation(blocks.clone().into_iter(), 1, 4, 16),
+            Some(0.5)
+        );
+        // no block known in that cycle
+        assert_eq!(
+            endorsement_participation(blocks.into_iter(), 2, 4, 16),
+            None
+        );
+        assert_eq!(
+            endorsement_participation(vec![(Slot::new(1, 0), 0)].into_iter(), 0, 4, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_production_rate_per_thread() {
+        // thread 0 produces every period, thread 1 every other period, thread 2 never
+        let mut slots = Vec::new();
+        for period in 0..20 {
+            slots.push(Slot::new(period, 0));
+            if period % 2 == 0 {
+                slots.push(Slot::new(period, 1));
+            }
+        }
+        // an equivocation does not count twice
+        slots.push(Slot::new(18, 0));
+
+        assert_eq!(
+            production_rate_per_thread(slots.clone().into_iter(), 3, 19, 10),
+            vec![1.0, 0.5, 0.0]
+        );
+        // periods 15 to 19: thread 1 produced at 16 and 18
+        assert_eq!(
+            production_rate_per_thread(slots.clone().into_iter(), 3, 19, 5),
+            vec![1.0, 0.4, 0.0]
+        );
+        // blocks after the end of the window are ignored
+        assert_eq!(
+            production_rate_per_thread(slots.clone().into_iter(), 3, 9, 10),
+            vec![1.0, 0.5, 0.0]
+        );
+        // the window is shortened near genesis: periods 0 to 2
+        assert_eq!(
+            production_rate_per_thread(slots.clone().into_iter(), 3, 2, 10),
+            vec![1.0, 2.0 / 3.0, 0.0]
+        );
+        assert_eq!(
+            production_rate_per_thread(slots.into_iter(), 3, 19, 0),
+            vec![0.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_discard_history_window() {
+        let mut history = DiscardHistory::new(10);
+        history.record(Slot::new(1, 0), &DiscardReason::Stale);
+        history.record(Slot::new(5, 1), &DiscardReason::Invalid("bad".to_string()));
+        history.record(Slot::new(5, 1), &DiscardReason::Stale);
+        history.record(Slot::new(12, 0), &DiscardReason::Stale);
+        // blocks discarded because final are not counted
+        history.record(Slot::new(12, 0), &DiscardReason::Final);
+
+        history.prune(Slot::new(11, 0));
+        assert_eq!(
+            history.counts(),
+            DiscardedBlockCounts {
+                stale: 3,
+                invalid: 1
+            }
+        );
+
+        // period 1 leaves the window
+        history.prune(Slot::new(12, 0));
+        assert_eq!(
+            history.counts(),
+            DiscardedBlockCounts {
+                stale: 2,
+                invalid: 1
+            }
+        );
+
+        // only the block of slot (12, 0) remains
+        history.prune(Slot::new(16, 0));
+        assert_eq!(
+            history.counts(),
+            DiscardedBlockCounts {
+                stale: 1,
+                invalid: 0
+            }
+        );
+
+        history.prune(Slot::new(30, 0));
+        assert_eq!(history.counts(), DiscardedBlockCounts::default());
+    }
+
+    #[test]
+    fn test_slots_since_last_finality() {
+        let block_id = |name: &str| {
+            BlockId::generate_from_hash(massa_hash::Hash::compute_from(name.as_bytes()))
+        };
+        let mut progress = FinalityProgress::default();
+        assert_eq!(progress.slots_since_advance(Slot::new(3, 0), 2), 0);
+
+        // finality is stuck at periods (2, 2) while slots advance
+        let stuck = vec![(block_id("a"), 2), (block_id("b"), 2)];
+        progress.observe(&stuck, Slot::new(3, 0));
+        for (i, slot) in [Slot::new(3, 1), Slot::new(4, 0), Slot::new(4, 1)]
+            .into_iter()
+            .enumerate()
+        {
+            progress.observe(&stuck, slot);
+            assert_eq!(progress.slots_since_advance(slot, 2), i as u64 + 1);
+        }
+        assert_eq!(progress.slots_since_advance(Slot::new(10, 0), 2), 14);
+
+        // a new final block in one thread resets the count
+        let advanced = vec![(block_id("a"), 2), (block_id("c"), 3)];
+        progress.observe(&advanced, Slot::new(10, 0));
+        assert_eq!(progress.slots_since_advance(Slot::new(10, 0), 2), 0);
+        assert_eq!(progress.slots_since_advance(Slot::new(11, 1), 2), 3);
+    }
+}
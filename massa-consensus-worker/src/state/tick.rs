@@ -43,7 +43,7 @@ impl ConsensusState {
         self.rec_process(to_process, Some(current_slot))?;
 
         // Update the stats
-        self.stats_tick()?;
+        self.stats_tick(current_slot)?;
 
         // take care of block db changes
         self.block_db_changed()?;
@@ -132,6 +132,7 @@ impl ConsensusState {
     ) -> BlockStatus {
         self.maybe_note_attack_attempt(&reason, &block_id);
         massa_trace!("consensus.block_graph.process.incoming_header.discarded", {"block_id": block_id, "reason": reason});
+        self.discard_history.record(header.content.slot, &reason);
         // count stales
         if reason == DiscardReason::Stale {
             self.new_stale_blocks.insert(
@@ -227,7 +228,9 @@ impl ConsensusState {
                             "discarded because a parent was discarded for the following reason: {}",
                             invalid_reason
                         )),
-                        r => r.clone(),
+                        // the parent is a final block pruned from the graph:
+                        // the block arrived too late to build on it and will never become final itself
+                        DiscardReason::Stale | DiscardReason::Final => DiscardReason::Stale,
                     });
                 }
                 Some(BlockStatus::Active {
@@ -1,17 +1,28 @@
 use std::{
     collections::{HashSet, VecDeque},
+    sync::{Arc, Mutex},
     time::Duration,
 };
 
 use super::{
-    tools::{consensus_test, register_block},
+    tools::{
+        consensus_test, fast_consensus_config, register_block, roll_buy_operation,
+        staker_foreign_controllers,
+    },
     universe::{ConsensusForeignControllers, ConsensusTestUniverse},
 };
-use crate::tests::tools::create_block;
-use massa_consensus_exports::ConsensusConfig;
+use crate::tests::tools::{
+    create_block, create_block_with_merkle_root, create_block_with_operations,
+};
+use massa_consensus_exports::{block_status::DiscardReason, ConsensusConfig};
 use massa_execution_exports::MockExecutionController;
 use massa_models::{
-    address::Address, block::BlockGraphStatus, block_id::BlockId, config::ENDORSEMENT_COUNT,
+    address::Address,
+    block::BlockGraphStatus,
+    block_id::BlockId,
+    config::ENDORSEMENT_COUNT,
+    operation::{compute_operations_hash, OperationIdSerializer},
+    prehash::PreHashSet,
     slot::Slot,
 };
 use massa_pool_exports::MockPoolController;
@@ -57,6 +68,229 @@ fn test_genesis_block_creation() {
         .expect("could not get block graph status")
         .genesis_blocks;
     assert_eq!(genesis_hashes.len() as u8, thread_count);
+    // no block became final since startup
+    assert!(!universe.module_controller.is_ready());
+    // genesis blocks are created with the genesis key
+    let genesis_address = Address::from_public_key(&staking_key.get_public_key());
+    for genesis_id in genesis_hashes {
+        assert_eq!(
+            universe.module_controller.get_block_creator(genesis_id),
+            Some(genesis_address)
+        );
+    }
+    let unknown_id = BlockId::generate_from_hash(massa_hash::Hash::compute_from(b"unknown"));
+    assert_eq!(
+        universe.module_controller.get_block_creator(unknown_id),
+        None
+    );
+}
+
+#[test]
+fn test_operation_validity_range() {
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let cfg = ConsensusConfig {
+        t0: MassaTime::from_millis(1000),
+        operation_validity_periods: 10,
+        ..fast_consensus_config(&staking_key)
+    };
+    let foreign_controllers = staker_foreign_controllers(&staking_key);
+    let mut storage = foreign_controllers.storage.clone();
+    let universe = ConsensusTestUniverse::new(foreign_controllers, cfg);
+
+    let operation = roll_buy_operation(&staking_key, 50);
+    assert_eq!(
+        universe
+            .module_controller
+            .get_operation_validity_range(operation.id),
+        None
+    );
+
+    // operations of the pool and of the blocks share the same storage
+    storage.store_operations(vec![operation.clone()]);
+    assert_eq!(
+        universe
+            .module_controller
+            .get_operation_validity_range(operation.id),
+        Some((40, 50))
+    );
+}
+
+#[test]
+fn test_earliest_inclusion_slot() {
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let thread_count = 2;
+    let cfg = ConsensusConfig {
+        t0: MassaTime::from_millis(1000),
+        thread_count,
+        operation_validity_periods: 10,
+        ..fast_consensus_config(&staking_key)
+    };
+    let foreign_controllers = staker_foreign_controllers(&staking_key);
+    let mut storage = foreign_controllers.storage.clone();
+    let universe = ConsensusTestUniverse::new(foreign_controllers, cfg);
+
+    let operation = |expire_period: u64| roll_buy_operation(&staking_key, expire_period);
+    let thread = Address::from_public_key(&staking_key.get_public_key()).get_thread(thread_count);
+    // the best parents are the genesis blocks, at period 0
+    let later = operation(50);
+    let soon = operation(5);
+    let expired = operation(0);
+    assert_eq!(
+        universe
+            .module_controller
+            .get_earliest_inclusion_slot(later.id),
+        None
+    );
+
+    storage.store_operations(vec![later.clone(), soon.clone(), expired.clone()]);
+    // not valid before period 40
+    assert_eq!(
+        universe
+            .module_controller
+            .get_earliest_inclusion_slot(later.id),
+        Some(Slot::new(40, thread))
+    );
+    // already valid: the next block of the thread can include it
+    assert_eq!(
+        universe
+            .module_controller
+            .get_earliest_inclusion_slot(soon.id),
+        Some(Slot::new(1, thread))
+    );
+    // only valid at period 0, taken by the genesis blocks
+    assert_eq!(
+        universe
+            .module_controller
+            .get_earliest_inclusion_slot(expired.id),
+        None
+    );
+}
+
+/// When the best parents lag behind the clock, an operation cannot be included before the next slot of its thread.
+#[test]
+fn test_earliest_inclusion_slot_after_current_slot() {
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let thread_count = 2;
+    let t0 = MassaTime::from_millis(10000);
+    // a quarter of period into period 20: the current slot is (20, 0)
+    let cfg = ConsensusConfig {
+        t0,
+        thread_count,
+        genesis_timestamp: MassaTime::now().saturating_sub(MassaTime::from_millis(202500)),
+        operation_validity_periods: 10,
+        ..fast_consensus_config(&staking_key)
+    };
+    let foreign_controllers = staker_foreign_controllers(&staking_key);
+    let mut storage = foreign_controllers.storage.clone();
+    let universe = ConsensusTestUniverse::new(foreign_controllers, cfg);
+
+    let operation = roll_buy_operation(&staking_key, 25);
+    let thread = Address::from_public_key(&staking_key.get_public_key()).get_thread(thread_count);
+    storage.store_operations(vec![operation.clone()]);
+    // the best parents are still the genesis blocks, but slots up to (20, 0) are already past
+    let expected_period = if thread == 0 { 21 } else { 20 };
+    assert_eq!(
+        universe
+            .module_controller
+            .get_earliest_inclusion_slot(operation.id),
+        Some(Slot::new(expected_period, thread))
+    );
+}
+
+/// Pruning runs on a timer checked at each slot tick, even when no block is received:
+/// repeated prunings of an unchanged graph must leave it as is.
+#[test]
+fn test_repeated_pruning_without_new_blocks() {
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let staking_address = Address::from_public_key(&staking_key.get_public_key());
+    let thread_count = 2;
+    let cfg = ConsensusConfig {
+        t0: MassaTime::from_millis(200),
+        thread_count,
+        genesis_timestamp: MassaTime::now(),
+        force_keep_final_periods: 50,
+        force_keep_final_periods_without_ops: 128,
+        max_future_processing_blocks: 10,
+        block_db_prune_interval: MassaTime::from_millis(1),
+        genesis_key: staking_key.clone(),
+        ..ConsensusConfig::default()
+    };
+    let mut foreign_controllers = ConsensusForeignControllers::new_with_mocks();
+    let storage = foreign_controllers.storage.clone();
+    let (blockclique_tx, blockclique_rx) = std::sync::mpsc::channel();
+    foreign_controllers
+        .execution_controller
+        .expect_update_blockclique_status()
+        .returning(move |_, new_blockclique, _| {
+            if let Some(new_blockclique) = new_blockclique {
+                let _ = blockclique_tx.send(new_blockclique);
+            }
+        });
+    foreign_controllers
+        .pool_controller
+        .expect_notify_final_cs_periods()
+        .returning(|_| {});
+    foreign_controllers
+        .pool_controller
+        .expect_add_denunciation_precursor()
+        .returning(|_| {});
+    foreign_controllers
+        .selector_controller
+        .expect_get_producer()
+        .returning(move |_| Ok(staking_address));
+    foreign_controllers
+        .selector_controller
+        .expect_get_selection()
+        .returning(move |_| {
+            Ok(Selection {
+                producer: staking_address,
+                endorsements: vec![staking_address; ENDORSEMENT_COUNT as usize],
+            })
+        });
+    let universe = ConsensusTestUniverse::new(foreign_controllers, cfg);
+    let genesis_hashes = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status")
+        .genesis_blocks;
+    let statuses_before = universe
+        .module_controller
+        .get_block_statuses(&genesis_hashes);
+    let cliques_before = universe.module_controller.get_cliques();
+
+    // a block waiting for its slot: the slot ticks before it, and therefore several prunings,
+    // happen without any change to the graph
+    let block = create_block(Slot::new(3, 0), genesis_hashes.clone(), &staking_key);
+    register_block(&universe.module_controller, block.clone(), storage);
+    loop {
+        let new_blockclique = blockclique_rx
+            .recv_timeout(Duration::from_secs(5))
+            .expect("the block was not processed at its slot");
+        if new_blockclique
+            .values()
+            .any(|block_id| *block_id == block.id)
+        {
+            break;
+        }
+    }
+
+    // the prunings kept the graph and the waiting block
+    assert_eq!(
+        universe
+            .module_controller
+            .get_block_statuses(&genesis_hashes),
+        statuses_before
+    );
+    assert!(statuses_before
+        .iter()
+        .all(|status| *status == BlockGraphStatus::Final));
+    assert_eq!(
+        universe.module_controller.get_block_statuses(&[block.id]),
+        vec![BlockGraphStatus::ActiveInBlockclique]
+    );
+    let cliques_after = universe.module_controller.get_cliques();
+    assert_eq!(cliques_after.len(), cliques_before.len());
+    assert!(cliques_after[0].block_ids.contains(&block.id));
 }
 
 /// This test tests that the blocks are well processed by consensus even if they are not sent in a sorted way.
@@ -400,3 +634,335 @@ fn test_parent_in_the_future() {
         "wrong status"
     );
 }
+
+/// A late block whose parent is a final block already pruned from the graph
+/// must be discarded as stale right away.
+#[test]
+fn test_block_with_pruned_final_parent() {
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let cfg = ConsensusConfig {
+        delta_f0: 2,
+        force_keep_final_periods: 0,
+        force_keep_final_periods_without_ops: 0,
+        block_db_prune_interval: MassaTime::from_millis(1),
+        ..fast_consensus_config(&staking_key)
+    };
+    let foreign_controllers = staker_foreign_controllers(&staking_key);
+    let storage = foreign_controllers.storage.clone();
+
+    let universe = ConsensusTestUniverse::new(foreign_controllers, cfg);
+    let genesis_hashes = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status")
+        .genesis_blocks;
+
+    assert!(!universe.module_controller.is_ready());
+
+    // a chain always using the latest blocks as parents
+    let mut parents = genesis_hashes;
+    let mut blocks = Vec::new();
+    for period in 1..=10 {
+        for thread in 0..2u8 {
+            let block = create_block(Slot::new(period, thread), parents.clone(), &staking_key);
+            parents[thread as usize] = block.id;
+            register_block(&universe.module_controller, block.clone(), storage.clone());
+            blocks.push(block);
+        }
+    }
+
+    // let the slots of the chain pass, finalize its first blocks and prune them
+    std::thread::sleep(Duration::from_millis(1500));
+    let first_block = &blocks[0];
+    let export = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status");
+    assert_eq!(
+        export.discarded_blocks.get(&first_block.id).map(|d| &d.0),
+        Some(&DiscardReason::Final)
+    );
+    // blocks became final: the graph caught up
+    assert!(universe.module_controller.is_ready());
+
+    // a late block of thread 1 building on the pruned block of thread 0
+    let late_block = create_block(
+        Slot::new(11, 1),
+        vec![first_block.id, parents[1]],
+        &staking_key,
+    );
+    register_block(
+        &universe.module_controller,
+        late_block.clone(),
+        storage.clone(),
+    );
+    std::thread::sleep(Duration::from_millis(300));
+    let export = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status");
+    assert_eq!(
+        export.discarded_blocks.get(&late_block.id).map(|d| &d.0),
+        Some(&DiscardReason::Stale)
+    );
+}
+
+#[test]
+fn test_pruned_operation_count() {
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let cfg = ConsensusConfig {
+        delta_f0: 2,
+        force_keep_final_periods: 0,
+        force_keep_final_periods_without_ops: 0,
+        block_db_prune_interval: MassaTime::from_millis(1),
+        ..fast_consensus_config(&staking_key)
+    };
+    let foreign_controllers = staker_foreign_controllers(&staking_key);
+    let storage = foreign_controllers.storage.clone();
+
+    let universe = ConsensusTestUniverse::new(foreign_controllers, cfg);
+    assert_eq!(
+        universe
+            .module_controller
+            .get_stats()
+            .unwrap()
+            .pruned_operation_count,
+        0
+    );
+    let genesis_hashes = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status")
+        .genesis_blocks;
+
+    // the first block of the chain includes two operations
+    let operation = |expire_period: u64| roll_buy_operation(&staking_key, expire_period).id;
+    let mut parents = genesis_hashes;
+    let mut first_block_id = None;
+    for period in 1..=10 {
+        for thread in 0..2u8 {
+            let operations = if first_block_id.is_none() {
+                vec![operation(1), operation(2)]
+            } else {
+                Vec::new()
+            };
+            let block = create_block_with_operations(
+                Slot::new(period, thread),
+                parents.clone(),
+                &staking_key,
+                operations,
+            );
+            first_block_id.get_or_insert(block.id);
+            parents[thread as usize] = block.id;
+            register_block(&universe.module_controller, block, storage.clone());
+        }
+    }
+
+    // let the periods of the chain pass: its first blocks become final and are pruned
+    std::thread::sleep(Duration::from_millis(1500));
+    let export = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status");
+    assert_eq!(
+        export
+            .discarded_blocks
+            .get(&first_block_id.unwrap())
+            .map(|d| &d.0),
+        Some(&DiscardReason::Final)
+    );
+    assert_eq!(
+        universe
+            .module_controller
+            .get_stats()
+            .unwrap()
+            .pruned_operation_count,
+        2
+    );
+}
+
+#[test]
+fn test_strict_operation_merkle_root() {
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let cfg = ConsensusConfig {
+        strict_operation_merkle_root: true,
+        ..fast_consensus_config(&staking_key)
+    };
+    let foreign_controllers = staker_foreign_controllers(&staking_key);
+    let storage = foreign_controllers.storage.clone();
+
+    let universe = ConsensusTestUniverse::new(foreign_controllers, cfg);
+    let genesis_hashes = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status")
+        .genesis_blocks;
+
+    // the header commits to a placeholder instead of the hash of the (empty) operation list
+    let tampered_block = create_block(Slot::new(1, 0), genesis_hashes.clone(), &staking_key);
+    let valid_block = create_block_with_merkle_root(
+        compute_operations_hash(&[], &OperationIdSerializer::new()),
+        Slot::new(1, 1),
+        genesis_hashes,
+        &staking_key,
+    );
+    register_block(
+        &universe.module_controller,
+        tampered_block.clone(),
+        storage.clone(),
+    );
+    register_block(
+        &universe.module_controller,
+        valid_block.clone(),
+        storage.clone(),
+    );
+    std::thread::sleep(Duration::from_millis(300));
+
+    let export = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status");
+    assert!(matches!(
+        export
+            .discarded_blocks
+            .get(&tampered_block.id)
+            .map(|d| &d.0),
+        Some(DiscardReason::Invalid(_))
+    ));
+    assert!(export.active_blocks.contains_key(&valid_block.id));
+}
+
+#[test]
+fn test_all_active_operations() {
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let cfg = ConsensusConfig {
+        force_keep_final_periods_without_ops: 128,
+        force_keep_final_periods: 10,
+        delta_f0: 32,
+        ..fast_consensus_config(&staking_key)
+    };
+    let foreign_controllers = staker_foreign_controllers(&staking_key);
+    let storage = foreign_controllers.storage.clone();
+
+    let universe = ConsensusTestUniverse::new(foreign_controllers, cfg);
+    assert!(universe
+        .module_controller
+        .get_all_active_operations()
+        .is_empty());
+    let genesis = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status")
+        .genesis_blocks;
+
+    let operation = |expire_period: u64| roll_buy_operation(&staking_key, expire_period).id;
+    let (shared_op, op_1, op_2, op_3) =
+        (operation(10), operation(11), operation(12), operation(13));
+
+    // two parallel blocks, then one block on top of each of them: the last two are incompatible
+    let block_1 = create_block_with_operations(
+        Slot::new(1, 0),
+        vec![genesis[0], genesis[1]],
+        &staking_key,
+        vec![shared_op],
+    );
+    register_block(
+        &universe.module_controller,
+        block_1.clone(),
+        storage.clone(),
+    );
+    let block_2 = create_block_with_operations(
+        Slot::new(1, 1),
+        vec![genesis[0], genesis[1]],
+        &staking_key,
+        vec![op_1],
+    );
+    register_block(
+        &universe.module_controller,
+        block_2.clone(),
+        storage.clone(),
+    );
+    let block_3 = create_block_with_operations(
+        Slot::new(2, 0),
+        vec![block_1.id, genesis[1]],
+        &staking_key,
+        vec![op_2],
+    );
+    register_block(&universe.module_controller, block_3, storage.clone());
+    let block_4 = create_block_with_operations(
+        Slot::new(2, 1),
+        vec![genesis[0], block_2.id],
+        &staking_key,
+        vec![op_3],
+    );
+    register_block(&universe.module_controller, block_4, storage.clone());
+
+    std::thread::sleep(Duration::from_millis(500));
+    assert_eq!(universe.module_controller.get_cliques().len(), 2);
+    // the operations of both cliques are listed
+    assert_eq!(
+        universe.module_controller.get_all_active_operations(),
+        [shared_op, op_1, op_2, op_3].into_iter().collect()
+    );
+}
+
+#[test]
+fn test_prioritized_wishlist() {
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let cfg = ConsensusConfig {
+        max_wishlist_fetches: 1,
+        ..fast_consensus_config(&staking_key)
+    };
+    let mut foreign_controllers = staker_foreign_controllers(&staking_key);
+    let storage = foreign_controllers.storage.clone();
+    // keep track of the blocks wished to protocol
+    let wishlist = Arc::new(Mutex::new(PreHashSet::<BlockId>::default()));
+    let wishlist_clone = wishlist.clone();
+    foreign_controllers
+        .protocol_controller
+        .expect_send_wishlist_delta()
+        .returning(move |new, remove| {
+            let mut wishlist = wishlist_clone.lock().unwrap();
+            wishlist.retain(|block_id| !remove.contains(block_id));
+            wishlist.extend(new.into_keys());
+            Ok(())
+        });
+
+    let universe = ConsensusTestUniverse::new(foreign_controllers, cfg);
+    let genesis = universe
+        .module_controller
+        .get_block_graph_status(None, None)
+        .expect("could not get block graph status")
+        .genesis_blocks;
+
+    // two blocks that are never received
+    let missing_1 = create_block(Slot::new(1, 0), vec![genesis[0], genesis[1]], &staking_key);
+    let missing_2 = create_block(Slot::new(1, 1), vec![genesis[0], genesis[1]], &staking_key);
+
+    // one block waits on the second one, then two blocks wait on the first one
+    let block = create_block(
+        Slot::new(2, 1),
+        vec![genesis[0], missing_2.id],
+        &staking_key,
+    );
+    register_block(&universe.module_controller, block, storage.clone());
+    let block = create_block(
+        Slot::new(2, 0),
+        vec![missing_1.id, genesis[1]],
+        &staking_key,
+    );
+    register_block(&universe.module_controller, block, storage.clone());
+    let block = create_block(
+        Slot::new(3, 0),
+        vec![missing_1.id, genesis[1]],
+        &staking_key,
+    );
+    register_block(&universe.module_controller, block, storage.clone());
+
+    // only the most waited on block is wished
+    std::thread::sleep(Duration::from_millis(500));
+    assert_eq!(
+        *wishlist.lock().unwrap(),
+        PreHashSet::from_iter([missing_1.id])
+    );
+}
@@ -1,5 +1,6 @@
 use std::{time::Duration, vec};
 
+use super::universe::ConsensusForeignControllers;
 use crate::start_consensus_worker;
 use massa_channel::MassaChannel;
 use massa_consensus_exports::{
@@ -10,18 +11,22 @@ use massa_hash::Hash;
 use massa_metrics::MassaMetrics;
 use massa_models::config::CHAINID;
 use massa_models::{
+    address::Address,
+    amount::Amount,
     block::{Block, BlockSerializer, SecureShareBlock},
     block_header::{BlockHeader, BlockHeaderSerializer},
     block_id::BlockId,
-    config::THREAD_COUNT,
+    config::{ENDORSEMENT_COUNT, THREAD_COUNT},
+    operation::{Operation, OperationId, OperationSerializer, OperationType, SecureShareOperation},
     secure_share::SecureShareContent,
     slot::Slot,
 };
 use massa_pool_exports::MockPoolController;
-use massa_pos_exports::MockSelectorController;
+use massa_pos_exports::{MockSelectorController, Selection};
 use massa_protocol_exports::MockProtocolController;
 use massa_signature::KeyPair;
 use massa_storage::Storage;
+use massa_time::MassaTime;
 
 pub fn consensus_test<F>(
     cfg: ConsensusConfig,
@@ -83,6 +88,65 @@ pub fn consensus_test<F>(
     consensus_manager.stop();
 }
 
+/// Config of a two-thread consensus starting now with 100ms periods, whose genesis blocks are created by `staking_key`
+pub fn fast_consensus_config(staking_key: &KeyPair) -> ConsensusConfig {
+    ConsensusConfig {
+        t0: MassaTime::from_millis(100),
+        thread_count: 2,
+        genesis_timestamp: MassaTime::now(),
+        genesis_key: staking_key.clone(),
+        ..ConsensusConfig::default()
+    }
+}
+
+/// Mocks of the controllers used by consensus, in which `staking_key` is drawn for every block and endorsement
+/// and the notifications to execution and pool are ignored
+pub fn staker_foreign_controllers(staking_key: &KeyPair) -> ConsensusForeignControllers {
+    let staking_address = Address::from_public_key(&staking_key.get_public_key());
+    let mut foreign_controllers = ConsensusForeignControllers::new_with_mocks();
+    foreign_controllers
+        .execution_controller
+        .expect_update_blockclique_status()
+        .returning(|_, _, _| {});
+    foreign_controllers
+        .pool_controller
+        .expect_notify_final_cs_periods()
+        .returning(|_| {});
+    foreign_controllers
+        .pool_controller
+        .expect_add_denunciation_precursor()
+        .returning(|_| {});
+    foreign_controllers
+        .selector_controller
+        .expect_get_producer()
+        .returning(move |_| Ok(staking_address));
+    foreign_controllers
+        .selector_controller
+        .expect_get_selection()
+        .returning(move |_| {
+            Ok(Selection {
+                producer: staking_address,
+                endorsements: vec![staking_address; ENDORSEMENT_COUNT as usize],
+            })
+        });
+    foreign_controllers
+}
+
+/// Signed purchase of one roll by `creator`, with no fee
+pub fn roll_buy_operation(creator: &KeyPair, expire_period: u64) -> SecureShareOperation {
+    Operation::new_verifiable(
+        Operation {
+            fee: Amount::zero(),
+            expire_period,
+            op: OperationType::RollBuy { roll_count: 1 },
+        },
+        OperationSerializer::new(),
+        creator,
+        *CHAINID,
+    )
+    .unwrap()
+}
+
 // returns hash and resulting discarded blocks
 pub fn create_block(slot: Slot, best_parents: Vec<BlockId>, creator: &KeyPair) -> SecureShareBlock {
     create_block_with_merkle_root(
@@ -99,6 +163,39 @@ pub fn create_block_with_merkle_root(
     slot: Slot,
     best_parents: Vec<BlockId>,
     creator: &KeyPair,
+) -> SecureShareBlock {
+    new_block(
+        operation_merkle_root,
+        slot,
+        best_parents,
+        creator,
+        Default::default(),
+    )
+}
+
+/// Creates a block including the given operations.
+/// Consensus does not check the operation merkle root, so it is left to a default value.
+pub fn create_block_with_operations(
+    slot: Slot,
+    best_parents: Vec<BlockId>,
+    creator: &KeyPair,
+    operations: Vec<OperationId>,
+) -> SecureShareBlock {
+    new_block(
+        Hash::compute_from("default_val".as_bytes()),
+        slot,
+        best_parents,
+        creator,
+        operations,
+    )
+}
+
+fn new_block(
+    operation_merkle_root: Hash,
+    slot: Slot,
+    best_parents: Vec<BlockId>,
+    creator: &KeyPair,
+    operations: Vec<OperationId>,
 ) -> SecureShareBlock {
     let header = BlockHeader::new_verifiable(
         BlockHeader {
@@ -117,10 +214,7 @@ pub fn create_block_with_merkle_root(
     .unwrap();
 
     Block::new_verifiable(
-        Block {
-            header,
-            operations: Default::default(),
-        },
+        Block { header, operations },
         BlockSerializer::new(),
         creator,
         *CHAINID,
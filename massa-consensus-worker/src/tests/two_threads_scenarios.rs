@@ -2,8 +2,10 @@ use std::time::Duration;
 
 use massa_consensus_exports::ConsensusConfig;
 use massa_execution_exports::MockExecutionController;
+use massa_hash::Hash;
 use massa_models::{
-    address::Address, block::BlockGraphStatus, config::ENDORSEMENT_COUNT, slot::Slot,
+    address::Address, block::BlockGraphStatus, config::ENDORSEMENT_COUNT, operation::OperationId,
+    slot::Slot,
 };
 use massa_pool_exports::MockPoolController;
 use massa_pos_exports::{MockSelectorController, Selection};
@@ -11,7 +13,7 @@ use massa_signature::KeyPair;
 use massa_storage::Storage;
 use massa_time::MassaTime;
 
-use super::tools::{consensus_test, create_block, register_block};
+use super::tools::{consensus_test, create_block, create_block_with_operations, register_block};
 
 // Always use latest blocks as parents.
 // Blocks should be finalized as expected.
@@ -1505,3 +1507,79 @@ fn test_tts_incompatible_parents() {
         },
     );
 }
+
+// The lineage of an operation follows the parents in the thread of its block down to genesis.
+#[test]
+fn test_tts_operation_lineage() {
+    let t0_millis: u64 = 200;
+    let staking_key: KeyPair = KeyPair::generate(0).unwrap();
+    let cfg = ConsensusConfig {
+        t0: MassaTime::from_millis(t0_millis),
+        thread_count: 2,
+        genesis_timestamp: MassaTime::now(),
+        force_keep_final_periods_without_ops: 128,
+        force_keep_final_periods: 10,
+        delta_f0: 32,
+        ..ConsensusConfig::default()
+    };
+    let storage = Storage::create_root();
+    let staking_address = Address::from_public_key(&staking_key.get_public_key());
+    let mut execution_controller = Box::new(MockExecutionController::new());
+    execution_controller
+        .expect_update_blockclique_status()
+        .returning(|_, _, _| {});
+    let mut pool_controller = Box::new(MockPoolController::new());
+    pool_controller
+        .expect_notify_final_cs_periods()
+        .returning(|_| {});
+    pool_controller
+        .expect_add_denunciation_precursor()
+        .returning(|_| {});
+    let mut selector_controller = Box::new(MockSelectorController::new());
+    selector_controller
+        .expect_get_producer()
+        .returning(move |_| Ok(staking_address));
+    selector_controller
+        .expect_get_selection()
+        .returning(move |_| {
+            Ok(Selection {
+                producer: staking_address,
+                endorsements: vec![staking_address; ENDORSEMENT_COUNT as usize],
+            })
+        });
+    consensus_test(
+        cfg.clone(),
+        execution_controller,
+        pool_controller,
+        selector_controller,
+        move |consensus_controller| {
+            let genesis = consensus_controller
+                .get_block_graph_status(None, None)
+                .expect("could not get block graph status")
+                .genesis_blocks;
+            let op_id = OperationId::new(Hash::compute_from(b"lineage"));
+            assert_eq!(consensus_controller.get_operation_lineage(op_id), None);
+
+            let block_1_0 =
+                create_block(Slot::new(1, 0), vec![genesis[0], genesis[1]], &staking_key);
+            register_block(&consensus_controller, block_1_0.clone(), storage.clone());
+            let block_1_1 =
+                create_block(Slot::new(1, 1), vec![genesis[0], genesis[1]], &staking_key);
+            register_block(&consensus_controller, block_1_1.clone(), storage.clone());
+            // the operation is included in thread 0
+            let block_2_0 = create_block_with_operations(
+                Slot::new(2, 0),
+                vec![block_1_0.id, block_1_1.id],
+                &staking_key,
+                vec![op_id],
+            );
+            register_block(&consensus_controller, block_2_0.clone(), storage.clone());
+            std::thread::sleep(Duration::from_millis(4 * t0_millis));
+
+            assert_eq!(
+                consensus_controller.get_operation_lineage(op_id),
+                Some(vec![block_2_0.id, block_1_0.id, genesis[0]])
+            );
+        },
+    );
+}
@@ -3,6 +3,7 @@ use massa_consensus_exports::{
     block_status::{BlockStatus, StorageOrBlock},
     bootstrapable_graph::BootstrapableGraph,
     error::ConsensusError,
+    export_active_block::ExportActiveBlock,
     ConsensusConfig,
 };
 use massa_execution_exports::ExecutionBlockMetadata;
@@ -14,7 +15,7 @@ use massa_models::{
     block_header::{BlockHeader, BlockHeaderSerializer},
     block_id::BlockId,
     prehash::PreHashMap,
-    secure_share::SecureShareContent,
+    secure_share::{Id, SecureShareContent},
     slot::Slot,
     timeslots::{get_block_slot_timestamp, get_latest_block_slot_at_timestamp},
 };
@@ -70,6 +71,72 @@ pub fn create_genesis_block(
     )?)
 }
 
+/// Computes a fingerprint of the network from its genesis block ids.
+/// The ids are sorted first so that the fingerprint does not depend on their order.
+pub fn compute_genesis_fingerprint(genesis_block_ids: &[BlockId]) -> Hash {
+    let mut sorted_ids = genesis_block_ids.to_vec();
+    sorted_ids.sort_unstable();
+    let bytes: Vec<u8> = sorted_ids
+        .iter()
+        .flat_map(|id| id.get_hash().to_bytes().to_vec())
+        .collect();
+    Hash::compute_from(&bytes)
+}
+
+/// Checks that bootstrapped final blocks were built on top of the locally created genesis blocks.
+/// A mismatch means that the bootstrap server runs on another network (different genesis key or thread layout).
+///
+/// # Arguments
+/// * `cfg`: consensus configuration
+/// * `genesis_block_ids`: ids of the locally created genesis blocks, one per thread
+/// * `final_blocks`: final blocks received from the bootstrap
+///
+/// # Returns
+/// An error if a genesis block, or a parent at the genesis period, differs from the local genesis of its thread
+pub(crate) fn check_bootstrap_genesis(
+    cfg: &ConsensusConfig,
+    genesis_block_ids: &[BlockId],
+    final_blocks: &[ExportActiveBlock],
+) -> Result<(), ConsensusError> {
+    for export_block in final_blocks {
+        let block_id = export_block.block.id;
+        let slot = export_block.block.content.header.content.slot;
+        if slot.thread >= cfg.thread_count {
+            return Err(ConsensusError::InvalidBootstrapGraph(format!(
+                "block {} is in thread {} but there are only {} threads",
+                block_id, slot.thread, cfg.thread_count
+            )));
+        }
+        if export_block.parents.is_empty() {
+            // genesis block
+            if block_id != genesis_block_ids[slot.thread as usize] {
+                return Err(ConsensusError::InvalidBootstrapGraph(format!(
+                    "genesis block {} of thread {} does not match the local genesis block {}",
+                    block_id, slot.thread, genesis_block_ids[slot.thread as usize]
+                )));
+            }
+            continue;
+        }
+        if export_block.parents.len() != cfg.thread_count as usize {
+            return Err(ConsensusError::InvalidBootstrapGraph(format!(
+                "block {} has {} parents but there are {} threads",
+                block_id,
+                export_block.parents.len(),
+                cfg.thread_count
+            )));
+        }
+        for (thread, (parent_id, parent_period)) in export_block.parents.iter().enumerate() {
+            if *parent_period == cfg.last_start_period && *parent_id != genesis_block_ids[thread] {
+                return Err(ConsensusError::InvalidBootstrapGraph(format!(
+                    "block {} has parent {} at the genesis period of thread {} instead of the local genesis block {}",
+                    block_id, parent_id, thread, genesis_block_ids[thread]
+                )));
+            }
+        }
+    }
+    Ok(())
+}
+
 impl ConsensusWorker {
     /// Creates a new consensus worker.
     ///
@@ -203,6 +270,9 @@ impl ConsensusWorker {
         // If the node starts after the genesis timestamp then it has to initialize its graph
         // with already produced blocks received from the bootstrap.
         if let Some(BootstrapableGraph { final_blocks }) = init_graph {
+            // make sure that the bootstrap server is on the same network
+            check_bootstrap_genesis(&config, &genesis_block_ids, &final_blocks)?;
+
             // load final blocks
             let final_blocks: Vec<(ActiveBlock, StorageOrBlock)> = final_blocks
                 .into_iter()
@@ -328,3 +398,81 @@ impl ConsensusWorker {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use massa_signature::KeyPair;
+
+    fn genesis_export_blocks(cfg: &ConsensusConfig) -> Vec<ExportActiveBlock> {
+        (0..cfg.thread_count)
+            .map(|thread| ExportActiveBlock {
+                block: create_genesis_block(cfg, thread).unwrap(),
+                parents: Vec::new(),
+                is_final: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_check_bootstrap_genesis() {
+        let cfg = ConsensusConfig::default();
+        let genesis_block_ids: Vec<BlockId> = (0..cfg.thread_count)
+            .map(|thread| create_genesis_block(&cfg, thread).unwrap().id)
+            .collect();
+
+        // same network
+        check_bootstrap_genesis(&cfg, &genesis_block_ids, &genesis_export_blocks(&cfg)).unwrap();
+
+        // genesis blocks created with another genesis key
+        let other_cfg = ConsensusConfig {
+            genesis_key: KeyPair::generate(0).unwrap(),
+            ..cfg.clone()
+        };
+        let mismatched_blocks = genesis_export_blocks(&other_cfg);
+        assert!(matches!(
+            check_bootstrap_genesis(&cfg, &genesis_block_ids, &mismatched_blocks),
+            Err(ConsensusError::InvalidBootstrapGraph(_))
+        ));
+
+        // block pointing to foreign genesis blocks
+        let child = ExportActiveBlock {
+            block: create_genesis_block(&other_cfg, 0).unwrap(),
+            parents: mismatched_blocks
+                .iter()
+                .map(|b| (b.block.id, cfg.last_start_period))
+                .collect(),
+            is_final: true,
+        };
+        assert!(matches!(
+            check_bootstrap_genesis(&cfg, &genesis_block_ids, &[child]),
+            Err(ConsensusError::InvalidBootstrapGraph(_))
+        ));
+    }
+
+    #[test]
+    fn test_genesis_fingerprint() {
+        let cfg = ConsensusConfig::default();
+        let genesis_block_ids: Vec<BlockId> = (0..cfg.thread_count)
+            .map(|thread| create_genesis_block(&cfg, thread).unwrap().id)
+            .collect();
+        let fingerprint = compute_genesis_fingerprint(&genesis_block_ids);
+
+        // genesis blocks are deterministic: re-creating them gives the same fingerprint
+        let recreated_ids: Vec<BlockId> = (0..cfg.thread_count)
+            .rev()
+            .map(|thread| create_genesis_block(&cfg, thread).unwrap().id)
+            .collect();
+        assert_eq!(compute_genesis_fingerprint(&recreated_ids), fingerprint);
+
+        // another network has another fingerprint
+        let other_cfg = ConsensusConfig {
+            genesis_key: KeyPair::generate(0).unwrap(),
+            ..cfg.clone()
+        };
+        let other_ids: Vec<BlockId> = (0..other_cfg.thread_count)
+            .map(|thread| create_genesis_block(&other_cfg, thread).unwrap().id)
+            .collect();
+        assert_ne!(compute_genesis_fingerprint(&other_ids), fingerprint);
+    }
+}
@@ -19,7 +19,7 @@ use std::time::Instant;
 use crate::commands::ConsensusCommand;
 use crate::controller::ConsensusControllerImpl;
 use crate::manager::ConsensusManagerImpl;
-use crate::state::{blocks_state::BlocksState, ConsensusState};
+use crate::state::{blocks_state::BlocksState, stats::DiscardHistory, ConsensusState};
 
 /// The consensus worker structure that contains all information and tools for the consensus worker thread.
 pub struct ConsensusWorker {
@@ -37,7 +37,7 @@ pub struct ConsensusWorker {
     next_instant: Instant,
 }
 
-mod init;
+pub(crate) mod init;
 mod main_loop;
 
 /// Create a new consensus worker thread.
@@ -86,6 +86,12 @@ pub fn start_consensus_worker(
         gi_head: Default::default(),
         final_block_stats: Default::default(),
         stale_block_stats: Default::default(),
+        discard_history: DiscardHistory::new(config.discard_stats_window_periods),
+        finality_progress: Default::default(),
+        pruned_operation_count: 0,
+        ready: false,
+        active_block_times: Default::default(),
+        finality_latency_stats: Default::default(),
         protocol_blocks: Default::default(),
         wishlist: Default::default(),
         launch_time: MassaTime::now(),
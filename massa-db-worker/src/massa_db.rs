@@ -807,9 +807,14 @@ impl MassaDBController for RawMassaDB<Slot, SlotSerializer, SlotDeserializer> {
 
     /// Flushes the underlying db.
     fn flush(&self) -> Result<(), MassaDBError> {
-        self.db
-            .flush()
-            .map_err(|e| MassaDBError::RocksDBError(format!("{:?}", e)))
+        // `DB::flush` only covers the default column family, which holds no data
+        for cf in [STATE_CF, METADATA_CF, VERSIONING_CF] {
+            let handle = self.db.cf_handle(cf).expect(CF_ERROR);
+            self.db
+                .flush_cf(handle)
+                .map_err(|e| MassaDBError::RocksDBError(format!("{:?}", e)))?;
+        }
+        Ok(())
     }
 
     /// Write a stream_batch of database entries received from a bootstrap server
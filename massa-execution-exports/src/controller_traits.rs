@@ -7,7 +7,7 @@ use crate::types::{
 };
 
 use crate::ExecutionError;
-use crate::{ExecutionAddressInfo, ReadOnlyExecutionOutput};
+use crate::{ExecutionAddressBalances, ExecutionAddressInfo, ReadOnlyExecutionOutput};
 use massa_models::address::Address;
 use massa_models::amount::Amount;
 use massa_models::block_id::BlockId;
@@ -112,6 +112,10 @@ pub trait ExecutionController: Send + Sync {
         deferred_credits_max_slot: std::ops::Bound<Slot>,
     ) -> Vec<ExecutionAddressInfo>;
 
+    /// Gets the balances and roll counts of a batch of addresses, in the order of `addresses`.
+    /// Unlike `get_addresses_infos`, the datastore keys and deferred credits are not loaded.
+    fn get_addresses_balances(&self, addresses: &[Address]) -> Vec<ExecutionAddressBalances>;
+
     /// Get execution statistics
     fn get_stats(&self) -> ExecutionStats;
 
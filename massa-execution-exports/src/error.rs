@@ -67,6 +67,12 @@ pub enum ExecutionError {
 
     /// Factory error: {0}
     FactoryError(#[from] FactoryError),
+
+    /// Final ledger inconsistency: {0}
+    LedgerInconsistency(String),
+
+    /// Datastore entry too large: {0}
+    DatastoreEntryTooLarge(String),
 }
 
 /// Execution query errors
@@ -183,7 +183,7 @@ pub struct ExecutionQueryStakerInfo {
 }
 
 /// Execution info about an address
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct ExecutionAddressInfo {
     /// candidate balance of the address
     pub candidate_balance: Amount,
@@ -207,6 +207,21 @@ pub struct ExecutionAddressInfo {
     pub cycle_infos: Vec<ExecutionAddressCycleInfo>,
 }
 
+/// Execution balances and roll counts of an address, a subset of `ExecutionAddressInfo` that is cheaper to compute
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionAddressBalances {
+    /// candidate balance of the address
+    pub candidate_balance: Amount,
+    /// final balance of the address
+    pub final_balance: Amount,
+    /// candidate number of rolls the address has
+    pub candidate_roll_count: u64,
+    /// final number of rolls the address has
+    pub final_roll_count: u64,
+    /// number of rolls of the address used by the selector in the current cycle, if known
+    pub active_rolls: Option<u64>,
+}
+
 /// structure describing the output of the execution of a slot
 #[derive(Debug, Clone)]
 pub enum SlotExecutionOutput {
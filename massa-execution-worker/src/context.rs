@@ -32,6 +32,7 @@ use massa_models::{
     address::Address,
     amount::Amount,
     block_id::BlockId,
+    config::MAX_DATASTORE_ENTRY_LENGTH,
     operation::OperationId,
     output_event::{EventExecutionContext, SCOutputEvent},
     slot::Slot,
@@ -208,6 +209,7 @@ impl ExecutionContext {
                 config.max_datastore_key_length,
                 config.max_bytecode_size,
                 config.max_datastore_value_size,
+                MAX_DATASTORE_ENTRY_LENGTH,
                 config.storage_costs_constants,
             ),
             speculative_async_pool: SpeculativeAsyncPool::new(
@@ -7,10 +7,11 @@ use crate::execution::ExecutionState;
 use crate::request_queue::{RequestQueue, RequestWithResponseSender};
 use massa_channel::MassaChannel;
 use massa_execution_exports::{
-    ExecutionAddressInfo, ExecutionBlockMetadata, ExecutionConfig, ExecutionController,
-    ExecutionError, ExecutionManager, ExecutionQueryError, ExecutionQueryExecutionStatus,
-    ExecutionQueryRequest, ExecutionQueryRequestItem, ExecutionQueryResponse,
-    ExecutionQueryResponseItem, ReadOnlyExecutionOutput, ReadOnlyExecutionRequest,
+    ExecutionAddressBalances, ExecutionAddressInfo, ExecutionBlockMetadata, ExecutionConfig,
+    ExecutionController, ExecutionError, ExecutionManager, ExecutionQueryError,
+    ExecutionQueryExecutionStatus, ExecutionQueryRequest, ExecutionQueryRequestItem,
+    ExecutionQueryResponse, ExecutionQueryResponseItem, ReadOnlyExecutionOutput,
+    ReadOnlyExecutionRequest,
 };
 use massa_models::denunciation::DenunciationIndex;
 use massa_models::execution::EventFilter;
@@ -468,6 +469,30 @@ impl ExecutionController for ExecutionControllerImpl {
         res
     }
 
+    /// Gets the balances and roll counts of a batch of addresses
+    fn get_addresses_balances(&self, addresses: &[Address]) -> Vec<ExecutionAddressBalances> {
+        let exec_state = self.execution_state.read();
+        addresses
+            .iter()
+            .map(|addr| {
+                let (final_balance, candidate_balance) =
+                    exec_state.get_final_and_candidate_balance(addr);
+                let (final_roll_count, candidate_roll_count) =
+                    exec_state.get_final_and_candidate_rolls(addr);
+                ExecutionAddressBalances {
+                    candidate_balance: candidate_balance.unwrap_or_default(),
+                    final_balance: final_balance.unwrap_or_default(),
+                    candidate_roll_count,
+                    final_roll_count,
+                    active_rolls: exec_state
+                        .get_address_cycle_infos(addr)
+                        .last()
+                        .and_then(|cycle_info| cycle_info.active_rolls),
+                }
+            })
+            .collect()
+    }
+
     /// Get execution statistics
     fn get_stats(&self) -> ExecutionStats {
         self.execution_state.read().get_stats()
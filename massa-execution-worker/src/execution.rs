@@ -1694,7 +1694,21 @@ impl ExecutionState {
             {
                 // speculative execution front result matches what we want to compute
                 // apply the cached output and return
+                #[cfg(debug_assertions)]
+                let ledger_changes = exec_out.state_changes.ledger_changes.clone();
                 self.apply_final_execution_output(exec_out);
+                #[cfg(debug_assertions)]
+                {
+                    // in debug builds, check that the final ledger matches the speculative output
+                    let sample_addrs = ledger_changes.0.keys().copied().collect();
+                    if let Err(err) = check_final_ledger_consistency(
+                        self.final_state.read().get_ledger().as_ref(),
+                        &ledger_changes,
+                        &sample_addrs,
+                    ) {
+                        warn!("execute_final_slot: {}", err);
+                    }
+                }
                 return;
             } else {
                 // speculative cache mismatch
@@ -2272,3 +2286,31 @@ impl ExecutionState {
         );
     }
 }
+
+/// Checks that the final ledger holds the balances set by ledger changes that were just finalized.
+///
+/// # Arguments
+/// * `ledger`: the final ledger, after the changes were applied
+/// * `ledger_changes`: the finalized changes
+/// * `sample_addrs`: the addresses to check
+///
+/// # Returns
+/// An error naming the first divergent address, if any
+#[cfg(debug_assertions)]
+pub(crate) fn check_final_ledger_consistency(
+    ledger: &dyn massa_ledger_exports::LedgerController,
+    ledger_changes: &massa_ledger_exports::LedgerChanges,
+    sample_addrs: &PreHashSet<Address>,
+) -> Result<(), ExecutionError> {
+    for addr in sample_addrs {
+        let final_balance = ledger.get_balance(addr);
+        let expected_balance = ledger_changes.get_balance_or_else(addr, || final_balance);
+        if final_balance != expected_balance {
+            return Err(ExecutionError::LedgerInconsistency(format!(
+                "final balance of {} is {:?} but the finalized changes set it to {:?}",
+                addr, final_balance, expected_balance
+            )));
+        }
+    }
+    Ok(())
+}
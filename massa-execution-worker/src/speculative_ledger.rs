@@ -55,6 +55,9 @@ pub(crate) struct SpeculativeLedger {
     /// Max datastore value size
     max_datastore_value_size: u64,
 
+    /// Max total size of a datastore entry (key and value)
+    max_datastore_entry_bytes: u64,
+
     /// Max bytecode size
     max_bytecode_size: u64,
 
@@ -74,6 +77,7 @@ impl SpeculativeLedger {
         max_datastore_key_length: u8,
         max_bytecode_size: u64,
         max_datastore_value_size: u64,
+        max_datastore_entry_bytes: u64,
         storage_costs_constants: StorageCostsConstants,
     ) -> Self {
         SpeculativeLedger {
@@ -82,6 +86,7 @@ impl SpeculativeLedger {
             active_history,
             max_datastore_key_length,
             max_datastore_value_size,
+            max_datastore_entry_bytes,
             max_bytecode_size,
             storage_costs_constants,
         }
@@ -620,6 +625,15 @@ impl SpeculativeLedger {
             )));
         }
 
+        // check the size of the whole entry, to bound the growth of the ledger
+        let entry_size = key_length.saturating_add(value.len());
+        if entry_size as u64 > self.max_datastore_entry_bytes {
+            return Err(ExecutionError::DatastoreEntryTooLarge(format!(
+                "entry of key {:?} for address {} would be {} bytes long, but it must be at most {}",
+                key, addr, entry_size, self.max_datastore_entry_bytes
+            )));
+        }
+
         // charge the storage costs of the entry change
         {
             let prev_value = self.get_data_entry(addr, &key);
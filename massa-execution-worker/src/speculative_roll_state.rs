@@ -125,15 +125,9 @@ impl SpeculativeRollState {
         }
 
         // compute deferred credit slot
-        let cur_cycle = slot.get_cycle(periods_per_cycle);
-        let target_slot = Slot::new_last_of_cycle(
-            cur_cycle
-                .checked_add(3)
-                .expect("unexpected cycle overflow in try_sell_rolls"),
-            periods_per_cycle,
-            thread_count,
-        )
-        .expect("unexpected slot overflow in try_sell_rolls");
+        let target_slot = slot
+            .get_roll_sell_credit_slot(periods_per_cycle, thread_count)
+            .expect("unexpected slot overflow in try_sell_rolls");
 
         // Note 1: Deferred credits are stored as absolute value
         let new_deferred_credits = self
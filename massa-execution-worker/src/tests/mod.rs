@@ -9,4 +9,10 @@ mod universe;
 #[cfg(test)]
 mod tests_active_history;
 
+#[cfg(all(test, debug_assertions))]
+mod tests_ledger_consistency;
+
+#[cfg(test)]
+mod tests_datastore_limits;
+
 mod interface;
@@ -0,0 +1,52 @@
+use crate::active_history::ActiveHistory;
+use crate::speculative_ledger::SpeculativeLedger;
+use massa_execution_exports::{ExecutionConfig, ExecutionError};
+use massa_final_state::MockFinalStateController;
+use massa_hash::Hash;
+use massa_models::address::{Address, UserAddress, UserAddressV0};
+use massa_models::amount::Amount;
+use massa_models::config::MAX_DATASTORE_ENTRY_LENGTH;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+#[test]
+fn test_oversized_datastore_entry_rejected() {
+    let config = ExecutionConfig::default();
+    let mut ledger = SpeculativeLedger::new(
+        Arc::new(RwLock::new(MockFinalStateController::new())),
+        Arc::new(RwLock::new(ActiveHistory::default())),
+        config.max_datastore_key_length,
+        config.max_bytecode_size,
+        config.max_datastore_value_size,
+        MAX_DATASTORE_ENTRY_LENGTH,
+        config.storage_costs_constants,
+    );
+    let addr = Address::User(UserAddress::UserAddressV0(UserAddressV0(
+        Hash::compute_from("AU1".as_bytes()),
+    )));
+    // the address only exists in the speculative changes, so that the final state is never read
+    ledger
+        .added_changes
+        .set_balance(addr, Amount::from_raw(1_000_000_000));
+
+    // the entry limit is tighter than the key and value limits together
+    assert!(
+        MAX_DATASTORE_ENTRY_LENGTH
+            < config.max_datastore_key_length as u64 + config.max_datastore_value_size
+    );
+
+    // a value that respects the value limit is rejected once the key is counted
+    let value = vec![1u8; (MAX_DATASTORE_ENTRY_LENGTH - 3) as usize];
+    assert!(value.len() as u64 <= config.max_datastore_value_size);
+    match ledger.set_data_entry(&addr, &addr, b"key1".to_vec(), value) {
+        Err(ExecutionError::DatastoreEntryTooLarge(msg)) => {
+            assert!(msg.contains(&format!("{} bytes", MAX_DATASTORE_ENTRY_LENGTH + 1)))
+        }
+        res => panic!("unexpected result: {:?}", res),
+    }
+    // nothing was written
+    assert!(ledger
+        .get_snapshot()
+        .get_data_entry_or_else(&addr, b"key1", || None)
+        .is_none());
+}
@@ -0,0 +1,42 @@
+use crate::execution::check_final_ledger_consistency;
+use massa_execution_exports::ExecutionError;
+use massa_hash::Hash;
+use massa_ledger_exports::{LedgerChanges, MockLedgerController};
+use massa_models::address::{Address, UserAddress, UserAddressV0};
+use massa_models::amount::Amount;
+use massa_models::prehash::PreHashSet;
+
+#[test]
+fn test_final_ledger_consistency() {
+    let addr1 = Address::User(UserAddress::UserAddressV0(UserAddressV0(
+        Hash::compute_from("AU1".as_bytes()),
+    )));
+    let addr2 = Address::User(UserAddress::UserAddressV0(UserAddressV0(
+        Hash::compute_from("AU2".as_bytes()),
+    )));
+
+    let mut ledger_changes = LedgerChanges::default();
+    ledger_changes.set_balance(addr1, Amount::from_raw(1000));
+    let sample_addrs: PreHashSet<Address> = [addr1, addr2].into_iter().collect();
+
+    // the final ledger holds the finalized balance, addr2 is untouched by the changes
+    let mut ledger = MockLedgerController::new();
+    ledger.expect_get_balance().returning(move |addr| {
+        if *addr == addr1 {
+            Some(Amount::from_raw(1000))
+        } else {
+            Some(Amount::from_raw(5))
+        }
+    });
+    check_final_ledger_consistency(&ledger, &ledger_changes, &sample_addrs).unwrap();
+
+    // a corrupted final balance is detected
+    let mut corrupted_ledger = MockLedgerController::new();
+    corrupted_ledger
+        .expect_get_balance()
+        .returning(|_| Some(Amount::from_raw(999)));
+    match check_final_ledger_consistency(&corrupted_ledger, &ledger_changes, &sample_addrs) {
+        Err(ExecutionError::LedgerInconsistency(msg)) => assert!(msg.contains(&addr1.to_string())),
+        res => panic!("unexpected result: {:?}", res),
+    }
+}
@@ -45,4 +45,8 @@ pub struct FinalStateConfig {
     pub genesis_timestamp: MassaTime,
     /// Interval of periods between creation of each ledger backup
     pub ledger_backup_periods_interval: u64,
+    /// Interval of final slots between two logs of the ledger state hash, 0 to disable
+    pub ledger_hash_log_slots_interval: u64,
+    /// flush the state db to disk after each finalized slot, so that the final state survives a machine crash
+    pub flush_on_finality: bool,
 }
@@ -492,11 +492,33 @@ impl FinalState {
             .write()
             .write_batch(db_batch, db_versioning_batch, Some(slot));
 
+        // persist the finalized state right away instead of relying on the write-ahead log
+        if self.config.flush_on_finality {
+            self.db.read().flush().map_err(|err| {
+                anyhow!("could not flush the final state at slot {}: {}", slot, err)
+            })?;
+        }
+
         let final_state_hash = self.db.read().get_xof_db_hash();
 
         // compute the final state hash
         info!("final_state hash at slot {}: {}", slot, final_state_hash);
 
+        // log the ledger hash if needed, at the same slots on every node
+        if self.config.ledger_hash_log_slots_interval != 0
+            && (slot.period * self.config.thread_count as u64 + slot.thread as u64)
+                % self.config.ledger_hash_log_slots_interval
+                == 0
+        {
+            match self.ledger.state_hash() {
+                Ok(ledger_hash) => info!("ledger hash at slot {}: {}", slot, ledger_hash),
+                Err(err) => warn!(
+                    "could not compute the ledger hash at slot {}: {}",
+                    slot, err
+                ),
+            }
+        }
+
         // Backup DB if needed
         #[cfg(feature = "bootstrap_server")]
         if slot.period % self.config.ledger_backup_periods_interval == 0
@@ -983,6 +1005,7 @@ mod test {
             initial_deferred_credits_path: Some(
                 massa_node_base.join("base_config/deferred_credits.json"),
             ),
+            min_active_rolls_for_counting: 1,
         };
         let executed_ops_config = ExecutedOpsConfig {
             thread_count: THREAD_COUNT,
@@ -1011,6 +1034,8 @@ mod test {
             max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
             t0: T0,
             ledger_backup_periods_interval: 10,
+            ledger_hash_log_slots_interval: 0,
+            flush_on_finality: false,
             genesis_timestamp,
         };
 
@@ -1023,8 +1048,16 @@ mod test {
         let temp_dir_db = tempdir().expect("Unable to create a temp folder");
         // println!("Using temp dir: {:?}", temp_dir.path());
 
+        get_final_state_in(temp_dir_db.path(), final_state_config, ledger_config)
+    }
+
+    fn get_final_state_in(
+        db_path: &std::path::Path,
+        final_state_config: FinalStateConfig,
+        ledger_config: LedgerConfig,
+    ) -> FinalState {
         let db_config = MassaDBConfig {
-            path: temp_dir_db.path().to_path_buf(),
+            path: db_path.to_path_buf(),
             max_history_length: 100,
             max_final_state_elements_size: 100,
             max_versioning_elements_size: 100,
@@ -1150,6 +1183,39 @@ mod test {
         assert_eq!(fstate.get_slot(), ok_next_slot);
     }
 
+    #[test]
+    fn test_final_state_flush_on_finality() {
+        let count_sst_files = |path: &std::path::Path| {
+            std::fs::read_dir(path)
+                .unwrap()
+                .filter(|entry| entry.as_ref().unwrap().path().extension() == Some("sst".as_ref()))
+                .count()
+        };
+
+        for flush_on_finality in [false, true] {
+            let (mut final_state_config, ledger_config) = get_final_state_config();
+            final_state_config.flush_on_finality = flush_on_finality;
+            let temp_dir_db = tempdir().expect("Unable to create a temp folder");
+            let mut fstate =
+                get_final_state_in(temp_dir_db.path(), final_state_config, ledger_config);
+            let mut batch = DBBatch::new();
+            fstate.pos_state.create_initial_cycle(&mut batch);
+
+            let sst_files_before = count_sst_files(temp_dir_db.path());
+            fstate
+                ._finalize(Slot::new(0, 1), get_state_changes())
+                .unwrap();
+            let sst_files_after = count_sst_files(temp_dir_db.path());
+
+            // flushing writes the finalized changes from memory to a new sst file
+            if flush_on_finality {
+                assert!(sst_files_after > sst_files_before);
+            } else {
+                assert_eq!(sst_files_after, sst_files_before);
+            }
+        }
+    }
+
     #[test]
     fn test_final_state_from_snapshot_1() {
         // 0- Create a final state
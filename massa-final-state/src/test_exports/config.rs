@@ -80,6 +80,7 @@ impl Default for FinalStateConfig {
                 max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
                 max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
                 initial_deferred_credits_path: None,
+                min_active_rolls_for_counting: 1,
             },
             final_history_length: 10,
             thread_count: 2,
@@ -92,6 +93,8 @@ impl Default for FinalStateConfig {
             t0: T0,
             genesis_timestamp: *GENESIS_TIMESTAMP,
             ledger_backup_periods_interval: 100,
+            ledger_hash_log_slots_interval: 0,
+            flush_on_finality: false,
         }
     }
 }
@@ -202,6 +202,8 @@ pub fn get_sample_state(
         t0: T0,
         genesis_timestamp: *GENESIS_TIMESTAMP,
         ledger_backup_periods_interval: 10,
+        ledger_hash_log_slots_interval: 0,
+        flush_on_finality: false,
     };
 
     let mut final_state = if last_start_period > 0 {
@@ -72,6 +72,7 @@ fn create_final_state(temp_dir: &TempDir, reset_final_state: bool) -> Arc<RwLock
             max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
             max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
             initial_deferred_credits_path: None,
+            min_active_rolls_for_counting: 1,
         },
         executed_ops_config: ExecutedOpsConfig {
             thread_count,
@@ -94,6 +95,8 @@ fn create_final_state(temp_dir: &TempDir, reset_final_state: bool) -> Arc<RwLock
         t0: T0,
         genesis_timestamp: *GENESIS_TIMESTAMP,
         ledger_backup_periods_interval: 10,
+        ledger_hash_log_slots_interval: 0,
+        flush_on_finality: false,
     };
 
     // setup selector local config
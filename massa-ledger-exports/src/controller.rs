@@ -1,7 +1,8 @@
+use massa_hash::Hash;
 use massa_models::{address::Address, amount::Amount, bytecode::Bytecode};
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::{LedgerChanges, LedgerError};
+use crate::{LedgerChanges, LedgerEntry, LedgerError};
 use massa_db_exports::DBBatch;
 
 #[cfg(feature = "test-exports")]
@@ -56,6 +57,29 @@ pub trait LedgerController: Send + Sync {
     /// Deserializes the key and value, useful after bootstrap
     fn is_key_value_valid(&self, serialized_key: &[u8], serialized_value: &[u8]) -> bool;
 
+    /// Checks that every ledger entry can be deserialized and that the total balance does not overflow.
+    /// Reads the whole ledger.
+    fn verify_integrity(&self) -> Result<(), LedgerError>;
+
+    /// Lists the empty entries: entries with a zero balance, no bytecode and an empty datastore.
+    /// Execution never deletes ledger entries, so these are normal accounts emptied by transfers:
+    /// this is a read-only audit, the entries must be kept for the ledger to stay consistent across nodes.
+    /// Reads the whole ledger.
+    fn find_empty_entries(&self) -> Result<Vec<Address>, LedgerError>;
+
+    /// Hashes a deterministic serialization of every ledger entry, in the order of the ledger keys.
+    /// Honest nodes at the same final slot get the same hash, so it can be used as a cross-node integrity check.
+    /// Reads the whole ledger.
+    fn state_hash(&self) -> Result<Hash, LedgerError>;
+
+    /// Get every ledger entry, along with its whole datastore.
+    ///
+    /// IMPORTANT: This reads the entire ledger into memory.
+    ///
+    /// # Returns
+    /// A `BTreeMap` with the address as key and the complete entry as value
+    fn get_every_entry(&self) -> BTreeMap<Address, LedgerEntry>;
+
     /// Get every address and their corresponding balance.
     ///
     /// IMPORTANT: This should only be used for debug and test purposes.
@@ -4,6 +4,8 @@
 
 use crate::ledger_changes::LedgerEntryUpdate;
 use crate::types::{Applicable, SetOrDelete};
+use crate::LedgerError;
+use massa_models::address::Address;
 use massa_models::amount::{Amount, AmountDeserializer, AmountSerializer};
 use massa_models::bytecode::{Bytecode, BytecodeDeserializer, BytecodeSerializer};
 use massa_models::datastore::{Datastore, DatastoreDeserializer, DatastoreSerializer};
@@ -13,7 +15,10 @@ use nom::sequence::tuple;
 use nom::{IResult, Parser};
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use std::collections::BTreeMap;
+use std::io::Write;
 use std::ops::Bound::Included;
+use std::path::Path;
 
 /// Structure defining an entry associated to an address in the `FinalLedger`
 #[serde_as]
@@ -30,6 +35,35 @@ pub struct LedgerEntry {
     pub datastore: Datastore,
 }
 
+/// Writes ledger entries to a JSON file, in the same format as the initial ledger file.
+///
+/// # Returns
+/// The number of written entries
+pub fn write_ledger_file(
+    path: &Path,
+    entries: &BTreeMap<Address, LedgerEntry>,
+) -> Result<usize, LedgerError> {
+    let file = std::fs::File::create(path).map_err(|err| {
+        LedgerError::FileError(format!(
+            "error creating ledger export file {}: {}",
+            path.to_str().unwrap_or("(non-utf8 path)"),
+            err
+        ))
+    })?;
+    let mut writer = std::io::BufWriter::new(file);
+    serde_json::to_writer(&mut writer, entries)
+        .map_err(std::io::Error::from)
+        .and_then(|_| writer.flush())
+        .map_err(|err| {
+            LedgerError::FileError(format!(
+                "error writing ledger export file {}: {}",
+                path.to_str().unwrap_or("(non-utf8 path)"),
+                err
+            ))
+        })?;
+    Ok(entries.len())
+}
+
 /// Serializer for `LedgerEntry`
 pub struct LedgerEntrySerializer {
     amount_serializer: AmountSerializer,
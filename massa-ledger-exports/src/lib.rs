@@ -23,7 +23,9 @@ pub use ledger_changes::{
     LedgerChangesDeserializer, LedgerChangesSerializer, LedgerEntryUpdate,
     LedgerEntryUpdateDeserializer, LedgerEntryUpdateSerializer,
 };
-pub use ledger_entry::{LedgerEntry, LedgerEntryDeserializer, LedgerEntrySerializer};
+pub use ledger_entry::{
+    write_ledger_file, LedgerEntry, LedgerEntryDeserializer, LedgerEntrySerializer,
+};
 pub use types::{
     Applicable, SetOrDelete, SetOrKeep, SetOrKeepDeserializer, SetOrKeepSerializer,
     SetUpdateOrDelete, SetUpdateOrDeleteDeserializer, SetUpdateOrDeleteSerializer,
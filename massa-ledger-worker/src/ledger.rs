@@ -4,6 +4,7 @@
 
 use crate::ledger_db::{LedgerDB, LedgerSubEntry};
 use massa_db_exports::{DBBatch, ShareableMassaDBController};
+use massa_hash::Hash;
 use massa_ledger_exports::{
     LedgerChanges, LedgerConfig, LedgerController, LedgerEntry, LedgerError,
 };
@@ -13,7 +14,7 @@ use massa_models::{
     bytecode::{Bytecode, BytecodeDeserializer},
 };
 use massa_serialization::{DeserializeError, Deserializer};
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::ops::Bound::Included;
 
 /// Represents a final ledger associating addresses to their balances, bytecode and data.
@@ -161,6 +162,34 @@ impl LedgerController for FinalLedger {
             .is_key_value_valid(serialized_key, serialized_value)
     }
 
+    /// Checks that every ledger entry can be deserialized and that the total balance does not overflow.
+    ///
+    /// IMPORTANT: This reads the entire ledger.
+    fn verify_integrity(&self) -> Result<(), LedgerError> {
+        self.sorted_ledger.verify_integrity()
+    }
+
+    /// Lists the entries with a zero balance, no bytecode and an empty datastore, such as accounts emptied by transfers.
+    ///
+    /// IMPORTANT: This reads the entire ledger.
+    fn find_empty_entries(&self) -> Result<Vec<Address>, LedgerError> {
+        self.sorted_ledger.find_empty_entries()
+    }
+
+    /// Hashes every ledger entry in address order, to compare ledgers across nodes.
+    ///
+    /// IMPORTANT: This reads the entire ledger.
+    fn state_hash(&self) -> Result<Hash, LedgerError> {
+        self.sorted_ledger.get_state_hash()
+    }
+
+    /// Get every ledger entry, along with its whole datastore.
+    ///
+    /// IMPORTANT: This reads the entire ledger into memory.
+    fn get_every_entry(&self) -> BTreeMap<Address, LedgerEntry> {
+        self.sorted_ledger.get_every_entry()
+    }
+
     /// Get every address and their corresponding balance.
     ///
     /// IMPORTANT: This should only be used for debug and test purposes.
@@ -4,19 +4,23 @@
 
 use massa_db_exports::{
     DBBatch, MassaDirection, MassaIteratorMode, ShareableMassaDBController, CRUD_ERROR,
-    KEY_SER_ERROR, LEDGER_PREFIX, STATE_CF,
+    KEY_DESER_ERROR, KEY_SER_ERROR, LEDGER_PREFIX, STATE_CF,
 };
+use massa_hash::Hash;
 use massa_ledger_exports::*;
 use massa_models::amount::AmountDeserializer;
 use massa_models::bytecode::BytecodeDeserializer;
 use massa_models::datastore::get_prefix_bounds;
 use massa_models::{
-    address::Address, amount::AmountSerializer, bytecode::BytecodeSerializer, slot::Slot,
+    address::{Address, AddressSerializer},
+    amount::AmountSerializer,
+    bytecode::BytecodeSerializer,
+    slot::Slot,
 };
 use massa_serialization::{
     DeserializeError, Deserializer, Serializer, U64VarIntDeserializer, U64VarIntSerializer,
 };
-use std::collections::{BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fmt::Debug;
 
 use massa_models::amount::Amount;
@@ -273,10 +277,220 @@ impl LedgerDB {
 
         true
     }
+
+    /// Goes through the whole disk ledger and checks that every entry can be deserialized
+    /// and that the sum of all balances fits in an `Amount`.
+    ///
+    /// IMPORTANT: this reads the entire ledger, it should only be used at startup.
+    ///
+    /// # Returns
+    /// An error pointing to the first corrupt key, if any
+    pub fn verify_integrity(&self) -> Result<(), LedgerError> {
+        let db = self.db.read();
+
+        let mut total_balance = Amount::zero();
+        for (serialized_key, serialized_value) in db
+            .prefix_iterator_cf(STATE_CF, LEDGER_PREFIX.as_bytes())
+            .take_while(|(key, _)| key.starts_with(LEDGER_PREFIX.as_bytes()))
+        {
+            if !self.is_key_value_valid(&serialized_key, &serialized_value) {
+                return Err(LedgerError::ContainerInconsistency(format!(
+                    "corrupt ledger entry at key {:?}",
+                    serialized_key
+                )));
+            }
+            let (_, key) = self
+                .key_deserializer_db
+                .deserialize::<DeserializeError>(&serialized_key)
+                .expect(KEY_DESER_ERROR);
+            if key.key_type == KeyType::BALANCE {
+                let (_, balance) = self
+                    .amount_deserializer
+                    .deserialize::<DeserializeError>(&serialized_value)
+                    .expect("critical: invalid balance format");
+                total_balance = total_balance.checked_add(balance).ok_or_else(|| {
+                    LedgerError::ContainerInconsistency(format!(
+                        "total ledger balance overflows at key {:?}",
+                        serialized_key
+                    ))
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Hashes a deterministic serialization of every ledger entry, in the order of the ledger keys.
+    /// Two ledgers with the same contents have the same hash, whatever the order in which they were written.
+    ///
+    /// The hash is chained entry by entry while iterating the ledger: only one entry is held in memory at a time.
+    pub fn get_state_hash(&self) -> Result<Hash, LedgerError> {
+        let address_serializer = AddressSerializer::new();
+        let entry_serializer = LedgerEntrySerializer::new();
+        let mut buffer = Vec::new();
+        let mut hash = Hash::zero();
+        let mut hash_entry = |address: Address, entry: LedgerEntry, hash: &mut Hash| {
+            buffer.clear();
+            address_serializer
+                .serialize(&address, &mut buffer)
+                .and_then(|_| entry_serializer.serialize(&entry, &mut buffer))
+                .map_err(|err| {
+                    LedgerError::ContainerInconsistency(format!(
+                        "could not serialize the ledger entry of {}: {}",
+                        address, err
+                    ))
+                })?;
+            *hash = Hash::compute_from_tuple(&[hash.to_bytes(), &buffer]);
+            Ok::<(), LedgerError>(())
+        };
+
+        let db = self.db.read();
+        // the sub-entries of an address are contiguous: an entry is complete when the next address shows up
+        let mut entries: BTreeMap<Address, LedgerEntry> = BTreeMap::new();
+        let mut current_address = None;
+        for (serialized_key, serialized_value) in db
+            .prefix_iterator_cf(STATE_CF, LEDGER_PREFIX.as_bytes())
+            .take_while(|(key, _)| key.starts_with(LEDGER_PREFIX.as_bytes()))
+        {
+            let address = self.read_sub_entry(&mut entries, &serialized_key, &serialized_value)?;
+            if current_address != Some(address) {
+                if let Some(previous_address) = current_address.replace(address) {
+                    let entry = entries.remove(&previous_address).unwrap_or_default();
+                    hash_entry(previous_address, entry, &mut hash)?;
+                }
+            }
+        }
+        if let Some(address) = current_address {
+            let entry = entries.remove(&address).unwrap_or_default();
+            hash_entry(address, entry, &mut hash)?;
+        }
+        Ok(hash)
+    }
+
+    /// Reads every ledger entry, along with its whole datastore.
+    ///
+    /// IMPORTANT: this reads the entire ledger into memory.
+    ///
+    /// # Returns
+    /// A `BTreeMap` with the address as key and the complete entry as value
+    pub fn get_every_entry(&self) -> BTreeMap<Address, LedgerEntry> {
+        let db = self.db.read();
+
+        let mut entries: BTreeMap<Address, LedgerEntry> = BTreeMap::new();
+        for (serialized_key, serialized_value) in db
+            .prefix_iterator_cf(STATE_CF, LEDGER_PREFIX.as_bytes())
+            .take_while(|(key, _)| key.starts_with(LEDGER_PREFIX.as_bytes()))
+        {
+            self.read_sub_entry(&mut entries, &serialized_key, &serialized_value)
+                .expect("critical: invalid ledger entry");
+        }
+        entries
+    }
+
+    /// Same as `get_every_entry`, but the sub-entries that cannot be deserialized are skipped instead of panicking,
+    /// so that what is left of a damaged ledger can be salvaged.
+    /// The other sub-entries of the same address are still read: an address whose balance is corrupt is returned with a zero balance.
+    ///
+    /// IMPORTANT: this reads the entire ledger into memory.
+    ///
+    /// # Returns
+    /// The entries that could be read, and the serialized key of every skipped sub-entry along with the reason it was skipped
+    pub fn get_every_entry_lenient(
+        &self,
+    ) -> (BTreeMap<Address, LedgerEntry>, Vec<(Vec<u8>, LedgerError)>) {
+        let db = self.db.read();
+
+        let mut entries: BTreeMap<Address, LedgerEntry> = BTreeMap::new();
+        let mut skipped = Vec::new();
+        for (serialized_key, serialized_value) in db
+            .prefix_iterator_cf(STATE_CF, LEDGER_PREFIX.as_bytes())
+            .take_while(|(key, _)| key.starts_with(LEDGER_PREFIX.as_bytes()))
+        {
+            if let Err(err) = self.read_sub_entry(&mut entries, &serialized_key, &serialized_value)
+            {
+                skipped.push((serialized_key.to_vec(), err));
+            }
+        }
+        (entries, skipped)
+    }
+
+    /// Lists the entries that hold nothing: a zero balance, no bytecode and an empty datastore.
+    /// Execution never deletes ledger entries, so such an entry is a normal account emptied by transfers.
+    ///
+    /// IMPORTANT: this reads the entire ledger into memory.
+    pub fn find_empty_entries(&self) -> Result<Vec<Address>, LedgerError> {
+        let db = self.db.read();
+
+        let mut entries: BTreeMap<Address, LedgerEntry> = BTreeMap::new();
+        for (serialized_key, serialized_value) in db
+            .prefix_iterator_cf(STATE_CF, LEDGER_PREFIX.as_bytes())
+            .take_while(|(key, _)| key.starts_with(LEDGER_PREFIX.as_bytes()))
+        {
+            self.read_sub_entry(&mut entries, &serialized_key, &serialized_value)?;
+        }
+        Ok(entries
+            .into_iter()
+            .filter(|(_, entry)| {
+                entry.balance.is_zero() && entry.bytecode.0.is_empty() && entry.datastore.is_empty()
+            })
+            .map(|(addr, _)| addr)
+            .collect())
+    }
 }
 
 // Private helpers
 impl LedgerDB {
+    /// Deserializes a ledger sub-entry and adds it to the entry of its address
+    ///
+    /// # Returns
+    /// The address of the sub-entry
+    fn read_sub_entry(
+        &self,
+        entries: &mut BTreeMap<Address, LedgerEntry>,
+        serialized_key: &[u8],
+        serialized_value: &[u8],
+    ) -> Result<Address, LedgerError> {
+        let (_, key) = self
+            .key_deserializer_db
+            .deserialize::<DeserializeError>(serialized_key)
+            .map_err(|err| {
+                LedgerError::ContainerInconsistency(format!("invalid key format: {}", err))
+            })?;
+        let entry = entries.entry(key.address).or_default();
+        match key.key_type {
+            KeyType::VERSION => {}
+            KeyType::BALANCE => {
+                entry.balance = self
+                    .amount_deserializer
+                    .deserialize::<DeserializeError>(serialized_value)
+                    .map_err(|err| {
+                        LedgerError::ContainerInconsistency(format!(
+                            "invalid balance format: {}",
+                            err
+                        ))
+                    })?
+                    .1;
+            }
+            KeyType::BYTECODE => {
+                entry.bytecode = self
+                    .bytecode_deserializer
+                    .deserialize::<DeserializeError>(serialized_value)
+                    .map_err(|err| {
+                        LedgerError::ContainerInconsistency(format!(
+                            "invalid bytecode format: {}",
+                            err
+                        ))
+                    })?
+                    .1;
+            }
+            KeyType::DATASTORE(datastore_key) => {
+                entry
+                    .datastore
+                    .insert(datastore_key, serialized_value.to_vec());
+            }
+        }
+        Ok(key.address)
+    }
+
     /// Add every sub-entry individually for a given entry.
     ///
     /// # Arguments
@@ -557,6 +771,7 @@ fn end_prefix(prefix: &[u8]) -> Option<Vec<u8>> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::FinalLedger;
     use massa_db_exports::{MassaDBConfig, MassaDBController, STATE_HASH_INITIAL_BYTES};
     use massa_db_worker::MassaDB;
     use massa_hash::HashXof;
@@ -564,6 +779,7 @@ mod tests {
     use massa_models::{
         address::Address,
         amount::{Amount, AmountDeserializer},
+        bytecode::Bytecode,
     };
     use massa_serialization::{DeserializeError, Deserializer};
     use massa_signature::KeyPair;
@@ -572,7 +788,7 @@ mod tests {
     use std::ops::Bound::Included;
     use std::str::FromStr;
     use std::sync::Arc;
-    use tempfile::TempDir;
+    use tempfile::{NamedTempFile, TempDir};
 
     fn init_test_ledger(addr: Address) -> (LedgerDB, BTreeMap<Vec<u8>, Vec<u8>>) {
         // init data
@@ -675,6 +891,213 @@ mod tests {
         assert!(ledger_db.get_entire_datastore(&addr).is_empty());
     }
 
+    #[test]
+    fn test_empty_entries() {
+        let addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let (ledger_db, _) = init_test_ledger(addr);
+        assert!(ledger_db.find_empty_entries().unwrap().is_empty());
+
+        // an account emptied by transfers, and an empty-balance smart contract
+        let emptied = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let contract = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let mut batch = DBBatch::new();
+        ledger_db.put_entry(&emptied, LedgerEntry::default(), &mut batch);
+        ledger_db.put_entry(
+            &contract,
+            LedgerEntry {
+                bytecode: Bytecode(vec![1, 2, 3]),
+                ..Default::default()
+            },
+            &mut batch,
+        );
+        ledger_db
+            .db
+            .write()
+            .write_batch(batch, Default::default(), None);
+        assert_eq!(ledger_db.find_empty_entries().unwrap(), vec![emptied]);
+        // the audit does not touch the ledger
+        assert!(ledger_db
+            .get_sub_entry(&emptied, LedgerSubEntry::Version)
+            .is_some());
+    }
+
+    #[test]
+    fn test_verify_integrity() {
+        let addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let (ledger_db, _) = init_test_ledger(addr);
+        ledger_db.verify_integrity().unwrap();
+
+        // overwrite the balance with bytes that cannot be deserialized
+        let mut serialized_key = Vec::new();
+        ledger_db
+            .key_serializer_db
+            .serialize(&Key::new(&addr, KeyType::BALANCE), &mut serialized_key)
+            .unwrap();
+        let mut batch = DBBatch::new();
+        ledger_db
+            .db
+            .read()
+            .put_or_update_entry_value(&mut batch, serialized_key, &[255; 16]);
+        ledger_db
+            .db
+            .write()
+            .write_batch(batch, Default::default(), None);
+
+        assert!(matches!(
+            ledger_db.verify_integrity(),
+            Err(LedgerError::ContainerInconsistency(_))
+        ));
+    }
+
+    #[test]
+    fn test_get_every_entry_lenient() {
+        let addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let (ledger_db, data) = init_test_ledger(addr);
+        let healthy_addr =
+            Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let mut batch = DBBatch::new();
+        ledger_db.put_entry(
+            &healthy_addr,
+            LedgerEntry {
+                balance: Amount::from_str("7").unwrap(),
+                ..Default::default()
+            },
+            &mut batch,
+        );
+
+        // overwrite the balance of the first address with bytes that cannot be deserialized
+        let mut corrupt_key = Vec::new();
+        ledger_db
+            .key_serializer_db
+            .serialize(&Key::new(&addr, KeyType::BALANCE), &mut corrupt_key)
+            .unwrap();
+        ledger_db
+            .db
+            .read()
+            .put_or_update_entry_value(&mut batch, corrupt_key.clone(), &[255; 16]);
+        ledger_db
+            .db
+            .write()
+            .write_batch(batch, Default::default(), None);
+
+        let (entries, skipped) = ledger_db.get_every_entry_lenient();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].0, corrupt_key);
+        assert!(matches!(
+            skipped[0].1,
+            LedgerError::ContainerInconsistency(_)
+        ));
+
+        // the rest of the ledger is salvaged
+        assert_eq!(entries[&addr].balance, Amount::zero());
+        assert_eq!(entries[&addr].datastore, data);
+        assert_eq!(
+            entries[&healthy_addr].balance,
+            Amount::from_str("7").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_export_ledger_round_trip() {
+        let addr = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let (ledger_db, data) = init_test_ledger(addr);
+        let ledger_config = LedgerConfig {
+            thread_count: 32,
+            initial_ledger_path: Default::default(),
+            max_key_length: 255,
+            max_datastore_value_length: 1000,
+        };
+        let ledger = FinalLedger {
+            config: ledger_config.clone(),
+            sorted_ledger: ledger_db,
+        };
+
+        // dump the ledger to a file
+        let export_file = NamedTempFile::new().unwrap();
+        assert_eq!(
+            write_ledger_file(export_file.path(), &ledger.get_every_entry()).unwrap(),
+            1
+        );
+
+        // load it into an empty ledger as its initial ledger
+        let temp_dir = TempDir::new().unwrap();
+        let db_config = MassaDBConfig {
+            path: temp_dir.path().to_path_buf(),
+            max_history_length: 10,
+            max_final_state_elements_size: 100_000,
+            max_versioning_elements_size: 100_000,
+            max_ledger_backups: 10,
+            thread_count: 32,
+        };
+        let db = Arc::new(RwLock::new(
+            Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>
+        ));
+        let mut restored = FinalLedger::new(
+            LedgerConfig {
+                initial_ledger_path: export_file.path().to_path_buf(),
+                ..ledger_config
+            },
+            db,
+        );
+        restored.load_initial_ledger().unwrap();
+
+        let entries = restored.sorted_ledger.get_every_entry();
+        assert_eq!(entries, ledger.sorted_ledger.get_every_entry());
+        assert_eq!(entries[&addr].balance, Amount::from_str("21").unwrap());
+        assert_eq!(entries[&addr].datastore, data);
+    }
+
+    #[test]
+    fn test_state_hash_ignores_insertion_order() {
+        let addresses: Vec<Address> = (0..4)
+            .map(|_| Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key()))
+            .collect();
+        let entry_of = |i: usize| {
+            let mut datastore = BTreeMap::new();
+            datastore.insert(vec![i as u8], vec![1, 2, 3]);
+            datastore.insert(vec![i as u8, 9], vec![4]);
+            LedgerEntry {
+                balance: Amount::from_str(&format!("{}", i + 1)).unwrap(),
+                datastore,
+                ..Default::default()
+            }
+        };
+        let ledger_with = |order: &[usize]| {
+            let temp_dir = TempDir::new().unwrap();
+            let db_config = MassaDBConfig {
+                path: temp_dir.path().to_path_buf(),
+                max_history_length: 10,
+                max_final_state_elements_size: 100_000,
+                max_versioning_elements_size: 100_000,
+                max_ledger_backups: 10,
+                thread_count: 32,
+            };
+            let db = Arc::new(RwLock::new(
+                Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>
+            ));
+            let ledger_db = LedgerDB::new(db, 32, 255, 1000);
+            // write each entry in its own batch so that the insertion order is preserved
+            for i in order {
+                let mut batch = DBBatch::new();
+                ledger_db.put_entry(&addresses[*i], entry_of(*i), &mut batch);
+                ledger_db
+                    .db
+                    .write()
+                    .write_batch(batch, Default::default(), None);
+            }
+            (ledger_db, temp_dir)
+        };
+
+        let (ledger_a, _dir_a) = ledger_with(&[0, 1, 2, 3]);
+        let (ledger_b, _dir_b) = ledger_with(&[3, 1, 0, 2]);
+        let hash = ledger_a.get_state_hash().unwrap();
+        assert_eq!(hash, ledger_b.get_state_hash().unwrap());
+
+        // a different content gives a different hash
+        let (ledger_c, _dir_c) = ledger_with(&[3, 1, 0]);
+        assert_ne!(hash, ledger_c.get_state_hash().unwrap());
+    }
+
     #[test]
     fn test_end_prefix() {
         assert_eq!(end_prefix(&[5, 6, 7]), Some(vec![5, 6, 8]));
@@ -120,6 +120,22 @@ pub struct MassaMetrics {
     protocol_known_peers: IntGauge,
     /// banned peers in protocol
     protocol_banned_peers: IntGauge,
+    /// messages received from the network and waiting for the operation retrieval thread
+    protocol_operation_retrieval_queue_depth: IntGauge,
+    /// commands waiting for the operation propagation thread
+    protocol_operation_propagation_queue_depth: IntGauge,
+    /// new operations received from peers that were checked and sent to the pool
+    protocol_operations_accepted: IntCounter,
+    /// operations received from peers that we already knew about
+    protocol_operations_duplicate: IntCounter,
+    /// expired operations received from peers and operations of messages that failed the checks
+    protocol_operations_invalid: IntCounter,
+    /// operations received from peers whose validity starts too far in the future
+    protocol_operations_future_validity: IntCounter,
+    /// announced operations whose retrieval was postponed
+    protocol_operations_rate_limited: IntCounter,
+    /// operations received from peers that pay less than the congestion fee floor of the pool
+    protocol_operations_below_fee_floor: IntCounter,
 
     /// executed final slot
     executed_final_slot: IntCounter,
@@ -291,6 +307,46 @@ impl MassaMetrics {
             "number of banned peers in protocol",
         )
         .unwrap();
+        let operation_retrieval_queue_depth = IntGauge::new(
+            "protocol_operation_retrieval_queue_depth",
+            "number of messages waiting for the operation retrieval thread",
+        )
+        .unwrap();
+        let operation_propagation_queue_depth = IntGauge::new(
+            "protocol_operation_propagation_queue_depth",
+            "number of commands waiting for the operation propagation thread",
+        )
+        .unwrap();
+        let operations_accepted = IntCounter::new(
+            "protocol_operations_accepted",
+            "new operations received from peers and sent to the pool",
+        )
+        .unwrap();
+        let operations_duplicate = IntCounter::new(
+            "protocol_operations_duplicate",
+            "operations received from peers that were already known",
+        )
+        .unwrap();
+        let operations_invalid = IntCounter::new(
+            "protocol_operations_invalid",
+            "expired or invalid operations received from peers",
+        )
+        .unwrap();
+        let operations_future_validity = IntCounter::new(
+            "protocol_operations_future_validity",
+            "operations received from peers whose validity starts too far in the future",
+        )
+        .unwrap();
+        let operations_rate_limited = IntCounter::new(
+            "protocol_operations_rate_limited",
+            "announced operations whose retrieval was postponed",
+        )
+        .unwrap();
+        let operations_below_fee_floor = IntCounter::new(
+            "protocol_operations_below_fee_floor",
+            "operations received from peers that pay less than the congestion fee floor of the pool",
+        )
+        .unwrap();
 
         // active cursor
         let active_cursor_thread =
@@ -441,6 +497,14 @@ impl MassaMetrics {
                 let _ = prometheus::register(Box::new(rolls.clone()));
                 let _ = prometheus::register(Box::new(know_peers.clone()));
                 let _ = prometheus::register(Box::new(banned_peers.clone()));
+                let _ = prometheus::register(Box::new(operation_retrieval_queue_depth.clone()));
+                let _ = prometheus::register(Box::new(operation_propagation_queue_depth.clone()));
+                let _ = prometheus::register(Box::new(operations_accepted.clone()));
+                let _ = prometheus::register(Box::new(operations_duplicate.clone()));
+                let _ = prometheus::register(Box::new(operations_invalid.clone()));
+                let _ = prometheus::register(Box::new(operations_future_validity.clone()));
+                let _ = prometheus::register(Box::new(operations_rate_limited.clone()));
+                let _ = prometheus::register(Box::new(operations_below_fee_floor.clone()));
                 let _ = prometheus::register(Box::new(executed_final_slot.clone()));
                 let _ = prometheus::register(Box::new(executed_final_slot_with_block.clone()));
                 let _ = prometheus::register(Box::new(active_history.clone()));
@@ -485,6 +549,14 @@ impl MassaMetrics {
                 protocol_tester_failed,
                 protocol_known_peers: know_peers,
                 protocol_banned_peers: banned_peers,
+                protocol_operation_retrieval_queue_depth: operation_retrieval_queue_depth,
+                protocol_operation_propagation_queue_depth: operation_propagation_queue_depth,
+                protocol_operations_accepted: operations_accepted,
+                protocol_operations_duplicate: operations_duplicate,
+                protocol_operations_invalid: operations_invalid,
+                protocol_operations_future_validity: operations_future_validity,
+                protocol_operations_rate_limited: operations_rate_limited,
+                protocol_operations_below_fee_floor: operations_below_fee_floor,
                 executed_final_slot,
                 executed_final_slot_with_block,
                 peernet_total_bytes_received,
@@ -626,6 +698,32 @@ impl MassaMetrics {
         self.protocol_banned_peers.set(nb as i64);
     }
 
+    pub fn set_operation_handler_queue_depths(&self, retrieval: usize, propagation: usize) {
+        self.protocol_operation_retrieval_queue_depth
+            .set(retrieval as i64);
+        self.protocol_operation_propagation_queue_depth
+            .set(propagation as i64);
+    }
+
+    pub fn inc_protocol_received_operations(
+        &self,
+        accepted: u64,
+        duplicate: u64,
+        invalid: u64,
+        future_validity: u64,
+        rate_limited: u64,
+        below_fee_floor: u64,
+    ) {
+        self.protocol_operations_accepted.inc_by(accepted);
+        self.protocol_operations_duplicate.inc_by(duplicate);
+        self.protocol_operations_invalid.inc_by(invalid);
+        self.protocol_operations_future_validity
+            .inc_by(future_validity);
+        self.protocol_operations_rate_limited.inc_by(rate_limited);
+        self.protocol_operations_below_fee_floor
+            .inc_by(below_fee_floor);
+    }
+
     pub fn inc_executed_final_slot(&self) {
         self.executed_final_slot.inc();
     }
@@ -0,0 +1,166 @@
+// Copyright (c) 2022 MASSA LABS <info@massa.net>
+
+use massa_hash::Hash;
+use massa_signature::{KeyPair, PublicKey};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    address::Address, composite::PubkeySig, error::ModelsResult, operation::OperationId,
+    secure_share::Id, slot::Slot,
+};
+
+/// Domain separation tag of the signed address histories, so that their signature can not be reused for another kind of message
+const ADDRESS_HISTORY_HASH_DOMAIN: &[u8] = b"massa_address_history";
+
+/// Statement of the activity of an address in the final blocks retained in the block graph of a node.
+///
+/// Final blocks are pruned from the graph after a while: older activity is not covered
+/// and has to be taken from an earlier statement or from an indexer.
+///
+/// Only the inclusion of operations is stated, not their execution: an included operation may still have failed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AddressHistory {
+    /// the address
+    pub address: Address,
+    /// first and last slots of the final blocks covered, `None` if no final block with its operations is retained
+    pub covered_slots: Option<(Slot, Slot)>,
+    /// the operations created by the address or declaring a transfer to it, with the slot of their block, in slot order
+    pub operations: Vec<(OperationId, Slot)>,
+    /// the roll buys and sells requested by the address as `(operation, roll_count, is_buy)`, in slot order
+    pub roll_changes: Vec<(OperationId, u64, bool)>,
+}
+
+impl AddressHistory {
+    /// Hash of the content of the statement for the network `chain_id`, which is what gets signed.
+    ///
+    /// The hashed bytes start with a domain separation tag and the chain id,
+    /// the presence of the covered slots is tagged and each list is prefixed with its length.
+    pub fn compute_hash(&self, chain_id: u64) -> Hash {
+        let mut bytes = ADDRESS_HISTORY_HASH_DOMAIN.to_vec();
+        bytes.extend(chain_id.to_be_bytes());
+        let address = self.address.to_prefixed_bytes();
+        bytes.extend((address.len() as u64).to_be_bytes());
+        bytes.extend(address);
+        match &self.covered_slots {
+            Some((first, last)) => {
+                bytes.push(1);
+                bytes.extend(first.to_bytes_key());
+                bytes.extend(last.to_bytes_key());
+            }
+            None => bytes.push(0),
+        }
+        bytes.extend((self.operations.len() as u64).to_be_bytes());
+        for (op_id, slot) in &self.operations {
+            bytes.extend(op_id.get_hash().to_bytes());
+            bytes.extend(slot.to_bytes_key());
+        }
+        bytes.extend((self.roll_changes.len() as u64).to_be_bytes());
+        for (op_id, roll_count, is_buy) in &self.roll_changes {
+            bytes.extend(op_id.get_hash().to_bytes());
+            bytes.extend(roll_count.to_be_bytes());
+            bytes.push(u8::from(*is_buy));
+        }
+        Hash::compute_from(&bytes)
+    }
+}
+
+/// Address history along with the signature of the node that produced it, if any
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressHistoryBundle {
+    /// the statement
+    pub history: AddressHistory,
+    /// public key of the node and its signature of the statement
+    pub signature: Option<PubkeySig>,
+}
+
+impl AddressHistoryBundle {
+    /// Bundles `history`, signed with `keypair` for the network `chain_id` if provided
+    pub fn new(
+        history: AddressHistory,
+        keypair: Option<&KeyPair>,
+        chain_id: u64,
+    ) -> ModelsResult<Self> {
+        let signature = match keypair {
+            Some(keypair) => Some(PubkeySig {
+                public_key: keypair.get_public_key(),
+                signature: keypair.sign(&history.compute_hash(chain_id))?,
+            }),
+            None => None,
+        };
+        Ok(AddressHistoryBundle { history, signature })
+    }
+
+    /// Checks the signature of the bundle for the network `chain_id`.
+    /// Returns the public key of the signer, `None` if the bundle is not signed.
+    pub fn verify(&self, chain_id: u64) -> ModelsResult<Option<PublicKey>> {
+        match &self.signature {
+            Some(PubkeySig {
+                public_key,
+                signature,
+            }) => {
+                public_key.verify_signature(&self.history.compute_hash(chain_id), signature)?;
+                Ok(Some(*public_key))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CHAIN_ID: u64 = 77;
+
+    fn history() -> AddressHistory {
+        let keypair = KeyPair::generate(0).unwrap();
+        let op_id = OperationId::new(Hash::compute_from(b"op"));
+        let slot = Slot::new(3, 1);
+        AddressHistory {
+            address: Address::from_public_key(&keypair.get_public_key()),
+            covered_slots: Some((Slot::new(1, 0), slot)),
+            operations: vec![(op_id, slot)],
+            roll_changes: vec![(op_id, 1, true)],
+        }
+    }
+
+    #[test]
+    fn test_address_history_bundle_verify() {
+        let node_keypair = KeyPair::generate(0).unwrap();
+        let bundle = AddressHistoryBundle::new(history(), Some(&node_keypair), CHAIN_ID).unwrap();
+        assert_eq!(
+            bundle.verify(CHAIN_ID).unwrap(),
+            Some(node_keypair.get_public_key())
+        );
+
+        // tampering with the statement breaks the signature
+        let mut tampered = bundle.clone();
+        tampered.history.roll_changes[0].1 = 2;
+        assert!(tampered.verify(CHAIN_ID).is_err());
+
+        // the signature is only valid on the network it was made for
+        assert!(bundle.verify(CHAIN_ID + 1).is_err());
+
+        // unsigned bundles have no signer
+        let unsigned = AddressHistoryBundle::new(history(), None, CHAIN_ID).unwrap();
+        assert_eq!(unsigned.verify(CHAIN_ID).unwrap(), None);
+    }
+
+    #[test]
+    fn test_address_history_hash_is_unambiguous() {
+        let base = history();
+        let hash = base.compute_hash(CHAIN_ID);
+
+        // a list emptied
+        let mut moved = base.clone();
+        moved.roll_changes.clear();
+        assert_ne!(moved.compute_hash(CHAIN_ID), hash);
+
+        // covered slots dropped
+        let mut uncovered = base.clone();
+        uncovered.covered_slots = None;
+        assert_ne!(uncovered.compute_hash(CHAIN_ID), hash);
+
+        assert_ne!(base.compute_hash(CHAIN_ID + 1), hash);
+    }
+}
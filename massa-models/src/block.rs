@@ -7,10 +7,12 @@ use crate::secure_share::{
     Id, SecureShare, SecureShareContent, SecureShareDeserializer, SecureShareSerializer,
 };
 use crate::{
+    address::Address,
     // endorsement::{Endorsement, EndorsementDeserializerLW, SecureShareEndorsement},
     error::ModelsError,
     operation::{
-        OperationId, OperationIdsDeserializer, OperationIdsSerializer, SecureShareOperation,
+        compute_operations_hash, OperationId, OperationIdSerializer, OperationIdsDeserializer,
+        OperationIdsSerializer, SecureShareOperation,
     },
     // slot::{Slot, SlotDeserializer, SlotSerializer},
 };
@@ -365,6 +367,53 @@ impl Deserializer<Block> for BlockDeserializer {
     }
 }
 
+impl Block {
+    /// Ratio between the serialized size of the block and `max_block_size`.
+    /// A ratio above `1.0` means that the block exceeds the maximal block size.
+    pub fn fill_ratio(&self, max_block_size: u64) -> Result<f64, ModelsError> {
+        let mut buffer = Vec::new();
+        BlockSerializer::new().serialize(self, &mut buffer)?;
+        Ok(buffer.len() as f64 / max_block_size as f64)
+    }
+
+    /// Recomputes the hash of the operation list and checks that it matches
+    /// the `operation_merkle_root` committed in the header.
+    pub fn verify_operation_merkle_root(&self) -> bool {
+        compute_operations_hash(&self.operations, &OperationIdSerializer::new())
+            == self.header.content.operation_merkle_root
+    }
+
+    /// Checks that every operation of the block is sent from the thread of the block.
+    ///
+    /// # Arguments
+    /// * `thread_count`: number of threads
+    /// * `get_sender`: returns the sender address of an operation, or `None` if the operation is not known yet.
+    ///   Unknown operations are skipped, so the check can be run on a partially gathered block.
+    pub fn operations_thread_consistent<F>(
+        &self,
+        thread_count: u8,
+        get_sender: F,
+    ) -> Result<(), ModelsError>
+    where
+        F: Fn(&OperationId) -> Option<Address>,
+    {
+        let block_thread = self.header.content.slot.thread;
+        for op_id in &self.operations {
+            if let Some(sender) = get_sender(op_id) {
+                let sender_thread = sender.get_thread(thread_count);
+                if sender_thread != block_thread {
+                    return Err(ModelsError::OperationThreadMismatch(
+                        op_id.to_string(),
+                        sender_thread,
+                        block_thread,
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 impl SecureShareBlock {
     /// size in bytes of the whole block
     pub fn bytes_count(&self) -> u64 {
@@ -440,17 +489,16 @@ mod test {
         config::{ENDORSEMENT_COUNT, MAX_OPERATIONS_PER_BLOCK, THREAD_COUNT},
         endorsement::Endorsement,
         endorsement::EndorsementSerializer,
+        prehash::PreHashMap,
         slot::Slot,
     };
     use massa_hash::Hash;
     use massa_serialization::DeserializeError;
     use massa_signature::KeyPair;
     use serde_json::Value;
-    use serial_test::serial;
     use std::str::FromStr;
 
     #[test]
-    #[serial]
     fn test_block_serialization() {
         let keypair =
             KeyPair::from_str("S1bXjyPwrssNmG4oUG5SEqaUhQkVArQi7rzQDWpCprTSmEgZDGG").unwrap();
@@ -578,7 +626,6 @@ mod test {
     }
 
     #[test]
-    #[serial]
     fn test_genesis_block_serialization() {
         let keypair = KeyPair::generate(0).unwrap();
         let parents: Vec<BlockId> = vec![];
@@ -666,7 +713,6 @@ mod test {
     }
 
     #[test]
-    #[serial]
     fn test_invalid_genesis_block_serialization_with_endorsements() {
         let keypair = KeyPair::generate(0).unwrap();
         let parents: Vec<BlockId> = vec![];
@@ -736,7 +782,6 @@ mod test {
         // };
     }
     #[test]
-    #[serial]
     fn test_invalid_genesis_block_serialization_with_parents() {
         let keypair = KeyPair::generate(0).unwrap();
         let parents = (0..THREAD_COUNT)
@@ -792,7 +837,6 @@ mod test {
         assert!(res.is_err());
     }
     #[test]
-    #[serial]
     fn test_invalid_block_serialization_no_parents() {
         let keypair = KeyPair::generate(0).unwrap();
         // Non genesis block must have THREAD_COUNT parents
@@ -846,7 +890,6 @@ mod test {
         assert!(res.is_err());
     }
     #[test]
-    #[serial]
     fn test_invalid_block_serialization_obo_high_parent_count() {
         let keypair = KeyPair::generate(0).unwrap();
         // Non genesis block must have THREAD_COUNT parents
@@ -904,7 +947,6 @@ mod test {
     }
 
     #[test]
-    #[serial]
     fn test_block_serialization_max_endo_count() {
         let keypair =
             KeyPair::from_str("S1bXjyPwrssNmG4oUG5SEqaUhQkVArQi7rzQDWpCprTSmEgZDGG").unwrap();
@@ -986,7 +1028,6 @@ mod test {
             .unwrap();
     }
     #[test]
-    #[serial]
     fn test_invalid_block_serialization_obo_low_parent_count() {
         let keypair = KeyPair::generate(0).unwrap();
         // Non genesis block must have THREAD_COUNT parents
@@ -1043,7 +1084,6 @@ mod test {
         assert!(res.is_err());
     }
     #[test]
-    #[serial]
     fn test_invalid_block_serialization_obo_high_endo_count() {
         let keypair = KeyPair::generate(0).unwrap();
         // Non genesis block must have THREAD_COUNT parents
@@ -1115,7 +1155,6 @@ mod test {
         assert!(res.is_err());
     }
     #[test]
-    #[serial]
     fn test_invalid_endorsement_idx() {
         let keypair =
             KeyPair::from_str("S1bXjyPwrssNmG4oUG5SEqaUhQkVArQi7rzQDWpCprTSmEgZDGG").unwrap();
@@ -1191,7 +1230,6 @@ mod test {
         assert!(res.is_err());
     }
     #[test]
-    #[serial]
     fn test_invalid_dupe_endo_idx() {
         let keypair =
             KeyPair::from_str("S1bXjyPwrssNmG4oUG5SEqaUhQkVArQi7rzQDWpCprTSmEgZDGG").unwrap();
@@ -1283,7 +1321,6 @@ mod test {
     }
 
     #[test]
-    #[serial]
     fn test_block_serde() {
         let keypair =
             KeyPair::from_str("S1bXjyPwrssNmG4oUG5SEqaUhQkVArQi7rzQDWpCprTSmEgZDGG").unwrap();
@@ -1357,4 +1394,144 @@ mod test {
             res_block["header"]["signature"]
         );
     }
+
+    #[test]
+    fn test_block_fill_ratio_near_limit() {
+        let keypair =
+            KeyPair::from_str("S1bXjyPwrssNmG4oUG5SEqaUhQkVArQi7rzQDWpCprTSmEgZDGG").unwrap();
+        let parents = (0..THREAD_COUNT)
+            .map(|i| BlockId::generate_from_hash(Hash::compute_from(&[i])))
+            .collect();
+        let header = BlockHeader::new_verifiable(
+            BlockHeader {
+                current_version: 0,
+                announced_version: None,
+                slot: Slot::new(1, 0),
+                parents,
+                operation_merkle_root: Hash::compute_from("mno".as_bytes()),
+                endorsements: Vec::new(),
+                denunciations: Vec::new(),
+            },
+            BlockHeaderSerializer::new(),
+            &keypair,
+            *CHAINID,
+        )
+        .unwrap();
+        let block = Block {
+            header,
+            operations: (0..10u8)
+                .map(|i| OperationId::new(Hash::compute_from(&[i])))
+                .collect(),
+        };
+
+        let mut serialized = Vec::new();
+        BlockSerializer::new()
+            .serialize(&block, &mut serialized)
+            .unwrap();
+        let size = serialized.len() as u64;
+
+        assert_eq!(block.fill_ratio(size).unwrap(), 1.0);
+        assert!(block.fill_ratio(size - 1).unwrap() > 1.0);
+        assert!(block.fill_ratio(size + 1).unwrap() < 1.0);
+    }
+
+    #[test]
+    fn test_verify_operation_merkle_root() {
+        let keypair =
+            KeyPair::from_str("S1bXjyPwrssNmG4oUG5SEqaUhQkVArQi7rzQDWpCprTSmEgZDGG").unwrap();
+        let parents = (0..THREAD_COUNT)
+            .map(|i| BlockId::generate_from_hash(Hash::compute_from(&[i])))
+            .collect();
+        let operations: Vec<OperationId> = (0..3u8)
+            .map(|i| OperationId::new(Hash::compute_from(&[i])))
+            .collect();
+        let header = BlockHeader::new_verifiable(
+            BlockHeader {
+                current_version: 0,
+                announced_version: None,
+                slot: Slot::new(1, 0),
+                parents,
+                operation_merkle_root: compute_operations_hash(
+                    &operations,
+                    &OperationIdSerializer::new(),
+                ),
+                endorsements: Vec::new(),
+                denunciations: Vec::new(),
+            },
+            BlockHeaderSerializer::new(),
+            &keypair,
+            *CHAINID,
+        )
+        .unwrap();
+        let mut block = Block { header, operations };
+        assert!(block.verify_operation_merkle_root());
+
+        // reordering the operations changes the root
+        block.operations.swap(0, 1);
+        assert!(!block.verify_operation_merkle_root());
+        block.operations.swap(0, 1);
+
+        // so does replacing one of them
+        block.operations[2] = OperationId::new(Hash::compute_from("tampered".as_bytes()));
+        assert!(!block.verify_operation_merkle_root());
+    }
+
+    #[test]
+    fn test_operations_thread_consistent() {
+        let keypair =
+            KeyPair::from_str("S1bXjyPwrssNmG4oUG5SEqaUhQkVArQi7rzQDWpCprTSmEgZDGG").unwrap();
+        let parents = (0..THREAD_COUNT)
+            .map(|i| BlockId::generate_from_hash(Hash::compute_from(&[i])))
+            .collect();
+        let header = BlockHeader::new_verifiable(
+            BlockHeader {
+                current_version: 0,
+                announced_version: None,
+                slot: Slot::new(1, 3),
+                parents,
+                operation_merkle_root: Hash::compute_from(&[]),
+                endorsements: Vec::new(),
+                denunciations: Vec::new(),
+            },
+            BlockHeaderSerializer::new(),
+            &keypair,
+            *CHAINID,
+        )
+        .unwrap();
+        let sender_in_thread = |thread: u8| loop {
+            let address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+            if address.get_thread(THREAD_COUNT) == thread {
+                return address;
+            }
+        };
+        let same_thread_ops: Vec<OperationId> = (0..3u8)
+            .map(|i| OperationId::new(Hash::compute_from(&[i])))
+            .collect();
+        let cross_thread_op = OperationId::new(Hash::compute_from("cross".as_bytes()));
+        let unknown_op = OperationId::new(Hash::compute_from("unknown".as_bytes()));
+        let mut senders: PreHashMap<OperationId, Address> = same_thread_ops
+            .iter()
+            .map(|op_id| (*op_id, sender_in_thread(3)))
+            .collect();
+        senders.insert(cross_thread_op, sender_in_thread(4));
+
+        let mut block = Block {
+            header,
+            operations: same_thread_ops,
+        };
+        // unknown operations are skipped
+        block.operations.push(unknown_op);
+        assert!(block
+            .operations_thread_consistent(THREAD_COUNT, |op_id| senders.get(op_id).copied())
+            .is_ok());
+
+        block.operations.push(cross_thread_op);
+        match block.operations_thread_consistent(THREAD_COUNT, |op_id| senders.get(op_id).copied())
+        {
+            Err(ModelsError::OperationThreadMismatch(op_id, 4, 3)) => {
+                assert_eq!(op_id, cross_thread_op.to_string())
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
 }
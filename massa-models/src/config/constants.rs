@@ -118,6 +118,8 @@ pub fn get_period_from_args() -> u64 {
 
 /// Price of a roll in the network
 pub const ROLL_PRICE: Amount = Amount::const_init(100, 0);
+/// Number of cycles after the cycle of a roll sale at the end of which the price of the sold rolls is credited
+pub const ROLL_SELL_CREDIT_DELAY_CYCLES: u64 = 3;
 /// Block reward is given for each block creation
 pub const BLOCK_REWARD: Amount = Amount::const_init(102, 2);
 /// Cost to store one byte in the ledger
@@ -136,6 +138,8 @@ pub const THREAD_COUNT: u8 = 32;
 pub const ENDORSEMENT_COUNT: u32 = 16;
 /// Threshold for fitness.
 pub const DELTA_F0: u64 = 64 * (ENDORSEMENT_COUNT as u64 + 1);
+/// Maximum number of blocks in the incompatibility graph before the lowest-fitness forks are discarded early
+pub const MAX_GI_HEAD_SIZE: usize = 10000;
 /// Maximum number of operations per block
 pub const MAX_OPERATIONS_PER_BLOCK: u32 = 5000;
 /// Maximum block size in bytes
@@ -180,6 +184,9 @@ pub const MAX_DATASTORE_KEY_LENGTH: u8 = 255;
 pub const MAX_OPERATION_DATASTORE_KEY_LENGTH: u8 = MAX_DATASTORE_KEY_LENGTH;
 /// Maximum length of a datastore value
 pub const MAX_DATASTORE_VALUE_LENGTH: u64 = 10_000_000;
+/// Maximum total length of a datastore entry (key and value).
+/// The key takes room from the value: an entry holding a value of maximal length cannot be stored.
+pub const MAX_DATASTORE_ENTRY_LENGTH: u64 = MAX_DATASTORE_VALUE_LENGTH;
 /// Maximum length of a datastore value
 pub const MAX_BYTECODE_LENGTH: u64 = 10_000_000;
 /// Maximum length of an operation datastore value
@@ -195,6 +202,11 @@ pub const MAX_LEDGER_CHANGES_COUNT: u64 =
 pub const MAX_DATASTORE_ENTRY_COUNT: u64 = u64::MAX;
 /// Maximum number of key/values in the datastore of a `ExecuteSC` operation
 pub const MAX_OPERATION_DATASTORE_ENTRY_COUNT: u64 = 128;
+/// Operation datastore key reserved for the memo of an `ExecuteSC` operation.
+/// Memos are public: anyone reading the operation can see them
+pub const OPERATION_MEMO_DATASTORE_KEY: &[u8] = b"memo";
+/// Maximum length in bytes of an operation memo
+pub const MAX_OPERATION_MEMO_LENGTH: usize = 256;
 /// Maximum length function name in call SC
 pub const MAX_FUNCTION_NAME_LENGTH: u16 = u16::MAX;
 /// Maximum size of parameters in call SC
@@ -517,10 +517,8 @@ mod tests {
     use crate::config::CHAINID;
     use massa_serialization::DeserializeError;
     use massa_signature::KeyPair;
-    use serial_test::serial;
 
     #[test]
-    #[serial]
     fn test_endorsement_serialization() {
         let sender_keypair = KeyPair::generate(0).unwrap();
         let content = Endorsement {
@@ -549,7 +547,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_endorsement_lightweight_serialization() {
         let sender_keypair = KeyPair::generate(0).unwrap();
         let content = Endorsement {
@@ -647,7 +644,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_endorsement_id() {
         let expected_endorsement_id =
             EndorsementId::from_str("E12Uy7hrAUHpmHQTWu68p17v7VtZJ6syBTWEJH6jwMTWJB6fdSc7")
@@ -658,7 +654,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_endorsement_id_errors() {
         let actual_error = EndorsementId::from_str("SomeUnvalidEndorsementId")
             .unwrap_err()
@@ -669,7 +664,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_endorsement_serde() {
         let orig_endorsement = Endorsement {
             slot: Slot::new(10, 1),
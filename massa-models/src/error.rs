@@ -42,6 +42,8 @@ pub enum ModelsError {
     EndorsementIdParseError,
     /// checked operation error
     CheckedOperationError(String),
+    /// operation {0} is sent from thread {1} but its block is in thread {2}
+    OperationThreadMismatch(String, u8, u8),
     /// invalid version identifier: {0}
     InvalidVersionError(String),
     /// invalid ledger change: {0}
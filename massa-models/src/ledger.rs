@@ -31,6 +31,15 @@ pub struct LedgerData {
     pub balance: Amount,
 }
 
+/// final and candidate consensus ledger entries of a set of addresses
+#[derive(Debug, Default, Deserialize, Clone, Serialize)]
+pub struct LedgerDataExport {
+    /// candidate ledger entries
+    pub candidate_data: PreHashMap<Address, LedgerData>,
+    /// final ledger entries
+    pub final_data: PreHashMap<Address, LedgerData>,
+}
+
 /// Basic serializer for `LedgerData`
 #[derive(Default)]
 pub struct LedgerDataSerializer {
@@ -144,7 +153,7 @@ impl LedgerData {
                 .checked_sub(change.balance_delta)
                 .ok_or_else(|| {
                     ModelsError::InvalidLedgerChange(
-                        "balance overflow in LedgerData::apply_change".into(),
+                        "balance underflow in LedgerData::apply_change".into(),
                     )
                 })?;
         }
@@ -497,3 +506,55 @@ impl LedgerChanges {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{LedgerChange, LedgerChanges, LedgerData};
+    use crate::{address::Address, amount::Amount, error::ModelsError};
+    use massa_signature::KeyPair;
+
+    #[test]
+    fn test_ledger_changes_overflow() {
+        let address = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let credit = |raw: u64| LedgerChange {
+            balance_delta: Amount::from_raw(raw),
+            balance_increment: true,
+        };
+        let debit = |raw: u64| LedgerChange {
+            balance_delta: Amount::from_raw(raw),
+            balance_increment: false,
+        };
+
+        // accumulating credits beyond the maximum amount fails instead of wrapping
+        let mut changes = LedgerChanges::default();
+        changes.apply(&address, &credit(u64::MAX - 1)).unwrap();
+        assert!(matches!(
+            changes.apply(&address, &credit(2)),
+            Err(ModelsError::InvalidLedgerChange(_))
+        ));
+        // the same holds when chaining whole sets of changes
+        let mut other = LedgerChanges::default();
+        other.apply(&address, &credit(2)).unwrap();
+        assert!(matches!(
+            changes.chain(&other),
+            Err(ModelsError::InvalidLedgerChange(_))
+        ));
+        // opposite changes compensate each other without overflowing
+        changes.apply(&address, &debit(u64::MAX)).unwrap();
+        assert_eq!(changes.0[&address].balance_delta, Amount::from_raw(1));
+        assert!(!changes.0[&address].balance_increment);
+
+        // applying the changes to a balance is checked both ways
+        let mut data = LedgerData::new(Amount::MAX);
+        assert!(matches!(
+            data.apply_change(&credit(1)),
+            Err(ModelsError::InvalidLedgerChange(_))
+        ));
+        let mut data = LedgerData::new(Amount::from_raw(1));
+        assert!(matches!(
+            data.apply_change(&debit(2)),
+            Err(ModelsError::InvalidLedgerChange(_))
+        ));
+        assert_eq!(data.balance, Amount::from_raw(1));
+    }
+}
@@ -10,6 +10,8 @@ extern crate lazy_static;
 pub mod active_block;
 /// address related structures
 pub mod address;
+/// statement of the activity of an address
+pub mod address_history;
 /// amount related structures
 pub mod amount;
 /// block structure
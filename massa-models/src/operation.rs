@@ -9,7 +9,9 @@ use crate::secure_share::{
 use crate::{
     address::{Address, AddressDeserializer},
     amount::{Amount, AmountDeserializer, AmountSerializer},
+    config::{MAX_OPERATION_MEMO_LENGTH, OPERATION_MEMO_DATASTORE_KEY},
     error::ModelsError,
+    ledger::{LedgerChange, LedgerChanges},
     serialization::{StringDeserializer, StringSerializer, VecU8Deserializer, VecU8Serializer},
 };
 use massa_hash::{Hash, HashDeserializer};
@@ -679,6 +681,22 @@ impl std::fmt::Display for OperationType {
     }
 }
 
+impl OperationType {
+    /// Get the memo attached to the operation, if any.
+    ///
+    /// Memos are stored in the datastore of `ExecuteSC` operations under `OPERATION_MEMO_DATASTORE_KEY`,
+    /// and are only returned if they are valid UTF-8 of at most `MAX_OPERATION_MEMO_LENGTH` bytes.
+    pub fn memo(&self) -> Option<String> {
+        match self {
+            OperationType::ExecuteSC { datastore, .. } => datastore
+                .get(OPERATION_MEMO_DATASTORE_KEY)
+                .filter(|memo| memo.len() <= MAX_OPERATION_MEMO_LENGTH)
+                .and_then(|memo| String::from_utf8(memo.clone()).ok()),
+            _ => None,
+        }
+    }
+}
+
 /// Serializer for `OperationType`
 pub struct OperationTypeSerializer {
     u32_serializer: U32VarIntSerializer,
@@ -1037,6 +1055,82 @@ impl SecureShareOperation {
         max_non_fee_seq_spending.saturating_add(self.content.fee)
     }
 
+    /// Gets the coin movements declared by this operation, by address:
+    /// the maximal spending of the sender (see `get_max_spending`), the fee credited to `fee_target`,
+    /// and the coins credited to the recipient of a transaction or to the target of a call.
+    /// Those are not the executed changes: a failed execution spends less than declared.
+    pub fn get_ledger_changes(
+        &self,
+        fee_target: &Address,
+        roll_price: Amount,
+    ) -> Result<LedgerChanges, ModelsError> {
+        let mut changes = LedgerChanges::default();
+        changes.apply(
+            &self.content_creator_address,
+            &LedgerChange {
+                balance_delta: self.get_max_spending(roll_price),
+                balance_increment: false,
+            },
+        )?;
+        changes.apply(
+            fee_target,
+            &LedgerChange {
+                balance_delta: self.content.fee,
+                balance_increment: true,
+            },
+        )?;
+        let credited = match &self.content.op {
+            OperationType::Transaction {
+                recipient_address,
+                amount,
+            } => Some((recipient_address, *amount)),
+            OperationType::CallSC {
+                target_addr, coins, ..
+            } => Some((target_addr, *coins)),
+            OperationType::RollBuy { .. }
+            | OperationType::RollSell { .. }
+            | OperationType::ExecuteSC { .. } => None,
+        };
+        if let Some((address, amount)) = credited {
+            changes.apply(
+                address,
+                &LedgerChange {
+                    balance_delta: amount,
+                    balance_increment: true,
+                },
+            )?;
+        }
+        Ok(changes)
+    }
+
+    /// Checks that the coins debited by `get_ledger_changes` equal the coins it credits.
+    /// Used as an invariant check to detect operation processing bugs.
+    ///
+    /// Expected result by operation type:
+    /// * `Transaction` and `CallSC`: always balanced
+    /// * `RollSell`: balanced, the price of the sold rolls is credited later as a deferred credit
+    /// * `RollBuy`: unbalanced unless no roll is bought, coins are converted into rolls
+    /// * `ExecuteSC`: unbalanced unless `max_coins` is zero, the coins spent depend on the execution
+    pub fn ledger_changes_balanced(
+        &self,
+        fee_target: &Address,
+        roll_price: Amount,
+    ) -> Result<bool, ModelsError> {
+        let mut credits = Amount::zero();
+        let mut debits = Amount::zero();
+        for change in self.get_ledger_changes(fee_target, roll_price)?.0.values() {
+            let total = if change.balance_increment {
+                &mut credits
+            } else {
+                &mut debits
+            };
+            *total = total
+                .checked_add(change.balance_delta)
+                .ok_or(ModelsError::AmountOverflowError)?;
+        }
+        Ok(credits == debits)
+    }
+
     /// get the addresses that are involved in this operation from a rolls point of view
     pub fn get_roll_involved_addresses(&self) -> Result<PreHashSet<Address>, ModelsError> {
         let mut res = PreHashSet::<Address>::default();
@@ -1500,11 +1594,9 @@ mod tests {
     use massa_serialization::DeserializeError;
     use massa_signature::KeyPair;
     use serde_json::Value;
-    use serial_test::serial;
     use std::collections::BTreeMap;
 
     #[test]
-    #[serial]
     fn test_transaction_massa_docs() {
         // Serialize an operation with values found in massa docs
         // This print the values as reported here:
@@ -1556,7 +1648,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_transaction() {
         let sender_keypair = KeyPair::generate(0).unwrap();
         let recv_keypair = KeyPair::generate(0).unwrap();
@@ -1632,7 +1723,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_executesc() {
         let sender_keypair = KeyPair::generate(0).unwrap();
 
@@ -1710,7 +1800,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_callsc() {
         let sender_keypair = KeyPair::generate(0).unwrap();
 
@@ -1789,7 +1878,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_transaction_serde() {
         let recv_keypair = KeyPair::generate(0).unwrap();
 
@@ -1828,7 +1916,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_executesc_serde() {
         let op = OperationType::ExecuteSC {
             max_gas: 123,
@@ -1869,7 +1956,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_callsc_serde() {
         let target_keypair = KeyPair::generate(0).unwrap();
         let target_addr = Address::from_public_key(&target_keypair.get_public_key());
@@ -1909,4 +1995,218 @@ mod tests {
         assert_eq!(orig_operation.fee.to_string(), res_operation["fee"]);
         assert_eq!(orig_operation.expire_period, res_operation["expire_period"]);
     }
+
+    #[test]
+    fn test_operation_memo() {
+        let execute_sc = |datastore: Datastore| OperationType::ExecuteSC {
+            data: vec![1, 2, 3],
+            max_gas: 100,
+            max_coins: Amount::from_str("1").unwrap(),
+            datastore,
+        };
+
+        let memo_op = execute_sc(BTreeMap::from([
+            (
+                OPERATION_MEMO_DATASTORE_KEY.to_vec(),
+                b"invoice #42".to_vec(),
+            ),
+            (vec![1], vec![2]),
+        ]));
+        assert_eq!(memo_op.memo(), Some("invoice #42".to_string()));
+
+        // no memo key, invalid UTF-8 or too long memos are ignored
+        assert_eq!(
+            execute_sc(BTreeMap::from([(vec![1], vec![2])])).memo(),
+            None
+        );
+        let invalid_utf8 = execute_sc(BTreeMap::from([(
+            OPERATION_MEMO_DATASTORE_KEY.to_vec(),
+            vec![0xff, 0xfe],
+        )]));
+        assert_eq!(invalid_utf8.memo(), None);
+        let too_long = execute_sc(BTreeMap::from([(
+            OPERATION_MEMO_DATASTORE_KEY.to_vec(),
+            vec![b'a'; MAX_OPERATION_MEMO_LENGTH + 1],
+        )]));
+        assert_eq!(too_long.memo(), None);
+
+        // other operation types carry no memo
+        let roll_buy = OperationType::RollBuy { roll_count: 1 };
+        assert_eq!(roll_buy.memo(), None);
+    }
+
+    #[test]
+    fn test_ledger_changes_balanced() {
+        let sender_keypair = KeyPair::generate(0).unwrap();
+        let sender = Address::from_public_key(&sender_keypair.get_public_key());
+        let other = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let fee_target = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let roll_price = Amount::from_str("100").unwrap();
+        let balanced = |op: OperationType, fee_target: &Address| {
+            let content = Operation {
+                fee: Amount::from_str("0.5").unwrap(),
+                op,
+                expire_period: 10,
+            };
+            Operation::new_verifiable(
+                content,
+                OperationSerializer::new(),
+                &sender_keypair,
+                *CHAINID,
+            )
+            .unwrap()
+            .ledger_changes_balanced(fee_target, roll_price)
+            .unwrap()
+        };
+        let transaction = |recipient_address| OperationType::Transaction {
+            recipient_address,
+            amount: Amount::from_str("3").unwrap(),
+        };
+        let call_sc = OperationType::CallSC {
+            target_addr: other,
+            target_func: "f".to_string(),
+            param: Vec::new(),
+            max_gas: 100,
+            coins: Amount::from_str("2").unwrap(),
+        };
+        let execute_sc = |max_coins| OperationType::ExecuteSC {
+            data: vec![1, 2, 3],
+            max_gas: 100,
+            max_coins,
+            datastore: Datastore::default(),
+        };
+
+        // transfers conserve value, even when the sender collects the fee or pays itself
+        assert!(balanced(transaction(other), &fee_target));
+        assert!(balanced(transaction(other), &sender));
+        assert!(balanced(transaction(sender), &fee_target));
+        assert!(balanced(call_sc, &fee_target));
+
+        // roll sales only move the fee, the price is credited later
+        assert!(balanced(
+            OperationType::RollSell { roll_count: 2 },
+            &fee_target
+        ));
+
+        // roll buys convert coins into rolls
+        assert!(!balanced(
+            OperationType::RollBuy { roll_count: 2 },
+            &fee_target
+        ));
+        assert!(balanced(
+            OperationType::RollBuy { roll_count: 0 },
+            &fee_target
+        ));
+
+        // the coins spent by a smart contract execution are not known in advance
+        assert!(!balanced(
+            execute_sc(Amount::from_str("1").unwrap()),
+            &fee_target
+        ));
+        assert!(balanced(execute_sc(Amount::zero()), &fee_target));
+    }
+
+    #[test]
+    fn test_get_ledger_changes() {
+        let sender_keypair = KeyPair::generate(0).unwrap();
+        let sender = Address::from_public_key(&sender_keypair.get_public_key());
+        let other = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let fee_target = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+        let roll_price = Amount::from_str("100").unwrap();
+        let changes = |op: OperationType, fee_target: &Address| {
+            let content = Operation {
+                fee: Amount::from_str("0.5").unwrap(),
+                op,
+                expire_period: 10,
+            };
+            let mut changes: Vec<(Address, String, bool)> = Operation::new_verifiable(
+                content,
+                OperationSerializer::new(),
+                &sender_keypair,
+                *CHAINID,
+            )
+            .unwrap()
+            .get_ledger_changes(fee_target, roll_price)
+            .unwrap()
+            .0
+            .into_iter()
+            .map(|(addr, change)| {
+                (
+                    addr,
+                    change.balance_delta.to_string(),
+                    change.balance_increment,
+                )
+            })
+            .collect();
+            changes.sort_unstable_by_key(|(addr, _, _)| *addr);
+            changes
+        };
+        let expected = |mut changes: Vec<(Address, &str, bool)>| {
+            changes.sort_unstable_by_key(|(addr, _, _)| *addr);
+            changes
+                .into_iter()
+                .map(|(addr, delta, increment)| (addr, delta.to_string(), increment))
+                .collect::<Vec<_>>()
+        };
+        let transaction = |recipient_address| OperationType::Transaction {
+            recipient_address,
+            amount: Amount::from_str("3").unwrap(),
+        };
+
+        // the recipient is credited the amount and the fee target the fee
+        assert_eq!(
+            changes(transaction(other), &fee_target),
+            expected(vec![
+                (sender, "3.5", false),
+                (other, "3", true),
+                (fee_target, "0.5", true),
+            ])
+        );
+        // a sender collecting its own fee only pays the amount
+        assert_eq!(
+            changes(transaction(other), &sender),
+            expected(vec![(sender, "3", false), (other, "3", true)])
+        );
+        assert_eq!(
+            changes(
+                OperationType::CallSC {
+                    target_addr: other,
+                    target_func: "f".to_string(),
+                    param: Vec::new(),
+                    max_gas: 100,
+                    coins: Amount::from_str("2").unwrap(),
+                },
+                &fee_target
+            ),
+            expected(vec![
+                (sender, "2.5", false),
+                (other, "2", true),
+                (fee_target, "0.5", true),
+            ])
+        );
+
+        // roll buys are accounted for their price, roll sales only move the fee
+        assert_eq!(
+            changes(OperationType::RollBuy { roll_count: 2 }, &fee_target),
+            expected(vec![(sender, "200.5", false), (fee_target, "0.5", true)])
+        );
+        assert_eq!(
+            changes(OperationType::RollSell { roll_count: 2 }, &fee_target),
+            expected(vec![(sender, "0.5", false), (fee_target, "0.5", true)])
+        );
+
+        // smart contract executions are accounted for the maximal coins they may spend
+        assert_eq!(
+            changes(
+                OperationType::ExecuteSC {
+                    data: vec![1, 2, 3],
+                    max_gas: 100,
+                    max_coins: Amount::from_str("1").unwrap(),
+                    datastore: Datastore::default(),
+                },
+                &fee_target
+            ),
+            expected(vec![(sender, "1.5", false), (fee_target, "0.5", true)])
+        );
+    }
 }
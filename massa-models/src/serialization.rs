@@ -673,10 +673,8 @@ impl Deserializer<BitVec<u8>> for BitVecDeserializer {
 mod tests {
     use super::*;
     use massa_serialization::DeserializeError;
-    use serial_test::serial;
     use std::ops::Bound::Included;
     #[test]
-    #[serial]
     fn vec_u8() {
         let vec: Vec<u8> = vec![9, 8, 7];
         let vec_u8_serializer = VecU8Serializer::new();
@@ -691,7 +689,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn vec_u8_big_length() {
         let vec: Vec<u8> = vec![9, 8, 7];
         let len: u64 = 10;
@@ -707,7 +704,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn vec_u8_min_length() {
         let vec: Vec<u8> = vec![9, 8, 7];
         let len: u64 = 1;
@@ -725,7 +721,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_be_min() {
         let x32 = 70_000u32;
         let x64 = 10_000_000_000u64;
@@ -754,7 +749,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_array_from_slice_with_zero_u64() {
         let zero: u64 = 0;
         let res = array_from_slice(&zero.to_be_bytes()).unwrap();
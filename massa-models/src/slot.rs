@@ -1,5 +1,6 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
+use crate::config::ROLL_SELL_CREDIT_DELAY_CYCLES;
 use crate::error::ModelsError;
 use massa_hash::Hash;
 use massa_serialization::{
@@ -213,6 +214,20 @@ impl Slot {
         self.period / periods_per_cycle
     }
 
+    /// slot at which the price of rolls sold at this slot is credited back to the seller:
+    /// the last slot of the cycle coming `ROLL_SELL_CREDIT_DELAY_CYCLES` cycles later
+    pub fn get_roll_sell_credit_slot(
+        &self,
+        periods_per_cycle: u64,
+        thread_count: u8,
+    ) -> Result<Slot, ModelsError> {
+        let credit_cycle = self
+            .get_cycle(periods_per_cycle)
+            .checked_add(ROLL_SELL_CREDIT_DELAY_CYCLES)
+            .ok_or(ModelsError::PeriodOverflowError)?;
+        Slot::new_last_of_cycle(credit_cycle, periods_per_cycle, thread_count)
+    }
+
     /// check if the slot is last in the cycle
     pub fn is_last_of_cycle(&self, periods_per_cycle: u64, thread_count: u8) -> bool {
         self.period % periods_per_cycle == (periods_per_cycle.saturating_sub(1))
@@ -391,6 +406,29 @@ mod test {
         assert_eq!(actual_error, expected_error);
     }
 
+    #[test]
+    fn test_get_roll_sell_credit_slot() {
+        // sold anywhere in cycle 5: credited at the end of cycle 8
+        let expected_slot = Slot::new_last_of_cycle(8, 128, 32).unwrap();
+        assert_eq!(
+            Slot::new(640, 0)
+                .get_roll_sell_credit_slot(128, 32)
+                .unwrap(),
+            expected_slot
+        );
+        assert_eq!(
+            Slot::new(767, 31)
+                .get_roll_sell_credit_slot(128, 32)
+                .unwrap(),
+            expected_slot
+        );
+
+        // overflow
+        assert!(Slot::new(u64::MAX, 0)
+            .get_roll_sell_credit_slot(1, 32)
+            .is_err());
+    }
+
     #[test]
     fn test_slot_serde() {
         let expected_slot = Slot::new(12, 32);
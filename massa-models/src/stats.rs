@@ -64,6 +64,9 @@ pub struct NetworkStats {
     pub banned_peer_count: u64,
     /// active node count
     pub active_node_count: u64,
+    /// number of messages received and sent during the current and previous slots
+    #[serde(default)]
+    pub message_counters: SlotMessageCounters,
 }
 
 impl std::fmt::Display for NetworkStats {
@@ -74,10 +77,72 @@ impl std::fmt::Display for NetworkStats {
         writeln!(f, "\tKnown peers: {}", self.known_peer_count)?;
         writeln!(f, "\tBanned peers: {}", self.banned_peer_count)?;
         writeln!(f, "\tActive nodes: {}", self.active_node_count)?;
+        write!(f, "{}", self.message_counters)?;
         Ok(())
     }
 }
 
+/// messages received and sent by the node, counted per slot
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SlotMessageCounters {
+    /// slot being counted
+    pub current_slot: Option<Slot>,
+    /// messages received during the current slot so far
+    pub current_received: u64,
+    /// messages sent during the current slot so far
+    pub current_sent: u64,
+    /// last slot counted before the current one
+    pub previous_slot: Option<Slot>,
+    /// messages received during the previous slot
+    pub previous_received: u64,
+    /// messages sent during the previous slot
+    pub previous_sent: u64,
+}
+
+impl SlotMessageCounters {
+    /// Makes `slot` the counted slot if it is later than the current one:
+    /// the current counts become the previous ones and counting restarts from zero.
+    pub fn advance(&mut self, slot: Slot) {
+        if self.current_slot.map_or(true, |current| slot > current) {
+            self.previous_slot = self.current_slot;
+            self.previous_received = self.current_received;
+            self.previous_sent = self.current_sent;
+            self.current_slot = Some(slot);
+            self.current_received = 0;
+            self.current_sent = 0;
+        }
+    }
+}
+
+impl std::fmt::Display for SlotMessageCounters {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(slot) = self.current_slot {
+            writeln!(
+                f,
+                "\tMessages in/out during current slot {}: {}/{}",
+                slot, self.current_received, self.current_sent
+            )?;
+        }
+        if let Some(slot) = self.previous_slot {
+            writeln!(
+                f,
+                "\tMessages in/out during previous slot {}: {}/{}",
+                slot, self.previous_received, self.previous_sent
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// number of blocks discarded over a window of periods, by discard reason
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiscardedBlockCounts {
+    /// blocks discarded because they were stale
+    pub stale: u64,
+    /// blocks discarded because they were invalid
+    pub invalid: u64,
+}
+
 /// stats produced by consensus module
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConsensusStats {
@@ -91,6 +156,27 @@ pub struct ConsensusStats {
     pub stale_block_count: u64,
     ///  number of actives cliques
     pub clique_count: u64,
+    /// average number of slots between a block becoming active and becoming final, over the time span
+    #[serde(default)]
+    pub average_finality_latency: Option<f64>,
+    /// blocks discarded over the discard stats window, whose length is set in the consensus settings
+    #[serde(default)]
+    pub recent_discarded_blocks: DiscardedBlockCounts,
+    /// number of slots elapsed since the latest final periods last advanced, a rising value signals a consensus stall
+    #[serde(default)]
+    pub slots_since_last_finality: u64,
+    /// average number of blocks produced per period in each thread over the time span,
+    /// a thread far below 1 signals missed draws or offline stakers
+    #[serde(default)]
+    pub production_rate_per_thread: Vec<f64>,
+    /// fraction of the endorsement draws of the current cycle filled in the final and blockclique blocks,
+    /// a low value signals offline endorsers, `None` if no such block of the current cycle is known
+    #[serde(default)]
+    pub endorsement_participation: Option<f64>,
+    /// number of operations pruned from the block graph since the node started:
+    /// they were included in final blocks that were pruned, and are only known to the final state from then on
+    #[serde(default)]
+    pub pruned_operation_count: u64,
 }
 
 impl std::fmt::Display for ConsensusStats {
@@ -109,6 +195,42 @@ impl std::fmt::Display for ConsensusStats {
         writeln!(f, "\tFinal block count: {}", self.final_block_count)?;
         writeln!(f, "\tStale block count: {}", self.stale_block_count)?;
         writeln!(f, "\tClique count: {}", self.clique_count)?;
+        if let Some(latency) = self.average_finality_latency {
+            writeln!(f, "\tAverage finality latency: {:.2} slots", latency)?;
+        }
+        writeln!(
+            f,
+            "\tRecently discarded blocks: {} stale, {} invalid",
+            self.recent_discarded_blocks.stale, self.recent_discarded_blocks.invalid
+        )?;
+        writeln!(
+            f,
+            "\tSlots since last finality: {}",
+            self.slots_since_last_finality
+        )?;
+        if !self.production_rate_per_thread.is_empty() {
+            writeln!(
+                f,
+                "\tProduction rate per thread: {}",
+                self.production_rate_per_thread
+                    .iter()
+                    .map(|rate| format!("{:.2}", rate))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+        if let Some(participation) = self.endorsement_participation {
+            writeln!(
+                f,
+                "\tEndorsement participation: {:.2}%",
+                participation * 100.0
+            )?;
+        }
+        writeln!(
+            f,
+            "\tPruned operation count: {}",
+            self.pruned_operation_count
+        )?;
         Ok(())
     }
 }
@@ -130,3 +252,32 @@ impl std::fmt::Display for PoolStats {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_slot_message_counters() {
+        let mut counters = SlotMessageCounters::default();
+        counters.advance(Slot::new(1, 0));
+        counters.current_received = 5;
+        counters.current_sent = 1;
+        assert_eq!(counters.current_slot, Some(Slot::new(1, 0)));
+        assert_eq!(counters.previous_slot, None);
+
+        // moving to the next slot resets the current counts
+        counters.advance(Slot::new(1, 1));
+        assert_eq!(counters.current_slot, Some(Slot::new(1, 1)));
+        assert_eq!((counters.current_received, counters.current_sent), (0, 0));
+        assert_eq!(counters.previous_slot, Some(Slot::new(1, 0)));
+        assert_eq!((counters.previous_received, counters.previous_sent), (5, 1));
+
+        // an earlier slot does not move the counting back
+        counters.current_received = 1;
+        counters.advance(Slot::new(1, 0));
+        assert_eq!(counters.current_slot, Some(Slot::new(1, 1)));
+        assert_eq!(counters.current_received, 1);
+        assert_eq!(counters.previous_slot, Some(Slot::new(1, 0)));
+    }
+}
@@ -203,10 +203,8 @@ pub fn get_closest_slot_to_timestamp(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serial_test::serial;
 
     #[test]
-    #[serial]
     fn test_slot_count_in_range() {
         assert_eq!(
             slot_count_in_range(Slot::new(100, 3), Slot::new(100, 3), 32).unwrap(),
@@ -227,7 +225,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_time_range_to_slot_range() {
         let thread_count = 3u8;
         let t0: MassaTime = MassaTime::from_millis(30);
@@ -284,7 +281,6 @@ mod tests {
     }
 
     #[test]
-    #[serial]
     fn test_get_closest_slot_to_timestamp() {
         let thread_count = 3u8;
         let t0: MassaTime = MassaTime::from_millis(30);
@@ -1,6 +1,6 @@
 use massa_hash::Hash;
 use massa_models::prehash::BuildHashMapper;
-use massa_sc_runtime::{Compiler, RuntimeModule};
+use massa_sc_runtime::{Compiler, GasCosts, RuntimeModule};
 use schnellru::{ByLength, LruMap};
 use tracing::debug;
 
@@ -54,6 +54,17 @@ impl ModuleCache {
         }
     }
 
+    /// Replace the gas costs used by the cache, for example after a network upgrade.
+    ///
+    /// Compiled modules and their initialization costs depend on the gas costs,
+    /// so every cached entry is dropped to avoid reusing results computed under the old ones.
+    pub fn set_gas_costs(&mut self, gas_costs: GasCosts) {
+        self.cfg.gas_costs = gas_costs;
+        self.lru_cache.clear();
+        self.hd_cache.clear();
+        debug!("module cache cleared after a gas costs change");
+    }
+
     /// Save a new or an already existing module in the cache
     pub fn save_module(&mut self, bytecode: &[u8]) {
         let hash = Hash::compute_from(bytecode);
@@ -185,3 +196,77 @@ impl ModuleCache {
         Ok(module)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    // wasm module exporting an `add_one` function
+    const BYTECODE: [u8; 72] = [
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01,
+        0x7f, 0x03, 0x02, 0x01, 0x00, 0x07, 0x0b, 0x01, 0x07, 0x61, 0x64, 0x64, 0x5f, 0x6f, 0x6e,
+        0x65, 0x00, 0x00, 0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x41, 0x01, 0x6a, 0x0b, 0x00,
+        0x1a, 0x04, 0x6e, 0x61, 0x6d, 0x65, 0x01, 0x0a, 0x01, 0x00, 0x07, 0x61, 0x64, 0x64, 0x5f,
+        0x6f, 0x6e, 0x65, 0x02, 0x07, 0x01, 0x00, 0x01, 0x00, 0x02, 0x70, 0x30,
+    ];
+
+    fn setup(hd_cache_dir: &TempDir) -> ModuleCache {
+        ModuleCache::new(ModuleCacheConfig {
+            hd_cache_path: hd_cache_dir.path().to_path_buf(),
+            gas_costs: GasCosts::default(),
+            lru_cache_size: 10,
+            hd_cache_size: 10,
+            snip_amount: 1,
+            max_module_length: 1000,
+        })
+    }
+
+    #[test]
+    #[serial]
+    fn test_gas_costs_change_clears_cache() {
+        let hd_cache_dir = TempDir::new().unwrap();
+        let mut cache = setup(&hd_cache_dir);
+
+        cache.save_module(&BYTECODE);
+        cache.set_init_cost(&BYTECODE, 100);
+        assert!(matches!(
+            cache.load_module_info(&BYTECODE),
+            ModuleInfo::ModuleAndDelta((_, 100))
+        ));
+
+        // the init cost computed under the old gas costs must be forgotten
+        cache.set_gas_costs(GasCosts::default());
+        assert!(matches!(
+            cache.load_module_info(&BYTECODE),
+            ModuleInfo::Module(_)
+        ));
+    }
+
+    #[test]
+    #[serial]
+    fn test_gas_costs_change_recompiles_module() {
+        let hd_cache_dir = TempDir::new().unwrap();
+        let mut cache = setup(&hd_cache_dir);
+
+        // the cached entry is served as long as the gas costs do not change
+        cache.save_module(&BYTECODE);
+        cache.set_invalid(&BYTECODE, "stale".to_string());
+        assert!(matches!(
+            cache.load_module_info(&BYTECODE),
+            ModuleInfo::Invalid(_)
+        ));
+
+        // new gas costs force the module to be compiled again from its bytecode
+        let mut gas_costs = GasCosts::default();
+        gas_costs.max_instance_cost += 1;
+        let execution_gas = gas_costs.max_instance_cost;
+        cache.set_gas_costs(gas_costs);
+        assert!(matches!(
+            cache.load_module_info(&BYTECODE),
+            ModuleInfo::Module(_)
+        ));
+        assert!(cache.load_module(&BYTECODE, execution_gas).is_ok());
+    }
+}
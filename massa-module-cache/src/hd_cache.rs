@@ -136,6 +136,18 @@ impl HDCache {
             .expect(CRUD_ERROR);
     }
 
+    /// Remove every entry from the db
+    pub fn clear(&mut self) {
+        let mut batch = WriteBatch::default();
+        for (key, _) in self.db.iterator(IteratorMode::Start).flatten() {
+            batch.delete(key);
+        }
+        self.db.write(batch).expect(CRUD_ERROR);
+        self.entry_count = 0;
+
+        debug!("(HD clear) entry_count is: {}", self.entry_count);
+    }
+
     /// Retrieve a module
     pub fn get(&self, hash: Hash, gas_costs: GasCosts) -> Option<ModuleInfo> {
         let mut iterator = self
@@ -292,6 +304,21 @@ mod tests {
         dbg!(cache.entry_count);
     }
 
+    #[test]
+    #[serial]
+    fn test_clear() {
+        let mut cache = setup();
+        let hash = Hash::compute_from(b"test_hash");
+        let module = make_default_module_info();
+
+        cache.insert(hash, module);
+        assert!(cache.get(hash, GasCosts::default()).is_some());
+
+        cache.clear();
+        assert_eq!(cache.entry_count, 0);
+        assert!(cache.get(hash, GasCosts::default()).is_none());
+    }
+
     #[test]
     #[serial]
     fn test_missing_module() {
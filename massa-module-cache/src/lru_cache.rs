@@ -64,4 +64,10 @@ impl LRUCache {
             *content = ModuleInfo::Invalid(err_msg);
         }
     }
+
+    /// Remove every module from the LRU cache
+    pub fn clear(&mut self) {
+        self.cache.clear();
+        debug!("(LRU clear) length is: {}", self.cache.len());
+    }
 }
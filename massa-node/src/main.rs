@@ -179,6 +179,7 @@ async fn launch(
         max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
         max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
         initial_deferred_credits_path: SETTINGS.ledger.initial_deferred_credits_path.clone(),
+        min_active_rolls_for_counting: SETTINGS.selector.min_active_rolls_for_counting,
     };
     let executed_ops_config = ExecutedOpsConfig {
         thread_count: THREAD_COUNT,
@@ -205,6 +206,8 @@ async fn launch(
         max_executed_denunciations_length: MAX_DENUNCIATION_CHANGES_LENGTH,
         max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
         ledger_backup_periods_interval: SETTINGS.ledger.ledger_backup_periods_interval,
+        ledger_hash_log_slots_interval: SETTINGS.ledger.ledger_hash_log_slots_interval,
+        flush_on_finality: SETTINGS.ledger.flush_on_finality,
         t0: T0,
         genesis_timestamp: *GENESIS_TIMESTAMP,
     };
@@ -396,6 +399,13 @@ async fn launch(
         panic!("critical: db is not valid after bootstrap");
     }
 
+    if SETTINGS.ledger.verify_integrity_at_startup {
+        info!("Verifying ledger integrity");
+        if let Err(err) = final_state.read().get_ledger().verify_integrity() {
+            panic!("critical: ledger integrity check failed: {}", err);
+        }
+    }
+
     if args.restart_from_snapshot_at_period.is_none() {
         final_state.write().recompute_caches();
 
@@ -590,7 +600,12 @@ async fn launch(
         denunciation_expire_periods: DENUNCIATION_EXPIRE_PERIODS,
         max_denunciations_per_block_header: MAX_DENUNCIATIONS_PER_BLOCK_HEADER,
         minimal_fees: SETTINGS.pool.minimal_fees,
+        congestion_fee_floors: SETTINGS.pool.congestion_fee_floors.clone(),
+        reject_transfers_to_unknown_addresses: SETTINGS.pool.reject_transfers_to_unknown_addresses,
         last_start_period: final_state.read().get_last_start_period(),
+        operation_selection_strategy: SETTINGS.pool.operation_selection_strategy,
+        max_inclusion_latency_samples: SETTINGS.pool.max_inclusion_latency_samples,
+        chain_id: *CHAINID,
     };
 
     let pool_channels = PoolChannels {
@@ -611,6 +626,7 @@ async fn launch(
         &shared_storage,
         pool_channels.clone(),
         node_wallet.clone(),
+        SETTINGS.pool.operations_persistence_path.clone(),
     );
 
     // launch protocol controller
@@ -637,6 +653,7 @@ async fn launch(
             .operation_announcement_buffer_capacity,
         operation_batch_proc_period: SETTINGS.protocol.operation_batch_proc_period,
         operation_announcement_interval: SETTINGS.protocol.operation_announcement_interval,
+        eager_local_operations_propagation: SETTINGS.protocol.eager_local_operations_propagation,
         max_operations_per_message: SETTINGS.protocol.max_operations_per_message,
         max_serialized_operations_size_per_block: MAX_BLOCK_SIZE as usize,
         max_operations_per_block: MAX_OPERATIONS_PER_BLOCK,
@@ -648,6 +665,9 @@ async fn launch(
         max_message_size: MAX_MESSAGE_SIZE as usize,
         max_ops_kept_for_propagation: SETTINGS.protocol.max_ops_kept_for_propagation,
         max_operations_propagation_time: SETTINGS.protocol.max_operations_propagation_time,
+        operation_validity_periods: OPERATION_VALIDITY_PERIODS,
+        max_future_validity_periods: SETTINGS.protocol.max_future_validity_periods,
+        max_signature_verification_threads: SETTINGS.protocol.max_signature_verification_threads,
         max_endorsements_propagation_time: SETTINGS.protocol.max_endorsements_propagation_time,
         last_start_period: final_state.read().get_last_start_period(),
         max_endorsements_per_message: MAX_ENDORSEMENTS_PER_MESSAGE as u64,
@@ -744,6 +764,11 @@ async fn launch(
             .consensus
             .force_keep_final_periods_without_ops,
         chain_id: *CHAINID,
+        max_ancestry_walk_blocks: SETTINGS.consensus.max_ancestry_walk_blocks,
+        log_finality_latency: SETTINGS.consensus.log_finality_latency,
+        discard_stats_window_periods: SETTINGS.consensus.discard_stats_window_periods,
+        strict_operation_merkle_root: SETTINGS.consensus.strict_operation_merkle_root,
+        max_wishlist_fetches: SETTINGS.consensus.max_wishlist_fetches.get(),
     };
 
     let (consensus_event_sender, consensus_event_receiver) =
@@ -848,6 +873,7 @@ async fn launch(
         bind_api: SETTINGS.api.bind_api,
         draw_lookahead_period_count: SETTINGS.api.draw_lookahead_period_count,
         max_arguments: SETTINGS.api.max_arguments,
+        max_ops_per_address_query: SETTINGS.api.max_ops_per_address_query,
         openrpc_spec_path: SETTINGS.api.openrpc_spec_path.clone(),
         bootstrap_whitelist_path: SETTINGS.bootstrap.bootstrap_whitelist_path.clone(),
         bootstrap_blacklist_path: SETTINGS.bootstrap.bootstrap_blacklist_path.clone(),
@@ -879,6 +905,7 @@ async fn launch(
         chain_id: *CHAINID,
         deferred_credits_delta: SETTINGS.api.deferred_credits_delta,
         minimal_fees: SETTINGS.pool.minimal_fees,
+        reject_queries_until_ready: SETTINGS.api.reject_queries_until_ready,
     };
 
     // spawn Massa API
@@ -1006,6 +1033,7 @@ async fn launch(
         api_config.clone(),
         sig_int_toggled,
         node_wallet,
+        final_state.clone(),
     );
     let api_private_handle = api_private
         .serve(&SETTINGS.api.bind_private, &api_config)
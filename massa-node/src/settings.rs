@@ -1,10 +1,11 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
 //! Build here the default node settings from the configuration file toml
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, num::NonZeroUsize, path::PathBuf};
 
 use massa_bootstrap::IpType;
 use massa_models::{amount::Amount, config::build_massa_settings, node::NodeId};
+use massa_pool_exports::{CongestionFeeFloor, OperationSelectionStrategy};
 use massa_protocol_exports::PeerCategoryInfo;
 use massa_time::MassaTime;
 use serde::Deserialize;
@@ -42,6 +43,7 @@ pub struct ExecutionSettings {
 #[derive(Clone, Debug, Deserialize)]
 pub struct SelectionSettings {
     pub initial_rolls_path: PathBuf,
+    pub min_active_rolls_for_counting: u64,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -51,7 +53,10 @@ pub struct LedgerSettings {
     pub final_history_length: usize,
     pub initial_deferred_credits_path: Option<PathBuf>,
     pub ledger_backup_periods_interval: u64,
+    pub ledger_hash_log_slots_interval: u64,
+    pub flush_on_finality: bool,
     pub max_ledger_backups: u64,
+    pub verify_integrity_at_startup: bool,
 }
 
 /// Bootstrap configuration.
@@ -105,6 +110,16 @@ pub struct PoolSettings {
     pub broadcast_operations_channel_capacity: usize,
     /// operations minimum fees for block creator
     pub minimal_fees: Amount,
+    /// minimal fees required to enter the pool depending on its fullness
+    pub congestion_fee_floors: Vec<CongestionFeeFloor>,
+    /// drop transactions sending coins to addresses unknown to the ledger
+    pub reject_transfers_to_unknown_addresses: bool,
+    /// order in which operations are picked when producing a block, "score" by default
+    pub operation_selection_strategy: OperationSelectionStrategy,
+    /// number of recently included operations over which the inclusion latency stats are computed
+    pub max_inclusion_latency_samples: usize,
+    /// file in which pool operations are kept across restarts (disabled if not set)
+    pub operations_persistence_path: Option<PathBuf>,
 }
 
 /// API and server configuration, read from a file configuration.
@@ -115,6 +130,7 @@ pub struct APISettings {
     pub bind_public: SocketAddr,
     pub bind_api: SocketAddr,
     pub max_arguments: u64,
+    pub max_ops_per_address_query: u64,
     pub openrpc_spec_path: PathBuf,
     pub max_request_body_size: u32,
     pub max_response_body_size: u32,
@@ -129,6 +145,8 @@ pub struct APISettings {
     // whether to broadcast for blocks, endorsement and operations
     pub enable_broadcast: bool,
     pub deferred_credits_delta: MassaTime,
+    /// whether read queries fail until the block graph caught up after startup
+    pub reject_queries_until_ready: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -174,6 +192,16 @@ pub struct ConsensusSettings {
     pub broadcast_blocks_channel_capacity: usize,
     /// filled blocks channel capacity
     pub broadcast_filled_blocks_channel_capacity: usize,
+    /// maximum number of blocks visited when walking the ancestry of a block
+    pub max_ancestry_walk_blocks: usize,
+    /// log the number of slots each block spent active before becoming final
+    pub log_finality_latency: bool,
+    /// number of periods over which block discards are counted in the stats
+    pub discard_stats_window_periods: u64,
+    /// discard the incoming blocks whose operation list does not match the operation merkle root of their header, before any other check
+    pub strict_operation_merkle_root: bool,
+    /// maximum number of missing dependencies asked to the network at once, 0 is rejected as nothing would ever be asked
+    pub max_wishlist_fetches: NonZeroUsize,
 }
 
 // TODO: Remove one date. Kept for retro compatibility.
@@ -234,12 +262,18 @@ pub struct ProtocolSettings {
     pub operation_batch_proc_period: MassaTime,
     /// Interval at which operations are announced in batches.
     pub operation_announcement_interval: MassaTime,
+    /// Announce the operations submitted to this node right away instead of waiting for the next batch.
+    pub eager_local_operations_propagation: bool,
     /// Maximum of operations sent in one message.
     pub max_operations_per_message: u64,
     /// MAx number of operations kept for propagation
     pub max_ops_kept_for_propagation: usize,
     /// Time threshold after which operation are not propagated
     pub max_operations_propagation_time: MassaTime,
+    /// Operations whose validity starts more than this number of periods after the current one are dropped
+    pub max_future_validity_periods: u64,
+    /// Max number of threads verifying operation signatures, defaults to the number of CPUs
+    pub max_signature_verification_threads: Option<usize>,
     /// Time threshold after which operation are not propagated
     pub max_endorsements_propagation_time: MassaTime,
     /// Path for initial peers
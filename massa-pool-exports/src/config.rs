@@ -5,7 +5,7 @@ use massa_time::MassaTime;
 use serde::{Deserialize, Serialize};
 
 /// Pool configuration
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct PoolConfig {
     /// thread count
     pub thread_count: u8,
@@ -59,9 +59,43 @@ pub struct PoolConfig {
     pub max_denunciations_per_block_header: u32,
     /// Minimum acceptable fees to include an operation in a block
     pub minimal_fees: Amount,
+    /// minimal fees required to enter the operation pool depending on how full it is
+    pub congestion_fee_floors: Vec<CongestionFeeFloor>,
+    /// drop transactions whose recipient does not exist in the final or candidate ledger
+    pub reject_transfers_to_unknown_addresses: bool,
     /// last_start_period
     /// * If start all new network: set to 0
     /// * If from snapshot: retrieve from args
     /// * If from bootstrap: set during bootstrap
     pub last_start_period: u64,
+    /// order in which operations are picked when filling a block, `Score` by default
+    pub operation_selection_strategy: OperationSelectionStrategy,
+    /// number of recently included operations over which the inclusion latency stats are computed
+    pub max_inclusion_latency_samples: usize,
+    /// chain id
+    pub chain_id: u64,
+}
+
+/// Minimal fee required to enter the operation pool once it is filled above a given ratio
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+pub struct CongestionFeeFloor {
+    /// pool fullness (in percent of `max_operation_pool_size`) from which the floor applies
+    pub fullness_percent: u8,
+    /// minimal fee of the operations entering the pool above that fullness
+    pub min_fee: Amount,
+}
+
+/// Order in which the pool picks operations when filling a block
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationSelectionStrategy {
+    /// best pool score first: fee weighted by the resources used and the inclusion chances
+    #[default]
+    Score,
+    /// highest fee first
+    HighestFee,
+    /// oldest operation in the pool first
+    Arrival,
+    /// one operation per creator address in turn, each address in score order
+    AddressFairness,
 }
@@ -1,6 +1,7 @@
 // Copyright (c) 2022 MASSA LABS <info@massa.net>
 
 use massa_models::{
+    amount::Amount,
     block_id::BlockId,
     denunciation::{Denunciation, DenunciationPrecursor},
     endorsement::EndorsementId,
@@ -8,6 +9,7 @@ use massa_models::{
     slot::Slot,
 };
 use massa_storage::Storage;
+use massa_time::MassaTime;
 
 #[cfg(feature = "test-exports")]
 use std::sync::{Arc, RwLock};
@@ -46,6 +48,14 @@ pub trait PoolController: Send + Sync {
     /// Get the number of operations in the pool
     fn get_operation_count(&self) -> usize;
 
+    /// Get the minimal fee currently required for an operation to enter the pool
+    fn get_operation_fee_floor(&self) -> Amount;
+
+    /// Get the fee-weighted median latency between the moment the pool first saw an operation
+    /// and its inclusion in a final block, over the latest included operations.
+    /// Returns `None` if no operation was included yet.
+    fn get_fee_weighted_median_inclusion_latency(&self) -> Option<MassaTime>;
+
     /// Check if the pool contains a list of endorsements. Returns one boolean per item.
     fn contains_endorsements(&self, endorsements: &[EndorsementId]) -> Vec<bool>;
 
@@ -10,7 +10,7 @@ mod config;
 mod controller_traits;
 
 pub use channels::{PoolBroadcasts, PoolChannels};
-pub use config::PoolConfig;
+pub use config::{CongestionFeeFloor, OperationSelectionStrategy, PoolConfig};
 pub use controller_traits::{PoolController, PoolManager};
 
 #[cfg(feature = "test-exports")]
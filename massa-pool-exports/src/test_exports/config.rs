@@ -3,9 +3,10 @@
 use massa_models::{
     amount::Amount,
     config::{
-        BASE_OPERATION_GAS_COST, DENUNCIATION_EXPIRE_PERIODS, ENDORSEMENT_COUNT, MAX_BLOCK_SIZE,
-        MAX_DENUNCIATIONS_PER_BLOCK_HEADER, MAX_GAS_PER_BLOCK, MAX_OPERATIONS_PER_BLOCK,
-        OPERATION_VALIDITY_PERIODS, PERIODS_PER_CYCLE, ROLL_PRICE, T0, THREAD_COUNT,
+        BASE_OPERATION_GAS_COST, CHAINID, DENUNCIATION_EXPIRE_PERIODS, ENDORSEMENT_COUNT,
+        MAX_BLOCK_SIZE, MAX_DENUNCIATIONS_PER_BLOCK_HEADER, MAX_GAS_PER_BLOCK,
+        MAX_OPERATIONS_PER_BLOCK, OPERATION_VALIDITY_PERIODS, PERIODS_PER_CYCLE, ROLL_PRICE, T0,
+        THREAD_COUNT,
     },
 };
 use massa_time::MassaTime;
@@ -42,6 +43,11 @@ impl Default for PoolConfig {
             operation_pool_refresh_interval: MassaTime::from_millis(2000),
             operation_max_future_start_delay: T0.saturating_mul(5),
             minimal_fees: Amount::zero(),
+            congestion_fee_floors: Vec::new(),
+            reject_transfers_to_unknown_addresses: false,
+            operation_selection_strategy: Default::default(),
+            max_inclusion_latency_samples: 1000,
+            chain_id: *CHAINID,
         }
     }
 }
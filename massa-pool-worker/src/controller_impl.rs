@@ -3,11 +3,13 @@
 //! Pool controller implementation
 
 use massa_models::{
-    block_id::BlockId, denunciation::Denunciation, denunciation::DenunciationPrecursor,
-    endorsement::EndorsementId, operation::OperationId, slot::Slot,
+    amount::Amount, block_id::BlockId, denunciation::Denunciation,
+    denunciation::DenunciationPrecursor, endorsement::EndorsementId, operation::OperationId,
+    slot::Slot,
 };
 use massa_pool_exports::{PoolConfig, PoolController, PoolManager};
 use massa_storage::Storage;
+use massa_time::MassaTime;
 use parking_lot::RwLock;
 use std::sync::mpsc::TrySendError;
 use std::sync::{mpsc::SyncSender, Arc};
@@ -204,6 +206,18 @@ impl PoolController for PoolControllerImpl {
         self.operation_pool.read().len()
     }
 
+    /// Get the minimal fee currently required for an operation to enter the pool
+    fn get_operation_fee_floor(&self) -> Amount {
+        self.operation_pool.read().get_fee_floor()
+    }
+
+    /// Get the fee-weighted median latency between the first sight of an operation and its inclusion in a final block
+    fn get_fee_weighted_median_inclusion_latency(&self) -> Option<MassaTime> {
+        self.operation_pool
+            .read()
+            .get_fee_weighted_median_inclusion_latency()
+    }
+
     /// Check if the pool contains a list of endorsements. Returns one boolean per item.
     fn contains_endorsements(&self, endorsements: &[EndorsementId]) -> Vec<bool> {
         let lck = self.endorsement_pool.read();
@@ -0,0 +1,99 @@
+// Copyright (c) 2022 MASSA LABS <info@massa.net>
+
+//! Latency between the moment an operation is first seen by the pool and its inclusion in a final block.
+//!
+//! The pool drops an operation as soon as it is executed in a candidate block, so such operations
+//! are kept aside until their execution becomes final (or is reverted) before their latency is recorded.
+
+use std::collections::VecDeque;
+
+use massa_models::{amount::Amount, operation::OperationId, prehash::PreHashMap};
+use massa_time::MassaTime;
+
+/// Inclusion latencies of the latest operations included in final blocks
+pub(crate) struct InclusionLatencies {
+    /// operations executed in candidate blocks, waiting for finality: fee and first-seen time
+    pending: PreHashMap<OperationId, (Amount, MassaTime)>,
+    /// fee and inclusion latency of the latest operations included in final blocks, oldest first
+    samples: VecDeque<(Amount, MassaTime)>,
+    /// maximal number of kept samples (and of operations waiting for finality)
+    max_samples: usize,
+}
+
+impl InclusionLatencies {
+    pub(crate) fn new(max_samples: usize) -> Self {
+        InclusionLatencies {
+            pending: Default::default(),
+            samples: VecDeque::with_capacity(max_samples),
+            max_samples,
+        }
+    }
+
+    /// Record the latency of an operation that was just included in a final block
+    pub(crate) fn record_final(&mut self, fee: Amount, first_seen: MassaTime, now: MassaTime) {
+        if self.max_samples == 0 {
+            return;
+        }
+        if self.samples.len() >= self.max_samples {
+            self.samples.pop_front();
+        }
+        self.samples
+            .push_back((fee, now.saturating_sub(first_seen)));
+    }
+
+    /// Keep an operation executed in a candidate block until its execution becomes final
+    pub(crate) fn track_candidate(&mut self, id: OperationId, fee: Amount, first_seen: MassaTime) {
+        if self.pending.len() < self.max_samples {
+            self.pending.insert(id, (fee, first_seen));
+        }
+    }
+
+    /// Operations waiting for their execution to become final
+    pub(crate) fn candidate_ids(&self) -> Vec<OperationId> {
+        self.pending.keys().copied().collect()
+    }
+
+    /// Update the status of an operation waiting for finality.
+    ///
+    /// `final_status` records its latency, `executed = false` means the candidate execution
+    /// was reverted and the operation is forgotten.
+    pub(crate) fn update_candidate(
+        &mut self,
+        id: &OperationId,
+        executed: bool,
+        final_status: bool,
+        now: MassaTime,
+    ) {
+        if final_status {
+            if let Some((fee, first_seen)) = self.pending.remove(id) {
+                self.record_final(fee, first_seen, now);
+            }
+        } else if !executed {
+            self.pending.remove(id);
+        }
+    }
+
+    /// Fee-weighted median of the recorded inclusion latencies: half of the fees were paid by
+    /// operations included at most that fast.
+    /// Falls back to the plain median if none of the operations paid fees.
+    pub(crate) fn fee_weighted_median(&self) -> Option<MassaTime> {
+        let mut samples: Vec<(MassaTime, u64)> = self
+            .samples
+            .iter()
+            .map(|(fee, latency)| (*latency, fee.to_raw()))
+            .collect();
+        samples.sort_unstable();
+        let total_weight: u128 = samples.iter().map(|(_, weight)| u128::from(*weight)).sum();
+        if total_weight == 0 {
+            return samples.get(samples.len() / 2).map(|(latency, _)| *latency);
+        }
+        let mut cumulated_weight: u128 = 0;
+        for (latency, weight) in samples {
+            cumulated_weight += u128::from(weight);
+            if cumulated_weight.saturating_mul(2) >= total_weight {
+                return Some(latency);
+            }
+        }
+        None
+    }
+}
@@ -8,7 +8,9 @@
 mod controller_impl;
 mod denunciation_pool;
 mod endorsement_pool;
+mod inclusion_latency;
 mod operation_pool;
+mod persistence;
 mod types;
 mod worker;
 
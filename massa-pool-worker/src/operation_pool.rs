@@ -3,20 +3,28 @@
 use massa_models::{
     address::Address,
     amount::Amount,
-    operation::OperationId,
+    operation::{OperationId, SecureShareOperation},
     prehash::{CapacityAllocator, PreHashMap, PreHashSet},
     slot::Slot,
     timeslots::get_latest_block_slot_at_timestamp,
 };
-use massa_pool_exports::{PoolChannels, PoolConfig};
+use massa_pool_exports::{
+    CongestionFeeFloor, OperationSelectionStrategy, PoolChannels, PoolConfig,
+};
 use massa_storage::Storage;
 use massa_time::MassaTime;
 use massa_wallet::Wallet;
 use parking_lot::RwLock;
-use std::{cmp::max, cmp::Ordering, cmp::PartialOrd, collections::BTreeSet, sync::Arc};
+use std::{
+    cmp::max,
+    cmp::Ordering,
+    cmp::PartialOrd,
+    collections::{BTreeSet, VecDeque},
+    sync::Arc,
+};
 use tracing::{debug, trace, warn};
 
-use crate::types::OperationInfo;
+use crate::{inclusion_latency::InclusionLatencies, types::OperationInfo};
 
 pub struct OperationPool {
     /// configuration
@@ -36,6 +44,12 @@ pub struct OperationPool {
 
     /// staking wallet, to know which addresses we are using to stake
     wallet: Arc<RwLock<Wallet>>,
+
+    /// arrival index of the next added operation
+    next_arrival_index: u64,
+
+    /// latency between the first sight of the operations and their inclusion in a final block
+    inclusion_latencies: InclusionLatencies,
 }
 
 impl OperationPool {
@@ -52,10 +66,12 @@ impl OperationPool {
                     .saturating_add(config.max_operation_pool_excess_items),
             ),
             last_cs_final_periods: vec![0u64; config.thread_count as usize],
+            inclusion_latencies: InclusionLatencies::new(config.max_inclusion_latency_samples),
             config,
             storage: storage.clone_without_refs(),
             channels,
             wallet,
+            next_arrival_index: 0,
         }
     }
 
@@ -124,6 +140,45 @@ impl OperationPool {
             .collect()
     }
 
+    /// Record the inclusion latency of the operations executed in final blocks.
+    /// The operations only executed in candidate blocks are kept aside until their execution becomes final.
+    fn update_inclusion_latencies(&mut self, exec_statuses: &PreHashMap<OperationId, bool>) {
+        let now = MassaTime::now();
+
+        // operations executed in candidate blocks at a previous refresh
+        let candidate_ids = self.inclusion_latencies.candidate_ids();
+        if !candidate_ids.is_empty() {
+            let statuses = self
+                .channels
+                .execution_controller
+                .get_ops_exec_status(&candidate_ids);
+            for (id, (spec_status, final_status)) in candidate_ids.iter().zip(statuses) {
+                self.inclusion_latencies.update_candidate(
+                    id,
+                    spec_status.is_some(),
+                    final_status.is_some(),
+                    now,
+                );
+            }
+        }
+
+        // pool operations that were just executed
+        for op_info in &self.sorted_ops {
+            match exec_statuses.get(&op_info.id) {
+                Some(true) => {
+                    self.inclusion_latencies
+                        .record_final(op_info.fee, op_info.first_seen, now)
+                }
+                Some(false) => self.inclusion_latencies.track_candidate(
+                    op_info.id,
+                    op_info.fee,
+                    op_info.first_seen,
+                ),
+                None => {}
+            }
+        }
+    }
+
     /// Get the candidate balances of the addresses sending the ops.
     /// Addresses that don't exist are not returned.
     fn get_sender_balances(&self) -> PreHashMap<Address, Amount> {
@@ -144,12 +199,45 @@ impl OperationPool {
             .collect()
     }
 
+    /// Get the recipients of the pooled transactions that exist neither in the final nor in the candidate ledger.
+    /// Always empty if `reject_transfers_to_unknown_addresses` is disabled.
+    fn get_unknown_recipients(&self) -> PreHashSet<Address> {
+        if !self.config.reject_transfers_to_unknown_addresses {
+            return PreHashSet::default();
+        }
+        let addrs: Vec<Address> = self
+            .sorted_ops
+            .iter()
+            .filter_map(|op_info| op_info.transfer_recipient)
+            .collect::<PreHashSet<Address>>()
+            .into_iter()
+            .collect();
+        if addrs.is_empty() {
+            return PreHashSet::default();
+        }
+        let ret = self
+            .channels
+            .execution_controller
+            .get_final_and_candidate_balance(&addrs);
+        ret.into_iter()
+            .zip(addrs)
+            .filter_map(|((f_balance, c_balance), addr)| {
+                if f_balance.is_none() && c_balance.is_none() {
+                    Some(addr)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
     /// Filter out ops that are not of interest.
     fn prefilter_ops(
         &mut self,
         exec_statuses: &PreHashMap<OperationId, bool>,
         pos_draws: &BTreeSet<Slot>,
         sender_balances: &PreHashMap<Address, Amount>,
+        unknown_recipients: &PreHashSet<Address>,
     ) {
         let mut removed = PreHashSet::default();
         self.sorted_ops.retain(|op_info| {
@@ -184,6 +272,13 @@ impl OperationPool {
                 };
             }
 
+            // filter out transactions towards unknown addresses (only filled if enabled in config)
+            if retain {
+                if let Some(recipient) = &op_info.transfer_recipient {
+                    retain = !unknown_recipients.contains(recipient);
+                }
+            }
+
             if !retain {
                 removed.insert(op_info.id);
                 return false;
@@ -339,11 +434,22 @@ impl OperationPool {
         // get execution statuses
         let exec_statuses = self.get_execution_statuses();
 
+        // measure how long the executed ops waited before being included
+        self.update_inclusion_latencies(&exec_statuses);
+
         // get sender balances
         let sender_balances = self.get_sender_balances();
 
+        // get the transaction recipients that are unknown to the ledger
+        let unknown_recipients = self.get_unknown_recipients();
+
         // pre-filter to eliminate obviously uninteresting ops
-        self.prefilter_ops(&exec_statuses, &pos_draws, &sender_balances);
+        self.prefilter_ops(
+            &exec_statuses,
+            &pos_draws,
+            &sender_balances,
+            &unknown_recipients,
+        );
 
         // score operations
         let scores = self.score_operations(&exec_statuses, &pos_draws);
@@ -375,6 +481,19 @@ impl OperationPool {
         self.storage.get_op_refs().contains(id)
     }
 
+    /// Get a copy of the operations currently in the pool, in pool order,
+    /// along with the time at which the pool first saw them
+    pub(crate) fn get_operations(&self) -> Vec<(SecureShareOperation, MassaTime)> {
+        let ops = self.storage.read_operations();
+        self.sorted_ops
+            .iter()
+            .filter_map(|op_info| {
+                ops.get(&op_info.id)
+                    .map(|op| (op.clone(), op_info.first_seen))
+            })
+            .collect()
+    }
+
     /// notify of new final slot
     pub(crate) fn notify_final_cs_periods(&mut self, final_cs_periods: &[u64]) {
         // update internal final slot counter
@@ -385,9 +504,44 @@ impl OperationPool {
         );
     }
 
+    /// Get the minimal fee an operation must pay to enter the pool at its current fullness.
+    /// It is enforced by the protocol operation handler on the operations received from peers.
+    pub(crate) fn get_fee_floor(&self) -> Amount {
+        congestion_fee_floor(
+            &self.config.congestion_fee_floors,
+            self.sorted_ops.len(),
+            self.config.max_operation_pool_size,
+        )
+    }
+
+    /// Get the fee-weighted median latency between the first sight of an operation and its inclusion in a final block
+    pub(crate) fn get_fee_weighted_median_inclusion_latency(&self) -> Option<MassaTime> {
+        self.inclusion_latencies.fee_weighted_median()
+    }
+
     /// Add a list of operations to the end of the pool.
     /// They will be cleaned up at the next refresh.
-    pub(crate) fn add_operations(&mut self, mut ops_storage: Storage) {
+    pub(crate) fn add_operations(&mut self, ops_storage: Storage) {
+        self.insert_operations(ops_storage, &PreHashMap::default());
+    }
+
+    /// Add back operations saved before a restart, keeping the time at which the pool first saw them.
+    /// They will be cleaned up at the next refresh.
+    pub(crate) fn restore_operations(
+        &mut self,
+        ops_storage: Storage,
+        first_seen: &PreHashMap<OperationId, MassaTime>,
+    ) {
+        self.insert_operations(ops_storage, first_seen);
+    }
+
+    /// Add a list of operations to the end of the pool.
+    /// Operations missing from `first_seen` are considered first seen now.
+    fn insert_operations(
+        &mut self,
+        mut ops_storage: Storage,
+        first_seen: &PreHashMap<OperationId, MassaTime>,
+    ) {
         // List all the new operations
         let mut new_op_ids = ops_storage.get_op_refs() - self.storage.get_op_refs();
 
@@ -419,6 +573,7 @@ impl OperationPool {
         // so that they can still be picked for block production before refresh but with low priority
         // because in that case we don't know anything about their quality.
         {
+            let now = MassaTime::now();
             let ops = ops_storage.read_operations();
             for new_op_id in &new_op_ids {
                 let op = ops
@@ -439,7 +594,10 @@ impl OperationPool {
                     self.config.thread_count,
                     self.config.base_operation_gas_cost,
                     self.config.sp_compilation_cost,
+                    self.next_arrival_index,
+                    first_seen.get(new_op_id).copied().unwrap_or(now),
                 ));
+                self.next_arrival_index = self.next_arrival_index.saturating_add(1);
             }
         }
 
@@ -472,23 +630,23 @@ impl OperationPool {
         // init remaining number of operations
         let mut remaining_ops = self.config.max_operations_per_block;
 
-        // iterate over pool operations in the right thread, from best to worst
-        for op_info in &self.sorted_ops {
+        // list the pool operations that can be included at this slot, from best to worst score
+        let candidates = self
+            .sorted_ops
+            .iter()
+            .filter(|op_info| {
+                op_info.thread == slot.thread
+                    && op_info.validity_period_range.contains(&slot.period)
+            })
+            .collect();
+
+        // iterate over them in the order of the configured strategy
+        for op_info in order_operations(candidates, self.config.operation_selection_strategy) {
             // if we have reached the maximum number of operations, stop
             if remaining_ops == 0 {
                 break;
             }
 
-            // check thread
-            if op_info.thread != slot.thread {
-                continue;
-            }
-
-            // exclude ops for which the block slot is outside of their validity range
-            if !op_info.validity_period_range.contains(&slot.period) {
-                continue;
-            }
-
             // exclude ops that are too large
             if op_info.size > remaining_space {
                 continue;
@@ -523,3 +681,67 @@ impl OperationPool {
         (op_ids, res_storage)
     }
 }
+
+/// Minimal fee required by `floors` for a pool holding `op_count` operations out of `capacity`.
+///
+/// The highest floor among the reached fullness thresholds applies, and there is none below them.
+pub(crate) fn congestion_fee_floor(
+    floors: &[CongestionFeeFloor],
+    op_count: usize,
+    capacity: usize,
+) -> Amount {
+    let fullness_percent = op_count.saturating_mul(100) / capacity.max(1);
+    floors
+        .iter()
+        .filter(|floor| fullness_percent >= usize::from(floor.fullness_percent))
+        .map(|floor| floor.min_fee)
+        .max()
+        .unwrap_or_default()
+}
+
+/// Orders the candidate operations for block production according to a selection strategy.
+///
+/// `ops` must be sorted from best to worst pool score.
+pub(crate) fn order_operations(
+    mut ops: Vec<&OperationInfo>,
+    strategy: OperationSelectionStrategy,
+) -> Vec<&OperationInfo> {
+    match strategy {
+        OperationSelectionStrategy::Score => ops,
+        OperationSelectionStrategy::HighestFee => {
+            // stable sort: equal fees stay in score order
+            ops.sort_by(|op1, op2| op2.fee.cmp(&op1.fee));
+            ops
+        }
+        OperationSelectionStrategy::Arrival => {
+            ops.sort_by_key(|op_info| op_info.arrival_index);
+            ops
+        }
+        OperationSelectionStrategy::AddressFairness => {
+            // one queue per creator address, in the order of their best operation
+            let mut queue_indices: PreHashMap<Address, usize> = PreHashMap::default();
+            let mut queues: Vec<VecDeque<&OperationInfo>> = Vec::new();
+            for op_info in &ops {
+                let queue_index =
+                    *queue_indices
+                        .entry(op_info.creator_address)
+                        .or_insert_with(|| {
+                            queues.push(VecDeque::new());
+                            queues.len() - 1
+                        });
+                queues[queue_index].push_back(op_info);
+            }
+
+            // take one operation from each address in turn
+            let mut res = Vec::with_capacity(ops.len());
+            while res.len() < ops.len() {
+                for queue in queues.iter_mut() {
+                    if let Some(op_info) = queue.pop_front() {
+                        res.push(op_info);
+                    }
+                }
+            }
+            res
+        }
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2022 MASSA LABS <info@massa.net>
+
+//! Persistence of the operation pool across node restarts.
+//!
+//! The pool operations are written to disk when the pool stops and read back when it starts,
+//! along with the time at which the pool first saw them.
+//! Re-loaded operations are re-validated: the ones with an invalid signature or that can no longer
+//! be included in a block at the current slot are dropped, and the remaining ones go through
+//! the usual pool checks at the next refresh, exactly like newly received operations.
+
+use std::{fs, io, ops::Bound::Included, path::Path};
+
+use massa_models::{
+    config::{
+        MAX_DATASTORE_VALUE_LENGTH, MAX_FUNCTION_NAME_LENGTH, MAX_OPERATION_DATASTORE_ENTRY_COUNT,
+        MAX_OPERATION_DATASTORE_KEY_LENGTH, MAX_OPERATION_DATASTORE_VALUE_LENGTH,
+        MAX_PARAMETERS_SIZE,
+    },
+    operation::{OperationDeserializer, SecureShareOperation},
+    secure_share::{SecureShareDeserializer, SecureShareSerializer},
+    slot::Slot,
+    timeslots::get_latest_block_slot_at_timestamp,
+};
+use massa_pool_exports::PoolConfig;
+use massa_serialization::{
+    DeserializeError, Deserializer, Serializer, U32VarIntDeserializer, U32VarIntSerializer,
+};
+use massa_time::{MassaTime, MassaTimeDeserializer, MassaTimeSerializer};
+use tracing::debug;
+
+fn invalid_data(err: impl std::fmt::Display) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err.to_string())
+}
+
+/// Write `operations` along with the time the pool first saw them to the file at `path`,
+/// replacing its previous content.
+///
+/// The layout is: operation count (varint), then each operation (see `SecureShareSerializer`)
+/// followed by its first-seen time.
+pub(crate) fn save_operations(
+    path: &Path,
+    operations: &[(SecureShareOperation, MassaTime)],
+) -> io::Result<()> {
+    let op_serializer = SecureShareSerializer::new();
+    let time_serializer = MassaTimeSerializer::new();
+    let count: u32 = operations
+        .len()
+        .try_into()
+        .map_err(|_| invalid_data("too many operations to persist"))?;
+    let mut bytes = Vec::new();
+    U32VarIntSerializer::new()
+        .serialize(&count, &mut bytes)
+        .map_err(invalid_data)?;
+    for (operation, first_seen) in operations {
+        op_serializer
+            .serialize(operation, &mut bytes)
+            .map_err(invalid_data)?;
+        time_serializer
+            .serialize(first_seen, &mut bytes)
+            .map_err(invalid_data)?;
+    }
+    // write to a temporary file first so that a crash never leaves a truncated file behind
+    let tmp_path = path.with_extension("tmp");
+    fs::write(&tmp_path, bytes)?;
+    fs::rename(&tmp_path, path)
+}
+
+/// Read the operations saved at `path`, along with the time the pool first saw them,
+/// and drop the ones that are not valid anymore.
+///
+/// A missing file is not an error: it yields no operations.
+pub(crate) fn load_operations(
+    path: &Path,
+    config: &PoolConfig,
+) -> io::Result<Vec<(SecureShareOperation, MassaTime)>> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let max_operations = config
+        .max_operation_pool_size
+        .saturating_add(config.max_operation_pool_excess_items)
+        .try_into()
+        .unwrap_or(u32::MAX);
+    let op_deserializer = SecureShareDeserializer::new(
+        OperationDeserializer::new(
+            MAX_DATASTORE_VALUE_LENGTH,
+            MAX_FUNCTION_NAME_LENGTH,
+            MAX_PARAMETERS_SIZE,
+            MAX_OPERATION_DATASTORE_ENTRY_COUNT,
+            MAX_OPERATION_DATASTORE_KEY_LENGTH,
+            MAX_OPERATION_DATASTORE_VALUE_LENGTH,
+        ),
+        config.chain_id,
+    );
+    let time_deserializer = MassaTimeDeserializer::new((
+        Included(MassaTime::from_millis(0)),
+        Included(MassaTime::max()),
+    ));
+    let (mut rest, count) = U32VarIntDeserializer::new(Included(0), Included(max_operations))
+        .deserialize::<DeserializeError>(&bytes)
+        .map_err(invalid_data)?;
+    let mut operations = Vec::with_capacity(count as usize);
+    for _ in 0..count {
+        let (after_op, operation) = op_deserializer
+            .deserialize::<DeserializeError>(rest)
+            .map_err(invalid_data)?;
+        let (after_time, first_seen) = time_deserializer
+            .deserialize::<DeserializeError>(after_op)
+            .map_err(invalid_data)?;
+        operations.push((operation, first_seen));
+        rest = after_time;
+    }
+    if !rest.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "trailing bytes after the persisted operations",
+        ));
+    }
+
+    let current_slot = get_latest_block_slot_at_timestamp(
+        config.thread_count,
+        config.t0,
+        config.genesis_timestamp,
+        MassaTime::now(),
+    )
+    .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?;
+
+    let count = operations.len();
+    let operations: Vec<(SecureShareOperation, MassaTime)> = operations
+        .into_iter()
+        .filter(|(op, _)| {
+            if op.verify_signature().is_err() {
+                return false;
+            }
+            match current_slot {
+                Some(slot) => {
+                    let thread = op.content_creator_address.get_thread(config.thread_count);
+                    Slot::new(op.content.expire_period, thread) >= slot
+                }
+                // before genesis, nothing has expired yet
+                None => true,
+            }
+        })
+        .collect();
+    debug!(
+        "re-loaded {} persisted operations, dropped {} invalid or expired ones",
+        operations.len(),
+        count.saturating_sub(operations.len())
+    );
+    Ok(operations)
+}
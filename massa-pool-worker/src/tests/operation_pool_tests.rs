@@ -12,20 +12,51 @@
 //! Same as classic but we try to add irrelevant operation. (See the definition
 //! chapter below)
 //!
+//! # Reject transfers to unknown addresses
+//! Function: [`test_reject_transfers_to_unknown_addresses`]
+//! Transactions towards addresses missing from the ledger are only dropped when enabled in config.
+//!
+//! # Persist operations
+//! Function: [`test_persist_operations`]
+//! Operations saved to disk are re-loaded, except the ones that expired in-between.
+//!
+//! # Congestion fee floor
+//! Function: [`test_congestion_fee_floor`]
+//! The minimal fee to enter the pool is zero under light load and climbs as the pool fills up.
+//!
+//! # Inclusion latency
+//! Function: [`test_fee_weighted_median_inclusion_latency`]
+//! Latencies from first sight to final inclusion are weighted by fee, candidate executions wait for finality.
+//!
 //! # Definition
 //! Relevant operation: Operation with a validity range corresponding to the
 //! latest period given his own thread. All operation which doesn't fit these
 //! requirements are "irrelevant"
 //!
+use crate::inclusion_latency::InclusionLatencies;
+use crate::operation_pool::{congestion_fee_floor, order_operations};
+use crate::persistence::{load_operations, save_operations};
 use crate::tests::tools::OpGenerator;
+use crate::types::OperationInfo;
 
 use super::tools::{
     create_some_operations, default_mock_execution_controller, pool_test, PoolTestBoilerPlate,
 };
-use massa_models::{amount::Amount, config::ENDORSEMENT_COUNT, operation::OperationId, slot::Slot};
-use massa_pool_exports::PoolConfig;
+use massa_execution_exports::MockExecutionController;
+use massa_hash::Hash;
+use massa_models::{
+    address::Address,
+    amount::Amount,
+    config::{ENDORSEMENT_COUNT, T0},
+    operation::OperationId,
+    secure_share::Id,
+    slot::Slot,
+};
+use massa_pool_exports::{CongestionFeeFloor, OperationSelectionStrategy, PoolConfig};
 use massa_pos_exports::{MockSelectorController, Selection};
-use std::{collections::BTreeMap, time::Duration};
+use massa_signature::KeyPair;
+use massa_time::MassaTime;
+use std::{collections::BTreeMap, str::FromStr, time::Duration};
 
 #[test]
 fn test_add_operation() {
@@ -136,6 +167,109 @@ fn test_add_irrelevant_operation() {
     );
 }
 
+/// Add transactions from a known sender, half of them towards a known recipient and half towards
+/// addresses missing from the ledger, with and without `reject_transfers_to_unknown_addresses`.
+#[test]
+fn test_reject_transfers_to_unknown_addresses() {
+    let creator = KeyPair::generate(0).unwrap();
+    let known_recipient = KeyPair::generate(0).unwrap();
+    let known_addrs = [
+        Address::from_public_key(&creator.get_public_key()),
+        Address::from_public_key(&known_recipient.get_public_key()),
+    ];
+    for (reject, expected_count) in [(false, 10), (true, 5)] {
+        let execution_controller = {
+            let mut res = Box::new(MockExecutionController::new());
+            res.expect_clone_box().returning(move || {
+                let mut story = MockExecutionController::new();
+                story
+                    .expect_get_ops_exec_status()
+                    .returning(|ops| vec![(None, None); ops.len()]);
+                story
+                    .expect_get_final_and_candidate_balance()
+                    .returning(move |addrs| {
+                        addrs
+                            .iter()
+                            .map(|addr| {
+                                if known_addrs.contains(addr) {
+                                    let balance = Some(Amount::const_init(1_000_000_000, 0));
+                                    (balance, balance)
+                                } else {
+                                    (None, None)
+                                }
+                            })
+                            .collect()
+                    });
+                Box::new(story)
+            });
+            res
+        };
+        let selector_controller = {
+            let mut res = Box::new(MockSelectorController::new());
+            res.expect_clone_box().times(2).returning(|| {
+                let mut story = MockSelectorController::new();
+                story.expect_get_available_selections_in_range().returning(
+                    |slot_range, opt_addrs| {
+                        let mut all_slots = BTreeMap::new();
+                        let addr = *opt_addrs
+                            .expect("No addresses filter given")
+                            .iter()
+                            .next()
+                            .expect("No addresses given");
+                        for i in 0..15 {
+                            for j in 0..32 {
+                                let s = Slot::new(i, j);
+                                if slot_range.contains(&s) {
+                                    all_slots.insert(
+                                        s,
+                                        Selection {
+                                            producer: addr,
+                                            endorsements: vec![addr; ENDORSEMENT_COUNT as usize],
+                                        },
+                                    );
+                                }
+                            }
+                        }
+                        Ok(all_slots)
+                    },
+                );
+                Box::new(story)
+            });
+            res
+        };
+        let creator = creator.clone();
+        let known_recipient = known_recipient.clone();
+        pool_test(
+            PoolConfig {
+                reject_transfers_to_unknown_addresses: reject,
+                ..Default::default()
+            },
+            execution_controller,
+            selector_controller,
+            None,
+            |mut operation_pool, mut storage| {
+                let op_gen = OpGenerator::default().expirery(2).creator(creator);
+                storage.store_operations(create_some_operations(5, &op_gen));
+                // vary the amount so that the transactions to the known recipient get distinct ids
+                let ops = (0..5)
+                    .map(|i| {
+                        op_gen
+                            .clone()
+                            .receiver(known_recipient.clone())
+                            .amount(Amount::from_raw(i))
+                            .generate()
+                    })
+                    .collect();
+                storage.store_operations(ops);
+                operation_pool.add_operations(storage);
+                // Allow some time for the pool to add the operations
+                std::thread::sleep(Duration::from_secs(3));
+                assert_eq!(operation_pool.get_operation_count(), expected_count);
+            },
+        );
+    }
+}
+
 #[test]
 fn test_pool() {
     let pool_config = PoolConfig {
@@ -180,7 +314,11 @@ fn test_pool() {
         mut pool_manager,
         mut pool_controller,
         storage: storage_base,
-    } = PoolTestBoilerPlate::pool_test(pool_config, execution_controller, selector_controller);
+    } = PoolTestBoilerPlate::pool_test(
+        pool_config.clone(),
+        execution_controller,
+        selector_controller,
+    );
 
     // // generate (id, transactions, range of validity) by threads
     let mut thread_tx_lists = vec![Vec::new(); pool_config.thread_count as usize];
@@ -237,3 +375,198 @@ fn test_pool() {
     }
     pool_manager.stop();
 }
+
+/// Builds a bare operation info for the ordering tests
+fn test_op_info(creator: Address, fee: u64, arrival_index: u64) -> OperationInfo {
+    OperationInfo {
+        id: OperationId::new(Hash::compute_from(&arrival_index.to_be_bytes())),
+        size: 10,
+        max_gas_usage: 10,
+        creator_address: creator,
+        thread: 0,
+        fee: Amount::from_raw(fee),
+        max_spending: Amount::from_raw(fee),
+        transfer_recipient: None,
+        validity_period_range: 0..=10,
+        arrival_index,
+        first_seen: MassaTime::from_millis(0),
+    }
+}
+
+#[test]
+fn test_order_operations() {
+    let addr_a = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+    let addr_b = Address::from_public_key(&KeyPair::generate(0).unwrap().get_public_key());
+
+    // listed in score order
+    let ops = vec![
+        test_op_info(addr_a, 30, 2),
+        test_op_info(addr_a, 50, 0),
+        test_op_info(addr_a, 10, 3),
+        test_op_info(addr_b, 30, 1),
+    ];
+    let ordered_indices = |strategy| {
+        order_operations(ops.iter().collect(), strategy)
+            .into_iter()
+            .map(|op_info| op_info.arrival_index)
+            .collect::<Vec<u64>>()
+    };
+
+    // score keeps the pool order
+    assert_eq!(
+        ordered_indices(OperationSelectionStrategy::Score),
+        vec![2, 0, 3, 1]
+    );
+    // highest fee first, equal fees in score order
+    assert_eq!(
+        ordered_indices(OperationSelectionStrategy::HighestFee),
+        vec![0, 2, 1, 3]
+    );
+    // oldest first
+    assert_eq!(
+        ordered_indices(OperationSelectionStrategy::Arrival),
+        vec![0, 1, 2, 3]
+    );
+    // addresses take turns, each one in score order
+    assert_eq!(
+        ordered_indices(OperationSelectionStrategy::AddressFairness),
+        vec![2, 1, 0, 3]
+    );
+}
+
+#[test]
+fn test_persist_operations() {
+    let pool_config = PoolConfig {
+        // current period is around 100
+        genesis_timestamp: MassaTime::now().saturating_sub(T0.saturating_mul(100)),
+        ..Default::default()
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("pool_operations.bin");
+
+    // no file yet: nothing to re-load
+    assert!(load_operations(&path, &pool_config).unwrap().is_empty());
+
+    let fresh_ops = create_some_operations(5, &OpGenerator::default().expirery(1000));
+    let expired_ops = create_some_operations(3, &OpGenerator::default().expirery(2));
+    // the first-seen times are saved along with the operations
+    let all_ops: Vec<_> = fresh_ops
+        .iter()
+        .chain(expired_ops.iter())
+        .cloned()
+        .enumerate()
+        .map(|(i, op)| (op, MassaTime::from_millis(1000 * (i as u64 + 1))))
+        .collect();
+    save_operations(&path, &all_ops).unwrap();
+
+    let loaded = load_operations(&path, &pool_config).unwrap();
+    assert_eq!(loaded, all_ops[..fresh_ops.len()].to_vec());
+}
+
+#[test]
+fn test_congestion_fee_floor() {
+    let floors = vec![
+        CongestionFeeFloor {
+            fullness_percent: 50,
+            min_fee: Amount::from_str("0.1").unwrap(),
+        },
+        CongestionFeeFloor {
+            fullness_percent: 90,
+            min_fee: Amount::from_str("1").unwrap(),
+        },
+    ];
+    // light load: no floor
+    assert_eq!(congestion_fee_floor(&floors, 0, 10), Amount::zero());
+    assert_eq!(congestion_fee_floor(&floors, 4, 10), Amount::zero());
+    // the floor climbs with the pool fullness
+    assert_eq!(
+        congestion_fee_floor(&floors, 5, 10),
+        Amount::from_str("0.1").unwrap()
+    );
+    assert_eq!(
+        congestion_fee_floor(&floors, 9, 10),
+        Amount::from_str("1").unwrap()
+    );
+    // the pool can exceed its size in-between refreshes
+    assert_eq!(
+        congestion_fee_floor(&floors, 15, 10),
+        Amount::from_str("1").unwrap()
+    );
+    // no floor configured
+    assert_eq!(congestion_fee_floor(&[], 10, 10), Amount::zero());
+
+    let execution_controller = default_mock_execution_controller();
+    let selector_controller = {
+        let mut res = Box::new(MockSelectorController::new());
+        res.expect_clone_box()
+            .returning(|| Box::new(MockSelectorController::new()));
+        res
+    };
+    let pool_config = PoolConfig {
+        max_operation_pool_size: 10,
+        congestion_fee_floors: floors,
+        // no refresh during the test
+        operation_pool_refresh_interval: MassaTime::from_millis(600_000),
+        ..Default::default()
+    };
+    pool_test(
+        pool_config,
+        execution_controller,
+        selector_controller,
+        None,
+        |mut operation_pool, storage| {
+            let mut add = |count| {
+                let op_gen = OpGenerator::default().expirery(1000);
+                let mut ops_storage = storage.clone_without_refs();
+                ops_storage.store_operations(create_some_operations(count, &op_gen));
+                operation_pool.add_operations(ops_storage);
+                // Allow some time for the pool to add the operations
+                std::thread::sleep(Duration::from_millis(200));
+                (
+                    operation_pool.get_operation_count(),
+                    operation_pool.get_operation_fee_floor(),
+                )
+            };
+            // light load: no floor
+            assert_eq!(add(4), (4, Amount::zero()));
+            // half full
+            assert_eq!(add(1), (5, Amount::from_str("0.1").unwrap()));
+            // heavy load: the floor climbed
+            assert_eq!(add(4), (9, Amount::from_str("1").unwrap()));
+        },
+    );
+}
+
+#[test]
+fn test_fee_weighted_median_inclusion_latency() {
+    let op_id = |i: u64| OperationId::new(Hash::compute_from(&i.to_be_bytes()));
+    let ms = MassaTime::from_millis;
+    let mut latencies = InclusionLatencies::new(3);
+    assert_eq!(latencies.fee_weighted_median(), None);
+
+    // free operations: plain median
+    latencies.record_final(Amount::zero(), ms(0), ms(3000));
+    latencies.record_final(Amount::zero(), ms(0), ms(1000));
+    assert_eq!(latencies.fee_weighted_median(), Some(ms(3000)));
+
+    // the slow operation paid most of the fees
+    latencies.record_final(Amount::from_raw(1), ms(1000), ms(2000));
+    latencies.record_final(Amount::from_raw(1), ms(1000), ms(3000));
+    latencies.record_final(Amount::from_raw(10), ms(1000), ms(4000));
+    assert_eq!(latencies.fee_weighted_median(), Some(ms(3000)));
+
+    // an operation executed in a candidate block is recorded once its execution is final
+    latencies.track_candidate(op_id(1), Amount::from_raw(10), ms(0));
+    latencies.update_candidate(&op_id(1), true, false, ms(400));
+    assert_eq!(latencies.candidate_ids(), vec![op_id(1)]);
+    latencies.update_candidate(&op_id(1), true, true, ms(500));
+    assert!(latencies.candidate_ids().is_empty());
+    // the oldest sample left the window: 500ms (fee 10), 2000ms (fee 1), 3000ms (fee 10)
+    assert_eq!(latencies.fee_weighted_median(), Some(ms(2000)));
+
+    // reverted candidate executions are forgotten
+    latencies.track_candidate(op_id(2), Amount::from_raw(10), ms(0));
+    latencies.update_candidate(&op_id(2), false, false, ms(600));
+    assert!(latencies.candidate_ids().is_empty());
+    assert_eq!(latencies.fee_weighted_median(), Some(ms(2000)));
+}
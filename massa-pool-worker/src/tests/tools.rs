@@ -25,7 +25,7 @@ use massa_wallet::test_exports::create_test_wallet;
 use parking_lot::RwLock;
 use tokio::sync::broadcast;
 
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub(crate) struct OpGenerator {
     creator: Option<KeyPair>,
     receiver: Option<KeyPair>,
@@ -40,7 +40,6 @@ impl OpGenerator {
         self
     }
 
-    #[allow(dead_code)]
     pub(crate) fn amount(mut self, amount: Amount) -> Self {
         self.amount = Some(amount);
         self
@@ -51,7 +50,6 @@ impl OpGenerator {
         self
     }
 
-    #[allow(dead_code)]
     pub(crate) fn receiver(mut self, receiver: KeyPair) -> Self {
         self.receiver = Some(receiver);
         self
@@ -125,6 +123,7 @@ impl PoolTestBoilerPlate {
                 selector: selector_story,
             },
             wallet,
+            None,
         );
 
         Self {
@@ -167,6 +166,7 @@ pub fn pool_test<F>(
             selector,
         },
         wallet,
+        None,
     );
     test(pool_controller, storage);
     pool_manager.stop();
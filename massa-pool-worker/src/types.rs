@@ -1,8 +1,9 @@
 use massa_models::{
     address::Address,
     amount::Amount,
-    operation::{OperationId, SecureShareOperation},
+    operation::{OperationId, OperationType, SecureShareOperation},
 };
+use massa_time::MassaTime;
 use std::ops::RangeInclusive;
 
 #[derive(Debug, Clone)]
@@ -16,7 +17,13 @@ pub struct OperationInfo {
     pub fee: Amount,
     /// max amount that the op might spend from the sender's balance
     pub max_spending: Amount,
+    /// recipient of the coins if the op is a transaction
+    pub transfer_recipient: Option<Address>,
     pub validity_period_range: RangeInclusive<u64>,
+    /// order of arrival in the pool
+    pub arrival_index: u64,
+    /// time at which the pool first saw the operation
+    pub first_seen: MassaTime,
 }
 
 impl OperationInfo {
@@ -27,6 +34,8 @@ impl OperationInfo {
         thread_count: u8,
         base_operation_gas_cost: u64,
         sp_compilation_cost: u64,
+        arrival_index: u64,
+        first_seen: MassaTime,
     ) -> Self {
         OperationInfo {
             id: op.id,
@@ -37,6 +46,14 @@ impl OperationInfo {
             thread: op.content_creator_address.get_thread(thread_count),
             validity_period_range: op.get_validity_range(operation_validity_periods),
             max_spending: op.get_max_spending(roll_price),
+            transfer_recipient: match &op.content.op {
+                OperationType::Transaction {
+                    recipient_address, ..
+                } => Some(*recipient_address),
+                _ => None,
+            },
+            arrival_index,
+            first_seen,
         }
     }
 }
@@ -5,12 +5,16 @@
 use crate::controller_impl::{Command, PoolManagerImpl};
 use crate::denunciation_pool::DenunciationPool;
 use crate::operation_pool::OperationPool;
+use crate::persistence::{load_operations, save_operations};
 use crate::{controller_impl::PoolControllerImpl, endorsement_pool::EndorsementPool};
+use massa_models::{operation::OperationId, prehash::PreHashMap};
 use massa_pool_exports::PoolConfig;
 use massa_pool_exports::{PoolChannels, PoolController, PoolManager};
 use massa_storage::Storage;
+use massa_time::MassaTime;
 use massa_wallet::Wallet;
 use parking_lot::RwLock;
+use std::path::PathBuf;
 use std::time::Instant;
 use std::{
     sync::mpsc::{sync_channel, Receiver, RecvError, RecvTimeoutError},
@@ -18,7 +22,7 @@ use std::{
     thread,
     thread::JoinHandle,
 };
-use tracing::warn;
+use tracing::{info, warn};
 
 /// Endorsement pool write thread instance
 pub(crate) struct EndorsementPoolThread {
@@ -76,6 +80,8 @@ pub(crate) struct OperationPoolThread {
     receiver: Receiver<Command>,
     /// Shared reference to the operation pool
     operation_pool: Arc<RwLock<OperationPool>>,
+    /// File in which the pool operations are saved when the thread stops
+    persistence_path: Option<PathBuf>,
 }
 
 impl OperationPoolThread {
@@ -84,6 +90,7 @@ impl OperationPoolThread {
         receiver: Receiver<Command>,
         operation_pool: Arc<RwLock<OperationPool>>,
         config: PoolConfig,
+        persistence_path: Option<PathBuf>,
     ) -> JoinHandle<()> {
         let thread_builder = thread::Builder::new().name("operation-pool".into());
         thread_builder
@@ -91,6 +98,7 @@ impl OperationPoolThread {
                 let this = Self {
                     receiver,
                     operation_pool,
+                    persistence_path,
                 };
                 this.run(config)
             })
@@ -124,6 +132,27 @@ impl OperationPoolThread {
                 start_time = Instant::now();
             }
         }
+        self.persist();
+    }
+
+    /// Save the pool operations to the persistence file, if any
+    fn persist(&self) {
+        let Some(path) = &self.persistence_path else {
+            return;
+        };
+        let operations = self.operation_pool.read().get_operations();
+        match save_operations(path, &operations) {
+            Ok(()) => info!(
+                "saved {} pool operations to {}",
+                operations.len(),
+                path.display()
+            ),
+            Err(err) => warn!(
+                "could not save pool operations to {}: {}",
+                path.display(),
+                err
+            ),
+        }
     }
 }
 
@@ -181,12 +210,17 @@ impl DenunciationPoolThread {
 }
 
 /// Start pool manager and controller
+///
+/// If `operations_persistence_path` is set, the operations saved in that file by a previous run
+/// are re-loaded into the pool (expired ones are dropped, the others are re-validated at the next refresh),
+/// and the pool operations are saved to it when the pool stops.
 #[allow(clippy::type_complexity)]
 pub fn start_pool_controller(
     config: PoolConfig,
     storage: &Storage,
     channels: PoolChannels,
     wallet: Arc<RwLock<Wallet>>,
+    operations_persistence_path: Option<PathBuf>,
 ) -> (Box<dyn PoolManager>, Box<dyn PoolController>) {
     let (operations_input_sender, operations_input_receiver) =
         sync_channel(config.operations_channel_size);
@@ -195,20 +229,49 @@ pub fn start_pool_controller(
     let (denunciations_input_sender, denunciations_input_receiver) =
         sync_channel(config.denunciations_channel_size);
     let operation_pool = Arc::new(RwLock::new(OperationPool::init(
-        config,
+        config.clone(),
         storage,
         channels.clone(),
         wallet.clone(),
     )));
+    if let Some(path) = &operations_persistence_path {
+        match load_operations(path, &config) {
+            Ok(operations) if !operations.is_empty() => {
+                info!(
+                    "re-loading {} operations saved in {}",
+                    operations.len(),
+                    path.display()
+                );
+                let first_seen: PreHashMap<OperationId, MassaTime> = operations
+                    .iter()
+                    .map(|(op, first_seen)| (op.id, *first_seen))
+                    .collect();
+                let mut ops_storage = storage.clone_without_refs();
+                ops_storage.store_operations(operations.into_iter().map(|(op, _)| op).collect());
+                operation_pool
+                    .write()
+                    .restore_operations(ops_storage, &first_seen);
+            }
+            Ok(_) => {}
+            Err(err) => warn!(
+                "could not re-load pool operations from {}: {}",
+                path.display(),
+                err
+            ),
+        }
+    }
     let endorsement_pool = Arc::new(RwLock::new(EndorsementPool::init(
-        config,
+        config.clone(),
         storage,
         channels.clone(),
         wallet,
     )));
-    let denunciation_pool = Arc::new(RwLock::new(DenunciationPool::init(config, channels)));
+    let denunciation_pool = Arc::new(RwLock::new(DenunciationPool::init(
+        config.clone(),
+        channels,
+    )));
     let controller = PoolControllerImpl {
-        _config: config,
+        _config: config.clone(),
         operation_pool: operation_pool.clone(),
         endorsement_pool: endorsement_pool.clone(),
         denunciation_pool: denunciation_pool.clone(),
@@ -218,8 +281,12 @@ pub fn start_pool_controller(
         last_cs_final_periods: vec![0u64; usize::from(config.thread_count)],
     };
 
-    let operations_thread_handle =
-        OperationPoolThread::spawn(operations_input_receiver, operation_pool, config);
+    let operations_thread_handle = OperationPoolThread::spawn(
+        operations_input_receiver,
+        operation_pool,
+        config,
+        operations_persistence_path,
+    );
     let endorsements_thread_handle =
         EndorsementPoolThread::spawn(endorsements_input_receiver, endorsement_pool);
     let denunciations_thread_handle =
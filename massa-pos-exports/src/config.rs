@@ -19,4 +19,6 @@ pub struct PoSConfig {
     pub max_credit_length: u64,
     /// initial deferred credits file path
     pub initial_deferred_credits_path: Option<PathBuf>,
+    /// minimum number of active rolls for an address to be counted as an active staker
+    pub min_active_rolls_for_counting: u64,
 }
@@ -692,6 +692,56 @@ impl PoSFinalState {
         }
     }
 
+    /// Gets the stake distribution used by the draws of a cycle:
+    /// the active rolls (taken at `cycle - 3`) of every address owning at least one roll.
+    ///
+    /// # Returns
+    /// `PosError::CycleUnavailable` if the roll counts of `cycle - 3` are not in the cycle history
+    pub fn get_stake_distribution(&self, cycle: u64) -> PosResult<BTreeMap<Address, u64>> {
+        let roll_counts = match cycle.checked_sub(3) {
+            Some(lookback_cycle) => {
+                if self.get_cycle_index(lookback_cycle).is_none() {
+                    return Err(PosError::CycleUnavailable(lookback_cycle));
+                }
+                self.get_all_roll_counts(lookback_cycle)
+            }
+            None => self.initial_rolls.clone(),
+        };
+        Ok(roll_counts
+            .into_iter()
+            .filter(|(_, rolls)| *rolls > 0)
+            .collect())
+    }
+
+    /// Counts the stakers of a cycle owning at least `min_active_rolls_for_counting` active rolls,
+    /// so that dust stakers do not inflate the participation stats.
+    ///
+    /// # Returns
+    /// `PosError::CycleUnavailable` if the roll counts of `cycle - 3` are not in the cycle history
+    pub fn active_staker_count(&self, cycle: u64) -> PosResult<usize> {
+        Ok(self
+            .get_stake_distribution(cycle)?
+            .values()
+            .filter(|rolls| **rolls >= self.config.min_active_rolls_for_counting)
+            .count())
+    }
+
+    /// Lists the addresses credited at `slot` for the rolls they sold, with the credited amounts, sorted by address.
+    /// Roll sales are credited at the last slot of the cycle coming `ROLL_SELL_CREDIT_DELAY_CYCLES` (3) cycles
+    /// after the one in which they were executed, see `Slot::get_roll_sell_credit_slot`,
+    /// so that dashboards can show the upcoming credit events of the whole network.
+    pub fn roll_credits_at_slot(&self, slot: Slot) -> Vec<(Address, Amount)> {
+        let mut credits: Vec<(Address, Amount)> = self
+            .get_deferred_credits_range(slot..=slot, None)
+            .credits
+            .remove(&slot)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+        credits.sort_unstable_by_key(|(address, _)| *address);
+        credits
+    }
+
     /// Retrieves every deferred credit in a slot range
     /// Warning: this can be quite slow if there is tons of Deferred credits (e.g. just after genesis) to fetch
     ///          so there is an option to filter by an address to improve the speed (cf json rpc api get_addresses)
@@ -1617,6 +1667,7 @@ mod tests {
             max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
             max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
             initial_deferred_credits_path: Some(initial_deferred_credits_file.path().to_path_buf()),
+            min_active_rolls_for_counting: 1,
         };
         // initialize the database and pos_state
         let tempdir = tempfile::TempDir::new().expect("cannot create temp directory");
@@ -1729,6 +1780,76 @@ mod tests {
         assert_eq!(credits_range_4.credits, expected_credits_range_4);
     }
 
+    #[test]
+    fn test_roll_credits_at_slot() {
+        let addr1 =
+            Address::from_str("AU12pAcVUzsgUBJHaYSAtDKVTYnUT9NorBDjoDovMfAFTLFa16MNa").unwrap();
+        let addr2 =
+            Address::from_str("AU1wN8rn4SkwYSTDF3dHFY4U28KtsqKL1NnEjDZhHnHEy6cEQm53").unwrap();
+
+        let initial_rolls_file =
+            tempfile::NamedTempFile::new().expect("could not create temporary initial rolls file");
+        std::fs::write(initial_rolls_file.path(), "{}".as_bytes())
+            .expect("failed writing initial rolls file");
+
+        let tempdir = tempfile::TempDir::new().expect("cannot create temp directory");
+        let db_config = MassaDBConfig {
+            path: tempdir.path().to_path_buf(),
+            max_history_length: 10,
+            max_final_state_elements_size: 100_000,
+            max_versioning_elements_size: 100_000,
+            thread_count: 2,
+            max_ledger_backups: 10,
+        };
+        let db = Arc::new(RwLock::new(
+            Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>
+        ));
+        let pos_config = PoSConfig {
+            periods_per_cycle: 2,
+            thread_count: 2,
+            cycle_history_length: POS_SAVED_CYCLES,
+            max_rolls_length: MAX_ROLLS_COUNT_LENGTH,
+            max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
+            max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
+            initial_deferred_credits_path: None,
+            min_active_rolls_for_counting: 1,
+        };
+        let pos_state = PoSFinalState::new(
+            pos_config,
+            "",
+            &initial_rolls_file.path().to_path_buf(),
+            Box::new(MockSelectorController::new()),
+            db,
+        )
+        .unwrap();
+
+        // both addresses sell rolls during cycle 0: they are credited at the end of cycle 3
+        let sale_slot = Slot::new(1, 0);
+        let credit_slot = sale_slot.get_roll_sell_credit_slot(2, 2).unwrap();
+        assert_eq!(credit_slot, Slot::new(7, 1));
+        let amount1 = Amount::from_str("300").unwrap();
+        let amount2 = Amount::from_str("100").unwrap();
+        let mut batch = DBBatch::new();
+        pos_state.put_deferred_credits_entry(&credit_slot, &addr1, &amount1, &mut batch);
+        pos_state.put_deferred_credits_entry(&credit_slot, &addr2, &amount2, &mut batch);
+        // another deferred credit in the same cycle
+        pos_state.put_deferred_credits_entry(&Slot::new(7, 0), &addr1, &amount2, &mut batch);
+        pos_state
+            .db
+            .write()
+            .write_batch(batch, DBBatch::new(), None);
+
+        let mut expected = vec![(addr1, amount1), (addr2, amount2)];
+        expected.sort_unstable_by_key(|(address, _)| *address);
+        assert_eq!(pos_state.roll_credits_at_slot(credit_slot), expected);
+        assert_eq!(
+            pos_state.roll_credits_at_slot(Slot::new(7, 0)),
+            vec![(addr1, amount2)]
+        );
+        // nothing is credited at the end of the cycle following the sale
+        assert!(pos_state.roll_credits_at_slot(Slot::new(3, 1)).is_empty());
+    }
+
     // This test checks that the initial rolls are loaded correctly
     #[test]
     fn test_initial_rolls_loading() {
@@ -1775,6 +1896,7 @@ mod tests {
             max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
             max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
             initial_deferred_credits_path: Some(initial_deferred_credits_file.path().to_path_buf()),
+            min_active_rolls_for_counting: 1,
         };
 
         let init_seed = "";
@@ -1930,6 +2052,153 @@ mod tests {
         assert_eq!(active_rolls, roll_counts_c1);
     }
 
+    #[test]
+    fn test_active_staker_count() {
+        let keypairs: Vec<KeyPair> = (0..5).map(|_| KeyPair::generate(0).unwrap()).collect();
+        let address = |i: usize| Address::from_public_key(&keypairs[i].get_public_key());
+        // two large stakers, two dust stakers and an address without rolls
+        let initial_rolls_file =
+            tempfile::NamedTempFile::new().expect("could not create temporary initial rolls file");
+        std::fs::write(
+            initial_rolls_file.path(),
+            serde_json::to_string(&HashMap::from([
+                (address(0).to_string(), 500u64),
+                (address(1).to_string(), 10u64),
+                (address(2).to_string(), 1u64),
+                (address(3).to_string(), 9u64),
+                (address(4).to_string(), 0u64),
+            ]))
+            .unwrap()
+            .as_bytes(),
+        )
+        .expect("failed writing initial rolls file");
+
+        let tempdir = tempfile::TempDir::new().expect("cannot create temp directory");
+        let db_config = MassaDBConfig {
+            path: tempdir.path().to_path_buf(),
+            max_history_length: 10,
+            max_final_state_elements_size: 100,
+            max_versioning_elements_size: 100,
+            thread_count: 2,
+            max_ledger_backups: 10,
+        };
+        let db = Arc::new(RwLock::new(
+            Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>
+        ));
+        let pos_state = |min_active_rolls_for_counting: u64| {
+            PoSFinalState::new(
+                PoSConfig {
+                    periods_per_cycle: 2,
+                    thread_count: 2,
+                    cycle_history_length: POS_SAVED_CYCLES,
+                    max_rolls_length: MAX_ROLLS_COUNT_LENGTH,
+                    max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
+                    max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
+                    initial_deferred_credits_path: None,
+                    min_active_rolls_for_counting,
+                },
+                "",
+                &initial_rolls_file.path().to_path_buf(),
+                Box::new(MockSelectorController::new()),
+                db.clone(),
+            )
+            .unwrap()
+        };
+
+        // the first cycles are drawn from the initial rolls
+        assert_eq!(pos_state(10).active_staker_count(2).unwrap(), 2);
+        assert_eq!(pos_state(1).active_staker_count(2).unwrap(), 4);
+        assert_eq!(pos_state(1000).active_staker_count(2).unwrap(), 0);
+        // cycle 1 is not in the history
+        assert_matches!(
+            pos_state(10).active_staker_count(4),
+            Err(PosError::CycleUnavailable(1))
+        );
+    }
+
+    #[test]
+    fn test_stake_distribution() {
+        let addr1 =
+            Address::from_str("AU12pAcVUzsgUBJHaYSAtDKVTYnUT9NorBDjoDovMfAFTLFa16MNa").unwrap();
+        let addr2 =
+            Address::from_str("AU1wN8rn4SkwYSTDF3dHFY4U28KtsqKL1NnEjDZhHnHEy6cEQm53").unwrap();
+
+        let initial_rolls_file =
+            tempfile::NamedTempFile::new().expect("could not create temporary initial rolls file");
+        std::fs::write(
+            initial_rolls_file.path(),
+            serde_json::to_string(&HashMap::from([
+                (addr1.to_string(), 5u64),
+                (addr2.to_string(), 3u64),
+            ]))
+            .unwrap()
+            .as_bytes(),
+        )
+        .expect("failed writing initial rolls file");
+
+        let tempdir = tempfile::TempDir::new().expect("cannot create temp directory");
+        let db_config = MassaDBConfig {
+            path: tempdir.path().to_path_buf(),
+            max_history_length: 10,
+            max_final_state_elements_size: 100,
+            max_versioning_elements_size: 100,
+            thread_count: 2,
+            max_ledger_backups: 10,
+        };
+        let db = Arc::new(RwLock::new(
+            Box::new(MassaDB::new(db_config)) as Box<(dyn MassaDBController + 'static)>
+        ));
+        let pos_config = PoSConfig {
+            periods_per_cycle: 2,
+            thread_count: 2,
+            cycle_history_length: POS_SAVED_CYCLES,
+            max_rolls_length: MAX_ROLLS_COUNT_LENGTH,
+            max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
+            max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
+            initial_deferred_credits_path: None,
+            min_active_rolls_for_counting: 1,
+        };
+        let mut pos_state = PoSFinalState::new(
+            pos_config,
+            "",
+            &initial_rolls_file.path().to_path_buf(),
+            Box::new(MockSelectorController::new()),
+            db,
+        )
+        .unwrap();
+
+        // the first cycles are drawn from the initial rolls
+        assert_eq!(
+            pos_state.get_stake_distribution(2).unwrap(),
+            BTreeMap::from([(addr1, 5), (addr2, 3)])
+        );
+
+        // during cycle 0, addr2 sold all its rolls
+        let cycle_info_0 = CycleInfo::new(
+            0,
+            Default::default(),
+            BTreeMap::from([(addr1, 4), (addr2, 0)]),
+            Default::default(),
+            Default::default(),
+        );
+        let mut batch = DBBatch::new();
+        pos_state.put_new_cycle_info(&cycle_info_0, &mut batch);
+        pos_state
+            .db
+            .write()
+            .write_batch(batch, DBBatch::new(), None);
+        assert_eq!(
+            pos_state.get_stake_distribution(3).unwrap(),
+            BTreeMap::from([(addr1, 4)])
+        );
+
+        // cycle 1 is not in the history yet
+        assert_matches!(
+            pos_state.get_stake_distribution(4),
+            Err(PosError::CycleUnavailable(1))
+        );
+    }
+
     // This test checks that the recompute_pos_cache function recovers every cycle and does return correctly.
     // The test example is chosen to have the cycles overlap a power of 2, to check that the order of cycles in the DB
     // (lexicographical over bytes) is the same as the order of cycles in the cache (numerical).
@@ -1944,6 +2213,7 @@ mod tests {
             max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
             max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
             initial_deferred_credits_path: None,
+            min_active_rolls_for_counting: 1,
         };
 
         // initialize the database and pos_state
@@ -2044,6 +2314,7 @@ mod tests {
             max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
             max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
             initial_deferred_credits_path: None,
+            min_active_rolls_for_counting: 1,
         };
 
         // initialize the database and pos_state
@@ -2244,6 +2515,7 @@ mod tests {
             max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
             max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
             initial_deferred_credits_path: Some(initial_deferred_credits_file.path().to_path_buf()),
+            min_active_rolls_for_counting: 1,
         };
 
         let init_seed = "";
@@ -2353,6 +2625,7 @@ mod tests {
             max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
             max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
             initial_deferred_credits_path: Some(initial_deferred_credits_file.path().to_path_buf()),
+            min_active_rolls_for_counting: 1,
         };
 
         let init_seed = "";
@@ -15,6 +15,7 @@ impl Default for PoSConfig {
             max_production_stats_length: MAX_PRODUCTION_STATS_LENGTH,
             max_credit_length: MAX_DEFERRED_CREDITS_LENGTH,
             initial_deferred_credits_path: None,
+            min_active_rolls_for_counting: 1,
         }
     }
 }
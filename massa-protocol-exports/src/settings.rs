@@ -66,6 +66,8 @@ pub struct ProtocolConfig {
     pub asked_operations_buffer_capacity: usize,
     /// Interval at which operations are announced in batches.
     pub operation_announcement_interval: MassaTime,
+    /// Announce the operations submitted to this node right away instead of waiting for the next batch.
+    pub eager_local_operations_propagation: bool,
     /// Maximum time we keep an operation in the storage
     pub max_operation_storage_time: MassaTime,
     /// Maximum of operations sent in one message.
@@ -86,6 +88,12 @@ pub struct ProtocolConfig {
     pub max_ops_kept_for_propagation: usize,
     /// max time we propagate operations
     pub max_operations_propagation_time: MassaTime,
+    /// number of periods during which an operation is valid
+    pub operation_validity_periods: u64,
+    /// operations whose validity starts more than this number of periods after the current one are dropped
+    pub max_future_validity_periods: u64,
+    /// max number of threads verifying the signatures of the received operations, defaults to the number of CPUs
+    pub max_signature_verification_threads: Option<usize>,
     /// max time we propagate endorsements
     pub max_endorsements_propagation_time: MassaTime,
     /// Max message size
@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use crate::{settings::PeerCategoryInfo, ProtocolConfig};
-use massa_models::config::{CHAINID, ENDORSEMENT_COUNT, MAX_MESSAGE_SIZE};
+use massa_models::config::{
+    CHAINID, ENDORSEMENT_COUNT, MAX_MESSAGE_SIZE, OPERATION_VALIDITY_PERIODS,
+};
 use massa_time::MassaTime;
 use tempfile::NamedTempFile;
 
@@ -33,6 +35,7 @@ impl Default for ProtocolConfig {
             operation_batch_proc_period: MassaTime::from_millis(200),
             asked_operations_buffer_capacity: 10000,
             operation_announcement_interval: MassaTime::from_millis(150),
+            eager_local_operations_propagation: false,
             max_operations_per_message: 1024,
             max_operations_per_block: 5000,
             thread_count: 32,
@@ -43,6 +46,10 @@ impl Default for ProtocolConfig {
             t0: MassaTime::from_millis(16000),
             max_ops_kept_for_propagation: 10000,
             max_operations_propagation_time: MassaTime::from_millis(30000),
+            operation_validity_periods: OPERATION_VALIDITY_PERIODS,
+            // tests freely use operations valid far in the future
+            max_future_validity_periods: 10_000_000,
+            max_signature_verification_threads: None,
             max_endorsements_propagation_time: MassaTime::from_millis(60000),
             initial_peers: NamedTempFile::new()
                 .expect("cannot create temp file")
@@ -19,6 +19,7 @@ use std::{thread::JoinHandle, time::Duration};
 use tracing::{debug, warn};
 
 use crate::handlers::peer_handler::models::ConnectionMetadata;
+use crate::sig_verifier::build_sig_verification_pool;
 use crate::{
     handlers::peer_handler::models::{InitialPeers, PeerState, SharedPeerDB},
     ip::to_canonical,
@@ -122,6 +123,14 @@ pub(crate) fn start_connectivity_thread(
                 config.max_node_known_blocks_size.try_into().unwrap(),
             )));
 
+            // Shared by the handlers that verify operation signatures
+            let sig_verification_pool = Arc::new(
+                build_sig_verification_pool(config.max_signature_verification_threads)
+                    .expect("failed to start signature verification"),
+            );
+
+            let message_counters = messages_handler.message_counters.clone();
+
             // Start handlers
             let mut peer_management_handler = PeerManagementHandler::new(
                 initial_peers,
@@ -149,6 +158,7 @@ pub(crate) fn start_connectivity_thread(
                 sender_operations_propagation_ext.clone(),
                 protocol_channels.operation_handler_propagation.1.clone(),
                 peer_management_handler.sender.command_sender.clone(),
+                sig_verification_pool.clone(),
                 massa_metrics.clone(),
             );
             let mut endorsement_handler = EndorsementHandler::new(
@@ -185,6 +195,7 @@ pub(crate) fn start_connectivity_thread(
                 block_cache,
                 storage.clone_without_refs(),
                 mip_store,
+                sig_verification_pool,
                 massa_metrics.clone(),
             );
 
@@ -227,6 +238,7 @@ pub(crate) fn start_connectivity_thread(
                                     out_connection_count,
                                     banned_peer_count,
                                     known_peer_count,
+                                    message_counters: message_counters.get(),
                                 };
                                 let peers: HashMap<PeerId, (SocketAddr, PeerConnectionType)> = network_controller.get_active_connections().get_peers_connected().into_iter().map(|(peer_id, peer)| {
                                     (peer_id, (peer.0, peer.1))
@@ -249,6 +261,8 @@ pub(crate) fn start_connectivity_thread(
                         let peer_db_read = peer_db.read();
                         massa_metrics.set_known_peers(peer_db_read.get_known_peer_count() as usize);
                         massa_metrics.set_banned_peers(peer_db_read.get_banned_peer_count() as usize);
+                        let (retrieval_depth, propagation_depth) = operation_handler.queue_depths();
+                        massa_metrics.set_operation_handler_queue_depths(retrieval_depth, propagation_depth);
                     },
                     recv(tick_try_connect) -> _ => {
                         let active_conn = network_controller.get_active_connections();
@@ -109,15 +109,14 @@ impl ProtocolController for ProtocolControllerImpl {
     }
 
     /// Propagate a batch of operation ids (from pool).
+    /// Those operations were submitted to this node, so they can be announced without waiting for the batch interval.
     ///
     /// note: Full `OperationId` is replaced by a `OperationPrefixId` later by the worker.
     fn propagate_operations(&self, operations: Storage) -> Result<(), ProtocolError> {
         self.sender_operation_handler
             .as_ref()
             .unwrap()
-            .try_send(OperationHandlerPropagationCommand::PropagateOperations(
-                operations,
-            ))
+            .try_send(OperationHandlerPropagationCommand::PropagateLocalOperations(operations))
             .map_err(|_| {
                 ProtocolError::ChannelError("propagate_operations command send error".into())
             })
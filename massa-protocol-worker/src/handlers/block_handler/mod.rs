@@ -1,4 +1,4 @@
-use std::thread::JoinHandle;
+use std::{sync::Arc, thread::JoinHandle};
 
 use massa_channel::{receiver::MassaReceiver, sender::MassaSender};
 use massa_consensus_exports::ConsensusController;
@@ -8,6 +8,7 @@ use massa_pos_exports::SelectorController;
 use massa_protocol_exports::ProtocolConfig;
 use massa_storage::Storage;
 use massa_versioning::versioning::MipStore;
+use rayon::ThreadPool;
 
 use crate::wrap_network::ActiveConnectionsTrait;
 
@@ -68,6 +69,7 @@ impl BlockHandler {
         cache: SharedBlockCache,
         storage: Storage,
         mip_store: MipStore,
+        sig_verification_pool: Arc<ThreadPool>,
         massa_metrics: MassaMetrics,
     ) -> Self {
         let block_retrieval_thread = start_retrieval_thread(
@@ -87,6 +89,7 @@ impl BlockHandler {
             cache.clone(),
             storage.clone_without_refs(),
             mip_store,
+            sig_verification_pool,
             massa_metrics,
         );
         let block_propagation_thread = start_propagation_thread(
@@ -1,5 +1,6 @@
 use std::{
     collections::{HashMap, HashSet},
+    sync::Arc,
     thread::JoinHandle,
     time::Instant,
 };
@@ -28,6 +29,7 @@ use massa_consensus_exports::ConsensusController;
 use massa_logging::massa_trace;
 use massa_metrics::MassaMetrics;
 use massa_models::{
+    amount::Amount,
     block::{Block, BlockSerializer},
     block_header::SecuredHeader,
     block_id::BlockId,
@@ -49,6 +51,7 @@ use massa_time::TimeError;
 use massa_versioning::versioning::MipStore;
 use rand::thread_rng;
 use rand::{seq::SliceRandom, Rng};
+use rayon::ThreadPool;
 use tracing::{debug, info, warn};
 
 use super::{
@@ -110,6 +113,7 @@ pub struct RetrievalThread {
     config: ProtocolConfig,
     storage: Storage,
     mip_store: MipStore,
+    sig_verification_pool: Arc<ThreadPool>,
     massa_metrics: MassaMetrics,
     operation_id_serializer: OperationIdSerializer,
 }
@@ -885,8 +889,11 @@ impl RetrievalThread {
             &self.config,
             operations.values().cloned().collect(),
             &from_peer_id,
+            // the operations of a block are needed whatever their fee
+            Amount::zero(),
             &mut self.sender_propagation_ops,
             &mut self.pool_controller,
+            &self.sig_verification_pool,
         ) {
             warn!(
                 "Peer id {} sent us operations for block id {} but they failed validity checks: {}",
@@ -1277,6 +1284,7 @@ pub fn start_retrieval_thread(
     cache: SharedBlockCache,
     storage: Storage,
     mip_store: MipStore,
+    sig_verification_pool: Arc<ThreadPool>,
     massa_metrics: MassaMetrics,
 ) -> JoinHandle<()> {
     let block_message_serializer =
@@ -1305,6 +1313,7 @@ pub fn start_retrieval_thread(
                 config,
                 storage,
                 mip_store,
+                sig_verification_pool,
                 massa_metrics,
                 operation_id_serializer: OperationIdSerializer::new(),
             };
@@ -5,4 +5,7 @@ pub enum OperationHandlerPropagationCommand {
     Stop,
     /// operations ids
     PropagateOperations(Storage),
+    /// operations created or submitted locally,
+    /// announced right away if `eager_local_operations_propagation` is enabled
+    PropagateLocalOperations(Storage),
 }
@@ -0,0 +1,337 @@
+use massa_channel::{receiver::MassaReceiver, sender::MassaSender};
+use massa_metrics::MassaMetrics;
+
+use super::commands_propagation::OperationHandlerPropagationCommand;
+use crate::handlers::peer_handler::models::PeerMessageTuple;
+
+/// Number of operations received from a peer, by outcome
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PeerOperationCounters {
+    /// new operations that were checked and sent to the pool
+    pub accepted: u64,
+    /// operations we already knew about
+    pub duplicate: u64,
+    /// expired operations and operations of messages that failed the checks
+    pub invalid: u64,
+    /// operations whose validity starts more than `max_future_validity_periods` after the current period
+    pub future_validity: u64,
+    /// announced operations whose retrieval was postponed because they were asked to another peer recently
+    pub rate_limited: u64,
+    /// operations paying less than the congestion fee floor of the pool
+    pub below_fee_floor: u64,
+}
+
+impl PeerOperationCounters {
+    /// Add the counts to the protocol metrics, summed over all peers
+    pub fn record(&self, massa_metrics: &MassaMetrics) {
+        massa_metrics.inc_protocol_received_operations(
+            self.accepted,
+            self.duplicate,
+            self.invalid,
+            self.future_validity,
+            self.rate_limited,
+            self.below_fee_floor,
+        );
+    }
+}
+
+/// Handles on the input channels of the operation handler threads, only used to read the depths of their queues.
+/// A queue that keeps growing means that its thread cannot keep up, and messages will be dropped once it is full.
+pub struct OperationQueues {
+    retrieval: MassaReceiver<PeerMessageTuple>,
+    propagation: MassaSender<OperationHandlerPropagationCommand>,
+}
+
+impl OperationQueues {
+    pub fn new(
+        retrieval: MassaReceiver<PeerMessageTuple>,
+        propagation: MassaSender<OperationHandlerPropagationCommand>,
+    ) -> Self {
+        Self {
+            retrieval,
+            propagation,
+        }
+    }
+
+    /// Get the number of messages waiting for the retrieval thread and of commands waiting for the propagation thread
+    pub fn depths(&self) -> (usize, usize) {
+        (self.retrieval.len(), self.propagation.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use massa_channel::MassaChannel;
+    use massa_models::amount::Amount;
+    use massa_pool_exports::MockPoolController;
+    use massa_pool_exports::PoolController;
+    use massa_protocol_exports::{
+        test_exports::tools::create_operation_with_expire_period, PeerId, ProtocolConfig,
+    };
+    use massa_signature::KeyPair;
+    use massa_storage::Storage;
+    use massa_time::MassaTime;
+    use parking_lot::RwLock;
+
+    use super::{OperationQueues, PeerOperationCounters};
+    use crate::{
+        handlers::operation_handler::{
+            cache::OperationCache, commands_propagation::OperationHandlerPropagationCommand,
+            note_operations_from_peer,
+        },
+        handlers::peer_handler::models::PeerMessageTuple,
+        sig_verifier::build_sig_verification_pool,
+    };
+
+    #[test]
+    fn test_queue_depths_count_unprocessed_items() {
+        let (network_sender, network_receiver) =
+            MassaChannel::new::<PeerMessageTuple>(String::from("test_queue_retrieval"), Some(10));
+        let (propagation_sender, propagation_receiver) =
+            MassaChannel::new(String::from("test_queue_propagation"), Some(10));
+        let queues = OperationQueues::new(network_receiver.clone(), propagation_sender.clone());
+        assert_eq!(queues.depths(), (0, 0));
+
+        let peer_id = PeerId::from_public_key(KeyPair::generate(0).unwrap().get_public_key());
+        for _ in 0..3 {
+            network_sender.send((peer_id, vec![0])).unwrap();
+        }
+        propagation_sender
+            .send(OperationHandlerPropagationCommand::Stop)
+            .unwrap();
+        assert_eq!(queues.depths(), (3, 1));
+
+        // processed items leave the queues
+        network_receiver.recv().unwrap();
+        propagation_receiver.recv().unwrap();
+        assert_eq!(queues.depths(), (2, 0));
+    }
+
+    #[test]
+    fn test_operation_counters_by_outcome() {
+        let config = ProtocolConfig {
+            genesis_timestamp: MassaTime::now().saturating_sub(MassaTime::from_millis(600_000)),
+            ..Default::default()
+        };
+        let storage = Storage::create_root();
+        let mut cache = Arc::new(RwLock::new(OperationCache::new(1000, 1000)));
+        let (mut propagation_sender, _propagation_receiver) =
+            MassaChannel::new(String::from("test_propagation"), Some(10));
+        let mut pool_controller: Box<dyn PoolController> = {
+            let mut pool = MockPoolController::new();
+            pool.expect_add_operations().returning(|_| ());
+            Box::new(pool)
+        };
+        let sig_verification_pool = build_sig_verification_pool(Some(1)).unwrap();
+        let peer_a = PeerId::from_public_key(KeyPair::generate(0).unwrap().get_public_key());
+        let peer_b = PeerId::from_public_key(KeyPair::generate(0).unwrap().get_public_key());
+
+        let keypair = KeyPair::generate(0).unwrap();
+        let ops = vec![
+            create_operation_with_expire_period(&keypair, 1000),
+            create_operation_with_expire_period(&keypair, 1001),
+        ];
+        // an operation that expired long ago
+        let expired_op = create_operation_with_expire_period(&keypair, 0);
+
+        let mut send = |peer_id, ops| {
+            note_operations_from_peer(
+                &storage,
+                &mut cache,
+                &config,
+                ops,
+                peer_id,
+                Amount::zero(),
+                &mut propagation_sender,
+                &mut pool_controller,
+                &sig_verification_pool,
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            send(&peer_a, ops.clone()),
+            PeerOperationCounters {
+                accepted: 2,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            send(&peer_b, vec![ops[0].clone(), expired_op]),
+            PeerOperationCounters {
+                duplicate: 1,
+                invalid: 1,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            send(&peer_a, ops),
+            PeerOperationCounters {
+                duplicate: 2,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_far_future_operations_are_dropped() {
+        // 600s after genesis with 16s periods, the current period is 37
+        let config = ProtocolConfig {
+            genesis_timestamp: MassaTime::now().saturating_sub(MassaTime::from_millis(600_000)),
+            max_future_validity_periods: 20,
+            ..Default::default()
+        };
+        let storage = Storage::create_root();
+        let mut cache = Arc::new(RwLock::new(OperationCache::new(1000, 1000)));
+        let (mut propagation_sender, _propagation_receiver) =
+            MassaChannel::new(String::from("test_propagation"), Some(10));
+        let keypair = KeyPair::generate(0).unwrap();
+        // valid from period 45, within the horizon
+        let op = create_operation_with_expire_period(&keypair, 55);
+        let op_id = op.id;
+        // valid from period 990
+        let far_future_op = create_operation_with_expire_period(&keypair, 1000);
+        let mut pool_controller: Box<dyn PoolController> = {
+            let mut pool = MockPoolController::new();
+            pool.expect_add_operations()
+                .times(1)
+                .withf(move |ops| {
+                    ops.get_op_refs().len() == 1 && ops.get_op_refs().contains(&op_id)
+                })
+                .returning(|_| ());
+            Box::new(pool)
+        };
+        let peer_id = PeerId::from_public_key(KeyPair::generate(0).unwrap().get_public_key());
+
+        let counters = note_operations_from_peer(
+            &storage,
+            &mut cache,
+            &config,
+            vec![op, far_future_op],
+            &peer_id,
+            Amount::zero(),
+            &mut propagation_sender,
+            &mut pool_controller,
+            &build_sig_verification_pool(Some(1)).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            counters,
+            PeerOperationCounters {
+                accepted: 1,
+                future_validity: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_operations_below_fee_floor_are_dropped() {
+        let config = ProtocolConfig {
+            genesis_timestamp: MassaTime::now().saturating_sub(MassaTime::from_millis(600_000)),
+            ..Default::default()
+        };
+        let storage = Storage::create_root();
+        let mut cache = Arc::new(RwLock::new(OperationCache::new(1000, 1000)));
+        let (mut propagation_sender, _propagation_receiver) =
+            MassaChannel::new(String::from("test_propagation"), Some(10));
+        let mut pool_controller: Box<dyn PoolController> = {
+            let mut pool = MockPoolController::new();
+            // only the operations sent once the pool is not congested anymore reach it
+            pool.expect_add_operations()
+                .times(1)
+                .withf(|ops| ops.get_op_refs().len() == 2)
+                .returning(|_| ());
+            Box::new(pool)
+        };
+        let sig_verification_pool = build_sig_verification_pool(Some(1)).unwrap();
+        let peer_id = PeerId::from_public_key(KeyPair::generate(0).unwrap().get_public_key());
+        let keypair = KeyPair::generate(0).unwrap();
+        // free operations
+        let ops = vec![
+            create_operation_with_expire_period(&keypair, 1000),
+            create_operation_with_expire_period(&keypair, 1001),
+        ];
+
+        let mut send = |fee_floor| {
+            note_operations_from_peer(
+                &storage,
+                &mut cache,
+                &config,
+                ops.clone(),
+                &peer_id,
+                fee_floor,
+                &mut propagation_sender,
+                &mut pool_controller,
+                &sig_verification_pool,
+            )
+            .unwrap()
+        };
+        assert_eq!(
+            send(Amount::from_raw(1)),
+            PeerOperationCounters {
+                below_fee_floor: 2,
+                ..Default::default()
+            }
+        );
+        // dropped operations are not marked as checked
+        assert_eq!(
+            send(Amount::zero()),
+            PeerOperationCounters {
+                accepted: 2,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_operations_verified_with_fixed_pool_size() {
+        let config = ProtocolConfig {
+            genesis_timestamp: MassaTime::now().saturating_sub(MassaTime::from_millis(600_000)),
+            max_signature_verification_threads: Some(2),
+            ..Default::default()
+        };
+        let sig_verification_pool =
+            build_sig_verification_pool(config.max_signature_verification_threads).unwrap();
+        assert_eq!(sig_verification_pool.current_num_threads(), 2);
+
+        let storage = Storage::create_root();
+        let mut cache = Arc::new(RwLock::new(OperationCache::new(1000, 1000)));
+        let (mut propagation_sender, _propagation_receiver) =
+            MassaChannel::new(String::from("test_propagation"), Some(10));
+        let mut pool_controller: Box<dyn PoolController> = {
+            let mut pool = MockPoolController::new();
+            pool.expect_add_operations()
+                .times(1)
+                .withf(|ops| ops.get_op_refs().len() == 10)
+                .returning(|_| ());
+            Box::new(pool)
+        };
+        let peer_id = PeerId::from_public_key(KeyPair::generate(0).unwrap().get_public_key());
+        // large enough to be split in chunks verified on both threads
+        let ops = (0..10)
+            .map(|i| create_operation_with_expire_period(&KeyPair::generate(0).unwrap(), 1000 + i))
+            .collect();
+
+        let counters = note_operations_from_peer(
+            &storage,
+            &mut cache,
+            &config,
+            ops,
+            &peer_id,
+            Amount::zero(),
+            &mut propagation_sender,
+            &mut pool_controller,
+            &sig_verification_pool,
+        )
+        .unwrap();
+        assert_eq!(
+            counters,
+            PeerOperationCounters {
+                accepted: 10,
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -1,23 +1,25 @@
-use std::thread::JoinHandle;
+use std::{sync::Arc, thread::JoinHandle};
 
 use massa_channel::{receiver::MassaReceiver, sender::MassaSender};
 use massa_metrics::MassaMetrics;
 use massa_pool_exports::PoolController;
 use massa_protocol_exports::ProtocolConfig;
 use massa_storage::Storage;
+use rayon::ThreadPool;
 
 use crate::wrap_network::ActiveConnectionsTrait;
 
 use self::{
     cache::SharedOperationCache, commands_propagation::OperationHandlerPropagationCommand,
-    commands_retrieval::OperationHandlerRetrievalCommand, propagation::start_propagation_thread,
-    retrieval::start_retrieval_thread,
+    commands_retrieval::OperationHandlerRetrievalCommand, metrics::OperationQueues,
+    propagation::start_propagation_thread, retrieval::start_retrieval_thread,
 };
 
 pub mod cache;
 pub mod commands_propagation;
 pub mod commands_retrieval;
 mod messages;
+pub mod metrics;
 mod propagation;
 mod retrieval;
 
@@ -35,6 +37,7 @@ pub struct OperationHandler {
         MassaSender<OperationHandlerPropagationCommand>,
         JoinHandle<()>,
     )>,
+    queues: OperationQueues,
 }
 
 impl OperationHandler {
@@ -51,8 +54,10 @@ impl OperationHandler {
         local_sender: MassaSender<OperationHandlerPropagationCommand>,
         local_receiver: MassaReceiver<OperationHandlerPropagationCommand>,
         peer_cmd_sender: MassaSender<PeerManagementCmd>,
+        sig_verification_pool: Arc<ThreadPool>,
         massa_metrics: MassaMetrics,
     ) -> Self {
+        let queues = OperationQueues::new(receiver_network.clone(), local_sender.clone());
         let operation_retrieval_thread = start_retrieval_thread(
             receiver_network,
             pool_controller,
@@ -63,6 +68,7 @@ impl OperationHandler {
             receiver_retrieval_ext,
             local_sender.clone(),
             peer_cmd_sender,
+            sig_verification_pool,
             massa_metrics.clone(),
         );
 
@@ -77,9 +83,15 @@ impl OperationHandler {
         Self {
             operation_retrieval_thread: Some((sender_retrieval_ext, operation_retrieval_thread)),
             operation_propagation_thread: Some((local_sender, operation_propagation_thread)),
+            queues,
         }
     }
 
+    /// Get the number of messages waiting for the retrieval thread and of commands waiting for the propagation thread
+    pub fn queue_depths(&self) -> (usize, usize) {
+        self.queues.depths()
+    }
+
     pub fn stop(&mut self) {
         if let Some((tx, thread)) = self.operation_retrieval_thread.take() {
             let _ = tx.send(OperationHandlerRetrievalCommand::Stop);
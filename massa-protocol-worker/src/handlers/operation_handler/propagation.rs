@@ -51,35 +51,20 @@ impl PropagationThread {
                 Ok(internal_message) => {
                     match internal_message {
                         OperationHandlerPropagationCommand::PropagateOperations(operations) => {
-                            // Note operations as checked.
-                            {
-                                let mut cache_write = self.cache.write();
-                                for op_id in operations.get_op_refs().iter().copied() {
-                                    cache_write.insert_checked_operation(op_id);
-                                }
-                            }
-
-                            // add to propagation storage
-                            let new_ops = operations.get_op_refs().clone();
-                            self.stored_for_propagation
-                                .push_back((std::time::Instant::now(), new_ops.clone()));
-                            self.op_storage.extend(operations);
-                            self.prune_propagation_storage();
-
-                            for op_id in new_ops {
-                                self.next_batch.insert(op_id);
-                                if self.next_batch.len()
-                                    >= self.config.operation_announcement_buffer_capacity
-                                {
-                                    self.announce_ops();
-                                    batch_deadline = std::time::Instant::now()
-                                        .checked_add(
-                                            self.config
-                                                .operation_announcement_interval
-                                                .to_duration(),
-                                        )
-                                        .expect("Can't init interval op propagation");
-                                }
+                            self.add_operations(operations, &mut batch_deadline);
+                        }
+                        OperationHandlerPropagationCommand::PropagateLocalOperations(
+                            operations,
+                        ) => {
+                            self.add_operations(operations, &mut batch_deadline);
+                            if self.config.eager_local_operations_propagation {
+                                // do not make the operations of the node wait for the batch interval
+                                self.announce_ops();
+                                batch_deadline = std::time::Instant::now()
+                                    .checked_add(
+                                        self.config.operation_announcement_interval.to_duration(),
+                                    )
+                                    .expect("Can't init interval op propagation");
                             }
                         }
                         OperationHandlerPropagationCommand::Stop => {
@@ -101,6 +86,35 @@ impl PropagationThread {
         }
     }
 
+    /// Note operations as checked, keep them for propagation and add them to the next announcement batch.
+    /// The batch is announced right away if it is full.
+    fn add_operations(&mut self, operations: Storage, batch_deadline: &mut std::time::Instant) {
+        // Note operations as checked.
+        {
+            let mut cache_write = self.cache.write();
+            for op_id in operations.get_op_refs().iter().copied() {
+                cache_write.insert_checked_operation(op_id);
+            }
+        }
+
+        // add to propagation storage
+        let new_ops = operations.get_op_refs().clone();
+        self.stored_for_propagation
+            .push_back((std::time::Instant::now(), new_ops.clone()));
+        self.op_storage.extend(operations);
+        self.prune_propagation_storage();
+
+        for op_id in new_ops {
+            self.next_batch.insert(op_id);
+            if self.next_batch.len() >= self.config.operation_announcement_buffer_capacity {
+                self.announce_ops();
+                *batch_deadline = std::time::Instant::now()
+                    .checked_add(self.config.operation_announcement_interval.to_duration())
+                    .expect("Can't init interval op propagation");
+            }
+        }
+    }
+
     /// Prune the list of operations kept for propagation.
     fn prune_propagation_storage(&mut self) {
         let mut removed = PreHashSet::default();
@@ -1,15 +1,16 @@
-use std::{collections::VecDeque, thread::JoinHandle, time::Instant};
+use std::{collections::VecDeque, sync::Arc, thread::JoinHandle, time::Instant};
 
 use crossbeam::{channel::tick, select};
 use massa_channel::{receiver::MassaReceiver, sender::MassaSender};
 use massa_logging::massa_trace;
 use massa_metrics::MassaMetrics;
 use massa_models::{
+    amount::Amount,
     operation::{OperationPrefixId, OperationPrefixIds, SecureShareOperation},
     prehash::{CapacityAllocator, PreHashMap, PreHashSet},
     secure_share::Id,
     slot::Slot,
-    timeslots::get_block_slot_timestamp,
+    timeslots::{get_block_slot_timestamp, get_latest_block_slot_at_timestamp},
 };
 use massa_pool_exports::PoolController;
 use massa_protocol_exports::PeerId;
@@ -17,12 +18,13 @@ use massa_protocol_exports::{ProtocolConfig, ProtocolError};
 use massa_serialization::{DeserializeError, Deserializer};
 use massa_storage::Storage;
 use massa_time::{MassaTime, TimeError};
+use rayon::ThreadPool;
 use schnellru::{ByLength, LruMap};
 
 use crate::{
     handlers::peer_handler::models::{PeerManagementCmd, PeerMessageTuple},
     messages::MessagesSerializer,
-    sig_verifier::verify_sigs_batch,
+    sig_verifier::verify_sigs_batch_in_pool,
     wrap_network::ActiveConnectionsTrait,
 };
 use tracing::{debug, info, warn};
@@ -32,6 +34,7 @@ use super::{
     commands_propagation::OperationHandlerPropagationCommand,
     commands_retrieval::OperationHandlerRetrievalCommand,
     messages::{OperationMessage, OperationMessageDeserializer, OperationMessageDeserializerArgs},
+    metrics::PeerOperationCounters,
     OperationMessageSerializer,
 };
 
@@ -64,7 +67,8 @@ pub struct RetrievalThread {
     receiver_ext: MassaReceiver<OperationHandlerRetrievalCommand>,
     operation_message_serializer: MessagesSerializer,
     peer_cmd_sender: MassaSender<PeerManagementCmd>,
-    _massa_metrics: MassaMetrics,
+    sig_verification_pool: Arc<ThreadPool>,
+    massa_metrics: MassaMetrics,
 }
 
 impl RetrievalThread {
@@ -104,19 +108,30 @@ impl RetrievalThread {
                             match message {
                                 OperationMessage::Operations(ops) => {
                                     debug!("Received operation message: Operations from {}", peer_id);
-                                    if let Err(err) = note_operations_from_peer(
+                                    let ops_count = ops.len() as u64;
+                                    let fee_floor = self.pool_controller.get_operation_fee_floor();
+                                    match note_operations_from_peer(
                                         &self.storage,
                                         &mut self.cache,
                                         &self.config,
                                         ops,
                                         &peer_id,
+                                        fee_floor,
                                         &mut self.internal_sender,
-                                        &mut self.pool_controller
+                                        &mut self.pool_controller,
+                                        &self.sig_verification_pool
                                     ) {
-                                        warn!("peer {} sent us critically incorrect operation, which may be an attack attempt by the remote peer or a loss of sync between us and the remote peer. Err = {}", peer_id, err);
-
-                                        if let Err(e) = self.ban_node(&peer_id) {
-                                            warn!("Error when banning node: {}", e);
+                                        Ok(counters) => counters.record(&self.massa_metrics),
+                                        Err(err) => {
+                                            PeerOperationCounters {
+                                                invalid: ops_count,
+                                                ..Default::default()
+                                            }.record(&self.massa_metrics);
+                                            warn!("peer {} sent us critically incorrect operation, which may be an attack attempt by the remote peer or a loss of sync between us and the remote peer. Err = {}", peer_id, err);
+
+                                            if let Err(e) = self.ban_node(&peer_id) {
+                                                warn!("Error when banning node: {}", e);
+                                            }
                                         }
                                     }
                                 }
@@ -252,6 +267,13 @@ impl RetrievalThread {
         if count_reask > 0 {
             massa_trace!("re-ask operations.", { "count": count_reask });
         }
+        if !future_set.is_empty() {
+            PeerOperationCounters {
+                rate_limited: future_set.len() as u64,
+                ..Default::default()
+            }
+            .record(&self.massa_metrics);
+        }
         if self.op_batch_buffer.len() < self.config.operation_batch_buffer_capacity
             && !future_set.is_empty()
         {
@@ -364,17 +386,34 @@ impl RetrievalThread {
     }
 }
 
+/// Checks the operations sent by a peer, stores the new ones and sends them to the pool.
+/// The operations paying less than `fee_floor` are dropped.
+/// Returns how many of them were accepted, already known, expired, valid too far in the future or below the fee floor.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn note_operations_from_peer(
     base_storage: &Storage,
     operations_cache: &mut SharedOperationCache,
     config: &ProtocolConfig,
     operations: Vec<SecureShareOperation>,
     source_peer_id: &PeerId,
+    fee_floor: Amount,
     ops_propagation_sender: &mut MassaSender<OperationHandlerPropagationCommand>,
     pool_controller: &mut Box<dyn PoolController>,
-) -> Result<(), ProtocolError> {
+    sig_verification_pool: &ThreadPool,
+) -> Result<PeerOperationCounters, ProtocolError> {
     massa_trace!("protocol.protocol_worker.note_operations_from_peer", { "peer": source_peer_id, "operations": operations });
     let now = MassaTime::now();
+    let mut counters = PeerOperationCounters::default();
+    let max_validity_start = get_latest_block_slot_at_timestamp(
+        config.thread_count,
+        config.t0,
+        config.genesis_timestamp,
+        now,
+    )
+    .ok()
+    .flatten()
+    .map_or(0, |slot| slot.period)
+    .saturating_add(config.max_future_validity_periods);
 
     let mut new_operations = PreHashMap::with_capacity(operations.len());
     for operation in operations {
@@ -393,10 +432,33 @@ pub(crate) fn note_operations_from_peer(
         match expire_period_timestamp {
             Ok(slot_timestamp) => {
                 if slot_timestamp.saturating_add(config.max_operations_propagation_time) < now {
+                    counters.invalid += 1;
                     continue;
                 }
             }
-            Err(_) => continue,
+            Err(_) => {
+                counters.invalid += 1;
+                continue;
+            }
+        }
+
+        // ignore if op only becomes valid far in the future: it would just take space in the pool
+        let validity_start = *operation
+            .get_validity_range(config.operation_validity_periods)
+            .start();
+        if validity_start > max_validity_start {
+            debug!(
+                "operation {} from peer {} dropped: its validity starts at period {}, after period {}",
+                operation.id, source_peer_id, validity_start, max_validity_start
+            );
+            counters.future_validity += 1;
+            continue;
+        }
+
+        // ignore if op does not pay the fee floor the pool requires at its current congestion
+        if operation.content.fee < fee_floor {
+            counters.below_fee_floor += 1;
+            continue;
         }
 
         // quit if op is too big
@@ -421,9 +483,12 @@ pub(crate) fn note_operations_from_peer(
         let cache_read = operations_cache.read();
         new_operations.retain(|op_id, _| cache_read.checked_operations.peek(op_id).is_none());
     }
+    counters.duplicate = (all_received_ids.len() - new_operations.len()) as u64;
+    counters.accepted = new_operations.len() as u64;
 
     // optimized signature verification
-    verify_sigs_batch(
+    verify_sigs_batch_in_pool(
+        sig_verification_pool,
         &new_operations
             .iter()
             .map(|(op_id, op)| (*op_id.get_hash(), op.signature, op.content_creator_pub_key))
@@ -465,7 +530,7 @@ pub(crate) fn note_operations_from_peer(
         pool_controller.add_operations(ops);
     }
 
-    Ok(())
+    Ok(counters)
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -479,6 +544,7 @@ pub fn start_retrieval_thread(
     receiver_ext: MassaReceiver<OperationHandlerRetrievalCommand>,
     internal_sender: MassaSender<OperationHandlerPropagationCommand>,
     peer_cmd_sender: MassaSender<PeerManagementCmd>,
+    sig_verification_pool: Arc<ThreadPool>,
     massa_metrics: MassaMetrics,
 ) -> JoinHandle<()> {
     std::thread::Builder::new()
@@ -503,7 +569,8 @@ pub fn start_retrieval_thread(
                     .with_operation_message_serializer(OperationMessageSerializer::new()),
                 op_batch_buffer: VecDeque::new(),
                 peer_cmd_sender,
-                _massa_metrics: massa_metrics,
+                sig_verification_pool,
+                massa_metrics,
             };
             retrieval_thread.run();
         })
@@ -630,7 +630,10 @@ mod tests {
     use parking_lot::RwLock;
     use peernet::{peer::InitConnectionHandler, transports::endpoint::Endpoint};
 
-    use crate::{context::Context, messages::MessagesHandler};
+    use crate::{
+        context::Context,
+        messages::{MessageCounters, MessagesHandler},
+    };
 
     use super::models::PeerDB;
 
@@ -641,7 +644,8 @@ mod tests {
         let (sender_operations, _) = MassaChannel::new(String::from("test_operations"), None);
         let (sender_peers, _) = MassaChannel::new(String::from("test_peers"), None);
         let shared_peer_db = Arc::new(RwLock::new(PeerDB::default()));
-        let mut handshake = super::MassaHandshake::new(shared_peer_db, ProtocolConfig::default());
+        let config = ProtocolConfig::default();
+        let mut handshake = super::MassaHandshake::new(shared_peer_db, config.clone());
         let our_keypair = KeyPair::generate(0).unwrap();
         let messages_handlers = MessagesHandler {
             id_deserializer: U64VarIntDeserializer::new(
@@ -652,6 +656,11 @@ mod tests {
             sender_endorsements,
             sender_operations,
             sender_peers,
+            message_counters: MessageCounters::new(
+                config.thread_count,
+                config.t0,
+                config.genesis_timestamp,
+            ),
         };
         let (local_sender, remote_receiver) =
             MassaChannel::new(String::from("Test_transport_local_to_remote"), None);
@@ -702,7 +711,8 @@ mod tests {
         let (sender_operations, _) = MassaChannel::new(String::from("test_operations"), None);
         let (sender_peers, _) = MassaChannel::new(String::from("test_peers"), None);
         let shared_peer_db = Arc::new(RwLock::new(PeerDB::default()));
-        let mut handshake = super::MassaHandshake::new(shared_peer_db, ProtocolConfig::default());
+        let config = ProtocolConfig::default();
+        let mut handshake = super::MassaHandshake::new(shared_peer_db, config.clone());
         let our_keypair = KeyPair::generate(0).unwrap();
         let messages_handlers = MessagesHandler {
             id_deserializer: U64VarIntDeserializer::new(
@@ -713,6 +723,11 @@ mod tests {
             sender_endorsements,
             sender_operations,
             sender_peers,
+            message_counters: MessageCounters::new(
+                config.thread_count,
+                config.t0,
+                config.genesis_timestamp,
+            ),
         };
         let (local_sender, _) =
             MassaChannel::new(String::from("Test_transport_local_to_remote"), None);
@@ -747,7 +762,8 @@ mod tests {
         let (sender_operations, _) = MassaChannel::new(String::from("test_operations"), None);
         let (sender_peers, _) = MassaChannel::new(String::from("test_peers"), None);
         let shared_peer_db = Arc::new(RwLock::new(PeerDB::default()));
-        let mut handshake = super::MassaHandshake::new(shared_peer_db, ProtocolConfig::default());
+        let config = ProtocolConfig::default();
+        let mut handshake = super::MassaHandshake::new(shared_peer_db, config.clone());
         let our_keypair = KeyPair::generate(0).unwrap();
         let messages_handlers = MessagesHandler {
             id_deserializer: U64VarIntDeserializer::new(
@@ -758,6 +774,11 @@ mod tests {
             sender_endorsements,
             sender_operations,
             sender_peers,
+            message_counters: MessageCounters::new(
+                config.thread_count,
+                config.t0,
+                config.genesis_timestamp,
+            ),
         };
         let (local_sender, _) =
             MassaChannel::new(String::from("Test_transport_local_to_remote"), None);
@@ -1,15 +1,24 @@
 use massa_channel::sender::MassaSender;
+use massa_models::{
+    slot::Slot, stats::SlotMessageCounters, timeslots::get_latest_block_slot_at_timestamp,
+};
 use massa_protocol_exports::PeerId;
 use massa_serialization::{
     DeserializeError, Deserializer, Serializer, U64VarIntDeserializer, U64VarIntSerializer,
 };
+use massa_time::MassaTime;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
+use parking_lot::Mutex;
 use peernet::{
     error::{PeerNetError, PeerNetResult},
     messages::{
         MessagesHandler as PeerNetMessagesHandler, MessagesSerializer as PeerNetMessagesSerializer,
     },
 };
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
 use tracing::debug;
 
 use crate::handlers::{
@@ -217,6 +226,98 @@ impl PeerNetMessagesSerializer<Message> for MessagesSerializer {
     }
 }
 
+/// Counts the messages received and sent by the node, keyed by the slot at which they go through.
+///
+/// Counting a message only touches atomics: the lock is only taken when the counted slot changes.
+#[derive(Clone)]
+pub struct MessageCounters {
+    thread_count: u8,
+    t0: MassaTime,
+    genesis_timestamp: MassaTime,
+    /// index of the counted slot plus one, zero before the first counted slot
+    slot_index: Arc<AtomicU64>,
+    /// messages received during the counted slot so far
+    received: Arc<AtomicU64>,
+    /// messages sent during the counted slot so far
+    sent: Arc<AtomicU64>,
+    /// counted slot, and previous slot with its counts
+    counters: Arc<Mutex<SlotMessageCounters>>,
+}
+
+impl MessageCounters {
+    pub fn new(thread_count: u8, t0: MassaTime, genesis_timestamp: MassaTime) -> Self {
+        Self {
+            thread_count,
+            t0,
+            genesis_timestamp,
+            slot_index: Arc::new(AtomicU64::new(0)),
+            received: Arc::new(AtomicU64::new(0)),
+            sent: Arc::new(AtomicU64::new(0)),
+            counters: Arc::new(Mutex::new(SlotMessageCounters::default())),
+        }
+    }
+
+    fn current_slot(&self) -> Option<Slot> {
+        get_latest_block_slot_at_timestamp(
+            self.thread_count,
+            self.t0,
+            self.genesis_timestamp,
+            MassaTime::now(),
+        )
+        .unwrap_or(None)
+    }
+
+    fn slot_index(&self, slot: Slot) -> u64 {
+        slot.period
+            .saturating_mul(self.thread_count as u64)
+            .saturating_add(slot.thread as u64)
+            .saturating_add(1)
+    }
+
+    /// Moves the counting to `slot` if it is later than the counted slot.
+    /// Messages counted at an earlier slot are added to the counted slot.
+    fn advance(&self, slot: Slot) {
+        let slot_index = self.slot_index(slot);
+        if slot_index <= self.slot_index.load(Ordering::Acquire) {
+            return;
+        }
+        let mut counters = self.counters.lock();
+        // another thread may have advanced while we were waiting for the lock
+        if slot_index <= self.slot_index.load(Ordering::Acquire) {
+            return;
+        }
+        counters.current_received = self.received.swap(0, Ordering::Relaxed);
+        counters.current_sent = self.sent.swap(0, Ordering::Relaxed);
+        counters.advance(slot);
+        self.slot_index.store(slot_index, Ordering::Release);
+    }
+
+    pub fn count_received(&self) {
+        if let Some(slot) = self.current_slot() {
+            self.advance(slot);
+            self.received.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn count_sent(&self) {
+        if let Some(slot) = self.current_slot() {
+            self.advance(slot);
+            self.sent.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Get the counters, moved to the current slot so that a quiet slot shows zero counts
+    pub fn get(&self) -> SlotMessageCounters {
+        if let Some(slot) = self.current_slot() {
+            self.advance(slot);
+        }
+        let mut counters = self.counters.lock().clone();
+        counters.current_received = self.received.load(Ordering::Relaxed);
+        counters.current_sent = self.sent.load(Ordering::Relaxed);
+        counters
+    }
+}
+
 #[derive(Clone)]
 pub struct MessagesHandler {
     pub id_deserializer: U64VarIntDeserializer,
@@ -224,10 +325,12 @@ pub struct MessagesHandler {
     pub sender_endorsements: MassaSender<PeerMessageTuple>,
     pub sender_operations: MassaSender<PeerMessageTuple>,
     pub sender_peers: MassaSender<PeerMessageTuple>,
+    pub message_counters: MessageCounters,
 }
 
 impl PeerNetMessagesHandler<PeerId> for MessagesHandler {
     fn handle(&self, data: &[u8], peer_id: &PeerId) -> PeerNetResult<()> {
+        self.message_counters.count_received();
         let (data, raw_id) = self
             .id_deserializer
             .deserialize::<DeserializeError>(data)
@@ -280,3 +383,31 @@ impl PeerNetMessagesHandler<PeerId> for MessagesHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_counters_advance() {
+        let counters = MessageCounters::new(2, MassaTime::from_millis(1000), MassaTime::now());
+        counters.advance(Slot::new(1, 0));
+        counters.received.fetch_add(3, Ordering::Relaxed);
+        counters.sent.fetch_add(1, Ordering::Relaxed);
+
+        // an earlier slot keeps counting in the current one
+        counters.advance(Slot::new(0, 1));
+        let snapshot = counters.counters.lock().clone();
+        assert_eq!(snapshot.current_slot, Some(Slot::new(1, 0)));
+        assert_eq!(counters.received.load(Ordering::Relaxed), 3);
+
+        // the next slot moves the counts to the previous slot
+        counters.advance(Slot::new(1, 1));
+        let snapshot = counters.counters.lock().clone();
+        assert_eq!(snapshot.current_slot, Some(Slot::new(1, 1)));
+        assert_eq!(snapshot.previous_slot, Some(Slot::new(1, 0)));
+        assert_eq!((snapshot.previous_received, snapshot.previous_sent), (3, 1));
+        assert_eq!(counters.received.load(Ordering::Relaxed), 0);
+        assert_eq!(counters.sent.load(Ordering::Relaxed), 0);
+    }
+}
@@ -5,7 +5,7 @@
 use massa_hash::Hash;
 use massa_protocol_exports::ProtocolError;
 use massa_signature::{verify_signature_batch, PublicKey, Signature};
-use rayon::{prelude::ParallelIterator, slice::ParallelSlice};
+use rayon::{prelude::ParallelIterator, slice::ParallelSlice, ThreadPool, ThreadPoolBuilder};
 
 //TODO: Benchmark
 /// Limit for small batch optimization
@@ -28,3 +28,28 @@ pub fn verify_sigs_batch(ops: &[(Hash, Signature, PublicKey)]) -> Result<(), Pro
         .try_for_each(verify_signature_batch)
         .map_err(|_err| ProtocolError::WrongSignature)
 }
+
+/// Builds the thread pool used to verify signatures in parallel.
+/// `max_threads` defaults to the number of CPUs.
+pub fn build_sig_verification_pool(
+    max_threads: Option<usize>,
+) -> Result<ThreadPool, ProtocolError> {
+    ThreadPoolBuilder::new()
+        .num_threads(max_threads.unwrap_or(0))
+        .thread_name(|index| format!("sig-verif-{}", index))
+        .build()
+        .map_err(|err| {
+            ProtocolError::GeneralProtocolError(format!(
+                "failed to build the signature verification thread pool: {}",
+                err
+            ))
+        })
+}
+
+/// Same as `verify_sigs_batch` but runs on the threads of `pool`
+pub fn verify_sigs_batch_in_pool(
+    pool: &ThreadPool,
+    ops: &[(Hash, Signature, PublicKey)],
+) -> Result<(), ProtocolError> {
+    pool.install(|| verify_sigs_batch(ops))
+}
@@ -661,3 +661,55 @@ fn test_protocol_on_ask_operations() {
     );
     waitpoint.wait();
 }
+
+#[test]
+fn test_protocol_announces_local_operations_right_away() {
+    let protocol_config = ProtocolConfig {
+        thread_count: 2,
+        operation_announcement_interval: MassaTime::from_millis(10_000),
+        eager_local_operations_propagation: true,
+        ..Default::default()
+    };
+    let creator = KeyPair::generate(0).unwrap();
+    let operation = ProtocolTestUniverse::create_operation(&creator, 1, *CHAINID);
+    let node_a_peer_id = PeerId::from_public_key(KeyPair::generate(0).unwrap().get_public_key());
+
+    let (announced_tx, announced_rx) = std::sync::mpsc::channel();
+    let mut foreign_controllers = ProtocolForeignControllers::new_with_mocks();
+    ProtocolTestUniverse::peer_db_boilerplate(&mut foreign_controllers.peer_db.write());
+    let mut shared_active_connections = MockActiveConnectionsTraitWrapper::new();
+    shared_active_connections.set_expectations(|active_connections| {
+        active_connections
+            .expect_send_to_peer()
+            .returning(move |peer_id, _, message, _| {
+                if let Message::Operation(OperationMessage::OperationsAnnouncement(operations)) =
+                    message
+                {
+                    let _ = announced_tx.send((*peer_id, operations));
+                }
+                Ok(())
+            });
+    });
+    ProtocolTestUniverse::active_connections_boilerplate(
+        &mut shared_active_connections,
+        HashSet::from([node_a_peer_id]),
+    );
+    foreign_controllers
+        .network_controller
+        .expect_get_active_connections()
+        .returning(move || Box::new(shared_active_connections.clone()));
+    let mut universe = ProtocolTestUniverse::new(foreign_controllers, protocol_config);
+
+    universe.storage.store_operations(vec![operation.clone()]);
+    universe
+        .module_controller
+        .propagate_operations(universe.storage.clone())
+        .unwrap();
+
+    // announced well before the end of the batch interval
+    let (peer_id, operations) = announced_rx
+        .recv_timeout(Duration::from_millis(5_000))
+        .expect("local operation was not announced right away");
+    assert_eq!(peer_id, node_a_peer_id);
+    assert!(operations.contains(&operation.id.into_prefix()));
+}
@@ -1,6 +1,6 @@
 use massa_channel::MassaChannel;
 use massa_consensus_exports::{ConsensusController, MockConsensusController};
-use massa_models::config::MIP_STORE_STATS_BLOCK_CONSIDERED;
+use massa_models::{amount::Amount, config::MIP_STORE_STATS_BLOCK_CONSIDERED};
 use massa_pool_exports::{MockPoolControllerWrapper, PoolController};
 use massa_pos_exports::{MockSelectorControllerWrapper, SelectorController};
 use massa_protocol_exports::{
@@ -29,7 +29,7 @@ use crate::{
         peer_handler::{models::SharedPeerDB, PeerManagementMessageSerializer},
     },
     manager::ProtocolManagerImpl,
-    messages::{Message, MessagesHandler, MessagesSerializer},
+    messages::{Message, MessageCounters, MessagesHandler, MessagesSerializer},
     wrap_network::{MockActiveConnectionsTraitWrapper, MockNetworkController, NetworkController},
     wrap_peer_db::MockPeerDBTrait,
 };
@@ -58,9 +58,16 @@ pub struct ProtocolForeignControllers {
 
 impl ProtocolForeignControllers {
     pub fn new_with_mocks() -> Self {
+        let mut pool_controller = Box::new(MockPoolControllerWrapper::new());
+        // the pool is never congested unless a test says otherwise
+        pool_controller.set_expectations(|pool_controller| {
+            pool_controller
+                .expect_get_operation_fee_floor()
+                .returning(Amount::zero);
+        });
         Self {
             consensus_controller: Box::new(MockConsensusController::new()),
-            pool_controller: Box::new(MockPoolControllerWrapper::new()),
+            pool_controller,
             selector_controller: Box::new(MockSelectorControllerWrapper::new()),
             network_controller: Box::new(MockNetworkController::new()),
             peer_db: Arc::new(RwLock::new(MockPeerDBTrait::new())),
@@ -222,6 +229,11 @@ pub fn start_protocol_controller_with_mock_network(
         sender_operations: sender_operations.clone(),
         sender_peers: sender_peers.clone(),
         id_deserializer: U64VarIntDeserializer::new(Included(0), Included(u64::MAX)),
+        message_counters: MessageCounters::new(
+            config.thread_count,
+            config.t0,
+            config.genesis_timestamp,
+        ),
     };
 
     let (controller, channels) = create_protocol_controller(config.clone());
@@ -49,7 +49,7 @@ use crate::{
     },
     ip::to_canonical,
     manager::ProtocolManagerImpl,
-    messages::MessagesHandler,
+    messages::{MessageCounters, MessagesHandler},
     wrap_network::NetworkControllerImpl,
 };
 
@@ -207,6 +207,10 @@ pub fn start_protocol_controller(
         Some(config.max_size_channel_network_to_peer_handler),
     );
 
+    // Count the messages going through the network, per slot
+    let message_counters =
+        MessageCounters::new(config.thread_count, config.t0, config.genesis_timestamp);
+
     // Register channels for handlers
     let message_handlers: MessagesHandler = MessagesHandler {
         sender_blocks: sender_blocks.clone(),
@@ -214,6 +218,7 @@ pub fn start_protocol_controller(
         sender_operations: sender_operations.clone(),
         sender_peers: sender_peers.clone(),
         id_deserializer: U64VarIntDeserializer::new(Included(0), Included(u64::MAX)),
+        message_counters: message_counters.clone(),
     };
 
     // try to read node keypair from file, otherwise generate it & write to file. Then derive nodeId
@@ -314,9 +319,10 @@ pub fn start_protocol_controller(
     };
     peernet_config.max_in_connections = config.max_in_connections;
 
-    let network_controller = Box::new(NetworkControllerImpl::new(PeerNetManager::new(
-        peernet_config,
-    )));
+    let network_controller = Box::new(NetworkControllerImpl::new(
+        PeerNetManager::new(peernet_config),
+        message_counters,
+    ));
 
     let connectivity_thread_handle = start_connectivity_thread(
         PeerId::from_public_key(keypair.get_public_key()),
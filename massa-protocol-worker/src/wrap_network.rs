@@ -13,7 +13,7 @@ use peernet::{
 use crate::{
     context::Context,
     handlers::peer_handler::MassaHandshake,
-    messages::{Message, MessagesHandler, MessagesSerializer},
+    messages::{Message, MessageCounters, MessagesHandler, MessagesSerializer},
 };
 
 #[cfg(test)]
@@ -46,7 +46,14 @@ impl Clone for Box<dyn ActiveConnectionsTrait> {
     }
 }
 
-impl ActiveConnectionsTrait for SharedActiveConnections<PeerId> {
+/// Active connections of the network manager, counting the messages sent through them
+#[derive(Clone)]
+pub struct CountedActiveConnections {
+    connections: SharedActiveConnections<PeerId>,
+    message_counters: MessageCounters,
+}
+
+impl ActiveConnectionsTrait for CountedActiveConnections {
     fn send_to_peer(
         &self,
         peer_id: &PeerId,
@@ -54,11 +61,13 @@ impl ActiveConnectionsTrait for SharedActiveConnections<PeerId> {
         message: Message,
         high_priority: bool,
     ) -> Result<(), ProtocolError> {
-        if let Some(connection) = self.read().connections.get(peer_id) {
+        if let Some(connection) = self.connections.read().connections.get(peer_id) {
             connection
                 .send_channels
                 .try_send(message_serializer, message, high_priority)
-                .map_err(|err| ProtocolError::SendError(err.to_string()))
+                .map_err(|err| ProtocolError::SendError(err.to_string()))?;
+            self.message_counters.count_sent();
+            Ok(())
         } else {
             Err(ProtocolError::PeerDisconnected(peer_id.to_string()))
         }
@@ -69,13 +78,19 @@ impl ActiveConnectionsTrait for SharedActiveConnections<PeerId> {
     }
 
     fn get_peer_ids_connected(&self) -> HashSet<PeerId> {
-        self.read().connections.keys().cloned().collect()
+        self.connections
+            .read()
+            .connections
+            .keys()
+            .cloned()
+            .collect()
     }
 
     fn get_peers_connected(
         &self,
     ) -> HashMap<PeerId, (SocketAddr, PeerConnectionType, Option<String>)> {
-        self.read()
+        self.connections
+            .read()
             .connections
             .iter()
             .map(|(peer_id, connection)| {
@@ -92,29 +107,29 @@ impl ActiveConnectionsTrait for SharedActiveConnections<PeerId> {
     }
 
     fn get_nb_out_connections(&self) -> usize {
-        self.read().nb_out_connections
+        self.connections.read().nb_out_connections
     }
 
     fn get_nb_in_connections(&self) -> usize {
-        self.read().nb_in_connections
+        self.connections.read().nb_in_connections
     }
 
     fn shutdown_connection(&mut self, peer_id: &PeerId) {
-        if let Some(connection) = self.write().connections.get_mut(peer_id) {
+        if let Some(connection) = self.connections.write().connections.get_mut(peer_id) {
             connection.shutdown();
         }
     }
 
     fn get_peers_connections_bandwidth(&self) -> HashMap<String, (u64, u64)> {
         let mut map = HashMap::new();
-        for (peerid, conn) in self.read().connections.iter() {
+        for (peerid, conn) in self.connections.read().connections.iter() {
             map.insert(peerid.to_string(), conn.endpoint.get_bandwidth());
         }
         map
     }
 
     fn get_peer_ids_out_connection_queue(&self) -> HashSet<SocketAddr> {
-        self.read().out_connection_queue.clone()
+        self.connections.read().out_connection_queue.clone()
     }
 }
 
@@ -142,19 +157,27 @@ pub trait NetworkController: Send + Sync {
 
 pub struct NetworkControllerImpl {
     peernet_manager: PeerNetManager<PeerId, Context, MassaHandshake, MessagesHandler>,
+    message_counters: MessageCounters,
 }
 
 impl NetworkControllerImpl {
     pub fn new(
         peernet_manager: PeerNetManager<PeerId, Context, MassaHandshake, MessagesHandler>,
+        message_counters: MessageCounters,
     ) -> Self {
-        Self { peernet_manager }
+        Self {
+            peernet_manager,
+            message_counters,
+        }
     }
 }
 
 impl NetworkController for NetworkControllerImpl {
     fn get_active_connections(&self) -> Box<dyn ActiveConnectionsTrait> {
-        Box::new(self.peernet_manager.active_connections.clone())
+        Box::new(CountedActiveConnections {
+            connections: self.peernet_manager.active_connections.clone(),
+            message_counters: self.message_counters.clone(),
+        })
     }
 
     fn start_listener(
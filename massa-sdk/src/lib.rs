@@ -17,19 +17,28 @@ use jsonrpsee_ws_client as _;
 use massa_api_exports::page::PagedVecV2;
 use massa_api_exports::ApiRequest;
 use massa_api_exports::{
-    address::AddressInfo,
+    address::{
+        AddressInfo, AddressInfoField, DrawKind, DrawScheduleQuery, MultiAddressInfo,
+        MultiAddressInfoQuery, PendingRollSalesQuery, RollSellPreview,
+    },
     block::{BlockInfo, BlockSummary},
+    clique::CompactCliques,
     datastore::{DatastoreEntryInput, DatastoreEntryOutput},
-    endorsement::EndorsementInfo,
+    endorsement::{EndorsementDraw, EndorsementDrawsQuery, EndorsementInfo},
     execution::{ExecuteReadOnlyResponse, ReadOnlyBytecodeExecution, ReadOnlyCall, Transfer},
+    ledger::{BalanceChangeDetail, TransferSimulation, TransferSimulationQuery},
     node::NodeStatus,
-    operation::{OperationInfo, OperationInput},
+    operation::{CombinedOpsQuery, OperationInfo, OperationInput, OperationsBySender},
+    rolls::PosParams,
     TimeInterval,
 };
+use massa_hash::Hash;
 use massa_models::secure_share::SecureShare;
 use massa_models::slot::Slot;
 use massa_models::{
     address::Address,
+    address_history::AddressHistoryBundle,
+    amount::Amount,
     block::FilledBlock,
     block_header::BlockHeader,
     block_id::BlockId,
@@ -40,11 +49,12 @@ use massa_models::{
     node::NodeId,
     operation::{Operation, OperationId},
     output_event::SCOutputEvent,
-    prehash::{PreHashMap, PreHashSet},
+    prehash::PreHashSet,
     version::Version,
 };
 use massa_proto_rs::massa::api::v1::private_service_client::PrivateServiceClient;
 use massa_proto_rs::massa::api::v1::public_service_client::PublicServiceClient;
+use massa_time::MassaTime;
 use std::net::{IpAddr, SocketAddr};
 use std::str::FromStr;
 use thiserror::Error;
@@ -164,6 +174,15 @@ impl RpcClient {
             .map_err(|e| to_error_obj(e.to_string()))
     }
 
+    /// Write every final ledger entry to a JSON file on the node's filesystem.
+    /// Returns the number of exported entries.
+    pub async fn node_export_ledger(&self, path: String) -> RpcResult<usize> {
+        self.http_client
+            .request("node_export_ledger", rpc_params![path])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
     /// Add a vector of new secret keys for the node to use to stake.
     /// No confirmation to expect.
     pub async fn add_staking_secret_keys(&self, secret_keys: Vec<String>) -> RpcResult<()> {
@@ -329,17 +348,45 @@ impl RpcClient {
             .map_err(|e| to_error_obj(e.to_string()))
     }
 
-    pub(crate) async fn _get_cliques(&self) -> RpcResult<Vec<Clique>> {
+    /// Returns the cliques of the block graph
+    pub async fn get_cliques(&self) -> RpcResult<Vec<Clique>> {
         self.http_client
             .request("get_cliques", rpc_params![])
             .await
             .map_err(|e| to_error_obj(e.to_string()))
     }
 
+    /// Returns the cliques of the block graph, decoded from their compact form
+    pub async fn get_compact_cliques(&self) -> RpcResult<Vec<Clique>> {
+        let compact: CompactCliques = self
+            .http_client
+            .request("get_compact_cliques", rpc_params![])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))?;
+        compact.decode().map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Returns a fingerprint of the genesis block ids, identifying the network of the node
+    pub async fn get_genesis_fingerprint(&self) -> RpcResult<Hash> {
+        self.http_client
+            .request("get_genesis_fingerprint", rpc_params![])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Returns the proof-of-stake parameters of the network
+    pub async fn get_pos_params(&self) -> RpcResult<PosParams> {
+        self.http_client
+            .request("get_pos_params", rpc_params![])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
     // Debug (specific information)
 
-    /// Returns the active stakers and their roll counts for the current cycle.
-    pub(crate) async fn _get_stakers(&self) -> RpcResult<PreHashMap<Address, u64>> {
+    /// Returns the active stakers and their roll counts for the current cycle,
+    /// sorted by decreasing roll count.
+    pub async fn get_stakers(&self) -> RpcResult<Vec<(Address, u64)>> {
         self.http_client
             .request("get_stakers", rpc_params![])
             .await
@@ -357,6 +404,40 @@ impl RpcClient {
             .map_err(|e| to_error_obj(e.to_string()))
     }
 
+    /// Same as `get_operations`, with the signed bytes and the signature of each operation
+    /// so that they can be verified independently.
+    pub async fn get_operations_with_raw(
+        &self,
+        operation_ids: Vec<OperationId>,
+    ) -> RpcResult<Vec<OperationInfo>> {
+        self.http_client
+            .request("get_operations", rpc_params![operation_ids, true])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Returns the operations created by the given address(es) that are still waiting in the pool
+    pub async fn get_pending_operations(
+        &self,
+        addresses: Vec<Address>,
+    ) -> RpcResult<Vec<OperationInfo>> {
+        self.http_client
+            .request("get_pending_operations", rpc_params![addresses])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Returns the operations created by an address, from the pool and the block graph
+    pub async fn get_operations_by_sender(
+        &self,
+        query: CombinedOpsQuery,
+    ) -> RpcResult<OperationsBySender> {
+        self.http_client
+            .request("get_operations_by_sender", rpc_params![query])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
     /// Returns endorsement(s) information associated to a given list of endorsement(s) ID(s)
     pub async fn get_endorsements(
         &self,
@@ -368,6 +449,17 @@ impl RpcClient {
             .map_err(|e| to_error_obj(e.to_string()))
     }
 
+    /// Returns the addresses drawn to create endorsements in a slot range
+    pub async fn get_endorsement_draws(
+        &self,
+        query: EndorsementDrawsQuery,
+    ) -> RpcResult<Vec<EndorsementDraw>> {
+        self.http_client
+            .request("get_endorsement_draws", rpc_params![query])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
     /// Returns block(s) information associated to a given list of block(s) ID(s)
     pub async fn get_blocks(&self, block_ids: Vec<BlockId>) -> RpcResult<Vec<BlockInfo>> {
         self.http_client
@@ -389,7 +481,7 @@ impl RpcClient {
 
     /// Get the block graph within the specified time interval.
     /// Optional parameters: from `<time_start>` (included) and to `<time_end>` (excluded) millisecond timestamp
-    pub(crate) async fn _get_graph_interval(
+    pub async fn get_graph_interval(
         &self,
         time_interval: TimeInterval,
     ) -> RpcResult<Vec<BlockSummary>> {
@@ -407,6 +499,94 @@ impl RpcClient {
             .map_err(|e| to_error_obj(e.to_string()))
     }
 
+    /// Get only the given sections of the info of addresses, the others being left empty
+    pub async fn get_addresses_fields(
+        &self,
+        addresses: Vec<Address>,
+        fields: Vec<AddressInfoField>,
+    ) -> RpcResult<Vec<AddressInfo>> {
+        self.http_client
+            .request("get_addresses", rpc_params![addresses, fields])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Get the information of many addresses at once, in the order of the query
+    pub async fn get_multi_address_info(
+        &self,
+        query: MultiAddressInfoQuery,
+    ) -> RpcResult<MultiAddressInfo> {
+        self.http_client
+            .request("get_multi_address_info", rpc_params![query])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Get the upcoming block and endorsement draws of an address
+    pub async fn get_draw_schedule(
+        &self,
+        query: DrawScheduleQuery,
+    ) -> RpcResult<Vec<(Slot, MassaTime, DrawKind)>> {
+        self.http_client
+            .request("get_draw_schedule", rpc_params![query])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Get the roll sales of an address whose coins are not credited yet
+    pub async fn get_pending_roll_sales(
+        &self,
+        query: PendingRollSalesQuery,
+    ) -> RpcResult<Vec<(Slot, u64, Amount)>> {
+        self.http_client
+            .request("get_pending_roll_sales", rpc_params![query])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Preview when and how many coins would be credited back by a roll sale
+    pub async fn preview_roll_sell(&self, preview: RollSellPreview) -> RpcResult<(Slot, Amount)> {
+        self.http_client
+            .request("preview_roll_sell", rpc_params![preview])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Get the executed operations of a slot range that changed the balance of an address
+    pub async fn get_balance_change_detail(
+        &self,
+        query: BalanceChangeDetail,
+    ) -> RpcResult<Vec<(OperationId, bool, Amount)>> {
+        self.http_client
+            .request("get_balance_change_detail", rpc_params![query])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Simulate a transfer on the final and candidate ledgers
+    pub async fn simulate_transfer(
+        &self,
+        query: TransferSimulationQuery,
+    ) -> RpcResult<TransferSimulation> {
+        self.http_client
+            .request("simulate_transfer", rpc_params![query])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
+    /// Export the history of an address in the final blocks retained by the node,
+    /// signed by the node if `sign` is true
+    pub async fn get_address_history(
+        &self,
+        address: Address,
+        sign: bool,
+    ) -> RpcResult<AddressHistoryBundle> {
+        self.http_client
+            .request("get_address_history", rpc_params![address, sign])
+            .await
+            .map_err(|e| to_error_obj(e.to_string()))
+    }
+
     /// Get datastore entries
     pub async fn get_datastore_entries(
         &self,
@@ -24,6 +24,8 @@ pub enum WalletError {
     MissingKeyError(Address),
     /// `MassaCipher` error: {0}
     MassaCipherError(#[from] massa_cipher::CipherError),
+    /// Corrupted wallet file {0:?}: {1}
+    CorruptedFileError(std::path::PathBuf, String),
     /// Version error: {0}
     VersionError(String),
 }
@@ -17,7 +17,7 @@ use massa_signature::{KeyPair, PublicKey};
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::Entry;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use zeroize::{Zeroize, ZeroizeOnDrop};
 
@@ -25,6 +25,12 @@ mod error;
 
 const WALLET_VERSION: u64 = 1;
 
+/// Extension appended to the name of a key file to name its backup
+const BACKUP_EXTENSION: &str = "bak";
+
+/// Extension of the temporary files written before being atomically renamed
+const TEMPORARY_EXTENSION: &str = "tmp";
+
 /// Contains the keypairs created in the wallet.
 #[derive(Clone, Debug, Deserialize, Serialize, Zeroize, ZeroizeOnDrop)]
 pub struct Wallet {
@@ -38,6 +44,10 @@ pub struct Wallet {
     password: String,
     /// chain id
     chain_id: u64,
+    /// Keep a backup of each key file before overwriting it, enabled by default
+    #[zeroize(skip)]
+    #[serde(skip)]
+    backup: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -62,9 +72,12 @@ impl Wallet {
             for entry in std::fs::read_dir(&path)? {
                 let entry = entry?;
                 let path = entry.path();
-                if path.is_file() {
+                if path.is_file() && !is_backup_or_temporary(&path) {
                     let content = &std::fs::read(&path)?[..];
-                    let mut wallet = serde_yaml::from_slice::<WalletFileFormat>(content)?;
+                    let mut wallet =
+                        serde_yaml::from_slice::<WalletFileFormat>(content).map_err(|err| {
+                            WalletError::CorruptedFileError(path.clone(), err.to_string())
+                        })?;
                     if wallet.version == 0 {
                         // fix bug in handling version 0
                         wallet.version = 1;
@@ -112,6 +125,7 @@ impl Wallet {
                 wallet_path: path,
                 password,
                 chain_id,
+                backup: true,
             })
         } else {
             let wallet = Wallet {
@@ -119,12 +133,30 @@ impl Wallet {
                 wallet_path: path,
                 password,
                 chain_id,
+                backup: true,
             };
             wallet.save()?;
             Ok(wallet)
         }
     }
 
+    /// Enables or disables the backup of the key files before they are overwritten by `save()`, enabled by default.
+    /// The backup of `wallet_<address>.yaml` is `wallet_<address>.yaml.bak`, in the wallet directory.
+    pub fn set_backup(&mut self, enabled: bool) {
+        self.backup = enabled;
+    }
+
+    /// Returns true if the key file `file_path` of a wallet has a backup
+    pub fn has_backup(file_path: &Path) -> bool {
+        backup_path(file_path).is_file()
+    }
+
+    /// Replaces the key file `file_path` of a wallet, typically reported as corrupted, by its backup.
+    /// The backup is kept.
+    pub fn restore_backup(file_path: &Path) -> Result<(), WalletError> {
+        atomic_copy(&backup_path(file_path), file_path)
+    }
+
     /// Sign arbitrary message with the associated keypair
     /// returns none if the address isn't in the wallet or if an error occurred during the signature
     /// else returns the public key that signed the message and the signature
@@ -199,7 +231,10 @@ impl Wallet {
         } else {
             let read_dir = std::fs::read_dir(&self.wallet_path)?;
             for path in read_dir {
-                existing_keys.insert(path?.path());
+                let path = path?.path();
+                if !is_backup_or_temporary(&path) {
+                    existing_keys.insert(path);
+                }
             }
         }
         let mut persisted_keys: HashSet<PathBuf> = HashSet::new();
@@ -218,13 +253,21 @@ impl Wallet {
             let ser_keys = serde_yaml::to_string(&file_formatted)?;
             let file_path = self.wallet_path.join(format!("wallet_{}.yaml", addr));
 
-            std::fs::write(&file_path, ser_keys)?;
+            // keep a copy of the previous key file, to restore it if the new one gets corrupted
+            if self.backup && file_path.is_file() {
+                atomic_copy(&file_path, &backup_path(&file_path))?;
+            }
+            atomic_write(&file_path, ser_keys.as_bytes())?;
             persisted_keys.insert(file_path);
         }
 
         let to_remove = existing_keys.difference(&persisted_keys);
         for path in to_remove {
             std::fs::remove_file(path)?;
+            let backup = backup_path(path);
+            if backup.is_file() {
+                std::fs::remove_file(backup)?;
+            }
         }
 
         Ok(())
@@ -254,6 +297,45 @@ impl Wallet {
     }
 }
 
+/// Path of the backup of a key file
+fn backup_path(file_path: &Path) -> PathBuf {
+    let mut path = file_path.as_os_str().to_owned();
+    path.push(".");
+    path.push(BACKUP_EXTENSION);
+    PathBuf::from(path)
+}
+
+/// Backups and temporary files are stored next to the key files but are not part of the wallet
+fn is_backup_or_temporary(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| {
+        ext == BACKUP_EXTENSION || ext == TEMPORARY_EXTENSION
+    })
+}
+
+/// Path of the temporary file written before being renamed to `file_path`
+fn temporary_path(file_path: &Path) -> PathBuf {
+    let mut path = file_path.as_os_str().to_owned();
+    path.push(".");
+    path.push(TEMPORARY_EXTENSION);
+    PathBuf::from(path)
+}
+
+/// Copies `from` to a temporary file renamed to `to`, so that `to` is never partially written
+fn atomic_copy(from: &Path, to: &Path) -> Result<(), WalletError> {
+    let temporary = temporary_path(to);
+    std::fs::copy(from, &temporary)?;
+    std::fs::rename(&temporary, to)?;
+    Ok(())
+}
+
+/// Writes `content` to a temporary file renamed to `to`, so that `to` is never partially written
+fn atomic_write(to: &Path, content: &[u8]) -> Result<(), WalletError> {
+    let temporary = temporary_path(to);
+    std::fs::write(&temporary, content)?;
+    std::fs::rename(&temporary, to)?;
+    Ok(())
+}
+
 impl std::fmt::Display for Wallet {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         writeln!(f)?;
@@ -269,3 +351,53 @@ impl std::fmt::Display for Wallet {
 /// Test utils
 #[cfg(feature = "test-exports")]
 pub mod test_exports;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_restore_corrupted_key_file_from_backup() {
+        let folder = TempDir::new().unwrap();
+        let path = folder.path().join("wallet");
+        let keypair = KeyPair::generate(0).unwrap();
+        let address = Address::from_public_key(&keypair.get_public_key());
+        let mut wallet = Wallet::new(path.clone(), "test".to_string(), 77).unwrap();
+        wallet.add_keypairs(vec![keypair]).unwrap();
+        let file_path = path.join(format!("wallet_{}.yaml", address));
+        assert!(!Wallet::has_backup(&file_path));
+
+        // adding a key rewrites the existing key file, which gets backed up
+        wallet
+            .add_keypairs(vec![KeyPair::generate(0).unwrap()])
+            .unwrap();
+        assert!(Wallet::has_backup(&file_path));
+        // backups are not loaded as keys
+        let loaded = Wallet::new(path.clone(), "test".to_string(), 77).unwrap();
+        assert_eq!(loaded.keys.len(), 2);
+
+        // no temporary file is left behind by the saves
+        assert!(std::fs::read_dir(&path).unwrap().all(|entry| entry
+            .unwrap()
+            .path()
+            .extension()
+            .unwrap()
+            != TEMPORARY_EXTENSION));
+
+        // a key file truncated by a faulty disk or an external tool
+        let content = std::fs::read(&file_path).unwrap();
+        std::fs::write(&file_path, &content[..content.len() / 3]).unwrap();
+        match Wallet::new(path.clone(), "test".to_string(), 77) {
+            Err(WalletError::CorruptedFileError(corrupted, _)) => {
+                assert_eq!(corrupted, file_path)
+            }
+            other => panic!("unexpected wallet loading result: {:?}", other),
+        }
+
+        Wallet::restore_backup(&file_path).unwrap();
+        let restored = Wallet::new(path, "test".to_string(), 77).unwrap();
+        assert_eq!(restored.keys.len(), 2);
+        assert!(restored.find_associated_keypair(&address).is_some());
+    }
+}